@@ -1,3 +1,4 @@
+use crate::history::matches_all_words;
 use crate::session;
 use gpui::{
     Context, EventEmitter, IntoElement, MouseButton, NativeMenuItem, ParentElement, Render,
@@ -86,6 +87,52 @@ impl BookmarkStore {
         }
     }
 
+    pub fn rename_bookmark(&mut self, url: &str, title: String) {
+        if let Some(bookmark) = self
+            .bookmarks
+            .iter_mut()
+            .find(|bookmark| bookmark.url == url)
+        {
+            bookmark.title = title;
+        }
+    }
+
+    pub fn rename_folder(&mut self, folder_id: u64, name: String) {
+        if let Some(folder) = self
+            .folders
+            .iter_mut()
+            .find(|folder| folder.id == folder_id)
+        {
+            folder.name = name;
+        }
+    }
+
+    /// Swaps a bookmark with its previous (`move_up`) or next neighbor in the
+    /// same folder. Bookmarks in other folders keep their positions.
+    pub fn reorder_bookmark(&mut self, url: &str, move_up: bool) {
+        let Some(index) = self
+            .bookmarks
+            .iter()
+            .position(|bookmark| bookmark.url == url)
+        else {
+            return;
+        };
+        let folder_id = self.bookmarks[index].folder_id;
+        let neighbor_index = if move_up {
+            self.bookmarks[..index]
+                .iter()
+                .rposition(|bookmark| bookmark.folder_id == folder_id)
+        } else {
+            self.bookmarks[index + 1..]
+                .iter()
+                .position(|bookmark| bookmark.folder_id == folder_id)
+                .map(|offset| index + 1 + offset)
+        };
+        if let Some(neighbor_index) = neighbor_index {
+            self.bookmarks.swap(index, neighbor_index);
+        }
+    }
+
     pub fn top_level_bookmarks(&self) -> Vec<&Bookmark> {
         self.bookmarks
             .iter()
@@ -104,6 +151,18 @@ impl BookmarkStore {
         self.bookmarks.iter().find(|bookmark| bookmark.url == url)
     }
 
+    pub fn matching(&self, query: &str, max_results: usize) -> Vec<&Bookmark> {
+        self.bookmarks
+            .iter()
+            .filter(|bookmark| matches_all_words(query, &bookmark.title, &bookmark.url))
+            .take(max_results)
+            .collect()
+    }
+
+    pub fn contains(&self, url: &str) -> bool {
+        self.find_by_url(url).is_some()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.bookmarks.is_empty() && self.folders.is_empty()
     }
@@ -170,7 +229,7 @@ impl BookmarkBar {
     }
 
     pub fn is_bookmarked(&self, url: &str) -> bool {
-        self.store.find_by_url(url).is_some()
+        self.store.contains(url)
     }
 
     pub fn add_folder(&mut self, name: String, cx: &mut Context<Self>) -> u64 {
@@ -192,6 +251,28 @@ impl BookmarkBar {
         cx.notify();
     }
 
+    pub fn rename_bookmark(&mut self, url: &str, title: String, cx: &mut Context<Self>) {
+        self.store.rename_bookmark(url, title);
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn rename_folder(&mut self, folder_id: u64, name: String, cx: &mut Context<Self>) {
+        self.store.rename_folder(folder_id, name);
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn reorder_bookmark(&mut self, url: &str, move_up: bool, cx: &mut Context<Self>) {
+        self.store.reorder_bookmark(url, move_up);
+        self.save(cx);
+        cx.notify();
+    }
+
+    pub fn store(&self) -> &BookmarkStore {
+        &self.store
+    }
+
     fn set_visibility(&mut self, visibility: BookmarkBarVisibility, cx: &mut Context<Self>) {
         self.store.visibility = visibility;
         self.save(cx);
@@ -202,19 +283,24 @@ impl BookmarkBar {
         let position = window.mouse_position();
         let is_always = self.store.visibility == BookmarkBarVisibility::Always;
         let view = cx.entity().downgrade();
-        let menu_items = vec![NativeMenuItem::action(if is_always {
-            "Show Only on New Tab"
-        } else {
-            "Show on All Tabs"
-        })];
+        let menu_items = vec![
+            NativeMenuItem::action(if is_always {
+                "Show Only on New Tab"
+            } else {
+                "Show on All Tabs"
+            }),
+            NativeMenuItem::action("Manage Bookmarks…"),
+        ];
 
         show_native_popup_menu(
             &menu_items,
             position,
             window,
             cx,
-            move |action_index, _window, cx| {
-                if action_index == 0 {
+            move |action_index, window, cx| {
+                if action_index == 1 {
+                    window.dispatch_action(Box::new(crate::ManageBookmarks), cx);
+                } else if action_index == 0 {
                     let new_visibility = if is_always {
                         BookmarkBarVisibility::NewTabOnly
                     } else {
@@ -471,3 +557,63 @@ fn truncate_url(url: &str) -> String {
         stripped.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_with_bookmarks(urls: &[(&str, Option<u64>)]) -> BookmarkStore {
+        let mut store = BookmarkStore::default();
+        for (url, folder_id) in urls {
+            store.add_bookmark(url.to_string(), url.to_string(), None, *folder_id);
+        }
+        store
+    }
+
+    fn urls(store: &BookmarkStore) -> Vec<&str> {
+        store
+            .bookmarks
+            .iter()
+            .map(|bookmark| bookmark.url.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_reorder_bookmark_within_folder() {
+        let mut store = store_with_bookmarks(&[
+            ("https://a.com", None),
+            ("https://b.com", Some(0)),
+            ("https://c.com", None),
+        ]);
+
+        store.reorder_bookmark("https://c.com", true);
+        assert_eq!(
+            urls(&store),
+            ["https://c.com", "https://b.com", "https://a.com"]
+        );
+
+        store.reorder_bookmark("https://c.com", true);
+        assert_eq!(
+            urls(&store),
+            ["https://c.com", "https://b.com", "https://a.com"]
+        );
+
+        store.reorder_bookmark("https://b.com", false);
+        assert_eq!(
+            urls(&store),
+            ["https://c.com", "https://b.com", "https://a.com"]
+        );
+    }
+
+    #[test]
+    fn test_rename_bookmark_and_folder() {
+        let mut store = store_with_bookmarks(&[("https://a.com", None)]);
+        let folder_id = store.add_folder("Reading".to_string());
+
+        store.rename_bookmark("https://a.com", "Example".to_string());
+        store.rename_folder(folder_id, "Later".to_string());
+
+        assert_eq!(store.bookmarks[0].title, "Example");
+        assert_eq!(store.folders[0].name, "Later");
+    }
+}
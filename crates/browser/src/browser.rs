@@ -4,6 +4,7 @@
 //! Chromium Embedded Framework (CEF) for a full browser experience within Glass.
 
 mod bookmarks;
+mod browser_settings;
 mod browser_view;
 mod cef_instance;
 mod client;
@@ -11,6 +12,7 @@ mod context_menu_handler;
 mod display_handler;
 mod download_handler;
 mod events;
+mod favicon_cache;
 mod find_handler;
 pub mod history;
 mod input;
@@ -19,24 +21,42 @@ mod life_span_handler;
 mod load_handler;
 #[cfg(target_os = "macos")]
 mod macos_protocol;
+mod media_playback;
 mod new_tab_page;
 mod omnibox;
+mod page_capture;
 mod page_chrome;
+mod page_language;
+mod page_security;
+mod pdf_print_callback;
 mod permission_handler;
 mod render_handler;
 mod request_handler;
+mod scroll_position;
 mod session;
+mod site_permissions;
 mod tab;
 mod text_input;
 mod toolbar;
+mod url_input;
+mod user_agent;
 
+pub use bookmarks::Bookmark;
+pub use browser_settings::BrowserSettings;
 pub use browser_view::{
-    BrowserDownloadItem, BrowserPaneItem, BrowserSidebarPanel, BrowserSurfaceState, BrowserView,
-    OpenBrowserPane,
+    BrowserDownloadItem, BrowserDownloadState, BrowserPaneItem, BrowserSidebarPanel,
+    BrowserSurfaceState, BrowserView, CapturePageScreenshot, CaptureVisibleScreenshot,
+    ExportHistory, ImportHistory, ManageBookmarks, MergeAllBrowserWindows, OmniboxFocusRequested,
+    OpenBrowserPane, OpenTabMatch, Print, SaveAsPdf, SetCustomUserAgent, SwitchToTab, TabInfo,
+    ToggleSidebar,
 };
 pub use cef_instance::CefInstance;
 pub use cef_instance::build_cef_app;
-pub use tab::BrowserTab;
+pub use page_security::{CertificateSummary, PageSecurity, PageSecurityState};
+pub use site_permissions::{PermissionDecision, SitePermission};
+pub use tab::{BrowserTab, FindState, TabStatus};
+pub use url_input::{search_engine_name, text_to_url};
+pub use user_agent::UserAgentPreset;
 
 /// Handle CEF subprocess execution. This MUST be called very early in main(),
 /// before any GUI initialization. See CefInstance::handle_subprocess() for details.
@@ -48,8 +68,13 @@ use gpui::{AnyView, App, AppContext as _, Entity, Focusable, Window};
 use std::sync::Arc;
 use toolbar::{BrowserToolbar, BrowserToolbarStyle};
 use util::ResultExt;
-use workspace::{register_browser_mode_url_opener, register_embedded_browser_item_factory};
-use workspace_modes::{ModeId, ModeNavigationHost, ModeViewRegistry, RegisteredModeView};
+use workspace::{
+    register_browser_mode_url_navigator, register_browser_mode_url_opener,
+    register_embedded_browser_item_factory,
+};
+use workspace_modes::{
+    ModeId, ModeNavigationHost, ModeViewRegistry, ModeWindowSnapshotHandler, RegisteredModeView,
+};
 
 fn browser_navigation_entries(
     view: &AnyView,
@@ -105,6 +130,22 @@ fn create_browser_navigation_entry(view: &AnyView, window: &mut Window, cx: &mut
     }
 }
 
+fn browser_window_snapshot(view: &AnyView, cx: &App) -> Option<String> {
+    view.clone()
+        .downcast::<BrowserView>()
+        .ok()?
+        .read(cx)
+        .window_snapshot(cx)
+}
+
+fn restore_browser_window_snapshot(view: &AnyView, snapshot: String, cx: &mut App) {
+    if let Ok(browser_view) = view.clone().downcast::<BrowserView>() {
+        browser_view.update(cx, |browser_view, cx| {
+            browser_view.restore_window_snapshot(&snapshot, cx);
+        });
+    }
+}
+
 fn open_browser_mode_url(view: &AnyView, url: &str, _window: &mut Window, cx: &mut App) {
     if let Ok(browser_view) = view.clone().downcast::<BrowserView>() {
         let url = url.to_string();
@@ -114,6 +155,21 @@ fn open_browser_mode_url(view: &AnyView, url: &str, _window: &mut Window, cx: &m
     }
 }
 
+fn navigate_browser_mode_url(
+    view: &AnyView,
+    url: &str,
+    background: bool,
+    _window: &mut Window,
+    cx: &mut App,
+) {
+    if let Ok(browser_view) = view.clone().downcast::<BrowserView>() {
+        let url = url.to_string();
+        let _ = browser_view.update(cx, |browser_view, cx| {
+            browser_view.open_url_from_text(&url, background, cx);
+        });
+    }
+}
+
 fn attach_browser_toolbar_to_pane(
     workspace: &workspace::Workspace,
     pane: &Entity<workspace::Pane>,
@@ -145,6 +201,13 @@ fn attach_browser_toolbar_to_pane(
     });
 }
 
+fn workspace_browser_view(workspace: &workspace::Workspace) -> Option<Entity<BrowserView>> {
+    workspace
+        .get_mode_view(ModeId::BROWSER)?
+        .downcast::<BrowserView>()
+        .ok()
+}
+
 fn attach_browser_toolbars_to_workspace(
     workspace: &workspace::Workspace,
     window: &mut Window,
@@ -191,7 +254,10 @@ pub fn init(cx: &mut App) {
         }
     }
 
+    site_permissions::load_saved_site_permissions(cx);
+    user_agent::load_saved_user_agent_overrides(cx);
     register_browser_mode_url_opener(Arc::new(open_browser_mode_url), cx);
+    register_browser_mode_url_navigator(Arc::new(navigate_browser_mode_url), cx);
     register_embedded_browser_item_factory(
         Arc::new(|workspace, browser_view, cx: &mut App| {
             let browser_view = browser_view
@@ -213,6 +279,44 @@ pub fn init(cx: &mut App) {
                     workspace.show_browser_surface(true, window, cx).log_err();
                 },
             );
+            workspace.register_action(|workspace, _: &ExportHistory, _, cx| {
+                if let Some(browser_view) = workspace_browser_view(workspace) {
+                    browser_view.update(cx, |browser_view, cx| browser_view.export_history(cx));
+                }
+            });
+            workspace.register_action(|workspace, _: &ImportHistory, _, cx| {
+                if let Some(browser_view) = workspace_browser_view(workspace) {
+                    browser_view.update(cx, |browser_view, cx| browser_view.import_history(cx));
+                }
+            });
+            workspace.register_action(|workspace, _: &SwitchToTab, window, cx| {
+                browser_view::toggle_tab_picker(workspace, window, cx);
+            });
+            workspace.register_action(|workspace, _: &ManageBookmarks, window, cx| {
+                browser_view::toggle_bookmark_manager(workspace, window, cx);
+            });
+            workspace.register_action(|workspace, _: &MergeAllBrowserWindows, window, cx| {
+                browser_view::merge_all_browser_windows(workspace, window, cx);
+            });
+            workspace.register_action(|workspace, _: &SetCustomUserAgent, window, cx| {
+                browser_view::set_custom_user_agent(workspace, window, cx);
+            });
+            workspace.register_action(|workspace, _: &Print, window, cx| {
+                browser_view::print_active_tab(workspace, window, cx);
+            });
+            workspace.register_action(|workspace, _: &SaveAsPdf, window, cx| {
+                browser_view::save_active_tab_as_pdf(workspace, window, cx);
+            });
+            workspace.register_action(|workspace, _: &CapturePageScreenshot, window, cx| {
+                browser_view::capture_page_screenshot(workspace, window, cx);
+            });
+            workspace.register_action(|workspace, _: &CaptureVisibleScreenshot, window, cx| {
+                browser_view::capture_visible_screenshot(workspace, window, cx);
+            });
+            #[cfg(target_os = "macos")]
+            workspace.register_action(|workspace, _: &ToggleSidebar, window, cx| {
+                workspace.toggle_browser_tabs_sidebar(window, cx);
+            });
 
             let Some(window) = window else {
                 return;
@@ -241,6 +345,13 @@ pub fn init(cx: &mut App) {
     )
     .detach();
 
+    ModeViewRegistry::global_mut(cx).register_window_snapshot_handler(
+        ModeId::BROWSER,
+        ModeWindowSnapshotHandler {
+            snapshot: browser_window_snapshot,
+            restore: restore_browser_window_snapshot,
+        },
+    );
     ModeViewRegistry::global_mut(cx).register_factory(
         ModeId::BROWSER,
         Arc::new(|cx: &mut App| {
@@ -0,0 +1,149 @@
+use crate::url_input::DEFAULT_SEARCH_URL;
+pub use settings::{
+    BrowserCloseLastTab, BrowserHistorySuggestionDetail, BrowserTabLayout, BrowserTabOverflow,
+};
+use settings::{RegisterSetting, Settings};
+use std::ops::RangeInclusive;
+
+const DEFAULT_ZOOM: u32 = 100;
+const ZOOM_RANGE: RangeInclusive<u32> = 25..=500;
+const MAX_SUGGESTIONS_RANGE: RangeInclusive<u32> = 3..=15;
+
+/// The settings for the built-in browser.
+#[derive(Clone, Debug, RegisterSetting)]
+pub struct BrowserSettings {
+    /// How many days a cached favicon is reused before it is fetched again.
+    ///
+    /// Default: 7
+    pub favicon_cache_days: u64,
+    /// The language foreign pages are offered translation into. Uses the
+    /// system locale when unset.
+    ///
+    /// Default: null
+    pub translation_language: Option<String>,
+    /// The translation provider URL. `{language}` is replaced with the target
+    /// language and `{url}` with the page's URL.
+    ///
+    /// Default: "https://translate.google.com/translate?sl=auto&tl={language}&u={url}"
+    pub translation_url: String,
+    /// The page new tabs open to. Shows the built-in new tab page when unset.
+    ///
+    /// Default: null
+    pub new_tab_url: Option<String>,
+    /// The search engine URL, with `{query}` replaced by the search terms.
+    ///
+    /// Default: "https://www.google.com/search?q={query}"
+    pub search_url: String,
+    /// The zoom level pages open at, as a percentage.
+    ///
+    /// Default: 100
+    pub default_zoom: u32,
+    /// Whether tabs left in the background for five minutes are frozen,
+    /// unless they are playing sound or holding a live connection.
+    ///
+    /// Default: true
+    pub throttle_background_tabs: bool,
+    /// How many URLs browsing history keeps before dropping the oldest, least
+    /// visited ones.
+    ///
+    /// Default: 50000
+    pub max_history_entries: u64,
+    /// Whether the omnibox suggests well-known domains when nothing in
+    /// browsing history matches.
+    ///
+    /// Default: true
+    pub suggest_domains: bool,
+    /// Extra domains to suggest alongside the built-in list.
+    ///
+    /// Default: []
+    pub suggested_domains: Vec<String>,
+    /// How many browsing history suggestions the omnibox shows, between 3
+    /// and 15.
+    ///
+    /// Default: 4
+    pub max_suggestions: usize,
+    /// What omnibox history suggestions show beside their title.
+    ///
+    /// Default: "domain"
+    pub history_suggestion_detail: BrowserHistorySuggestionDetail,
+    /// URLs opened as pinned tabs on every launch.
+    ///
+    /// Default: []
+    pub startup_tabs: Vec<String>,
+    /// What the top tab strip does when tabs no longer fit.
+    ///
+    /// Default: "shrink"
+    pub tab_overflow: BrowserTabOverflow,
+    /// Whether scrolling over the top tab strip wraps from the last tab to
+    /// the first and back.
+    ///
+    /// Default: false
+    pub tab_scroll_wraps: bool,
+    /// Where browser tabs are listed on Linux and Windows.
+    ///
+    /// Default: "top"
+    pub tab_layout: BrowserTabLayout,
+    /// What closing the last tab does.
+    ///
+    /// Default: "new_tab"
+    pub close_last_tab: BrowserCloseLastTab,
+}
+
+impl Settings for BrowserSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let browser = content.browser.clone().unwrap();
+        Self {
+            favicon_cache_days: browser.favicon_cache_days.unwrap(),
+            translation_language: browser.translation_language,
+            translation_url: browser.translation_url.unwrap(),
+            new_tab_url: browser.new_tab_url,
+            search_url: browser
+                .search_url
+                .filter(|search_url| search_url.contains("{query}"))
+                .unwrap_or_else(|| DEFAULT_SEARCH_URL.to_string()),
+            default_zoom: browser
+                .default_zoom
+                .filter(|zoom| ZOOM_RANGE.contains(zoom))
+                .unwrap_or(DEFAULT_ZOOM),
+            throttle_background_tabs: browser.throttle_background_tabs.unwrap(),
+            max_history_entries: browser.max_history_entries.unwrap(),
+            suggest_domains: browser.suggest_domains.unwrap(),
+            suggested_domains: browser.suggested_domains.unwrap(),
+            max_suggestions: browser
+                .max_suggestions
+                .unwrap()
+                .clamp(*MAX_SUGGESTIONS_RANGE.start(), *MAX_SUGGESTIONS_RANGE.end())
+                as usize,
+            history_suggestion_detail: browser.history_suggestion_detail.unwrap(),
+            startup_tabs: browser.startup_tabs.unwrap(),
+            tab_overflow: browser.tab_overflow.unwrap(),
+            tab_scroll_wraps: browser.tab_scroll_wraps.unwrap(),
+            tab_layout: browser.tab_layout.unwrap(),
+            close_last_tab: browser.close_last_tab.unwrap(),
+        }
+    }
+}
+
+impl BrowserSettings {
+    /// `default_zoom` as a CEF zoom level, where each step of 1.0 scales pages
+    /// by 20% and 0.0 is 100%.
+    pub fn default_zoom_level(&self) -> f64 {
+        zoom_level_for_percent(self.default_zoom)
+    }
+}
+
+fn zoom_level_for_percent(percent: u32) -> f64 {
+    (f64::from(percent) / 100.0).ln() / 1.2_f64.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::zoom_level_for_percent;
+
+    #[test]
+    fn zoom_percentages_map_to_cef_zoom_levels() {
+        assert!(zoom_level_for_percent(100).abs() < 1e-9);
+        assert!((zoom_level_for_percent(120) - 1.0).abs() < 1e-9);
+        assert!(zoom_level_for_percent(50) < 0.0);
+    }
+}
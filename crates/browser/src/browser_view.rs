@@ -1,37 +1,68 @@
 mod actions;
+mod app_window;
+mod bookmark_manager;
 mod bookmarks;
 mod content;
 mod context_menu;
+mod favicons;
+mod history_export;
 mod input;
 mod navigation;
+mod printing;
+mod screenshots;
 mod session;
+mod session_recovery;
+mod site_permissions;
+mod startup_tabs;
 mod swipe;
+mod tab_picker;
 mod tab_strip;
 mod tabs;
-
+mod translation;
+mod user_agent;
+mod window_merge;
+
+pub(crate) use bookmark_manager::toggle_bookmark_manager;
+pub(crate) use printing::{print_active_tab, save_active_tab_as_pdf};
+pub(crate) use screenshots::{capture_page_screenshot, capture_visible_screenshot};
+pub(crate) use tab_picker::toggle_tab_picker;
 pub use tab_strip::BrowserSidebarPanel;
+pub(crate) use user_agent::set_custom_user_agent;
+pub(crate) use window_merge::merge_all_browser_windows;
 
 use self::context_menu::{BrowserContextMenu, PendingContextMenu};
 use self::swipe::SwipeNavigationState;
 
-use crate::bookmarks::BookmarkBar;
+use crate::bookmarks::{Bookmark, BookmarkBar};
+use crate::browser_settings::{BrowserSettings, BrowserTabLayout};
 use crate::cef_instance::CefInstance;
 use crate::events::{BrowserTabOpenTarget, DownloadUpdatedEvent, OpenTargetRequest};
-use crate::history::BrowserHistory;
-use crate::session::{SerializedDownloadItem, SerializedTab};
+use crate::history::{BrowserHistory, matches_all_words};
+use crate::session::{SerializedBrowserTabs, SerializedDownloadItem, SerializedTab};
+use crate::site_permissions::{PermissionRequest, SitePermissions};
 use crate::tab::{BrowserTab, TabEvent};
 use crate::text_input::BrowserTextInputState;
 #[cfg(not(target_os = "macos"))]
 use crate::toolbar::{BrowserToolbar, BrowserToolbarStyle};
+use crate::url_input::text_to_url;
+use crate::user_agent::SiteUserAgents;
 use editor::{Editor, actions::SelectAll as EditorSelectAll};
 use gpui::px;
 use gpui::{
-    App, Bounds, Context, Entity, EntityInputHandler, EventEmitter, FocusHandle, Focusable,
-    InteractiveElement, IntoElement, ParentElement, Pixels, Render, SharedString, Styled,
-    Subscription, Task, UTF16Selection, WeakEntity, Window, actions, div, point, prelude::*, size,
+    Action, AnyElement, App, Bounds, Context, Div, Entity, EntityId, EntityInputHandler,
+    EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement, ParentElement, Pixels,
+    Render, SharedString, Stateful, Styled, Subscription, Task, UTF16Selection, WeakEntity, Window,
+    actions, div, point, prelude::*, size,
 };
+use schemars::JsonSchema;
+use serde::Deserialize;
+use settings::Settings as _;
+use settings::SettingsStore;
+use std::collections::{HashMap, HashSet};
 use std::ops::Range;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
 use workspace::{
     Workspace,
     item::{Item, ItemEvent, TabTooltipContent, WorkspaceItemKind},
@@ -39,6 +70,7 @@ use workspace::{
 use workspace_modes::ModeNavigationEntry;
 
 const MAX_CLOSED_TABS: usize = 20;
+const MAX_DOWNLOADS: usize = 50;
 
 static TABS_RESTORED: AtomicBool = AtomicBool::new(false);
 
@@ -58,23 +90,43 @@ actions!(
         PreviousTab,
         FocusOmnibox,
         Reload,
+        StopLoading,
         GoBack,
         GoForward,
         OpenDevTools,
         PinTab,
         UnpinTab,
+        TogglePinTab,
         OpenBrowserPane,
         BookmarkCurrentPage,
         CopyUrl,
+        CopyUrlAsMarkdown,
+        CopyAsCurl,
         ToggleSidebar,
         FindInPage,
         FindNextInPage,
         FindPreviousInPage,
         CloseFindInPage,
         ToggleDownloadCenter,
+        ActivateLastTab,
+        ExportHistory,
+        ImportHistory,
+        SwitchToTab,
+        ManageBookmarks,
+        MergeAllBrowserWindows,
+        SetCustomUserAgent,
+        Print,
+        SaveAsPdf,
+        CapturePageScreenshot,
+        CaptureVisibleScreenshot,
     ]
 );
 
+/// Activates the browser tab at the given index, counting pinned tabs first.
+#[derive(Clone, PartialEq, Debug, Deserialize, JsonSchema, Default, Action)]
+#[action(namespace = browser)]
+pub struct ActivateTab(pub usize);
+
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 enum TabBarMode {
     #[default]
@@ -82,6 +134,15 @@ enum TabBarMode {
     Sidebar,
 }
 
+impl From<BrowserTabLayout> for TabBarMode {
+    fn from(tab_layout: BrowserTabLayout) -> Self {
+        match tab_layout {
+            BrowserTabLayout::Top => Self::Horizontal,
+            BrowserTabLayout::Sidebar => Self::Sidebar,
+        }
+    }
+}
+
 #[derive(Clone)]
 struct DownloadItemState {
     item: DownloadUpdatedEvent,
@@ -121,6 +182,16 @@ impl DownloadItemState {
         self.item = update.clone();
     }
 
+    fn state(&self) -> BrowserDownloadState {
+        if self.item.is_complete {
+            BrowserDownloadState::Completed
+        } else if self.item.is_canceled || self.item.is_interrupted {
+            BrowserDownloadState::Failed
+        } else {
+            BrowserDownloadState::InProgress
+        }
+    }
+
     fn to_serialized(&self) -> SerializedDownloadItem {
         SerializedDownloadItem {
             id: self.item.id,
@@ -140,16 +211,42 @@ impl DownloadItemState {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserDownloadState {
+    InProgress,
+    Completed,
+    Failed,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BrowserDownloadItem {
     pub id: u32,
     pub display_name: String,
+    pub url: String,
     pub status_text: String,
     pub full_path: Option<String>,
-    pub is_complete: bool,
+    pub received_bytes: i64,
+    pub total_bytes: i64,
+    pub state: BrowserDownloadState,
     pub is_incognito: bool,
 }
 
+/// An open tab suggested by the omnibox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenTabMatch {
+    pub tab_id: u64,
+    pub title: String,
+    pub url: String,
+}
+
+/// A snapshot of the active tab for consumers outside the browser crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabInfo {
+    pub url: String,
+    pub title: String,
+    pub favicon_url: Option<String>,
+}
+
 #[derive(Clone)]
 struct PendingTabOpenRequest {
     url: String,
@@ -178,6 +275,12 @@ pub struct BrowserView {
     message_pump_started: bool,
     last_viewport: Option<(u32, u32, u32)>,
     pending_tab_opens: Vec<PendingTabOpenRequest>,
+    pending_permission_requests: Vec<PermissionRequest>,
+    permission_prompt_open: bool,
+    pending_favicon_fetches: HashSet<PathBuf>,
+    dismissed_translation_origins: HashSet<String>,
+    load_progress_fade_tab: Option<EntityId>,
+    _load_progress_fade_task: Option<Task<()>>,
     pending_toolbar_sync: bool,
     new_tab_search_text: String,
     new_tab_suggestions: Vec<crate::history::HistoryMatch>,
@@ -188,23 +291,37 @@ pub struct BrowserView {
     context_menu: Option<BrowserContextMenu>,
     pending_context_menu: Option<PendingContextMenu>,
     is_incognito_window: bool,
+    app_window: Option<app_window::AppWindow>,
     incognito_request_context: Option<cef::RequestContext>,
-    find_visible: bool,
+    site_permissions: SitePermissions,
+    site_user_agents: SiteUserAgents,
     find_editor: Option<Entity<Editor>>,
-    suppress_find_editor_event: bool,
-    find_query: String,
-    find_match_count: i32,
-    find_active_match_ordinal: i32,
     ime_marked_text: Option<String>,
     ime_selected_range: Option<Range<usize>>,
     download_center_visible: bool,
     downloads: Vec<DownloadItemState>,
     tab_bar_mode: TabBarMode,
+    #[cfg(not(target_os = "macos"))]
+    tab_layout: BrowserTabLayout,
+    default_zoom: u32,
+    throttle_background_tabs: bool,
     hovered_top_tab_index: Option<usize>,
     hovered_top_tab_close_index: Option<usize>,
     #[cfg(not(target_os = "macos"))]
     hovered_top_new_tab_button: bool,
     #[cfg(not(target_os = "macos"))]
+    tab_strip_scroll_handle: gpui::ScrollHandle,
+    #[cfg(not(target_os = "macos"))]
+    tab_preview_index: Option<usize>,
+    #[cfg(not(target_os = "macos"))]
+    dragged_tab_index: Option<usize>,
+    #[cfg(not(target_os = "macos"))]
+    tab_wheel_distance: Pixels,
+    #[cfg(not(target_os = "macos"))]
+    last_tab_wheel_switch: Option<std::time::Instant>,
+    #[cfg(not(target_os = "macos"))]
+    _tab_preview_task: Option<Task<()>>,
+    #[cfg(not(target_os = "macos"))]
     hovered_sidebar_tab_index: Option<usize>,
     #[cfg(not(target_os = "macos"))]
     hovered_sidebar_tab_close_index: Option<usize>,
@@ -215,6 +332,7 @@ pub struct BrowserView {
     sidebar_visible: bool,
     native_sidebar_panel: Option<Entity<tab_strip::BrowserSidebarPanel>>,
     focus_listeners_registered: bool,
+    recoverable_session: Option<SerializedBrowserTabs>,
     toast_layer: Entity<toast::ToastLayer>,
     surface_state: BrowserSurfaceState,
     swipe_state: SwipeNavigationState,
@@ -222,6 +340,7 @@ pub struct BrowserView {
     _message_pump_task: Option<Task<()>>,
     _schedule_save: Option<Task<()>>,
     _subscriptions: Vec<Subscription>,
+    tab_subscriptions: HashMap<EntityId, Subscription>,
 }
 
 impl BrowserView {
@@ -232,6 +351,7 @@ impl BrowserView {
 
     fn set_active_tab_index(&mut self, index: usize) {
         self.active_tab_index = index;
+        self.load_progress_fade_tab = None;
         self.clear_ime_state();
     }
 
@@ -251,14 +371,57 @@ impl BrowserView {
     }
 
     pub fn new(cx: &mut Context<Self>) -> Self {
+        let mut this = Self::new_without_tabs(cx);
+        if this.cef_available {
+            if !this.is_incognito_window {
+                this.restore_downloads();
+            }
+            let already_restored = TABS_RESTORED.swap(true, Ordering::SeqCst);
+            this.is_tab_owner = !already_restored;
+            let restored = if !already_restored {
+                this.remove_expired_favicons(cx);
+                this.open_startup_tabs(cx);
+                if session_recovery::last_session_crashed(cx) {
+                    this.recoverable_session =
+                        crate::session::restore().filter(|saved| !saved.tabs.is_empty());
+                    false
+                } else {
+                    this.restore_tabs(cx)
+                }
+            } else {
+                this.restore_pinned_tabs(cx)
+            };
+            if !restored && this.tabs.is_empty() {
+                this.add_tab(cx);
+            }
+
+            this.sync_bookmark_bar_visibility(cx);
+        }
+
+        this
+    }
+
+    /// A view with no tabs that leaves the saved session and pinned tabs alone.
+    fn new_without_tabs(cx: &mut Context<Self>) -> Self {
         let cef_available = CefInstance::global().is_some();
 
         let quit_subscription = cx.on_app_quit(Self::save_tabs_on_quit);
         let history = cx.new(|cx| BrowserHistory::new(cx));
+        let history_subscription = cx.observe(&history, |_, _, cx| cx.notify());
         let bookmark_bar = cx.new(|cx| BookmarkBar::new(cx));
         let bookmark_subscription = cx.subscribe(&bookmark_bar, Self::handle_bookmark_bar_event);
+        let bookmark_observation = cx.observe(&bookmark_bar, |_, _, cx| cx.notify());
         let toast_layer = cx.new(|_| toast::ToastLayer::new());
+        let zoom_settings_subscription =
+            cx.observe_global::<SettingsStore>(Self::sync_default_zoom_with_settings);
+        let throttle_settings_subscription =
+            cx.observe_global::<SettingsStore>(Self::sync_background_throttling_with_settings);
+        let tab_layout = BrowserSettings::get_global(cx).tab_layout;
+        #[cfg(not(target_os = "macos"))]
+        let settings_subscription =
+            cx.observe_global::<SettingsStore>(Self::sync_tab_layout_with_settings);
 
+        #[cfg_attr(target_os = "macos", allow(unused_mut))]
         let mut this = Self {
             focus_handle: cx.focus_handle(),
             tabs: Vec::new(),
@@ -274,6 +437,12 @@ impl BrowserView {
             message_pump_started: false,
             last_viewport: None,
             pending_tab_opens: Vec::new(),
+            pending_permission_requests: Vec::new(),
+            permission_prompt_open: false,
+            pending_favicon_fetches: HashSet::default(),
+            dismissed_translation_origins: HashSet::default(),
+            load_progress_fade_tab: None,
+            _load_progress_fade_task: None,
             pending_toolbar_sync: false,
             new_tab_search_text: String::new(),
             new_tab_suggestions: Vec::new(),
@@ -284,23 +453,37 @@ impl BrowserView {
             context_menu: None,
             pending_context_menu: None,
             is_incognito_window: false,
+            app_window: None,
             incognito_request_context: None,
-            find_visible: false,
+            site_permissions: SitePermissions::saved(),
+            site_user_agents: SiteUserAgents::saved(),
             find_editor: None,
-            suppress_find_editor_event: false,
-            find_query: String::new(),
-            find_match_count: 0,
-            find_active_match_ordinal: 0,
             ime_marked_text: None,
             ime_selected_range: None,
             download_center_visible: false,
             downloads: Vec::new(),
-            tab_bar_mode: TabBarMode::default(),
+            tab_bar_mode: tab_layout.into(),
+            #[cfg(not(target_os = "macos"))]
+            tab_layout,
+            default_zoom: BrowserSettings::get_global(cx).default_zoom,
+            throttle_background_tabs: BrowserSettings::get_global(cx).throttle_background_tabs,
             hovered_top_tab_index: None,
             hovered_top_tab_close_index: None,
             #[cfg(not(target_os = "macos"))]
             hovered_top_new_tab_button: false,
             #[cfg(not(target_os = "macos"))]
+            tab_strip_scroll_handle: gpui::ScrollHandle::new(),
+            #[cfg(not(target_os = "macos"))]
+            tab_preview_index: None,
+            #[cfg(not(target_os = "macos"))]
+            dragged_tab_index: None,
+            #[cfg(not(target_os = "macos"))]
+            tab_wheel_distance: px(0.),
+            #[cfg(not(target_os = "macos"))]
+            last_tab_wheel_switch: None,
+            #[cfg(not(target_os = "macos"))]
+            _tab_preview_task: None,
+            #[cfg(not(target_os = "macos"))]
             hovered_sidebar_tab_index: None,
             #[cfg(not(target_os = "macos"))]
             hovered_sidebar_tab_close_index: None,
@@ -308,36 +491,29 @@ impl BrowserView {
             hovered_sidebar_new_tab_button: false,
             #[cfg(not(target_os = "macos"))]
             sidebar_collapsed: false,
-            sidebar_visible: false,
+            sidebar_visible: cfg!(not(target_os = "macos"))
+                && tab_layout == BrowserTabLayout::Sidebar,
             native_sidebar_panel: None,
             focus_listeners_registered: false,
+            recoverable_session: None,
             toast_layer,
             surface_state: BrowserSurfaceState::Visible,
             swipe_state: SwipeNavigationState::default(),
             _swipe_dismiss_task: None,
             _message_pump_task: None,
             _schedule_save: None,
-            _subscriptions: vec![quit_subscription, bookmark_subscription],
+            _subscriptions: vec![
+                quit_subscription,
+                bookmark_subscription,
+                bookmark_observation,
+                history_subscription,
+                zoom_settings_subscription,
+                throttle_settings_subscription,
+            ],
+            tab_subscriptions: HashMap::default(),
         };
-
-        if cef_available {
-            if !this.is_incognito_window {
-                this.restore_downloads();
-            }
-            let already_restored = TABS_RESTORED.swap(true, Ordering::SeqCst);
-            this.is_tab_owner = !already_restored;
-            let restored = if !already_restored {
-                this.restore_tabs(cx)
-            } else {
-                this.restore_pinned_tabs(cx)
-            };
-            if !restored {
-                this.add_tab(cx);
-            }
-
-            this.sync_bookmark_bar_visibility(cx);
-        }
-
+        #[cfg(not(target_os = "macos"))]
+        this._subscriptions.push(settings_subscription);
         this
     }
 
@@ -397,11 +573,11 @@ impl BrowserView {
         match self.surface_state {
             BrowserSurfaceState::Visible => {
                 let (width, height, scale_factor) = self.current_dimensions(window);
-                tab.update(cx, |tab, _| {
+                tab.update(cx, |tab, cx| {
                     if tab.is_suspended() {
                         tab.set_scale_factor(scale_factor);
                         tab.set_size(width, height);
-                        tab.resume();
+                        tab.resume(cx);
                         if !tab.has_browser() && width > 0 && height > 0 {
                             let url = tab.url().to_string();
                             if let Err(error) = tab.create_browser(&url) {
@@ -424,9 +600,9 @@ impl BrowserView {
                 });
             }
             BrowserSurfaceState::Suspended => {
-                tab.update(cx, |tab, _| {
+                tab.update(cx, |tab, cx| {
                     tab.set_focus(false);
-                    tab.suspend();
+                    tab.suspend(cx);
                 });
             }
         }
@@ -456,7 +632,47 @@ impl BrowserView {
             .collect()
     }
 
-    pub(crate) fn activate_navigation_entry(
+    pub fn active_tab_info(&self, cx: &App) -> Option<TabInfo> {
+        let tab = self.active_tab()?.read(cx);
+        Some(TabInfo {
+            url: tab.url().to_string(),
+            title: tab.title().to_string(),
+            favicon_url: tab.favicon_url().map(str::to_string),
+        })
+    }
+
+    /// Open tabs other than the active one whose title or URL contains every
+    /// word of `query`, in tab order.
+    pub fn open_tab_matches(&self, query: &str, max_results: usize, cx: &App) -> Vec<OpenTabMatch> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != self.active_tab_index)
+            .filter_map(|(_, tab_entity)| {
+                let tab = tab_entity.read(cx);
+                (!tab.is_new_tab_page() && matches_all_words(query, tab.title(), tab.url())).then(
+                    || OpenTabMatch {
+                        tab_id: tab_entity.entity_id().as_u64(),
+                        title: tab.title().to_string(),
+                        url: tab.url().to_string(),
+                    },
+                )
+            })
+            .take(max_results)
+            .collect()
+    }
+
+    pub fn bookmark_matches(&self, query: &str, max_results: usize, cx: &App) -> Vec<Bookmark> {
+        self.bookmark_bar
+            .read(cx)
+            .store()
+            .matching(query, max_results)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    pub fn activate_navigation_entry(
         &mut self,
         tab_id: u64,
         window: &mut Window,
@@ -605,7 +821,7 @@ impl BrowserView {
         }
     }
 
-    fn navigate_new_tab_search_result(
+    pub(crate) fn navigate_new_tab_search_result(
         &mut self,
         url: String,
         window: &mut Window,
@@ -631,26 +847,41 @@ impl BrowserView {
         cx: &mut Context<Self>,
     ) {
         let url = if let Some(index) = self.new_tab_selected_index {
-            self.url_for_new_tab_row(index)
-                .unwrap_or_else(|| text_to_url(text.trim()))
+            self.url_for_new_tab_row(index, cx)
+                .unwrap_or_else(|| text_to_url(text.trim(), cx))
         } else {
             let query = text.trim();
             if query.is_empty() {
                 return;
             }
-            text_to_url(query)
+            text_to_url(query, cx)
         };
 
         self.navigate_new_tab_search_result(url, window, cx);
     }
 
+    pub fn clear_history_range(&mut self, since: Option<SystemTime>, cx: &mut Context<Self>) {
+        self.history.update(cx, |history, cx| {
+            history.clear_range(since, cx);
+        });
+        self.new_tab_suggestions.clear();
+        self.new_tab_selected_index = None;
+        cx.notify();
+    }
+
+    pub(crate) fn remove_top_site(&mut self, origin: &str, cx: &mut Context<Self>) {
+        self.history.update(cx, |history, cx| {
+            history.hide_top_site(origin, cx);
+        });
+    }
+
     pub(crate) fn activate_new_tab_row(
         &mut self,
         index: usize,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(url) = self.url_for_new_tab_row(index) else {
+        let Some(url) = self.url_for_new_tab_row(index, cx) else {
             return;
         };
 
@@ -691,8 +922,11 @@ impl BrowserView {
         let entries = self.history.read(cx).entries().to_vec();
         let executor = cx.background_executor().clone();
         let requested_query = query.clone();
+        let max_suggestions = BrowserSettings::get_global(cx).max_suggestions;
         cx.spawn(async move |this, cx| {
-            let matches = crate::history::BrowserHistory::search(entries, query, 8, executor).await;
+            let matches =
+                crate::history::BrowserHistory::search(entries, query, max_suggestions, executor)
+                    .await;
             let _ = cx.update(|cx| {
                 let _ = this.update(cx, |this, cx| {
                     if this.new_tab_search_text != requested_query {
@@ -706,12 +940,12 @@ impl BrowserView {
         .detach();
     }
 
-    fn url_for_new_tab_row(&self, index: usize) -> Option<String> {
+    fn url_for_new_tab_row(&self, index: usize, cx: &App) -> Option<String> {
         let mut current = 0;
 
         if !self.new_tab_search_text.is_empty() {
             if current == index {
-                return Some(text_to_url(&self.new_tab_search_text));
+                return Some(text_to_url(&self.new_tab_search_text, cx));
             }
             current += 1;
         }
@@ -757,6 +991,8 @@ impl BrowserView {
         log::info!("[browser] switching browser view into incognito mode");
         self.is_incognito_window = true;
         self.ensure_incognito_request_context();
+        self.site_permissions = SitePermissions::in_memory();
+        self.site_user_agents = SiteUserAgents::in_memory();
 
         for tab in &self.tabs {
             tab.update(cx, |tab, _| {
@@ -766,35 +1002,56 @@ impl BrowserView {
         }
 
         self.tabs.clear();
+        self.tab_subscriptions.clear();
         self.closed_tabs.clear();
         self.set_active_tab_index(0);
         self.pending_tab_opens.clear();
+        self.discard_pending_permission_requests();
         self.pending_toolbar_sync = true;
         self.context_menu = None;
         self.pending_context_menu = None;
-        self.find_visible = false;
-        self.find_query.clear();
-        self.find_match_count = 0;
-        self.find_active_match_ordinal = 0;
         self.download_center_visible = false;
         self.downloads.clear();
         self._schedule_save = None;
 
-        self.history.update(cx, |history, _| {
-            history.clear();
-        });
+        self.history = cx.new(|_| BrowserHistory::new_ephemeral());
+        self._subscriptions
+            .push(cx.observe(&self.history, |_, _, cx| cx.notify()));
+        self.new_tab_suggestions.clear();
+        self.new_tab_selected_index = None;
 
         self.add_tab(cx);
         self.sync_bookmark_bar_visibility(cx);
         cx.notify();
     }
 
+    /// Drops everything the incognito window accumulated, including cookies and storage
+    /// held by its request context, so the next tab starts from a clean partition.
+    fn discard_incognito_session(&mut self, cx: &mut Context<Self>) {
+        if !self.is_incognito_window {
+            return;
+        }
+
+        log::info!("[browser] discarding incognito session data");
+        self.closed_tabs.clear();
+        self.downloads.clear();
+        self.new_tab_suggestions.clear();
+        self.new_tab_selected_index = None;
+        self.history.update(cx, |history, cx| {
+            history.clear_range(None, cx);
+        });
+        self.incognito_request_context = None;
+        self.ensure_incognito_request_context();
+        self.site_permissions = SitePermissions::in_memory();
+        self.site_user_agents = SiteUserAgents::in_memory();
+    }
+
     pub(crate) fn update_toolbar_active_tab(
         &mut self,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let _ = window;
+        self.sync_find_editor_with_active_tab(window, cx);
 
         #[cfg(not(target_os = "macos"))]
         if let (Some(toolbar), Some(tab)) = (self.toolbar.clone(), self.active_tab().cloned()) {
@@ -839,6 +1096,11 @@ impl BrowserView {
         });
     }
 
+    pub(super) fn subscribe_to_tab(&mut self, tab: &Entity<BrowserTab>, cx: &mut Context<Self>) {
+        self.tab_subscriptions
+            .insert(tab.entity_id(), cx.subscribe(tab, Self::handle_tab_event));
+    }
+
     fn handle_tab_event(
         &mut self,
         tab_entity: Entity<BrowserTab>,
@@ -853,8 +1115,8 @@ impl BrowserView {
                 let url = url.clone();
                 self.create_browser_and_navigate(&tab_entity, &url, cx);
             }
-            TabEvent::OpenNewTab(url) => {
-                self.queue_tab_open(url.clone(), BrowserTabOpenTarget::Background, cx);
+            TabEvent::OpenNewTab(url, target) => {
+                self.queue_tab_open(url.clone(), *target, cx);
             }
             TabEvent::OpenTargetRequested(request) => {
                 self.handle_open_target_request(tab_entity, request, cx);
@@ -868,8 +1130,9 @@ impl BrowserView {
                             let tab = tab_handle.read(cx);
                             (tab.url().to_string(), tab.title().to_string())
                         };
-                        history.update(cx, |history, _| {
+                        history.update(cx, |history, cx| {
                             history.record_visit(&url, &title);
+                            cx.notify();
                         });
                     });
                     self.schedule_save(cx);
@@ -878,15 +1141,27 @@ impl BrowserView {
                 cx.notify();
             }
             TabEvent::FaviconChanged => {
+                self.resolve_tab_favicon(&tab_entity, cx);
                 self.schedule_save(cx);
                 cx.emit(ItemEvent::UpdateTab);
                 cx.notify();
             }
-            TabEvent::LoadingStateChanged => {
-                cx.emit(ItemEvent::UpdateTab);
-                cx.notify();
+            TabEvent::StatusChanged { previous } => {
+                if tab_entity.read(cx).is_loading() != previous.is_loading {
+                    cx.emit(ItemEvent::UpdateTab);
+                    cx.notify();
+                } else if self.active_tab() == Some(&tab_entity) {
+                    cx.notify();
+                }
             }
-            TabEvent::PageChromeChanged => {
+            TabEvent::LoadFinished => {
+                if self.active_tab() == Some(&tab_entity) {
+                    self.fade_out_load_progress(&tab_entity, cx);
+                }
+            }
+            TabEvent::PageChromeChanged
+            | TabEvent::PageLanguageChanged
+            | TabEvent::PageSecurityChanged => {
                 let is_active_tab = self
                     .active_tab()
                     .is_some_and(|active_tab| active_tab == &tab_entity);
@@ -915,13 +1190,11 @@ impl BrowserView {
                 });
                 cx.notify();
             }
-            TabEvent::FindResult(result) => {
+            TabEvent::FindResult(_) => {
                 let is_active_tab = self
                     .active_tab()
                     .is_some_and(|active_tab| active_tab == &tab_entity);
                 if is_active_tab {
-                    self.find_match_count = result.count;
-                    self.find_active_match_ordinal = result.active_match_ordinal;
                     cx.notify();
                 }
             }
@@ -929,6 +1202,9 @@ impl BrowserView {
                 self.update_download(update, cx);
                 cx.notify();
             }
+            TabEvent::PermissionRequested(request) => {
+                self.queue_permission_request(request.clone(), cx);
+            }
         }
     }
 
@@ -971,11 +1247,13 @@ impl BrowserView {
                 0,
                 DownloadItemState::from_update(update, self.is_incognito_window),
             );
+            self.downloads.truncate(MAX_DOWNLOADS);
         }
 
         if !self.is_incognito_window {
             self.schedule_save(cx);
         }
+        cx.notify();
     }
 
     pub fn download_items(&self) -> Vec<BrowserDownloadItem> {
@@ -984,14 +1262,43 @@ impl BrowserView {
             .map(|download| BrowserDownloadItem {
                 id: download.item.id,
                 display_name: Self::download_display_name(download),
+                url: download.item.url.clone(),
                 status_text: Self::download_status_line(download),
                 full_path: download.item.full_path.clone(),
-                is_complete: download.item.is_complete,
+                received_bytes: download.item.received_bytes,
+                total_bytes: download.item.total_bytes,
+                state: download.state(),
                 is_incognito: download.is_incognito,
             })
             .collect()
     }
 
+    /// Returns the combined completion fraction of in-progress downloads, or `None` when
+    /// nothing is downloading. Downloads with an unknown size count as not yet started.
+    pub fn active_download_progress(&self) -> Option<f32> {
+        let mut received_bytes = 0i64;
+        let mut total_bytes = 0i64;
+        let mut has_active_download = false;
+        for download in &self.downloads {
+            if download.state() != BrowserDownloadState::InProgress {
+                continue;
+            }
+            has_active_download = true;
+            if download.item.total_bytes > 0 {
+                received_bytes += download.item.received_bytes.max(0);
+                total_bytes += download.item.total_bytes;
+            }
+        }
+
+        if !has_active_download {
+            return None;
+        }
+        if total_bytes == 0 {
+            return Some(0.0);
+        }
+        Some((received_bytes as f32 / total_bytes as f32).clamp(0.0, 1.0))
+    }
+
     pub fn is_incognito_window(&self) -> bool {
         self.is_incognito_window
     }
@@ -1018,71 +1325,12 @@ impl BrowserView {
     }
 }
 
-fn text_to_url(text: &str) -> String {
-    if text.starts_with("http://") || text.starts_with("https://") {
-        return text.to_string();
-    }
-
-    if !looks_like_url(text) {
-        let encoded: String = url::form_urlencoded::byte_serialize(text.as_bytes()).collect();
-        return format!("https://www.google.com/search?q={}", encoded);
-    }
-
-    if should_use_http_by_default(text) {
-        format!("http://{text}")
-    } else {
-        format!("https://{text}")
-    }
-}
-
-fn looks_like_url(input: &str) -> bool {
-    if input.starts_with("http://") || input.starts_with("https://") {
-        return true;
-    }
-
-    if input.contains("://") {
-        return true;
-    }
-
-    if input.chars().any(char::is_whitespace) {
-        return false;
-    }
-
-    let Ok(url) = url::Url::parse(&format!("http://{input}")) else {
-        return false;
-    };
-
-    let Some(host) = url.host_str() else {
-        return false;
-    };
-
-    host.eq_ignore_ascii_case("localhost")
-        || host.contains('.')
-        || host.parse::<std::net::IpAddr>().is_ok()
-        || (url.port().is_some() && !host.contains('.'))
-}
-
-fn should_use_http_by_default(input: &str) -> bool {
-    let Ok(url) = url::Url::parse(&format!("http://{input}")) else {
-        return false;
-    };
-
-    let Some(host) = url.host_str() else {
-        return false;
-    };
-
-    if host.eq_ignore_ascii_case("localhost") {
-        return true;
-    }
-
-    if let Ok(address) = host.parse::<std::net::IpAddr>() {
-        return address.is_loopback();
-    }
-
-    url.port().is_some() && !host.contains('.')
-}
+/// Emitted when `FocusOmnibox` focuses the native toolbar omnibox, so the title
+/// bar can open its suggestions as if the user had started typing.
+pub struct OmniboxFocusRequested;
 
 impl EventEmitter<()> for BrowserView {}
+impl EventEmitter<OmniboxFocusRequested> for BrowserView {}
 impl EventEmitter<ItemEvent> for BrowserView {}
 
 impl Focusable for BrowserView {
@@ -1336,7 +1584,7 @@ impl Render for BrowserView {
         }
 
         #[cfg(not(target_os = "macos"))]
-        if self.toolbar.is_none() && !self.tabs.is_empty() {
+        if self.toolbar.is_none() && !self.tabs.is_empty() && !self.is_app_window() {
             cx.defer_in(window, |this, window, cx| {
                 this.create_toolbar(window, cx);
             });
@@ -1352,6 +1600,10 @@ impl Render for BrowserView {
             self.process_pending_tab_opens(window, cx);
         }
 
+        if !self.pending_permission_requests.is_empty() {
+            self.process_pending_permission_requests(window, cx);
+        }
+
         if !self.focus_listeners_registered {
             self.focus_listeners_registered = true;
             self._subscriptions.push(cx.on_focus_in(
@@ -1438,6 +1690,8 @@ impl Render for BrowserView {
             self.defer_focus_new_tab_search(window, cx);
         }
 
+        self.sync_app_window_title(window, cx);
+
         let element = div()
             .id("browser-view")
             .track_focus(&self.focus_handle)
@@ -1455,13 +1709,21 @@ impl Render for BrowserView {
             .on_action(cx.listener(Self::handle_reopen_closed_tab))
             .on_action(cx.listener(Self::handle_next_tab))
             .on_action(cx.listener(Self::handle_previous_tab))
+            .on_action(cx.listener(Self::handle_activate_tab))
+            .on_action(cx.listener(Self::handle_activate_last_tab))
+            .on_action(cx.listener(Self::handle_pin_tab))
+            .on_action(cx.listener(Self::handle_unpin_tab))
+            .on_action(cx.listener(Self::handle_toggle_pin_tab))
             .on_action(cx.listener(Self::handle_focus_omnibox))
             .on_action(cx.listener(Self::handle_reload))
+            .on_action(cx.listener(Self::handle_stop_loading))
             .on_action(cx.listener(Self::handle_go_back))
             .on_action(cx.listener(Self::handle_go_forward))
             .on_action(cx.listener(Self::handle_open_devtools))
             .on_action(cx.listener(Self::handle_bookmark_current_page))
             .on_action(cx.listener(Self::handle_copy_url))
+            .on_action(cx.listener(Self::handle_copy_url_as_markdown))
+            .on_action(cx.listener(Self::handle_copy_as_curl))
             .on_action(cx.listener(Self::handle_find_in_page))
             .on_action(cx.listener(Self::handle_find_next_in_page))
             .on_action(cx.listener(Self::handle_find_previous_in_page))
@@ -1473,9 +1735,36 @@ impl Render for BrowserView {
         #[cfg(not(target_os = "macos"))]
         let element = element.on_action(cx.listener(Self::handle_toggle_sidebar));
 
+        let element = if self.is_app_window() {
+            self.handle_app_window_actions(element, cx)
+                .flex_col()
+                .child(self.render_browser_content(window, cx))
+                .into_any_element()
+        } else {
+            self.render_browser_chrome(element, window, cx)
+        };
+
+        div()
+            .size_full()
+            .relative()
+            .child(element)
+            .children(self.render_load_progress_bar(cx))
+            .child(self.toast_layer.clone())
+            .into_any_element()
+    }
+}
+
+impl BrowserView {
+    fn render_browser_chrome(
+        &mut self,
+        element: Stateful<Div>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
         #[cfg(target_os = "macos")]
         let element = element
             .flex_col()
+            .children(self.render_session_recovery_banner(cx))
             .child(self.bookmark_bar.clone())
             .child(self.render_browser_content(window, cx))
             .into_any_element();
@@ -1485,6 +1774,7 @@ impl Render for BrowserView {
             TabBarMode::Horizontal => element
                 .flex_col()
                 .child(div().mt(px(-1.)).child(self.render_tab_strip(cx)))
+                .children(self.render_session_recovery_banner(cx))
                 .child(self.bookmark_bar.clone())
                 .child(self.render_browser_content(window, cx))
                 .into_any_element(),
@@ -1497,45 +1787,98 @@ impl Render for BrowserView {
                         .flex()
                         .flex_col()
                         .overflow_hidden()
+                        .children(self.render_session_recovery_banner(cx))
                         .child(self.bookmark_bar.clone())
                         .child(self.render_browser_content(window, cx)),
                 )
                 .into_any_element(),
         };
 
-        div()
-            .size_full()
-            .relative()
-            .child(element)
-            .child(self.toast_layer.clone())
-            .into_any_element()
+        element
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{looks_like_url, text_to_url};
+    use super::{BrowserView, navigation::markdown_link};
+    use crate::site_permissions::SitePermissions;
+    use crate::tab::BrowserTab;
+    use crate::user_agent::SiteUserAgents;
+    use gpui::{AppContext as _, TestAppContext};
+    use settings::SettingsStore;
 
     #[test]
-    fn localhost_inputs_are_treated_as_urls() {
-        assert!(looks_like_url("localhost"));
-        assert!(looks_like_url("localhost:5173"));
-        assert_eq!(text_to_url("localhost"), "http://localhost");
-        assert_eq!(text_to_url("localhost:5173"), "http://localhost:5173");
+    fn markdown_link_escapes_titles_and_urls() {
+        assert_eq!(
+            markdown_link("Glass", "https://glass.dev"),
+            "[Glass](https://glass.dev)"
+        );
+        assert_eq!(
+            markdown_link("  ", "https://glass.dev"),
+            "[https://glass.dev](https://glass.dev)"
+        );
+        assert_eq!(
+            markdown_link("[draft] notes", "https://glass.dev"),
+            "[\\[draft\\] notes](https://glass.dev)"
+        );
+        assert_eq!(
+            markdown_link("a\\b", "https://glass.dev"),
+            "[a\\\\b](https://glass.dev)"
+        );
+        assert_eq!(
+            markdown_link("", "https://en.wikipedia.org/wiki/Rust_(language)"),
+            "[https://en.wikipedia.org/wiki/Rust_(language)](https://en.wikipedia.org/wiki/Rust_%28language%29)"
+        );
+        assert_eq!(
+            markdown_link("Notes", "file:///tmp/my notes.txt"),
+            "[Notes](file:///tmp/my%20notes.txt)"
+        );
     }
 
-    #[test]
-    fn regular_domains_default_to_https() {
-        assert!(looks_like_url("example.com"));
-        assert_eq!(text_to_url("example.com"), "https://example.com");
-    }
+    #[gpui::test]
+    fn close_other_tabs_keeps_pinned_tabs(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_global(SettingsStore::test(cx)));
+        let browser_view = cx.new(|cx| {
+            let mut browser_view = BrowserView::new(cx);
+            // Incognito views skip session saves, so the test never touches disk.
+            browser_view.is_incognito_window = true;
+            for index in 0..5 {
+                let tab = cx.new(|cx| {
+                    let mut tab = BrowserTab::new_with_state(
+                        format!("https://example.com/{index}"),
+                        format!("Tab {index}"),
+                        false,
+                        None,
+                        SitePermissions::in_memory(),
+                        SiteUserAgents::in_memory(),
+                        cx,
+                    );
+                    tab.set_pinned(index < 2);
+                    tab
+                });
+                browser_view.tabs.push(tab);
+            }
+            browser_view
+        });
 
-    #[test]
-    fn plain_queries_still_search() {
-        assert!(!looks_like_url("rust async await"));
-        assert_eq!(
-            text_to_url("rust async await"),
-            "https://www.google.com/search?q=rust+async+await"
-        );
+        browser_view.update(cx, |browser_view, cx| {
+            let clicked_tab = browser_view.tabs[3].clone();
+            browser_view.close_other_tabs_at(3, cx);
+
+            let remaining_urls = browser_view
+                .tabs
+                .iter()
+                .map(|tab| tab.read(cx).url().to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                remaining_urls,
+                [
+                    "https://example.com/0",
+                    "https://example.com/1",
+                    "https://example.com/3",
+                ]
+            );
+            assert_eq!(browser_view.active_tab(), Some(&clicked_tab));
+        });
     }
 }
@@ -7,7 +7,7 @@ use super::{
     BrowserView, CloseFindInPage, Copy, Cut, FindInPage, FindNextInPage, FindPreviousInPage, Paste,
     Redo, SelectAll, ToggleDownloadCenter, Undo,
 };
-use crate::tab::BrowserTab;
+use crate::tab::FindState;
 
 impl BrowserView {
     pub(super) fn handle_copy(&mut self, _: &Copy, _window: &mut Window, cx: &mut Context<Self>) {
@@ -92,47 +92,60 @@ impl BrowserView {
         cx: &mut Context<Self>,
     ) {
         if let Some(editor) = self.find_editor.clone() {
-            self.suppress_find_editor_event = true;
             editor.update(cx, |editor, cx| {
                 editor.set_text(text.to_string(), window, cx);
             });
-            self.suppress_find_editor_event = false;
         }
     }
 
+    pub(super) fn find_state(&self, cx: &App) -> Option<FindState> {
+        self.active_tab()
+            .map(|tab| tab.read(cx).find_state().clone())
+    }
+
+    pub(super) fn is_find_open(&self, cx: &App) -> bool {
+        self.active_tab()
+            .is_some_and(|tab| tab.read(cx).find_state().is_open)
+    }
+
     pub(super) fn run_find(&mut self, forward: bool, find_next: bool, cx: &mut Context<Self>) {
-        if self.find_query.is_empty() {
-            if let Some(tab) = self.active_tab() {
-                tab.read(cx).stop_finding(true);
-            }
-            self.find_match_count = 0;
-            self.find_active_match_ordinal = 0;
-            return;
+        if let Some(tab) = self.active_tab() {
+            tab.read(cx).find_match(forward, find_next);
         }
+    }
+
+    /// Opens the find bar for the active tab with that tab's last query.
+    pub(super) fn open_find(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(tab) = self.active_tab().cloned() else {
+            return;
+        };
+        tab.update(cx, |tab, _| tab.open_find());
+        self.ensure_find_editor(window, cx);
+        let query = tab.read(cx).find_state().query.clone();
+        self.set_find_editor_text(&query, window, cx);
+        self.focus_find_editor(window, cx);
+    }
 
+    pub(super) fn close_find(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(tab) = self.active_tab() {
-            tab.read(cx)
-                .find_in_page(&self.find_query, forward, false, find_next);
+            tab.update(cx, |tab, _| tab.close_find());
         }
+        window.focus(&self.focus_handle, cx);
+        cx.notify();
     }
 
-    pub(super) fn clear_find_for_tab_switch(
+    /// Shows the newly active tab's query in the find bar. Other tabs keep
+    /// their own queries and highlights.
+    pub(super) fn sync_find_editor_with_active_tab(
         &mut self,
-        previous_tab: &Entity<BrowserTab>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if !self.find_visible && self.find_query.is_empty() {
-            return;
-        }
-
-        previous_tab.read(cx).stop_finding(true);
-        self.find_visible = false;
-        self.find_query.clear();
-        self.find_match_count = 0;
-        self.find_active_match_ordinal = 0;
-        self.set_find_editor_text("", window, cx);
-        cx.notify();
+        let query = self
+            .find_state(cx)
+            .map(|find_state| find_state.query)
+            .unwrap_or_default();
+        self.set_find_editor_text(&query, window, cx);
     }
 
     fn handle_find_editor_event(
@@ -141,18 +154,25 @@ impl BrowserView {
         event: &editor::EditorEvent,
         cx: &mut Context<Self>,
     ) {
-        if self.suppress_find_editor_event || !matches!(event, editor::EditorEvent::BufferEdited) {
+        if !matches!(event, editor::EditorEvent::BufferEdited) {
             return;
         }
-
         let Some(editor) = self.find_editor.clone() else {
             return;
         };
+        let Some(tab) = self.active_tab().cloned() else {
+            return;
+        };
 
-        self.find_query = editor.read(cx).text(cx);
-        self.find_match_count = 0;
-        self.find_active_match_ordinal = 0;
-        self.run_find(true, false, cx);
+        // Edits arrive after the fact, including the ones made by syncing the
+        // editor to a newly active tab, so only a changed query in an open
+        // find bar starts a search.
+        let query = editor.read(cx).text(cx);
+        let find_state = tab.read(cx).find_state();
+        if !find_state.is_open || find_state.query == query {
+            return;
+        }
+        tab.update(cx, |tab, _| tab.set_find_query(query));
         cx.notify();
     }
 
@@ -162,11 +182,8 @@ impl BrowserView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.find_visible = true;
-        self.focus_find_editor(window, cx);
-        if !self.find_query.is_empty() {
-            self.run_find(true, false, cx);
-        }
+        self.open_find(window, cx);
+        self.run_find(true, false, cx);
         cx.stop_propagation();
         cx.notify();
     }
@@ -177,9 +194,8 @@ impl BrowserView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if !self.find_visible {
-            self.find_visible = true;
-            self.focus_find_editor(window, cx);
+        if !self.is_find_open(cx) {
+            self.open_find(window, cx);
         }
         self.run_find(true, true, cx);
         cx.stop_propagation();
@@ -192,9 +208,8 @@ impl BrowserView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if !self.find_visible {
-            self.find_visible = true;
-            self.focus_find_editor(window, cx);
+        if !self.is_find_open(cx) {
+            self.open_find(window, cx);
         }
         self.run_find(false, true, cx);
         cx.stop_propagation();
@@ -207,17 +222,8 @@ impl BrowserView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Some(tab) = self.active_tab() {
-            tab.read(cx).stop_finding(true);
-        }
-        self.find_visible = false;
-        self.find_query.clear();
-        self.find_match_count = 0;
-        self.find_active_match_ordinal = 0;
-        self.set_find_editor_text("", window, cx);
-        window.focus(&self.focus_handle, cx);
+        self.close_find(window, cx);
         cx.stop_propagation();
-        cx.notify();
     }
 
     pub(super) fn handle_toggle_download_center(
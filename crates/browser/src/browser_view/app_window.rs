@@ -0,0 +1,244 @@
+use std::time::Duration;
+
+use gpui::{
+    App, AppContext as _, Bounds, Context, Div, Focusable as _, InteractiveElement as _, Pixels,
+    Stateful, Task, TitlebarOptions, Window, WindowBounds, WindowDecorations, WindowKind,
+    WindowOptions, point, px, size,
+};
+use url::Url;
+use util::ResultExt as _;
+
+use super::{
+    BrowserView, CapturePageScreenshot, CaptureVisibleScreenshot, Print, SaveAsPdf, printing,
+    screenshots,
+};
+use crate::session::{self, SerializedAppWindowBounds};
+
+/// State for a browser view that fills a window opened with "Open as App".
+pub(super) struct AppWindow {
+    bounds_key: String,
+    title: String,
+    _save_bounds: Option<Task<()>>,
+}
+
+impl BrowserView {
+    pub fn is_app_window(&self) -> bool {
+        self.app_window.is_some()
+    }
+
+    /// Opens the tab at `index` in its own window with no tab strip or
+    /// toolbar. The tab stays in this window.
+    pub(super) fn open_tab_as_app_at(&mut self, index: usize, cx: &mut Context<Self>) {
+        if self.is_incognito_window {
+            return;
+        }
+        let Some(tab) = self.tabs.get(index) else {
+            return;
+        };
+        let tab = tab.read(cx);
+        if tab.is_new_tab_page() {
+            return;
+        }
+
+        let url = tab.url().to_string();
+        let title = tab.title().to_string();
+        cx.defer(move |cx| open_app_window(url, title, cx));
+    }
+
+    /// Gives a view made with `new_without_tabs` a single tab for `url`. App
+    /// windows never save tabs, so closing one leaves the browser session
+    /// untouched.
+    fn configure_as_app_window(
+        &mut self,
+        url: &str,
+        title: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        log::info!("[browser] configuring browser view as an app window for {url}");
+        self.app_window = Some(AppWindow {
+            bounds_key: app_window_key(url),
+            title,
+            _save_bounds: None,
+        });
+        self.open_url(url, cx);
+
+        self._subscriptions
+            .push(cx.observe_window_bounds(window, |this, window, cx| {
+                this.schedule_app_window_bounds_save(window, cx);
+            }));
+    }
+
+    /// Keeps the window title in step with the page, since app windows have
+    /// no tab strip to show it.
+    pub(super) fn sync_app_window_title(&mut self, window: &mut Window, cx: &App) {
+        let Some(title) = self
+            .active_tab()
+            .map(|tab| tab.read(cx).title().to_string())
+        else {
+            return;
+        };
+        let Some(app_window) = self.app_window.as_mut() else {
+            return;
+        };
+        if app_window.title != title {
+            window.set_window_title(&title);
+            app_window.title = title;
+        }
+    }
+
+    /// App windows have no workspace, so the browser commands it would
+    /// handle are handled by the view instead.
+    pub(super) fn handle_app_window_actions(
+        &self,
+        element: Stateful<Div>,
+        cx: &mut Context<Self>,
+    ) -> Stateful<Div> {
+        element
+            .on_action(cx.listener(|this, _: &Print, _, cx| this.print_active_tab(cx)))
+            .on_action(cx.listener(|_, _: &SaveAsPdf, _, cx| {
+                let Some(save) = printing::prompt_to_save_as_pdf(&cx.entity(), cx) else {
+                    return;
+                };
+                cx.spawn(async move |this, cx| {
+                    if let Some(message) = save.await {
+                        this.update(cx, |this, cx| this.show_status_toast(message, cx))
+                            .ok();
+                    }
+                })
+                .detach();
+            }))
+            .on_action(cx.listener(|this, _: &CapturePageScreenshot, _, cx| {
+                this.save_app_window_screenshot(true, cx);
+            }))
+            .on_action(cx.listener(|this, _: &CaptureVisibleScreenshot, _, cx| {
+                this.save_app_window_screenshot(false, cx);
+            }))
+    }
+
+    fn save_app_window_screenshot(&self, full_page: bool, cx: &mut Context<Self>) {
+        let Some(save) = screenshots::prompt_to_save_screenshot(&cx.entity(), full_page, cx) else {
+            return;
+        };
+        cx.spawn(async move |this, cx| {
+            let message = match save.await {
+                Some(Ok(path)) => format!("Saved {}", path.display()),
+                Some(Err(error)) => format!("Failed to save screenshot: {error}"),
+                None => return,
+            };
+            this.update(cx, |this, cx| this.show_status_toast(message.into(), cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    fn schedule_app_window_bounds_save(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(app_window) = self.app_window.as_mut() else {
+            return;
+        };
+
+        let bounds = window.inner_window_bounds().get_bounds();
+        let bounds = SerializedAppWindowBounds {
+            x: f32::from(bounds.origin.x),
+            y: f32::from(bounds.origin.y),
+            width: f32::from(bounds.size.width),
+            height: f32::from(bounds.size.height),
+        };
+        let Some(json) = serde_json::to_string(&bounds).log_err() else {
+            return;
+        };
+        let bounds_key = app_window.bounds_key.clone();
+        app_window._save_bounds = Some(cx.spawn(async move |_, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(500))
+                .await;
+            session::save_app_window_bounds(bounds_key, json)
+                .await
+                .log_err();
+        }));
+    }
+}
+
+fn open_app_window(url: String, title: String, cx: &mut App) {
+    let window_bounds = restored_app_window_bounds(&url, cx)
+        .map(WindowBounds::Windowed)
+        .unwrap_or_else(|| WindowBounds::centered(size(px(1100.), px(800.)), cx));
+
+    cx.open_window(
+        WindowOptions {
+            titlebar: Some(TitlebarOptions {
+                title: Some(title.clone().into()),
+                ..Default::default()
+            }),
+            window_bounds: Some(window_bounds),
+            focus: true,
+            show: true,
+            kind: WindowKind::Normal,
+            is_movable: true,
+            window_decorations: Some(WindowDecorations::Server),
+            ..Default::default()
+        },
+        move |window, cx| {
+            let browser_view = cx.new(|cx| {
+                let mut browser_view = BrowserView::new_without_tabs(cx);
+                browser_view.configure_as_app_window(&url, title, window, cx);
+                browser_view
+            });
+            window.focus(&browser_view.focus_handle(cx), cx);
+            browser_view
+        },
+    )
+    .log_err();
+}
+
+/// Saved bounds are dropped when no connected display shows any part of
+/// them, such as after the display they were on was disconnected.
+fn restored_app_window_bounds(url: &str, cx: &App) -> Option<Bounds<Pixels>> {
+    let saved = session::restore_app_window_bounds(&app_window_key(url))?;
+    let bounds = Bounds::new(
+        point(px(saved.x), px(saved.y)),
+        size(px(saved.width), px(saved.height)),
+    );
+    let is_on_screen = saved.width > 0.
+        && saved.height > 0.
+        && cx
+            .displays()
+            .iter()
+            .any(|display| display.bounds().intersects(&bounds));
+    is_on_screen.then_some(bounds)
+}
+
+/// App windows remember their placement per site rather than per page, so
+/// an app keeps its window as it navigates.
+fn app_window_key(url: &str) -> String {
+    let Ok(parsed_url) = Url::parse(url) else {
+        return url.to_string();
+    };
+    let origin = parsed_url.origin();
+    if origin.is_tuple() {
+        origin.ascii_serialization()
+    } else {
+        url.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::app_window_key;
+
+    #[test]
+    fn app_windows_are_keyed_by_origin() {
+        assert_eq!(
+            app_window_key("https://mail.example.com/inbox?tab=1"),
+            "https://mail.example.com"
+        );
+        assert_eq!(
+            app_window_key("http://localhost:3000/calendar"),
+            "http://localhost:3000"
+        );
+        assert_eq!(
+            app_window_key("file:///tmp/app.html"),
+            "file:///tmp/app.html"
+        );
+    }
+}
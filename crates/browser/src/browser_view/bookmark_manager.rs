@@ -0,0 +1,408 @@
+use editor::{Editor, actions::SelectAll as EditorSelectAll};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription,
+    WeakEntity, Window,
+};
+use ui::{
+    ContextMenu, IconButton, ListItem, ListItemSpacing, Tooltip, prelude::*, right_click_menu,
+};
+use workspace::{ModalView, Workspace};
+
+use super::BrowserView;
+use crate::bookmarks::{Bookmark, BookmarkBar, BookmarkFolder};
+use crate::events::BrowserTabOpenTarget;
+
+pub(crate) fn toggle_bookmark_manager(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(browser_view) = crate::workspace_browser_view(workspace) else {
+        return;
+    };
+    let bookmark_bar = browser_view.read(cx).bookmark_bar.clone();
+    let browser_view = browser_view.downgrade();
+    workspace.toggle_modal(window, cx, |_, cx| {
+        BookmarkManager::new(bookmark_bar, browser_view, cx)
+    });
+}
+
+#[derive(Clone, PartialEq)]
+enum RenameTarget {
+    Bookmark(String),
+    Folder(u64),
+}
+
+struct PendingRename {
+    target: RenameTarget,
+    editor: Entity<Editor>,
+}
+
+pub struct BookmarkManager {
+    bookmark_bar: Entity<BookmarkBar>,
+    browser_view: WeakEntity<BrowserView>,
+    focus_handle: FocusHandle,
+    pending_rename: Option<PendingRename>,
+    _bookmark_bar_subscription: Subscription,
+}
+
+impl ModalView for BookmarkManager {}
+
+impl EventEmitter<DismissEvent> for BookmarkManager {}
+
+impl Focusable for BookmarkManager {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl BookmarkManager {
+    fn new(
+        bookmark_bar: Entity<BookmarkBar>,
+        browser_view: WeakEntity<BrowserView>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscription = cx.observe(&bookmark_bar, |_, _, cx| cx.notify());
+        Self {
+            bookmark_bar,
+            browser_view,
+            focus_handle: cx.focus_handle(),
+            pending_rename: None,
+            _bookmark_bar_subscription: subscription,
+        }
+    }
+
+    fn open_in_new_tab(&mut self, url: String, cx: &mut Context<Self>) {
+        self.browser_view
+            .update(cx, |browser_view, cx| {
+                browser_view.queue_tab_open(url, BrowserTabOpenTarget::Background, cx);
+            })
+            .ok();
+    }
+
+    fn start_rename(
+        &mut self,
+        target: RenameTarget,
+        current_name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text(current_name, window, cx);
+            editor.select_all(&EditorSelectAll, window, cx);
+            editor
+        });
+        window.focus(&editor.focus_handle(cx), cx);
+        self.pending_rename = Some(PendingRename { target, editor });
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(pending_rename) = self.pending_rename.take() else {
+            return;
+        };
+        let name = pending_rename.editor.read(cx).text(cx).trim().to_string();
+        if !name.is_empty() {
+            self.bookmark_bar
+                .update(cx, |bar, cx| match pending_rename.target {
+                    RenameTarget::Bookmark(url) => bar.rename_bookmark(&url, name, cx),
+                    RenameTarget::Folder(folder_id) => bar.rename_folder(folder_id, name, cx),
+                });
+        }
+        window.focus(&self.focus_handle, cx);
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.pending_rename.take().is_some() {
+            window.focus(&self.focus_handle, cx);
+            cx.notify();
+        } else {
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn new_folder(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let name = "New Folder".to_string();
+        let folder_id = self
+            .bookmark_bar
+            .update(cx, |bar, cx| bar.add_folder(name.clone(), cx));
+        self.start_rename(RenameTarget::Folder(folder_id), name, window, cx);
+    }
+
+    fn rename_editor(&self, target: &RenameTarget) -> Option<Entity<Editor>> {
+        self.pending_rename
+            .as_ref()
+            .filter(|pending_rename| &pending_rename.target == target)
+            .map(|pending_rename| pending_rename.editor.clone())
+    }
+
+    fn render_folder(
+        &self,
+        folder: &BookmarkFolder,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement + use<> {
+        let folder_id = folder.id;
+        let name = folder.name.clone();
+        let rename_editor = self.rename_editor(&RenameTarget::Folder(folder_id));
+
+        ListItem::new(SharedString::from(format!("bookmark-folder-{folder_id}")))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .start_slot(
+                Icon::new(IconName::Folder)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(match rename_editor {
+                Some(editor) => div().w_full().child(editor).into_any_element(),
+                None => Label::new(name.clone()).into_any_element(),
+            })
+            .end_slot(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new(
+                            SharedString::from(format!("rename-bookmark-folder-{folder_id}")),
+                            IconName::Pencil,
+                        )
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Rename Folder"))
+                        .on_click(cx.listener(
+                            move |this, _, window, cx| {
+                                this.start_rename(
+                                    RenameTarget::Folder(folder_id),
+                                    name.clone(),
+                                    window,
+                                    cx,
+                                );
+                            },
+                        )),
+                    )
+                    .child(
+                        IconButton::new(
+                            SharedString::from(format!("delete-bookmark-folder-{folder_id}")),
+                            IconName::Trash,
+                        )
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Delete Folder"))
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.bookmark_bar
+                                .update(cx, |bar, cx| bar.remove_folder(folder_id, cx));
+                        })),
+                    ),
+            )
+    }
+
+    fn render_bookmark(
+        &self,
+        index: usize,
+        bookmark: &Bookmark,
+        folders: &[BookmarkFolder],
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement + use<> {
+        let url = bookmark.url.clone();
+        let title = bookmark.title.clone();
+        let is_in_folder = bookmark.folder_id.is_some();
+        let rename_editor = self.rename_editor(&RenameTarget::Bookmark(url.clone()));
+        let bookmark_bar = self.bookmark_bar.clone();
+        let folders = folders.to_vec();
+        let menu_url = url.clone();
+
+        let row = ListItem::new(SharedString::from(format!("bookmark-{index}")))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .when(is_in_folder, |item| {
+                item.indent_level(1).indent_step_size(px(16.))
+            })
+            .start_slot(
+                Icon::new(IconName::Globe)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(match rename_editor {
+                Some(editor) => div().w_full().child(editor).into_any_element(),
+                None => v_flex()
+                    .min_w_0()
+                    .child(Label::new(title.clone()).truncate())
+                    .child(
+                        Label::new(url.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    )
+                    .into_any_element(),
+            })
+            .end_slot(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        IconButton::new(
+                            SharedString::from(format!("open-bookmark-{index}")),
+                            IconName::ArrowUpRight,
+                        )
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Open in New Tab"))
+                        .on_click(cx.listener({
+                            let url = url.clone();
+                            move |this, _, _, cx| this.open_in_new_tab(url.clone(), cx)
+                        })),
+                    )
+                    .child(
+                        IconButton::new(
+                            SharedString::from(format!("move-bookmark-up-{index}")),
+                            IconName::ArrowUp,
+                        )
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Move Up"))
+                        .on_click(cx.listener({
+                            let url = url.clone();
+                            move |this, _, _, cx| {
+                                this.bookmark_bar
+                                    .update(cx, |bar, cx| bar.reorder_bookmark(&url, true, cx));
+                            }
+                        })),
+                    )
+                    .child(
+                        IconButton::new(
+                            SharedString::from(format!("move-bookmark-down-{index}")),
+                            IconName::ArrowDown,
+                        )
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Move Down"))
+                        .on_click(cx.listener({
+                            let url = url.clone();
+                            move |this, _, _, cx| {
+                                this.bookmark_bar
+                                    .update(cx, |bar, cx| bar.reorder_bookmark(&url, false, cx));
+                            }
+                        })),
+                    )
+                    .child(
+                        IconButton::new(
+                            SharedString::from(format!("rename-bookmark-{index}")),
+                            IconName::Pencil,
+                        )
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Rename"))
+                        .on_click(cx.listener({
+                            let url = url.clone();
+                            move |this, _, window, cx| {
+                                this.start_rename(
+                                    RenameTarget::Bookmark(url.clone()),
+                                    title.clone(),
+                                    window,
+                                    cx,
+                                );
+                            }
+                        })),
+                    )
+                    .child(
+                        IconButton::new(
+                            SharedString::from(format!("delete-bookmark-{index}")),
+                            IconName::Trash,
+                        )
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Delete"))
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.bookmark_bar
+                                .update(cx, |bar, cx| bar.remove_bookmark(&url, cx));
+                        })),
+                    ),
+            );
+
+        right_click_menu(SharedString::from(format!("bookmark-menu-{index}")))
+            .trigger(move |_, _, _| row)
+            .menu(move |window, cx| {
+                let bookmark_bar = bookmark_bar.clone();
+                let folders = folders.clone();
+                let url = menu_url.clone();
+                ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    menu = menu.header("Move to Folder…").entry("No Folder", None, {
+                        let bookmark_bar = bookmark_bar.clone();
+                        let url = url.clone();
+                        move |_, cx| {
+                            bookmark_bar.update(cx, |bar, cx| bar.move_to_folder(&url, None, cx));
+                        }
+                    });
+                    for folder in &folders {
+                        let bookmark_bar = bookmark_bar.clone();
+                        let url = url.clone();
+                        let folder_id = folder.id;
+                        menu = menu.entry(folder.name.clone(), None, move |_, cx| {
+                            bookmark_bar.update(cx, |bar, cx| {
+                                bar.move_to_folder(&url, Some(folder_id), cx)
+                            });
+                        });
+                    }
+                    menu
+                })
+            })
+    }
+}
+
+impl Render for BookmarkManager {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let store = self.bookmark_bar.read(cx).store().clone();
+        let mut rows = Vec::new();
+        for (index, bookmark) in store.bookmarks.iter().enumerate() {
+            if bookmark.folder_id.is_none() {
+                rows.push(
+                    self.render_bookmark(index, bookmark, &store.folders, cx)
+                        .into_any_element(),
+                );
+            }
+        }
+        for folder in &store.folders {
+            rows.push(self.render_folder(folder, cx).into_any_element());
+            for (index, bookmark) in store.bookmarks.iter().enumerate() {
+                if bookmark.folder_id == Some(folder.id) {
+                    rows.push(
+                        self.render_bookmark(index, bookmark, &store.folders, cx)
+                            .into_any_element(),
+                    );
+                }
+            }
+        }
+
+        v_flex()
+            .key_context("BookmarkManager")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .w(rems(34.))
+            .elevation_3(cx)
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new("Bookmarks"))
+                    .child(
+                        Button::new("new-bookmark-folder", "New Folder")
+                            .start_icon(Icon::new(IconName::Plus).size(IconSize::Small))
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.new_folder(window, cx);
+                            })),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .id("bookmark-manager-list")
+                    .p_1()
+                    .max_h(rems(30.))
+                    .overflow_y_scroll()
+                    .when(rows.is_empty(), |list| {
+                        list.child(
+                            div()
+                                .p_2()
+                                .child(Label::new("No bookmarks yet").color(Color::Muted)),
+                        )
+                    })
+                    .children(rows),
+            )
+    }
+}
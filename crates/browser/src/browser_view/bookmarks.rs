@@ -1,7 +1,7 @@
 use crate::bookmarks::{BookmarkBar, BookmarkBarEvent};
 use crate::events::BrowserTabOpenTarget;
 use crate::tab::BrowserTab;
-use gpui::{Context, Entity, Window};
+use gpui::{App, Context, Entity, Window};
 
 use super::{BookmarkCurrentPage, BrowserView};
 
@@ -34,7 +34,16 @@ impl BrowserView {
         self.toggle_bookmark_active_tab(cx);
     }
 
-    fn toggle_bookmark_active_tab(&mut self, cx: &mut Context<Self>) {
+    /// Returns whether the active tab's page is bookmarked. Always `false` on
+    /// the new tab page.
+    pub fn is_active_tab_bookmarked(&self, cx: &App) -> bool {
+        self.active_tab().is_some_and(|tab| {
+            let tab = tab.read(cx);
+            !tab.is_new_tab_page() && self.bookmark_bar.read(cx).is_bookmarked(tab.url())
+        })
+    }
+
+    pub fn toggle_bookmark_active_tab(&mut self, cx: &mut Context<Self>) {
         if let Some(tab) = self.active_tab().cloned() {
             self.toggle_bookmark_for_tab(&tab, cx);
         }
@@ -1,15 +1,78 @@
 use gpui::{
-    Context, Corner, ElementInputHandler, IntoElement, MouseButton, NativeImageScaling,
-    NativeImageSymbolWeight, ObjectFit, ParentElement, Styled, anchored, canvas, deferred, div,
-    native_icon_button, native_image_view, prelude::*, px, surface,
+    Animation, AnimationExt as _, AnyElement, Context, Corner, ElementInputHandler, Entity,
+    IntoElement, MouseButton, NativeImageScaling, NativeImageSymbolWeight, ObjectFit,
+    ParentElement, Styled, anchored, canvas, deferred, div, native_icon_button, native_image_view,
+    prelude::*, px, relative, surface,
 };
+use std::time::Duration;
 use ui::prelude::*;
 
 use super::BrowserView;
 use super::swipe::{SWIPE_INDICATOR_SIZE, SwipePhase};
 use crate::new_tab_page;
+use crate::tab::BrowserTab;
+
+const LOAD_PROGRESS_FADE_DURATION: Duration = Duration::from_millis(350);
+/// Keeps the bar visible before the page reports its first progress update.
+const MIN_VISIBLE_LOAD_PROGRESS: f64 = 0.05;
 
 impl BrowserView {
+    pub(super) fn fade_out_load_progress(
+        &mut self,
+        tab: &Entity<BrowserTab>,
+        cx: &mut Context<Self>,
+    ) {
+        self.load_progress_fade_tab = Some(tab.entity_id());
+        self._load_progress_fade_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(LOAD_PROGRESS_FADE_DURATION)
+                .await;
+            this.update(cx, |this, cx| {
+                this.load_progress_fade_tab = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    pub(super) fn render_load_progress_bar(&self, cx: &App) -> Option<AnyElement> {
+        let tab = self.active_tab()?;
+        let tab_data = tab.read(cx);
+        if tab_data.is_new_tab_page() {
+            return None;
+        }
+
+        let bar = |progress: f64| {
+            div()
+                .absolute()
+                .top_0()
+                .left_0()
+                .h(px(2.))
+                .w(relative(progress as f32))
+                .bg(cx.theme().colors().text_accent)
+        };
+
+        if tab_data.is_loading() {
+            let progress = tab_data
+                .load_progress()
+                .clamp(MIN_VISIBLE_LOAD_PROGRESS, 1.0);
+            Some(bar(progress).into_any_element())
+        } else if self.load_progress_fade_tab == Some(tab.entity_id()) {
+            Some(
+                bar(1.0)
+                    .with_animation(
+                        ("browser-load-progress-fade", tab.entity_id().as_u64()),
+                        Animation::new(LOAD_PROGRESS_FADE_DURATION),
+                        |bar, delta| bar.opacity(1.0 - delta),
+                    )
+                    .into_any_element(),
+            )
+        } else {
+            None
+        }
+    }
+
     pub(super) fn render_placeholder(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.theme();
 
@@ -52,18 +115,12 @@ impl BrowserView {
     }
 
     fn render_find_overlay(&mut self, cx: &mut Context<Self>) -> Option<gpui::AnyElement> {
-        if !self.find_visible {
-            return None;
-        }
+        let find_state = self
+            .find_state(cx)
+            .filter(|find_state| find_state.is_open)?;
+        let match_text = find_state.match_position_label();
 
         let theme = cx.theme();
-        let match_count = self.find_match_count.max(0);
-        let active_match = self.find_active_match_ordinal.max(0);
-        let match_text = if match_count == 0 {
-            String::from("0/0")
-        } else {
-            format!("{active_match}/{match_count}")
-        };
 
         let overlay = div()
             .id("browser-find-overlay")
@@ -96,11 +153,7 @@ impl BrowserView {
                 native_icon_button("find-previous", "chevron.up")
                     .size(px(18.))
                     .tooltip("Previous Match")
-                    .on_click(cx.listener(|this, _, window, cx| {
-                        if !this.find_visible {
-                            this.find_visible = true;
-                            this.focus_find_editor(window, cx);
-                        }
+                    .on_click(cx.listener(|this, _, _window, cx| {
                         this.run_find(false, true, cx);
                         cx.notify();
                     })),
@@ -109,11 +162,7 @@ impl BrowserView {
                 native_icon_button("find-next", "chevron.down")
                     .size(px(18.))
                     .tooltip("Next Match")
-                    .on_click(cx.listener(|this, _, window, cx| {
-                        if !this.find_visible {
-                            this.find_visible = true;
-                            this.focus_find_editor(window, cx);
-                        }
+                    .on_click(cx.listener(|this, _, _window, cx| {
                         this.run_find(true, true, cx);
                         cx.notify();
                     })),
@@ -123,16 +172,7 @@ impl BrowserView {
                     .size(px(18.))
                     .tooltip("Close Find")
                     .on_click(cx.listener(|this, _, window, cx| {
-                        if let Some(tab) = this.active_tab() {
-                            tab.read(cx).stop_finding(true);
-                        }
-                        this.find_visible = false;
-                        this.find_query.clear();
-                        this.find_match_count = 0;
-                        this.find_active_match_ordinal = 0;
-                        this.set_find_editor_text("", window, cx);
-                        window.focus(&this.focus_handle, cx);
-                        cx.notify();
+                        this.close_find(window, cx);
                     })),
             );
 
@@ -202,7 +242,11 @@ impl BrowserView {
         }
 
         let theme = cx.theme();
-        let top_offset = if self.find_visible { px(52.) } else { px(8.) };
+        let top_offset = if self.is_find_open(cx) {
+            px(52.)
+        } else {
+            px(8.)
+        };
 
         let rows = self.downloads.iter().map(|download| {
             let id = download.item.id;
@@ -339,6 +383,20 @@ impl BrowserView {
         if is_new_tab_page {
             let browser_view = cx.entity();
             let search_editor = self.new_tab_search_editor_entity(window, cx);
+            let top_sites = if self.is_incognito_window {
+                Vec::new()
+            } else {
+                self.history
+                    .read(cx)
+                    .top_sites(new_tab_page::TOP_SITES_LIMIT)
+            };
+            let recently_closed = self
+                .closed_tabs
+                .iter()
+                .rev()
+                .take(new_tab_page::RECENTLY_CLOSED_LIMIT)
+                .map(|tab| (tab.url.clone(), tab.title.clone()))
+                .collect();
             return div()
                 .id("browser-content")
                 .relative()
@@ -350,6 +408,8 @@ impl BrowserView {
                     self.new_tab_search_text().to_string(),
                     self.new_tab_suggestions.clone(),
                     self.new_tab_selected_index,
+                    top_sites,
+                    recently_closed,
                     self.is_incognito_window,
                     window,
                     cx,
@@ -1,4 +1,5 @@
 use crate::context_menu_handler::ContextMenuContext;
+use crate::events::BrowserTabOpenTarget;
 use crate::tab::TabEvent;
 use gpui::{Context, DismissEvent, Entity, Pixels, Point, Subscription, Window};
 
@@ -27,7 +28,8 @@ impl BrowserView {
             None => return,
         };
 
-        let menu = ui::ContextMenu::build(window, cx, move |mut menu, _window, _cx| {
+        let browser_view = cx.entity().downgrade();
+        let menu = ui::ContextMenu::build(window, cx, move |mut menu, _window, cx| {
             let has_link = context.link_url.is_some();
             let has_selection = context.selection_text.is_some();
 
@@ -36,12 +38,26 @@ impl BrowserView {
                 let tab_for_open_new_tab = tab.clone();
                 menu = menu.entry("Open Link in New Tab", None, move |_window, cx| {
                     tab_for_open_new_tab.update(cx, |_, cx| {
-                        cx.emit(TabEvent::OpenNewTab(url.clone()));
+                        cx.emit(TabEvent::OpenNewTab(
+                            url.clone(),
+                            BrowserTabOpenTarget::Foreground,
+                        ));
                     });
                 });
 
                 let url = link_url.clone();
-                menu = menu.entry("Copy Link Address", None, move |_window, cx| {
+                let tab_for_open_background_tab = tab.clone();
+                menu = menu.entry("Open Link in Background Tab", None, move |_window, cx| {
+                    tab_for_open_background_tab.update(cx, |_, cx| {
+                        cx.emit(TabEvent::OpenNewTab(
+                            url.clone(),
+                            BrowserTabOpenTarget::Background,
+                        ));
+                    });
+                });
+
+                let url = link_url.clone();
+                menu = menu.entry("Copy Link", None, move |_window, cx| {
                     cx.write_to_clipboard(gpui::ClipboardItem::new_string(url.clone()));
                 });
 
@@ -129,12 +145,21 @@ impl BrowserView {
                 {
                     let tab = tab.clone();
                     menu = menu.entry("Reload", None, move |_window, cx| {
-                        tab.update(cx, |tab, _| tab.reload());
+                        tab.update(cx, |tab, cx| tab.reload(cx));
                     });
                 }
+                menu = menu
+                    .separator()
+                    .entry("Print…", None, |window, cx| {
+                        window.dispatch_action(Box::new(super::Print), cx);
+                    })
+                    .entry("Save as PDF…", None, |window, cx| {
+                        window.dispatch_action(Box::new(super::SaveAsPdf), cx);
+                    });
                 menu = menu.separator();
             }
 
+            menu = super::user_agent::user_agent_submenu(menu, browser_view, tab.clone(), cx);
             {
                 menu = menu.entry("Inspect", None, move |_window, cx| {
                     tab.update(cx, |tab, _| tab.open_devtools());
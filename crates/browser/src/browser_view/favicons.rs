@@ -0,0 +1,71 @@
+use crate::browser_settings::BrowserSettings;
+use crate::favicon_cache;
+use crate::tab::BrowserTab;
+use gpui::{Context, Entity};
+use log::Level;
+use settings::Settings as _;
+use util::ResultExt as _;
+
+use super::BrowserView;
+
+impl BrowserView {
+    /// Points the tab at its cached favicon when a fresh copy is on disk, and
+    /// otherwise downloads it once per origin in the background.
+    pub(super) fn resolve_tab_favicon(&mut self, tab: &Entity<BrowserTab>, cx: &mut Context<Self>) {
+        if self.is_incognito_window {
+            return;
+        }
+
+        let Some(cache_path) = tab_favicon_cache_path(tab.read(cx)) else {
+            return;
+        };
+        let favicon_cache_days = BrowserSettings::get_global(cx).favicon_cache_days;
+        if favicon_cache::is_fresh(&cache_path, favicon_cache::max_age(favicon_cache_days)) {
+            tab.update(cx, |tab, _| tab.set_favicon_cache_path(Some(cache_path)));
+            cx.notify();
+            return;
+        }
+
+        if !self.pending_favicon_fetches.insert(cache_path.clone()) {
+            return;
+        }
+        let Some(favicon_url) = tab.read(cx).favicon_url().map(str::to_string) else {
+            return;
+        };
+        let fetch = cx.background_spawn(favicon_cache::fetch(
+            cx.http_client(),
+            favicon_url,
+            cache_path.clone(),
+        ));
+        cx.spawn(async move |this, cx| {
+            let fetched_path = fetch.await.log_with_level(Level::Debug);
+            this.update(cx, |this, cx| {
+                this.pending_favicon_fetches.remove(&cache_path);
+                let Some(fetched_path) = fetched_path else {
+                    return;
+                };
+                for tab in &this.tabs {
+                    if tab_favicon_cache_path(tab.read(cx)).as_ref() == Some(&fetched_path) {
+                        let fetched_path = fetched_path.clone();
+                        tab.update(cx, |tab, _| tab.set_favicon_cache_path(Some(fetched_path)));
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    pub(super) fn remove_expired_favicons(&self, cx: &mut Context<Self>) {
+        let max_age = favicon_cache::max_age(BrowserSettings::get_global(cx).favicon_cache_days);
+        cx.background_spawn(async move {
+            favicon_cache::remove_expired_entries(max_age).log_err();
+        })
+        .detach();
+    }
+}
+
+fn tab_favicon_cache_path(tab: &BrowserTab) -> Option<std::path::PathBuf> {
+    favicon_cache::cache_path(tab.url(), tab.favicon_url()?)
+}
@@ -0,0 +1,100 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use gpui::{Context, PathPromptOptions, SharedString};
+
+use super::BrowserView;
+use crate::history::BrowserHistory;
+
+impl BrowserView {
+    /// Prompts for a destination and writes the browsing history there, as CSV
+    /// when the chosen file ends in `.csv` and as JSON otherwise.
+    pub fn export_history(&mut self, cx: &mut Context<Self>) {
+        let entries = self.history.read(cx).entries().to_vec();
+        let save_dialog = cx.prompt_for_new_path(paths::home_dir(), Some("glass-history.json"));
+        cx.spawn(async move |this, cx| {
+            let Ok(Ok(Some(path))) = save_dialog.await else {
+                return;
+            };
+
+            let result = cx
+                .background_spawn(async move {
+                    let writer = BufWriter::new(File::create(&path)?);
+                    if is_csv_path(&path) {
+                        BrowserHistory::export_csv(&entries, writer)
+                    } else {
+                        BrowserHistory::export_json(&entries, writer)
+                    }
+                })
+                .await;
+
+            let message = match result {
+                Ok(()) => SharedString::from("History exported"),
+                Err(error) => {
+                    log::error!("[browser::history] failed to export history: {error:#}");
+                    SharedString::from("Failed to export history")
+                }
+            };
+            this.update(cx, |this, cx| this.show_status_toast(message, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    /// Prompts for a file written by `export_history` in JSON and merges its
+    /// entries into the browsing history.
+    pub fn import_history(&mut self, cx: &mut Context<Self>) {
+        let open_dialog = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Import".into()),
+        });
+        let history = self.history.clone();
+        cx.spawn(async move |this, cx| {
+            let Ok(Ok(Some(paths))) = open_dialog.await else {
+                return;
+            };
+            let Some(path) = paths.into_iter().next() else {
+                return;
+            };
+
+            let imported = cx
+                .background_spawn(async move {
+                    BrowserHistory::read_json(BufReader::new(File::open(&path)?))
+                })
+                .await;
+            let result = imported.map(|imported| {
+                history.update(cx, |history, cx| history.import_entries(imported, cx))
+            });
+
+            let message = match result {
+                Ok(added) => SharedString::from(format!(
+                    "Imported {added} history {}",
+                    if added == 1 { "entry" } else { "entries" }
+                )),
+                Err(error) => {
+                    log::error!("[browser::history] failed to import history: {error:#}");
+                    SharedString::from("Failed to import history")
+                }
+            };
+            this.update(cx, |this, cx| this.show_status_toast(message, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    pub(super) fn show_status_toast(&mut self, message: SharedString, cx: &mut Context<Self>) {
+        let status_toast = toast::StatusToast::new(message, cx, |this, _| this);
+        self.toast_layer.update(cx, |layer, cx| {
+            layer.toggle_toast(cx, status_toast);
+            layer.start_dismiss_timer(std::time::Duration::from_secs(2), cx);
+        });
+    }
+}
+
+fn is_csv_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"))
+}
@@ -1,6 +1,10 @@
-use gpui::{Context, NativeSearchFieldTarget, Window};
+use gpui::{Context, Entity, NativeSearchFieldTarget, Window};
 
-use super::{BrowserView, CopyUrl, FocusOmnibox, GoBack, GoForward, OpenDevTools, Reload};
+use super::{
+    BrowserView, CopyAsCurl, CopyUrl, CopyUrlAsMarkdown, FocusOmnibox, GoBack, GoForward,
+    OmniboxFocusRequested, OpenDevTools, Reload, StopLoading,
+};
+use crate::tab::BrowserTab;
 
 impl BrowserView {
     pub(super) fn handle_focus_omnibox(
@@ -24,6 +28,7 @@ impl BrowserView {
             NativeSearchFieldTarget::ToolbarItem("glass.omnibox".into()),
             true,
         );
+        cx.emit(OmniboxFocusRequested);
 
         #[cfg(not(target_os = "macos"))]
         if let Some(toolbar) = self.toolbar.clone() {
@@ -46,27 +51,47 @@ impl BrowserView {
             if is_suspended {
                 let (width, height, scale_factor) = self.current_dimensions(window);
                 let url = tab.read(cx).url().to_string();
-                tab.update(cx, |tab, _| {
+                tab.update(cx, |tab, cx| {
                     tab.set_scale_factor(scale_factor);
                     tab.set_size(width, height);
-                    tab.resume();
+                    tab.resume(cx);
                     if !tab.has_browser() && width > 0 && height > 0 {
                         if let Err(e) = tab.create_browser(&url) {
                             log::error!("[browser::nav] Failed to create browser on reload: {}", e);
                             return;
                         }
                     }
-                    tab.reload();
+                    tab.reload(cx);
                     tab.set_focus(true);
                 });
             } else {
-                tab.update(cx, |tab, _| {
-                    tab.reload();
+                tab.update(cx, |tab, cx| {
+                    tab.reload(cx);
                 });
             }
         }
     }
 
+    pub(super) fn handle_stop_loading(
+        &mut self,
+        _: &StopLoading,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(tab) = self.active_tab().cloned() else {
+            cx.propagate();
+            return;
+        };
+        // Escape is also meaningful to the page itself, so only claim it while
+        // there is a load to stop.
+        if !tab.read(cx).is_loading() {
+            cx.propagate();
+            return;
+        }
+        tab.update(cx, |tab, cx| tab.stop_loading(cx));
+        cx.notify();
+    }
+
     pub(super) fn handle_go_back(
         &mut self,
         _: &GoBack,
@@ -110,17 +135,116 @@ impl BrowserView {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Some(tab) = self.active_tab() {
-            let url = tab.read(cx).url().to_string();
-            cx.write_to_clipboard(gpui::ClipboardItem::new_string(url));
+        self.copy_active_url(cx);
+    }
 
-            let status_toast = toast::StatusToast::new("URL copied to clipboard", cx, |this, _| {
-                this.icon(toast::ToastIcon::new(ui::IconName::Check).color(ui::Color::Success))
-            });
-            self.toast_layer.update(cx, |layer, cx| {
-                layer.toggle_toast(cx, status_toast);
-                layer.start_dismiss_timer(std::time::Duration::from_secs(2), cx);
-            });
+    pub(super) fn handle_copy_url_as_markdown(
+        &mut self,
+        _: &CopyUrlAsMarkdown,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.copy_active_url_as_markdown(cx);
+    }
+
+    pub(super) fn handle_copy_as_curl(
+        &mut self,
+        _: &CopyAsCurl,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(tab) = self.active_tab().cloned() {
+            self.copy_tab_as_curl(&tab, cx);
         }
     }
+
+    pub fn copy_active_url(&mut self, cx: &mut Context<Self>) {
+        if let Some(tab) = self.active_tab().cloned() {
+            self.copy_tab_url(&tab, cx);
+        }
+    }
+
+    pub fn copy_active_url_as_markdown(&mut self, cx: &mut Context<Self>) {
+        if let Some(tab) = self.active_tab().cloned() {
+            self.copy_tab_url_as_markdown(&tab, cx);
+        }
+    }
+
+    pub(super) fn copy_url_at(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(tab) = self.tabs.get(index).cloned() {
+            self.copy_tab_url(&tab, cx);
+        }
+    }
+
+    pub(super) fn copy_url_as_markdown_at(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(tab) = self.tabs.get(index).cloned() {
+            self.copy_tab_url_as_markdown(&tab, cx);
+        }
+    }
+
+    pub(super) fn copy_as_curl_at(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(tab) = self.tabs.get(index).cloned() {
+            self.copy_tab_as_curl(&tab, cx);
+        }
+    }
+
+    fn copy_tab_url(&mut self, tab: &Entity<BrowserTab>, cx: &mut Context<Self>) {
+        let Some(url) = copyable_tab_url(tab.read(cx)) else {
+            return;
+        };
+        cx.write_to_clipboard(gpui::ClipboardItem::new_string(url));
+        self.show_copied_toast("URL copied to clipboard", cx);
+    }
+
+    fn copy_tab_url_as_markdown(&mut self, tab: &Entity<BrowserTab>, cx: &mut Context<Self>) {
+        let tab = tab.read(cx);
+        let Some(url) = copyable_tab_url(tab) else {
+            return;
+        };
+        let link = markdown_link(tab.title(), &url);
+        cx.write_to_clipboard(gpui::ClipboardItem::new_string(link));
+        self.show_copied_toast("Link copied to clipboard", cx);
+    }
+
+    fn copy_tab_as_curl(&mut self, tab: &Entity<BrowserTab>, cx: &mut Context<Self>) {
+        let Some(command) = tab.read(cx).current_request_as_curl() else {
+            return;
+        };
+        cx.write_to_clipboard(gpui::ClipboardItem::new_string(command));
+        self.show_copied_toast("cURL command copied to clipboard", cx);
+    }
+
+    fn show_copied_toast(&mut self, message: &'static str, cx: &mut Context<Self>) {
+        let status_toast = toast::StatusToast::new(message, cx, |this, _| {
+            this.icon(toast::ToastIcon::new(ui::IconName::Check).color(ui::Color::Success))
+        });
+        self.toast_layer.update(cx, |layer, cx| {
+            layer.toggle_toast(cx, status_toast);
+            layer.start_dismiss_timer(std::time::Duration::from_secs(2), cx);
+        });
+    }
+}
+
+fn copyable_tab_url(tab: &BrowserTab) -> Option<String> {
+    let url = tab.url();
+    if tab.is_new_tab_page() || url.is_empty() || url == "glass://newtab" {
+        return None;
+    }
+    Some(url.to_string())
+}
+
+pub(super) fn markdown_link(title: &str, url: &str) -> String {
+    let title = title.trim();
+    let label = if title.is_empty() { url } else { title };
+    let label = label
+        .replace('\\', "\\\\")
+        .replace('[', "\\[")
+        .replace(']', "\\]");
+    // Percent-encoding keeps the URL equivalent while stopping a `)` or a space
+    // from ending the link destination early.
+    let destination = url
+        .replace('(', "%28")
+        .replace(')', "%29")
+        .replace(' ', "%20");
+    format!("[{label}]({destination})")
 }
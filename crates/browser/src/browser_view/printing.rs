@@ -0,0 +1,113 @@
+use gpui::{App, AppContext as _, Context, Entity, SharedString, Task, Window};
+use workspace::{Toast, Workspace, notifications::NotificationId};
+
+use super::{BrowserView, SaveAsPdf};
+
+pub(crate) fn print_active_tab(
+    workspace: &mut Workspace,
+    _window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(browser_view) = crate::workspace_browser_view(workspace) else {
+        return;
+    };
+    browser_view.update(cx, |browser_view, cx| browser_view.print_active_tab(cx));
+}
+
+pub(crate) fn save_active_tab_as_pdf(
+    workspace: &mut Workspace,
+    _window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(browser_view) = crate::workspace_browser_view(workspace) else {
+        return;
+    };
+    let Some(save) = prompt_to_save_as_pdf(&browser_view, cx) else {
+        return;
+    };
+    cx.spawn(async move |workspace, cx| {
+        let Some(message) = save.await else {
+            return;
+        };
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(NotificationId::unique::<SaveAsPdf>(), message),
+                    cx,
+                );
+            })
+            .ok();
+    })
+    .detach();
+}
+
+impl BrowserView {
+    pub(super) fn print_active_tab(&self, cx: &mut Context<Self>) {
+        if let Some(tab) = self.active_tab() {
+            tab.read(cx).print();
+        }
+    }
+}
+
+/// Prompts for a destination and saves the active tab there as a PDF. The
+/// task resolves to the message to show, or `None` if the prompt was dismissed.
+pub(super) fn prompt_to_save_as_pdf(
+    browser_view: &Entity<BrowserView>,
+    cx: &mut App,
+) -> Option<Task<Option<SharedString>>> {
+    let tab = browser_view.read(cx).active_tab().cloned()?;
+    if tab.read(cx).is_new_tab_page() {
+        return None;
+    }
+
+    let suggested_name = file_name_for_title(tab.read(cx).title(), "pdf");
+    let save_dialog = cx.prompt_for_new_path(paths::home_dir(), Some(&suggested_name));
+    Some(cx.spawn(async move |cx| {
+        let Ok(Ok(Some(path))) = save_dialog.await else {
+            return None;
+        };
+        let save_task = tab.update(cx, |tab, cx| tab.save_as_pdf(path.clone(), cx));
+
+        let message = match save_task.await {
+            Ok(()) => format!("Saved {}", path.display()),
+            Err(error) => {
+                log::error!("[browser::printing] failed to save page as PDF: {error:#}");
+                format!("Failed to save page as PDF: {error}")
+            }
+        };
+        Some(message.into())
+    }))
+}
+
+/// Names a file saved from a page after its title, replacing characters that
+/// aren't allowed in file names.
+pub(super) fn file_name_for_title(title: &str, extension: &str) -> String {
+    let name: String = title
+        .trim()
+        .chars()
+        .map(|character| match character {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            character if character.is_control() => '-',
+            character => character,
+        })
+        .collect();
+    if name.is_empty() {
+        format!("page.{extension}")
+    } else {
+        format!("{name}.{extension}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_files_after_the_page_title() {
+        assert_eq!(
+            file_name_for_title("Rust: Docs / Guide", "pdf"),
+            "Rust- Docs - Guide.pdf"
+        );
+        assert_eq!(file_name_for_title("  ", "png"), "page.png");
+    }
+}
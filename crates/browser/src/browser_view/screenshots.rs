@@ -0,0 +1,120 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use gpui::{App, AppContext as _, Context, Entity, Task, Window};
+use workspace::{OpenOptions, Toast, Workspace, notifications::NotificationId};
+use workspace_modes::ModeId;
+
+use super::printing::file_name_for_title;
+use super::{BrowserView, CapturePageScreenshot};
+
+pub(crate) fn capture_page_screenshot(
+    workspace: &mut Workspace,
+    _window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    capture_screenshot(workspace, true, cx);
+}
+
+pub(crate) fn capture_visible_screenshot(
+    workspace: &mut Workspace,
+    _window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    capture_screenshot(workspace, false, cx);
+}
+
+fn capture_screenshot(workspace: &mut Workspace, full_page: bool, cx: &mut Context<Workspace>) {
+    let Some(browser_view) = crate::workspace_browser_view(workspace) else {
+        return;
+    };
+    let Some(save) = prompt_to_save_screenshot(&browser_view, full_page, cx) else {
+        return;
+    };
+    cx.spawn(async move |workspace, cx| {
+        let Some(result) = save.await else {
+            return;
+        };
+
+        let toast = match result {
+            Ok(path) => {
+                let workspace = workspace.clone();
+                Toast::new(
+                    NotificationId::unique::<CapturePageScreenshot>(),
+                    format!("Saved {}", path.display()),
+                )
+                .on_click("Open", move |window, cx| {
+                    open_screenshot(&workspace, path.clone(), window, cx);
+                })
+            }
+            Err(error) => Toast::new(
+                NotificationId::unique::<CapturePageScreenshot>(),
+                format!("Failed to save screenshot: {error}"),
+            ),
+        };
+        workspace
+            .update(cx, |workspace, cx| workspace.show_toast(toast, cx))
+            .ok();
+    })
+    .detach();
+}
+
+/// Captures the active tab before prompting for a destination, so the
+/// screenshot shows the page as it was when the command ran. The task
+/// resolves to the saved path, or `None` if the prompt was dismissed.
+pub(super) fn prompt_to_save_screenshot(
+    browser_view: &Entity<BrowserView>,
+    full_page: bool,
+    cx: &mut App,
+) -> Option<Task<Option<Result<PathBuf>>>> {
+    let tab = browser_view.read(cx).active_tab().cloned()?;
+    if tab.read(cx).is_new_tab_page() {
+        return None;
+    }
+
+    let suggested_name = file_name_for_title(tab.read(cx).title(), "png");
+    let capture = tab.update(cx, |tab, cx| {
+        if full_page {
+            tab.capture_full_page(cx)
+        } else {
+            tab.capture_viewport(cx)
+        }
+    });
+    let save_dialog = cx.prompt_for_new_path(paths::home_dir(), Some(&suggested_name));
+    Some(cx.spawn(async move |cx| {
+        let Ok(Ok(Some(path))) = save_dialog.await else {
+            return None;
+        };
+
+        let result = match capture.await {
+            Ok(image) => {
+                let write_path = path.clone();
+                cx.background_spawn(async move { std::fs::write(&write_path, image.bytes()) })
+                    .await
+                    .map_err(anyhow::Error::from)
+            }
+            Err(error) => Err(error),
+        };
+        if let Err(error) = &result {
+            log::error!("[browser::screenshots] failed to save screenshot: {error:#}");
+        }
+        Some(result.map(|()| path))
+    }))
+}
+
+/// Opens a saved screenshot in the image viewer, which lives in editor mode.
+fn open_screenshot(
+    workspace: &gpui::WeakEntity<Workspace>,
+    path: PathBuf,
+    window: &mut Window,
+    cx: &mut gpui::App,
+) {
+    workspace
+        .update(cx, |workspace, cx| {
+            workspace.switch_to_mode(ModeId::EDITOR, window, cx);
+            workspace
+                .open_abs_path(path, OpenOptions::default(), window, cx)
+                .detach_and_log_err(cx);
+        })
+        .ok();
+}
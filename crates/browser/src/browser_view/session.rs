@@ -1,10 +1,11 @@
 use crate::session::{self, SerializedBrowserTabs, SerializedTab};
 use crate::tab::BrowserTab;
-use gpui::{App, AppContext as _, Context, Task};
+use gpui::{App, AppContext as _, Context, Entity, Task};
 use std::time::Duration;
 use util::ResultExt as _;
 
-use super::{BrowserView, DownloadItemState, TabBarMode};
+use super::startup_tabs::is_same_url;
+use super::{BrowserDownloadState, BrowserView, DownloadItemState};
 
 impl BrowserView {
     pub(super) fn restore_tabs(&mut self, cx: &mut Context<Self>) -> bool {
@@ -17,34 +18,75 @@ impl BrowserView {
             _ => return false,
         };
 
+        self.restore_serialized_tabs(saved, cx);
+        true
+    }
+
+    pub(super) fn restore_serialized_tabs(
+        &mut self,
+        saved: SerializedBrowserTabs,
+        cx: &mut Context<Self>,
+    ) {
+        // Startup tabs are opened first, and stand in for saved tabs showing
+        // the same page.
+        let mut restored_tabs: Vec<Entity<BrowserTab>> = Vec::with_capacity(saved.tabs.len());
         for serialized_tab in &saved.tabs {
-            let url = serialized_tab.url.clone();
-            let title = serialized_tab.title.clone();
-            let is_new_tab_page = serialized_tab.is_new_tab_page;
-            let is_pinned = serialized_tab.is_pinned;
-            let favicon_url = serialized_tab.favicon_url.clone();
-            let tab = cx.new(|cx| {
-                let mut tab =
-                    BrowserTab::new_with_state(url, title, is_new_tab_page, favicon_url, cx);
-                tab.set_pinned(is_pinned);
-                tab
-            });
-            self.configure_tab_request_context(&tab, cx);
-            let subscription = cx.subscribe(&tab, Self::handle_tab_event);
-            self._subscriptions.push(subscription);
-            self.tabs.push(tab);
+            let existing_tab = self
+                .tabs
+                .iter()
+                .find(|tab| {
+                    !restored_tabs.contains(tab)
+                        && is_same_url(tab.read(cx).url(), &serialized_tab.url)
+                })
+                .cloned();
+            let tab = match existing_tab {
+                Some(tab) => tab,
+                None => self.push_serialized_tab(serialized_tab, cx),
+            };
+            restored_tabs.push(tab);
         }
 
         self.sort_tabs_pinned_first(cx);
-        self.set_active_tab_index(saved.active_index.min(self.tabs.len().saturating_sub(1)));
-        self.tab_bar_mode = if saved.sidebar {
-            TabBarMode::Sidebar
-        } else {
-            TabBarMode::Horizontal
-        };
-        self.sidebar_visible = saved.sidebar_visible.unwrap_or(saved.sidebar);
+        let active_index = restored_tabs
+            .get(saved.active_index.min(restored_tabs.len().saturating_sub(1)))
+            .and_then(|active_tab| self.tabs.iter().position(|tab| tab == active_tab))
+            .unwrap_or(0);
+        self.set_active_tab_index(active_index);
         self.sync_bookmark_bar_visibility(cx);
-        true
+    }
+
+    pub(super) fn push_serialized_tab(
+        &mut self,
+        serialized_tab: &SerializedTab,
+        cx: &mut Context<Self>,
+    ) -> Entity<BrowserTab> {
+        let url = serialized_tab.url.clone();
+        let title = serialized_tab.title.clone();
+        let is_new_tab_page = serialized_tab.is_new_tab_page;
+        let is_pinned = serialized_tab.is_pinned;
+        let favicon_url = serialized_tab.favicon_url.clone();
+        let scroll_position = serialized_tab.scroll_position.clone();
+        let site_permissions = self.site_permissions.clone();
+        let site_user_agents = self.site_user_agents.clone();
+        let tab = cx.new(|cx| {
+            let mut tab = BrowserTab::new_with_state(
+                url,
+                title,
+                is_new_tab_page,
+                favicon_url,
+                site_permissions,
+                site_user_agents,
+                cx,
+            );
+            tab.set_pinned(is_pinned);
+            tab.set_restored_scroll_position(scroll_position);
+            tab
+        });
+        self.configure_tab_request_context(&tab, cx);
+        self.subscribe_to_tab(&tab, cx);
+        self.resolve_tab_favicon(&tab, cx);
+        self.tabs.push(tab.clone());
+        tab
     }
 
     pub(super) fn restore_pinned_tabs(&mut self, cx: &mut Context<Self>) -> bool {
@@ -62,15 +104,26 @@ impl BrowserView {
             let title = serialized_tab.title.clone();
             let is_new_tab_page = serialized_tab.is_new_tab_page;
             let favicon_url = serialized_tab.favicon_url.clone();
+            let scroll_position = serialized_tab.scroll_position.clone();
+            let site_permissions = self.site_permissions.clone();
+            let site_user_agents = self.site_user_agents.clone();
             let tab = cx.new(|cx| {
-                let mut tab =
-                    BrowserTab::new_with_state(url, title, is_new_tab_page, favicon_url, cx);
+                let mut tab = BrowserTab::new_with_state(
+                    url,
+                    title,
+                    is_new_tab_page,
+                    favicon_url,
+                    site_permissions,
+                    site_user_agents,
+                    cx,
+                );
                 tab.set_pinned(true);
+                tab.set_restored_scroll_position(scroll_position);
                 tab
             });
             self.configure_tab_request_context(&tab, cx);
-            let subscription = cx.subscribe(&tab, Self::handle_tab_event);
-            self._subscriptions.push(subscription);
+            self.subscribe_to_tab(&tab, cx);
+            self.resolve_tab_favicon(&tab, cx);
             self.tabs.push(tab);
         }
 
@@ -80,6 +133,37 @@ impl BrowserView {
         true
     }
 
+    /// This window's tabs, saved as it closes so "Reopen Closed Window" can
+    /// bring them back.
+    pub(crate) fn window_snapshot(&self, cx: &App) -> Option<String> {
+        if self.is_incognito_window || self.is_app_window() {
+            return None;
+        }
+        self.serialize_tabs(cx)
+    }
+
+    /// Replaces the tabs a reopened window started with by the ones it had
+    /// when it was closed.
+    pub(crate) fn restore_window_snapshot(&mut self, snapshot: &str, cx: &mut Context<Self>) {
+        if self.is_incognito_window {
+            return;
+        }
+        let Some(saved) = serde_json::from_str::<SerializedBrowserTabs>(snapshot).log_err() else {
+            return;
+        };
+        if saved.tabs.is_empty() {
+            return;
+        }
+
+        for tab in self.tabs.drain(..) {
+            tab.update(cx, |tab, _| tab.close_browser());
+        }
+        self.tab_subscriptions.clear();
+        self.restore_serialized_tabs(saved, cx);
+        self.schedule_save(cx);
+        cx.notify();
+    }
+
     pub(super) fn restore_downloads(&mut self) {
         if self.is_incognito_window {
             self.downloads.clear();
@@ -109,6 +193,7 @@ impl BrowserView {
                     is_new_tab_page: tab.is_new_tab_page(),
                     is_pinned: tab.is_pinned(),
                     favicon_url: tab.favicon_url().map(|s| s.to_string()),
+                    scroll_position: tab.scroll_position().cloned(),
                 }
             })
             .collect();
@@ -116,8 +201,6 @@ impl BrowserView {
         let data = SerializedBrowserTabs {
             tabs,
             active_index: self.active_tab_index,
-            sidebar: self.tab_bar_mode == TabBarMode::Sidebar,
-            sidebar_visible: Some(self.sidebar_visible),
         };
 
         serde_json::to_string(&data).log_err()
@@ -136,6 +219,7 @@ impl BrowserView {
                         is_new_tab_page: tab.is_new_tab_page(),
                         is_pinned: true,
                         favicon_url: tab.favicon_url().map(|s| s.to_string()),
+                        scroll_position: tab.scroll_position().cloned(),
                     })
                 } else {
                     None
@@ -150,14 +234,16 @@ impl BrowserView {
         let downloads = self
             .downloads
             .iter()
-            .filter(|download| !download.is_incognito)
+            .filter(|download| {
+                !download.is_incognito && download.state() == BrowserDownloadState::Completed
+            })
             .map(DownloadItemState::to_serialized)
             .collect::<Vec<_>>();
         serde_json::to_string(&downloads).log_err()
     }
 
     pub(super) fn schedule_save(&mut self, cx: &mut Context<Self>) {
-        if self.is_incognito_window {
+        if self.is_incognito_window || self.is_app_window() {
             return;
         }
 
@@ -168,7 +254,7 @@ impl BrowserView {
                 .await;
 
             let (tabs_json, pinned_json, history_json, downloads_json) = this
-                .read_with(cx, |this, cx| {
+                .update(cx, |this, cx| {
                     (
                         if is_tab_owner {
                             this.serialize_tabs(cx)
@@ -177,7 +263,8 @@ impl BrowserView {
                         },
                         Some(this.serialize_pinned_tabs(cx)),
                         if is_tab_owner {
-                            this.history.read(cx).serialize()
+                            this.history
+                                .update(cx, |history, _| history.take_unsaved_json())
                         } else {
                             None
                         },
@@ -212,7 +299,7 @@ impl BrowserView {
     }
 
     pub(super) fn save_tabs_on_quit(&mut self, cx: &mut Context<Self>) -> Task<()> {
-        if self.is_incognito_window {
+        if self.is_incognito_window || self.is_app_window() {
             return Task::ready(());
         }
 
@@ -223,7 +310,8 @@ impl BrowserView {
         };
         let pinned_json = self.serialize_pinned_tabs(cx);
         let history_json = if self.is_tab_owner {
-            self.history.read(cx).serialize()
+            self.history
+                .update(cx, |history, _| history.take_unsaved_json())
         } else {
             None
         };
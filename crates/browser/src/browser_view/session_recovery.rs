@@ -0,0 +1,105 @@
+use gpui::{AnyElement, App, Context, Window};
+use ui::{Button, ButtonStyle, Label, LabelSize, prelude::*};
+use workspace::AppState;
+
+use super::BrowserView;
+use super::startup_tabs::is_startup_url;
+
+/// Whether Glass quit uncleanly last time, in which case the saved tabs are
+/// offered for restoring instead of being reopened automatically.
+pub(super) fn last_session_crashed(cx: &App) -> bool {
+    AppState::try_global(cx)
+        .and_then(|app_state| app_state.upgrade())
+        .is_some_and(|app_state| app_state.session.read(cx).last_session_crashed())
+}
+
+impl BrowserView {
+    pub(super) fn restore_previous_session(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(saved) = self.recoverable_session.take() else {
+            return;
+        };
+
+        // Startup tabs stay open, standing in for their saved copies.
+        let (startup_tabs, closed_tabs): (Vec<_>, Vec<_>) = self
+            .tabs
+            .drain(..)
+            .partition(|tab| is_startup_url(tab.read(cx).url(), cx));
+        for tab in closed_tabs {
+            self.tab_subscriptions.remove(&tab.entity_id());
+            tab.update(cx, |tab, _| tab.close_browser());
+        }
+        self.tabs = startup_tabs;
+        self.restore_serialized_tabs(saved, cx);
+
+        if let Some(tab) = self.active_tab().cloned()
+            && !tab.read(cx).is_new_tab_page()
+        {
+            let url = tab.read(cx).url().to_string();
+            self.create_browser_and_navigate(&tab, &url, cx);
+            tab.update(cx, |tab, _| tab.restore_scroll_position());
+        }
+
+        self.update_toolbar_active_tab(window, cx);
+        self.schedule_save(cx);
+        cx.notify();
+    }
+
+    /// Forgets the crashed session, overwriting it with the current tabs so it
+    /// isn't offered again.
+    pub(super) fn dismiss_session_recovery(&mut self, cx: &mut Context<Self>) {
+        if self.recoverable_session.take().is_some() {
+            self.schedule_save(cx);
+            cx.notify();
+        }
+    }
+
+    pub(super) fn render_session_recovery_banner(
+        &self,
+        cx: &mut Context<Self>,
+    ) -> Option<AnyElement> {
+        let tab_count = self.recoverable_session.as_ref()?.tabs.len();
+        let theme = cx.theme();
+        let message = if tab_count == 1 {
+            "Glass didn't shut down correctly. Restore the tab from your previous session?"
+                .to_string()
+        } else {
+            format!(
+                "Glass didn't shut down correctly. Restore the {tab_count} tabs from your previous session?"
+            )
+        };
+
+        Some(
+            h_flex()
+                .id("browser-session-recovery-banner")
+                .w_full()
+                .flex_shrink_0()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .bg(theme.colors().elevated_surface_background)
+                .border_b_1()
+                .border_color(theme.colors().border)
+                .child(
+                    div()
+                        .flex_1()
+                        .min_w_0()
+                        .child(Label::new(message).size(LabelSize::Small)),
+                )
+                .child(
+                    Button::new("browser-session-recovery-restore", "Restore")
+                        .style(ButtonStyle::Filled)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.restore_previous_session(window, cx);
+                        })),
+                )
+                .child(
+                    Button::new("browser-session-recovery-dismiss", "Dismiss").on_click(
+                        cx.listener(|this, _, _, cx| {
+                            this.dismiss_session_recovery(cx);
+                        }),
+                    ),
+                )
+                .into_any_element(),
+        )
+    }
+}
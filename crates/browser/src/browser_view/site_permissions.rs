@@ -0,0 +1,163 @@
+use gpui::{Context, PromptLevel, Window};
+use util::ResultExt as _;
+
+use super::BrowserView;
+use crate::session;
+use crate::site_permissions::{
+    self, PermissionDecision, PermissionRequest, SitePermission, origin_for_url,
+};
+
+impl BrowserView {
+    pub(super) fn queue_permission_request(
+        &mut self,
+        request: PermissionRequest,
+        cx: &mut Context<Self>,
+    ) {
+        self.pending_permission_requests.push(request);
+        cx.notify();
+    }
+
+    /// Shows the next queued permission request, one prompt at a time.
+    pub(super) fn process_pending_permission_requests(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.permission_prompt_open || self.pending_permission_requests.is_empty() {
+            return;
+        }
+        let request = self.pending_permission_requests.remove(0);
+        self.permission_prompt_open = true;
+
+        let answer = window.prompt(
+            PromptLevel::Info,
+            &format!(
+                "{} wants to use your {}",
+                request.origin,
+                permission_list(&request.permissions)
+            ),
+            None,
+            &["Allow", "Block"],
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            let allow = answer.await.log_err() == Some(0);
+            site_permissions::resolve_permission_request(&request, allow);
+            let decision = if allow {
+                PermissionDecision::Allow
+            } else {
+                PermissionDecision::Deny
+            };
+            this.update(cx, |this, cx| {
+                this.site_permissions.update(|store| {
+                    for permission in &request.permissions {
+                        store.set_decision(&request.origin, *permission, decision);
+                    }
+                });
+                this.save_site_permissions(cx);
+                this.permission_prompt_open = false;
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Blocks requests that were never shown, for when the view's tabs go away.
+    pub(super) fn discard_pending_permission_requests(&mut self) {
+        for request in self.pending_permission_requests.drain(..) {
+            site_permissions::resolve_permission_request(&request, false);
+        }
+    }
+
+    /// The origin of the active tab and the saved decision for each permission.
+    pub fn active_site_permissions(
+        &self,
+        cx: &gpui::App,
+    ) -> Option<(String, Vec<(SitePermission, PermissionDecision)>)> {
+        let origin = origin_for_url(self.active_tab()?.read(cx).url())?;
+        let decisions = self
+            .site_permissions
+            .update(|store| store.decisions_for_origin(&origin));
+        Some((origin, decisions))
+    }
+
+    pub fn set_site_permission(
+        &mut self,
+        origin: &str,
+        permission: SitePermission,
+        decision: PermissionDecision,
+        cx: &mut Context<Self>,
+    ) {
+        self.site_permissions
+            .update(|store| store.set_decision(origin, permission, decision));
+        self.save_site_permissions(cx);
+        cx.notify();
+    }
+
+    /// Clears the active page's site data, telling the user if it fails.
+    pub fn clear_active_site_data(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(tab) = self.active_tab().cloned() else {
+            return;
+        };
+        let clear = tab.update(cx, |tab, cx| tab.clear_origin_data(cx));
+        cx.spawn_in(window, async move |_, cx| {
+            let Err(error) = clear.await else {
+                return;
+            };
+            log::error!("[browser::site_permissions] failed to clear site data: {error:#}");
+            let answer = cx.update(|window, cx| {
+                window.prompt(
+                    PromptLevel::Critical,
+                    "Couldn't clear site data",
+                    Some(&format!("{error:#}")),
+                    &["OK"],
+                    cx,
+                )
+            });
+            if let Ok(answer) = answer {
+                answer.await.ok();
+            }
+        })
+        .detach();
+    }
+
+    fn save_site_permissions(&self, cx: &mut Context<Self>) {
+        if let Some(json) = self.site_permissions.serialize() {
+            cx.background_spawn(async move {
+                session::save_site_permissions(json).await.log_err();
+            })
+            .detach();
+        }
+    }
+}
+
+fn permission_list(permissions: &[SitePermission]) -> String {
+    let names: Vec<String> = permissions
+        .iter()
+        .map(|permission| permission.label().to_lowercase())
+        .collect();
+    match names.as_slice() {
+        [] => String::new(),
+        [name] => name.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::permission_list;
+    use crate::site_permissions::SitePermission;
+
+    #[test]
+    fn permission_lists_read_as_a_sentence() {
+        assert_eq!(permission_list(&[SitePermission::Location]), "location");
+        assert_eq!(
+            permission_list(&[
+                SitePermission::Camera,
+                SitePermission::Microphone,
+                SitePermission::Notifications
+            ]),
+            "camera, microphone and notifications"
+        );
+    }
+}
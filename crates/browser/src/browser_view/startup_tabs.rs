@@ -0,0 +1,151 @@
+use std::collections::HashSet;
+
+use fs::Fs;
+use gpui::{App, Context};
+use settings::{Settings as _, update_settings_file};
+
+use super::BrowserView;
+use crate::browser_settings::BrowserSettings;
+use crate::session::SerializedTab;
+
+impl BrowserView {
+    /// Pins a tab for each of `browser.startup_tabs`, reusing any tab already
+    /// showing that URL. Runs before the session is restored, so startup tabs
+    /// are there on every launch whatever the previous session held.
+    pub(super) fn open_startup_tabs(&mut self, cx: &mut Context<Self>) {
+        if self.is_incognito_window {
+            return;
+        }
+        let startup_urls = startup_urls(&BrowserSettings::get_global(cx).startup_tabs);
+        if startup_urls.is_empty() {
+            return;
+        }
+
+        let active_tab = self.active_tab().cloned();
+        for url in startup_urls {
+            let existing_tab = self
+                .tabs
+                .iter()
+                .find(|tab| is_same_url(tab.read(cx).url(), &url))
+                .cloned();
+            match existing_tab {
+                Some(tab) => tab.update(cx, |tab, _| tab.set_pinned(true)),
+                None => {
+                    self.push_serialized_tab(
+                        &SerializedTab {
+                            title: url.clone(),
+                            url,
+                            is_new_tab_page: false,
+                            is_pinned: true,
+                            favicon_url: None,
+                            scroll_position: None,
+                        },
+                        cx,
+                    );
+                }
+            }
+        }
+
+        self.sort_tabs_pinned_first(cx);
+        if let Some(active_tab) = active_tab
+            && let Some(index) = self.tabs.iter().position(|tab| tab == &active_tab)
+        {
+            self.set_active_tab_index(index);
+        }
+        self.schedule_save(cx);
+    }
+
+    pub(super) fn is_startup_tab_at(&self, index: usize, cx: &App) -> bool {
+        self.tabs
+            .get(index)
+            .is_some_and(|tab| is_startup_url(tab.read(cx).url(), cx))
+    }
+
+    /// Adds the tab's URL to `browser.startup_tabs`, pinning the tab, or
+    /// removes it if it is already there.
+    pub(super) fn toggle_startup_tab_at(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(tab) = self.tabs.get(index) else {
+            return;
+        };
+        let tab = tab.read(cx);
+        if tab.is_new_tab_page() {
+            return;
+        }
+        let url = tab.url().to_string();
+        let is_startup_tab = self.is_startup_tab_at(index, cx);
+        if !is_startup_tab {
+            self.pin_tab_at(index, cx);
+        }
+
+        update_settings_file(<dyn Fs>::global(cx), cx, move |settings, _| {
+            let startup_tabs = settings
+                .browser
+                .get_or_insert_default()
+                .startup_tabs
+                .get_or_insert_default();
+            if is_startup_tab {
+                startup_tabs.retain(|startup_url| !is_same_url(startup_url, &url));
+            } else {
+                startup_tabs.push(url);
+            }
+        });
+    }
+}
+
+/// The configured startup URLs in order, with blank entries and repeats of
+/// the same page dropped.
+fn startup_urls(configured: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    configured
+        .iter()
+        .map(|url| url.trim())
+        .filter(|url| !url.is_empty() && seen.insert(url.trim_end_matches('/')))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether `url` is one of `browser.startup_tabs`.
+pub(super) fn is_startup_url(url: &str, cx: &App) -> bool {
+    BrowserSettings::get_global(cx)
+        .startup_tabs
+        .iter()
+        .any(|startup_url| is_same_url(startup_url, url))
+}
+
+/// Pages load with a trailing slash added to bare origins, so
+/// `https://example.com` and `https://example.com/` are the same tab.
+pub(super) fn is_same_url(left: &str, right: &str) -> bool {
+    left.trim().trim_end_matches('/') == right.trim().trim_end_matches('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_same_url, startup_urls};
+
+    #[test]
+    fn startup_urls_drop_duplicates_and_blanks() {
+        let configured = [
+            "https://grafana.example.com/d/overview".to_string(),
+            " ".to_string(),
+            "https://status.example.com".to_string(),
+            "https://status.example.com/".to_string(),
+            "https://grafana.example.com/d/overview".to_string(),
+        ];
+        assert_eq!(
+            startup_urls(&configured),
+            [
+                "https://grafana.example.com/d/overview",
+                "https://status.example.com",
+            ]
+        );
+    }
+
+    #[test]
+    fn urls_match_regardless_of_trailing_slash() {
+        assert!(is_same_url("https://example.com", "https://example.com/"));
+        assert!(!is_same_url(
+            "https://example.com/a",
+            "https://example.com/b"
+        ));
+    }
+}
@@ -0,0 +1,328 @@
+use std::sync::Arc;
+
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, Focusable, Render, Task, WeakEntity, Window,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use workspace::{ModalView, Workspace};
+use workspace_modes::ModeId;
+
+use super::BrowserView;
+
+pub(crate) fn toggle_tab_picker(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(browser_view) = crate::workspace_browser_view(workspace) else {
+        return;
+    };
+    let workspace_handle = cx.entity().downgrade();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        let delegate =
+            TabPickerDelegate::new(cx.entity().downgrade(), workspace_handle, &browser_view, cx);
+        TabPicker::new(delegate, window, cx)
+    });
+}
+
+pub struct TabPicker {
+    picker: Entity<Picker<TabPickerDelegate>>,
+}
+
+impl ModalView for TabPicker {}
+
+impl EventEmitter<DismissEvent> for TabPicker {}
+
+impl Focusable for TabPicker {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for TabPicker {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl TabPicker {
+    fn new(delegate: TabPickerDelegate, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+struct TabPickerEntry {
+    tab_id: u64,
+    title: String,
+    url: String,
+    is_pinned: bool,
+}
+
+impl TabPickerEntry {
+    fn match_text(&self) -> String {
+        format!("{} {}", self.title, self.url)
+    }
+}
+
+pub struct TabPickerDelegate {
+    tab_picker: WeakEntity<TabPicker>,
+    workspace: WeakEntity<Workspace>,
+    browser_view: WeakEntity<BrowserView>,
+    entries: Vec<TabPickerEntry>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    /// The index of the tab that was active when the picker opened, selected
+    /// again whenever the query is cleared.
+    active_index: usize,
+}
+
+impl TabPickerDelegate {
+    fn new(
+        tab_picker: WeakEntity<TabPicker>,
+        workspace: WeakEntity<Workspace>,
+        browser_view: &Entity<BrowserView>,
+        cx: &App,
+    ) -> Self {
+        let browser_view_ref = browser_view.read(cx);
+        let entries = browser_view_ref
+            .tabs
+            .iter()
+            .map(|tab_entity| {
+                let tab = tab_entity.read(cx);
+                TabPickerEntry {
+                    tab_id: tab_entity.entity_id().as_u64(),
+                    title: tab.title().to_string(),
+                    url: tab.url().to_string(),
+                    is_pinned: tab.is_pinned(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let matches = all_matches(&entries);
+        let active_index = browser_view_ref.active_tab_index;
+        Self {
+            tab_picker,
+            workspace,
+            browser_view: browser_view.downgrade(),
+            entries,
+            matches,
+            selected_index: active_index,
+            active_index,
+        }
+    }
+}
+
+fn all_matches(entries: &[TabPickerEntry]) -> Vec<StringMatch> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| StringMatch {
+            candidate_id: index,
+            score: 0.0,
+            positions: Vec::new(),
+            string: entry.match_text(),
+        })
+        .collect()
+}
+
+impl PickerDelegate for TabPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _: &mut Window, _: &mut App) -> Arc<str> {
+        "Switch to tab...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut Window, _: &mut Context<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        if query.is_empty() {
+            self.matches = all_matches(&self.entries);
+            self.selected_index = self.active_index.min(self.matches.len().saturating_sub(1));
+            return Task::ready(());
+        }
+
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| StringMatchCandidate::new(index, &entry.match_text()))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = match_strings(
+                &candidates,
+                &query,
+                false,
+                true,
+                100,
+                &Default::default(),
+                background,
+            )
+            .await;
+            this.update(cx, |this, cx| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = 0;
+                cx.notify();
+            })
+            .ok();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|string_match| self.entries.get(string_match.candidate_id))
+        else {
+            return;
+        };
+        let tab_id = entry.tab_id;
+
+        if let Some(workspace) = self.workspace.upgrade() {
+            workspace.update(cx, |workspace, cx| {
+                if workspace.active_mode_id() != ModeId::BROWSER
+                    && !workspace.is_browser_split_open()
+                {
+                    workspace.switch_to_mode(ModeId::BROWSER, window, cx);
+                }
+            });
+        }
+        self.browser_view
+            .update(cx, |browser_view, cx| {
+                browser_view.activate_navigation_entry(tab_id, window, cx);
+            })
+            .ok();
+        self.tab_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .ok();
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.tab_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .ok();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let string_match = self.matches.get(ix)?;
+        let entry = self.entries.get(string_match.candidate_id)?;
+        let title_length = entry.title.len();
+        let title_positions = string_match
+            .positions
+            .iter()
+            .copied()
+            .filter(|position| *position < title_length)
+            .collect::<Vec<_>>();
+        let url_positions = string_match
+            .positions
+            .iter()
+            .filter_map(|position| position.checked_sub(title_length + 1))
+            .collect::<Vec<_>>();
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .when(entry.is_pinned, |item| {
+                    item.start_slot(
+                        Icon::new(IconName::Pin)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                })
+                .child(
+                    v_flex()
+                        .child(HighlightedLabel::new(entry.title.clone(), title_positions))
+                        .child(
+                            HighlightedLabel::new(entry.url.clone(), url_positions)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use settings::SettingsStore;
+
+    fn entry(index: usize) -> TabPickerEntry {
+        TabPickerEntry {
+            tab_id: index as u64,
+            title: format!("Tab {index}"),
+            url: format!("https://example.com/{index}"),
+            is_pinned: false,
+        }
+    }
+
+    #[gpui::test]
+    async fn test_empty_query_selects_the_active_tab(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            cx.set_global(SettingsStore::test(cx));
+            theme::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+        });
+
+        let entries = (0..4).map(entry).collect::<Vec<_>>();
+        let delegate = TabPickerDelegate {
+            tab_picker: WeakEntity::new_invalid(),
+            workspace: WeakEntity::new_invalid(),
+            browser_view: WeakEntity::new_invalid(),
+            matches: all_matches(&entries),
+            entries,
+            selected_index: 2,
+            active_index: 2,
+        };
+        let (picker, cx) =
+            cx.add_window_view(|window, cx| Picker::uniform_list(delegate, window, cx));
+
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker
+                    .delegate
+                    .update_matches("example.com/0".to_string(), window, cx)
+            })
+            .await;
+        picker.read_with(cx, |picker, _| {
+            assert_eq!(picker.delegate.match_count(), 1);
+            assert_eq!(picker.delegate.selected_index(), 0);
+        });
+
+        picker
+            .update_in(cx, |picker, window, cx| {
+                picker.delegate.update_matches(String::new(), window, cx)
+            })
+            .await;
+        picker.read_with(cx, |picker, _| {
+            assert_eq!(picker.delegate.match_count(), 4);
+            assert_eq!(picker.delegate.selected_index(), 2);
+        });
+    }
+}
@@ -4,16 +4,114 @@ use gpui::{
     div, native_image_view, prelude::*, px, show_native_popup_menu,
 };
 #[cfg(not(target_os = "macos"))]
-use gpui::{native_tracking_view, rems};
+use gpui::{
+    Corner, MouseUpEvent, ObjectFit, ScrollWheelEvent, TouchPhase, anchored, deferred,
+    native_tracking_view, point, rems, surface,
+};
+#[cfg(not(target_os = "macos"))]
+use settings::Settings as _;
+#[cfg(not(target_os = "macos"))]
+use std::time::{Duration, Instant};
 use ui::{IconButtonShape, Tooltip, prelude::*};
 use workspace::{Workspace, WorkspaceSidebarSection};
 use workspace_chrome::SidebarRow;
 use workspace_modes::ModeId;
 
 use super::BrowserView;
+#[cfg(not(target_os = "macos"))]
+use super::tabs::adjacent_tab_index;
+#[cfg(not(target_os = "macos"))]
+use crate::browser_settings::{BrowserSettings, BrowserTabOverflow};
 
 #[cfg(not(target_os = "macos"))]
 const SIDEBAR_WIDTH_PX: f32 = 200.0;
+#[cfg(not(target_os = "macos"))]
+const TAB_PREVIEW_DELAY: Duration = Duration::from_millis(600);
+#[cfg(not(target_os = "macos"))]
+const TAB_WIDTH: Pixels = px(180.);
+/// Wide enough for the favicon and the tab's horizontal padding.
+#[cfg(not(target_os = "macos"))]
+const MIN_SHRUNK_TAB_WIDTH: Pixels = px(32.);
+#[cfg(not(target_os = "macos"))]
+const TAB_STRIP_SCROLL_STEP: Pixels = px(180.);
+/// How far the wheel has to travel over the tab strip to switch one tab.
+#[cfg(not(target_os = "macos"))]
+const TAB_WHEEL_SWITCH_DISTANCE: Pixels = px(40.);
+/// Keeps a single flick of the wheel from skipping past several tabs.
+#[cfg(not(target_os = "macos"))]
+const TAB_WHEEL_SWITCH_INTERVAL: Duration = Duration::from_millis(150);
+
+#[cfg(not(target_os = "macos"))]
+fn render_tab_strip_scroll_button(
+    id: &'static str,
+    icon: IconName,
+    tooltip: &'static str,
+    enabled: bool,
+    delta: Pixels,
+    cx: &mut Context<BrowserView>,
+) -> impl IntoElement {
+    IconButton::new(id, icon)
+        .icon_size(IconSize::Small)
+        .disabled(!enabled)
+        .tooltip(Tooltip::text(tooltip))
+        .on_click(cx.listener(move |this, _, _, cx| this.scroll_tab_strip_by(delta, cx)))
+}
+
+/// A browser tab being dragged from the horizontal tab strip. Releasing it
+/// outside the strip moves the tab into a new window.
+#[cfg(not(target_os = "macos"))]
+struct DraggedBrowserTab {
+    index: usize,
+    title: SharedString,
+}
+
+#[cfg(not(target_os = "macos"))]
+impl Render for DraggedBrowserTab {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .h(px(24.))
+            .w(TAB_WIDTH)
+            .px_2()
+            .flex()
+            .items_center()
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .text_ellipsis()
+            .text_size(rems(0.75))
+            .rounded(cx.theme().component_radius().tab.unwrap_or(px(8.0)))
+            .bg(cx.theme().colors().elevated_surface_background)
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .child(self.title.clone())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn start_tab_drag(
+    view: &WeakEntity<BrowserView>,
+    tab: &DraggedBrowserTab,
+    cx: &mut App,
+) -> Entity<DraggedBrowserTab> {
+    view.update(cx, |this, _| {
+        this.dragged_tab_index = Some(tab.index);
+    })
+    .ok();
+    cx.new(|_| DraggedBrowserTab {
+        index: tab.index,
+        title: tab.title.clone(),
+    })
+}
+
+/// Only a tab dropped beyond the window's edges moves to a new window;
+/// releasing it over the page or the sidebar leaves it where it is.
+#[cfg(not(target_os = "macos"))]
+fn is_outside_window(position: Point<Pixels>, window: &Window) -> bool {
+    let viewport_size = window.viewport_size();
+    position.x < px(0.)
+        || position.y < px(0.)
+        || position.x > viewport_size.width
+        || position.y > viewport_size.height
+}
 
 fn show_tab_context_menu(
     view: WeakEntity<BrowserView>,
@@ -33,6 +131,56 @@ fn show_tab_context_menu(
     menu_items.push(NativeMenuItem::action("Close Tab"));
     let close_others_index = menu_items.len();
     menu_items.push(NativeMenuItem::action("Close Other Tabs"));
+    menu_items.push(NativeMenuItem::separator());
+    let copy_url_index = menu_items.len();
+    menu_items.push(NativeMenuItem::action("Copy URL"));
+    let copy_markdown_link_index = menu_items.len();
+    menu_items.push(NativeMenuItem::action("Copy as Markdown Link"));
+    let can_copy_as_curl = view.upgrade().is_some_and(|view| {
+        view.read(cx)
+            .tabs
+            .get(index)
+            .is_some_and(|tab| tab.read(cx).current_request_as_curl().is_some())
+    });
+    let copy_as_curl_index = if can_copy_as_curl {
+        let index = menu_items.len();
+        menu_items.push(NativeMenuItem::action("Copy as cURL"));
+        Some(index)
+    } else {
+        None
+    };
+    let is_web_page = view.upgrade().is_some_and(|view| {
+        view.read(cx)
+            .tabs
+            .get(index)
+            .is_some_and(|tab| !tab.read(cx).is_new_tab_page())
+    });
+    let can_open_as_app =
+        is_web_page && view.upgrade().is_some_and(|view| !view.read(cx).is_incognito_window());
+    if is_web_page {
+        menu_items.push(NativeMenuItem::separator());
+    }
+    let open_as_app_index = if can_open_as_app {
+        let index = menu_items.len();
+        menu_items.push(NativeMenuItem::action("Open as App"));
+        Some(index)
+    } else {
+        None
+    };
+    let startup_tab_index = if is_web_page {
+        let is_startup_tab = view
+            .upgrade()
+            .is_some_and(|view| view.read(cx).is_startup_tab_at(index, cx));
+        let index = menu_items.len();
+        menu_items.push(NativeMenuItem::action(if is_startup_tab {
+            "Don't Open on Startup"
+        } else {
+            "Open on Startup"
+        }));
+        Some(index)
+    } else {
+        None
+    };
     let bookmark_index = if !is_pinned {
         menu_items.push(NativeMenuItem::separator());
         let index = menu_items.len();
@@ -79,6 +227,46 @@ fn show_tab_context_menu(
                 return;
             }
 
+            if action_index == copy_url_index {
+                view.update(cx, |this, cx| {
+                    this.copy_url_at(index, cx);
+                })
+                .ok();
+                return;
+            }
+
+            if action_index == copy_markdown_link_index {
+                view.update(cx, |this, cx| {
+                    this.copy_url_as_markdown_at(index, cx);
+                })
+                .ok();
+                return;
+            }
+
+            if copy_as_curl_index == Some(action_index) {
+                view.update(cx, |this, cx| {
+                    this.copy_as_curl_at(index, cx);
+                })
+                .ok();
+                return;
+            }
+
+            if open_as_app_index == Some(action_index) {
+                view.update(cx, |this, cx| {
+                    this.open_tab_as_app_at(index, cx);
+                })
+                .ok();
+                return;
+            }
+
+            if startup_tab_index == Some(action_index) {
+                view.update(cx, |this, cx| {
+                    this.toggle_startup_tab_at(index, cx);
+                })
+                .ok();
+                return;
+            }
+
             if bookmark_index == Some(action_index) {
                 view.update(cx, |this, cx| {
                     this.toggle_bookmark_at(index, cx);
@@ -89,8 +277,8 @@ fn show_tab_context_menu(
     );
 }
 
-fn render_tab_favicon(id: SharedString, favicon_url: Option<&str>, _cx: &App) -> gpui::AnyElement {
-    if let Some(url) = favicon_url {
+fn render_tab_favicon(id: SharedString, favicon_uri: Option<&str>, _cx: &App) -> gpui::AnyElement {
+    if let Some(url) = favicon_uri {
         native_image_view(id)
             .image_uri(url.to_string())
             .scaling(NativeImageScaling::ScaleUpOrDown)
@@ -108,6 +296,25 @@ fn render_tab_favicon(id: SharedString, favicon_url: Option<&str>, _cx: &App) ->
     }
 }
 
+fn render_incognito_badge(cx: &App) -> gpui::Div {
+    h_flex()
+        .px_2()
+        .py_1()
+        .gap_1p5()
+        .rounded(cx.theme().component_radius().tab.unwrap_or(px(8.0)))
+        .bg(cx.theme().colors().text.opacity(0.12))
+        .child(
+            Icon::new(IconName::EyeOff)
+                .size(IconSize::Small)
+                .color(Color::Muted),
+        )
+        .child(
+            Label::new("Incognito")
+                .size(LabelSize::Small)
+                .color(Color::Muted),
+        )
+}
+
 pub struct BrowserSidebarPanel {
     browser_view: WeakEntity<BrowserView>,
     hovered_tab_index: Option<usize>,
@@ -164,8 +371,13 @@ impl Render for BrowserSidebarPanel {
             _ => Some("Show Browser in Editor"),
         };
 
+        let is_incognito_window = browser_view_data.is_incognito_window;
+
         v_flex()
             .size_full()
+            .when(is_incognito_window, |this| {
+                this.child(render_incognito_badge(cx).m_1())
+            })
             .child(
                 v_flex()
                     .id("native-sidebar-tab-list")
@@ -182,7 +394,7 @@ impl Render for BrowserSidebarPanel {
                             .map(|(index, tab)| {
                                 let tab_data = tab.read(cx);
                                 let title = tab_data.title().to_string();
-                                let favicon_url = tab_data.favicon_url();
+                                let favicon_uri = tab_data.favicon_image_uri();
                                 let is_pinned = tab_data.is_pinned();
                                 let is_active = index == active_tab_index;
                                 let tab_id =
@@ -193,7 +405,7 @@ impl Render for BrowserSidebarPanel {
                                     SharedString::from(format!(
                                         "native-sidebar-tab-favicon-{index}"
                                     )),
-                                    favicon_url,
+                                    favicon_uri.as_deref(),
                                     cx,
                                 );
 
@@ -325,6 +537,18 @@ impl BrowserView {
         let view = cx.entity().downgrade();
 
         let pinned_count = self.tabs.iter().filter(|t| t.read(cx).is_pinned()).count();
+        let has_unpinned_tabs = pinned_count < self.tabs.len();
+        let scrolls_tabs =
+            BrowserSettings::get_global(cx).tab_overflow == BrowserTabOverflow::Scroll;
+        let scroll_offset = self.tab_strip_scroll_handle.offset().x;
+        let max_scroll_offset = self.tab_strip_scroll_handle.max_offset().x;
+        let can_scroll_left = scrolls_tabs && scroll_offset < px(0.);
+        let can_scroll_right = scrolls_tabs && -scroll_offset < max_scroll_offset;
+        // A drag can also end without a mouse up reaching the strip, such as
+        // when it is cancelled.
+        if !cx.has_active_drag() {
+            self.dragged_tab_index = None;
+        }
 
         h_flex()
             .w_full()
@@ -333,6 +557,30 @@ impl BrowserView {
             .gap_1()
             .items_center()
             .flex_shrink_0()
+            .when(!scrolls_tabs, |this| {
+                this.on_scroll_wheel(cx.listener(Self::handle_tab_strip_scroll_wheel))
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _, _, _| {
+                    this.dragged_tab_index = None;
+                }),
+            )
+            .on_mouse_up_out(
+                MouseButton::Left,
+                cx.listener(|this, event: &MouseUpEvent, window, cx| {
+                    let Some(index) = this.dragged_tab_index.take() else {
+                        return;
+                    };
+                    if cx.has_active_drag() && is_outside_window(event.position, window) {
+                        this.detach_tab_to_new_window(index, cx);
+                    }
+                }),
+            )
+            .when(self.is_incognito_window, |this| {
+                this.bg(theme.colors().text.opacity(0.08))
+                    .child(render_incognito_badge(cx))
+            })
             // Pinned tabs dock
             .when(pinned_count > 0, |this| {
                 this.child(
@@ -348,7 +596,7 @@ impl BrowserView {
                         .children(self.tabs.iter().enumerate().take(pinned_count).map(
                             |(index, tab)| {
                                 let tab_data = tab.read(cx);
-                                let favicon_url = tab_data.favicon_url();
+                                let favicon_uri = tab_data.favicon_image_uri();
                                 let is_active = index == active_index;
                                 let is_hovered = self.hovered_top_tab_index == Some(index);
                                 let selected_bg = theme.colors().text.opacity(0.14);
@@ -356,7 +604,7 @@ impl BrowserView {
 
                                 let favicon_element = render_tab_favicon(
                                     SharedString::from(format!("browser-tab-favicon-{index}")),
-                                    favicon_url,
+                                    favicon_uri.as_deref(),
                                     cx,
                                 );
 
@@ -383,6 +631,16 @@ impl BrowserView {
                                     .on_click(cx.listener(move |this, _, window, cx| {
                                         this.switch_to_tab(index, window, cx);
                                     }))
+                                    .on_drag(
+                                        DraggedBrowserTab {
+                                            index,
+                                            title: tab_data.title().to_string().into(),
+                                        },
+                                        {
+                                            let view = view.clone();
+                                            move |tab, _, _, cx| start_tab_drag(&view, tab, cx)
+                                        },
+                                    )
                                     .on_mouse_down(MouseButton::Right, move |event, window, cx| {
                                         show_tab_context_menu(
                                             context_view.clone(),
@@ -394,6 +652,10 @@ impl BrowserView {
                                         );
                                     })
                                     .child(favicon_element)
+                                    .when_some(
+                                        self.render_tab_preview(index, cx),
+                                        |this, preview| this.child(preview),
+                                    )
                                     .child(
                                         native_tracking_view(format!("browser-tab-track-{index}"))
                                             .on_mouse_enter(move |_, _window, cx| {
@@ -403,6 +665,7 @@ impl BrowserView {
                                                         {
                                                             this.hovered_top_tab_index =
                                                                 Some(index);
+                                                            this.schedule_tab_preview(index, cx);
                                                             cx.notify();
                                                         }
                                                     })
@@ -419,6 +682,7 @@ impl BrowserView {
                                                                 this.hovered_top_tab_index = None;
                                                                 this.hovered_top_tab_close_index =
                                                                     None;
+                                                                this.dismiss_tab_preview(cx);
                                                                 cx.notify();
                                                             }
                                                         })
@@ -435,134 +699,215 @@ impl BrowserView {
                         )),
                 )
             })
+            .when(pinned_count > 0 && has_unpinned_tabs, |this| {
+                this.child(
+                    div()
+                        .id("browser-pinned-tabs-separator")
+                        .w(px(1.))
+                        .h(px(16.))
+                        .mx_0p5()
+                        .flex_shrink_0()
+                        .bg(theme.colors().border.opacity(0.6)),
+                )
+            })
+            .when(can_scroll_left || can_scroll_right, |this| {
+                this.child(render_tab_strip_scroll_button(
+                    "browser-tab-strip-scroll-left",
+                    IconName::ChevronLeft,
+                    "Scroll Tabs Left",
+                    can_scroll_left,
+                    -TAB_STRIP_SCROLL_STEP,
+                    cx,
+                ))
+            })
             // Unpinned tabs
-            .children(
-                self.tabs
-                    .iter()
-                    .enumerate()
-                    .skip(pinned_count)
-                    .map(|(index, tab)| {
-                        let tab_data = tab.read(cx);
-                        let title = tab_data.title().to_string();
-                        let favicon_url = tab_data.favicon_url();
-                        let is_pinned = tab_data.is_pinned();
-                        let is_active = index == active_index;
-                        let is_hovered = self.hovered_top_tab_index == Some(index);
-                        let is_close_hovered = self.hovered_top_tab_close_index == Some(index);
-                        let selected_bg = theme.colors().text.opacity(0.14);
-                        let hover_bg = theme.colors().text.opacity(0.09);
+            .child(
+                h_flex()
+                    .id("browser-unpinned-tabs")
+                    .min_w_0()
+                    .flex_shrink()
+                    .gap_1()
+                    .map(|this| {
+                        if scrolls_tabs {
+                            this.overflow_x_scroll()
+                                .track_scroll(&self.tab_strip_scroll_handle)
+                        } else {
+                            this.overflow_hidden()
+                        }
+                    })
+                    .children(self.tabs.iter().enumerate().skip(pinned_count).map(
+                        |(index, tab)| {
+                            let tab_data = tab.read(cx);
+                            let title = tab_data.title().to_string();
+                            let favicon_uri = tab_data.favicon_image_uri();
+                            let is_pinned = tab_data.is_pinned();
+                            let is_active = index == active_index;
+                            let is_hovered = self.hovered_top_tab_index == Some(index);
+                            let is_close_hovered = self.hovered_top_tab_close_index == Some(index);
+                            let selected_bg = theme.colors().text.opacity(0.14);
+                            let hover_bg = theme.colors().text.opacity(0.09);
 
-                        let favicon_element = render_tab_favicon(
-                            SharedString::from(format!("browser-tab-favicon-{index}")),
-                            favicon_url,
-                            cx,
-                        );
+                            let favicon_element = render_tab_favicon(
+                                SharedString::from(format!("browser-tab-favicon-{index}")),
+                                favicon_uri.as_deref(),
+                                cx,
+                            );
 
-                        let display_title = if title.len() > 24 {
-                            let truncated = match title.char_indices().nth(21) {
-                                Some((byte_index, _)) => &title[..byte_index],
-                                None => &title,
+                            let display_title = if title.len() > 24 {
+                                let truncated = match title.char_indices().nth(21) {
+                                    Some((byte_index, _)) => &title[..byte_index],
+                                    None => &title,
+                                };
+                                format!("{truncated}...")
+                            } else {
+                                title
                             };
-                            format!("{truncated}...")
-                        } else {
-                            title
-                        };
 
-                        let hover_view = view.clone();
-                        let context_view = view.clone();
-                        div()
-                            .id(("browser-tab-inner", index))
-                            .relative()
-                            .flex()
-                            .items_center()
-                            .h(px(24.))
-                            .px_2()
-                            .gap_1()
-                            .min_w(px(92.))
-                            .max_w(px(220.))
-                            .rounded(cx.theme().component_radius().tab.unwrap_or(px(8.0)))
-                            .cursor_pointer()
-                            .when(is_active, |this| this.bg(selected_bg))
-                            .when(is_hovered && !is_active, |this| this.bg(hover_bg))
-                            .when(!is_active, |this| {
-                                this.hover(move |style| style.bg(hover_bg))
-                            })
-                            .on_click(cx.listener(move |this, _, window, cx| {
-                                this.switch_to_tab(index, window, cx);
-                            }))
-                            .on_mouse_down(MouseButton::Right, move |event, window, cx| {
-                                show_tab_context_menu(
-                                    context_view.clone(),
-                                    index,
-                                    is_pinned,
-                                    event.position,
-                                    window,
-                                    cx,
-                                );
-                            })
-                            .child(favicon_element)
-                            .child(
-                                div()
-                                    .flex_1()
-                                    .overflow_hidden()
-                                    .whitespace_nowrap()
-                                    .text_ellipsis()
-                                    .text_size(rems(0.75))
-                                    .text_color(if is_active {
-                                        theme.colors().text
+                            let hover_view = view.clone();
+                            let context_view = view.clone();
+                            div()
+                                .id(("browser-tab-inner", index))
+                                .relative()
+                                .flex()
+                                .items_center()
+                                .h(px(24.))
+                                .px_2()
+                                .gap_1()
+                                .map(|this| {
+                                    if scrolls_tabs {
+                                        this.w(TAB_WIDTH).flex_shrink_0()
                                     } else {
-                                        theme.colors().text_muted
-                                    })
-                                    .child(display_title),
-                            )
-                            .when(is_hovered, |this| {
-                                let close_hover_view = view.clone();
-                                this.child(
+                                        this.flex_basis(TAB_WIDTH)
+                                            .flex_shrink()
+                                            .min_w(MIN_SHRUNK_TAB_WIDTH)
+                                    }
+                                })
+                                .rounded(cx.theme().component_radius().tab.unwrap_or(px(8.0)))
+                                .cursor_pointer()
+                                .when(is_active, |this| this.bg(selected_bg))
+                                .when(is_hovered && !is_active, |this| this.bg(hover_bg))
+                                .when(!is_active, |this| {
+                                    this.hover(move |style| style.bg(hover_bg))
+                                })
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.switch_to_tab(index, window, cx);
+                                }))
+                                .on_drag(
+                                    DraggedBrowserTab {
+                                        index,
+                                        title: display_title.clone().into(),
+                                    },
+                                    {
+                                        let view = view.clone();
+                                        move |tab, _, _, cx| start_tab_drag(&view, tab, cx)
+                                    },
+                                )
+                                .on_mouse_down(MouseButton::Right, move |event, window, cx| {
+                                    show_tab_context_menu(
+                                        context_view.clone(),
+                                        index,
+                                        is_pinned,
+                                        event.position,
+                                        window,
+                                        cx,
+                                    );
+                                })
+                                .child(favicon_element)
+                                .child(
                                     div()
-                                        .id(SharedString::from(format!("close-tab-{index}")))
-                                        .relative()
-                                        .flex()
-                                        .items_center()
-                                        .justify_center()
-                                        .w(px(16.))
-                                        .h(px(16.))
-                                        .rounded(
-                                            cx.theme().component_radius().button.unwrap_or(px(4.0)),
-                                        )
-                                        .cursor_pointer()
-                                        .when(is_close_hovered, |this| this.bg(hover_bg))
-                                        .on_click(cx.listener(move |this, _, window, cx| {
-                                            this.close_tab_at(index, window, cx);
-                                        }))
-                                        .child(
-                                            native_image_view(SharedString::from(format!(
-                                                "close-tab-icon-{index}"
-                                            )))
-                                            .sf_symbol("xmark")
-                                            .w(px(8.))
-                                            .h(px(8.)),
-                                        )
-                                        .child(
-                                            native_tracking_view(format!(
-                                                "close-tab-track-{index}"
-                                            ))
-                                            .on_mouse_enter(move |_, _window, cx| {
-                                                close_hover_view
-                                                    .update(cx, |this, cx| {
-                                                        if this.hovered_top_tab_close_index
-                                                            != Some(index)
-                                                        {
-                                                            this.hovered_top_tab_close_index =
-                                                                Some(index);
-                                                            cx.notify();
-                                                        }
-                                                    })
-                                                    .ok();
-                                            })
-                                            .on_mouse_exit({
-                                                let close_hover_view = view.clone();
-                                                move |_, _window, cx| {
+                                        .flex_1()
+                                        .overflow_hidden()
+                                        .whitespace_nowrap()
+                                        .text_ellipsis()
+                                        .text_size(rems(0.75))
+                                        .text_color(if is_active {
+                                            theme.colors().text
+                                        } else {
+                                            theme.colors().text_muted
+                                        })
+                                        .child(display_title),
+                                )
+                                .when(is_hovered, |this| {
+                                    this.child(
+                                        div()
+                                            .id(SharedString::from(format!("pin-tab-{index}")))
+                                            .flex()
+                                            .items_center()
+                                            .justify_center()
+                                            .w(px(16.))
+                                            .h(px(16.))
+                                            .rounded(
+                                                cx.theme()
+                                                    .component_radius()
+                                                    .button
+                                                    .unwrap_or(px(4.0)),
+                                            )
+                                            .cursor_pointer()
+                                            .hover(move |style| style.bg(hover_bg))
+                                            .on_click(cx.listener(move |this, _, _, cx| {
+                                                this.pin_tab_at(index, cx);
+                                            }))
+                                            .child(
+                                                native_image_view(SharedString::from(format!(
+                                                    "pin-tab-icon-{index}"
+                                                )))
+                                                .sf_symbol("pin")
+                                                .w(px(9.))
+                                                .h(px(9.)),
+                                            ),
+                                    )
+                                })
+                                .when(is_hovered, |this| {
+                                    let close_hover_view = view.clone();
+                                    this.child(
+                                        div()
+                                            .id(SharedString::from(format!("close-tab-{index}")))
+                                            .relative()
+                                            .flex()
+                                            .items_center()
+                                            .justify_center()
+                                            .w(px(16.))
+                                            .h(px(16.))
+                                            .rounded(
+                                                cx.theme()
+                                                    .component_radius()
+                                                    .button
+                                                    .unwrap_or(px(4.0)),
+                                            )
+                                            .cursor_pointer()
+                                            .when(is_close_hovered, |this| this.bg(hover_bg))
+                                            .on_click(cx.listener(move |this, _, window, cx| {
+                                                this.close_tab_at(index, window, cx);
+                                            }))
+                                            .child(
+                                                native_image_view(SharedString::from(format!(
+                                                    "close-tab-icon-{index}"
+                                                )))
+                                                .sf_symbol("xmark")
+                                                .w(px(8.))
+                                                .h(px(8.)),
+                                            )
+                                            .child(
+                                                native_tracking_view(format!(
+                                                    "close-tab-track-{index}"
+                                                ))
+                                                .on_mouse_enter(move |_, _window, cx| {
                                                     close_hover_view
+                                                        .update(cx, |this, cx| {
+                                                            if this.hovered_top_tab_close_index
+                                                                != Some(index)
+                                                            {
+                                                                this.hovered_top_tab_close_index =
+                                                                    Some(index);
+                                                                cx.notify();
+                                                            }
+                                                        })
+                                                        .ok();
+                                                })
+                                                .on_mouse_exit({
+                                                    let close_hover_view = view.clone();
+                                                    move |_, _window, cx| {
+                                                        close_hover_view
                                                         .update(cx, |this, cx| {
                                                             if this.hovered_top_tab_close_index
                                                                 == Some(index)
@@ -573,49 +918,66 @@ impl BrowserView {
                                                             }
                                                         })
                                                         .ok();
-                                                }
-                                            })
-                                            .absolute()
-                                            .top_0()
-                                            .left_0()
-                                            .size_full(),
-                                        ),
-                                )
-                            })
-                            .child(
-                                native_tracking_view(format!("browser-tab-track-{index}"))
-                                    .on_mouse_enter(move |_, _window, cx| {
-                                        hover_view
-                                            .update(cx, |this, cx| {
-                                                if this.hovered_top_tab_index != Some(index) {
-                                                    this.hovered_top_tab_index = Some(index);
-                                                    cx.notify();
-                                                }
-                                            })
-                                            .ok();
-                                    })
-                                    .on_mouse_exit({
-                                        let hover_view = view.clone();
-                                        move |_, _window, cx| {
+                                                    }
+                                                })
+                                                .absolute()
+                                                .top_0()
+                                                .left_0()
+                                                .size_full(),
+                                            ),
+                                    )
+                                })
+                                .when_some(self.render_tab_preview(index, cx), |this, preview| {
+                                    this.child(preview)
+                                })
+                                .child(
+                                    native_tracking_view(format!("browser-tab-track-{index}"))
+                                        .on_mouse_enter(move |_, _window, cx| {
                                             hover_view
                                                 .update(cx, |this, cx| {
-                                                    if this.hovered_top_tab_index == Some(index) {
-                                                        this.hovered_top_tab_index = None;
-                                                        this.hovered_top_tab_close_index = None;
+                                                    if this.hovered_top_tab_index != Some(index) {
+                                                        this.hovered_top_tab_index = Some(index);
+                                                        this.schedule_tab_preview(index, cx);
                                                         cx.notify();
                                                     }
                                                 })
                                                 .ok();
-                                        }
-                                    })
-                                    .absolute()
-                                    .top_0()
-                                    .left_0()
-                                    .size_full(),
-                            )
-                            .into_any_element()
-                    }),
+                                        })
+                                        .on_mouse_exit({
+                                            let hover_view = view.clone();
+                                            move |_, _window, cx| {
+                                                hover_view
+                                                    .update(cx, |this, cx| {
+                                                        if this.hovered_top_tab_index == Some(index)
+                                                        {
+                                                            this.hovered_top_tab_index = None;
+                                                            this.hovered_top_tab_close_index = None;
+                                                            this.dismiss_tab_preview(cx);
+                                                            cx.notify();
+                                                        }
+                                                    })
+                                                    .ok();
+                                            }
+                                        })
+                                        .absolute()
+                                        .top_0()
+                                        .left_0()
+                                        .size_full(),
+                                )
+                                .into_any_element()
+                        },
+                    )),
             )
+            .when(can_scroll_left || can_scroll_right, |this| {
+                this.child(render_tab_strip_scroll_button(
+                    "browser-tab-strip-scroll-right",
+                    IconName::ChevronRight,
+                    "Scroll Tabs Right",
+                    can_scroll_right,
+                    TAB_STRIP_SCROLL_STEP,
+                    cx,
+                ))
+            })
             .child(
                 div()
                     .id("new-tab-button")
@@ -675,6 +1037,169 @@ impl BrowserView {
             )
     }
 
+    #[cfg(not(target_os = "macos"))]
+    pub(super) fn scroll_tab_strip_to_active_tab(&self, cx: &App) {
+        let pinned_count = self
+            .tabs
+            .iter()
+            .filter(|tab| tab.read(cx).is_pinned())
+            .count();
+        if BrowserSettings::get_global(cx).tab_overflow == BrowserTabOverflow::Scroll
+            && self.active_tab_index >= pinned_count
+        {
+            self.tab_strip_scroll_handle
+                .scroll_to_item(self.active_tab_index - pinned_count);
+        }
+    }
+
+    /// Switches tabs as the wheel scrolls over the tab strip, in either axis.
+    /// Strips that scroll their overflowing tabs keep the wheel for scrolling.
+    #[cfg(not(target_os = "macos"))]
+    fn handle_tab_strip_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.stop_propagation();
+        if event.touch_phase == TouchPhase::Started {
+            self.tab_wheel_distance = px(0.);
+        }
+
+        let delta = event.delta.pixel_delta(TAB_WHEEL_SWITCH_DISTANCE);
+        self.tab_wheel_distance += if delta.x.abs() > delta.y.abs() {
+            delta.x
+        } else {
+            delta.y
+        };
+        if self.tab_wheel_distance.abs() < TAB_WHEEL_SWITCH_DISTANCE {
+            return;
+        }
+
+        let forward = self.tab_wheel_distance < px(0.);
+        self.tab_wheel_distance = px(0.);
+        let now = Instant::now();
+        if self
+            .last_tab_wheel_switch
+            .is_some_and(|last_switch| now.duration_since(last_switch) < TAB_WHEEL_SWITCH_INTERVAL)
+        {
+            return;
+        }
+
+        let wrap = BrowserSettings::get_global(cx).tab_scroll_wraps;
+        if let Some(index) =
+            adjacent_tab_index(self.active_tab_index, self.tabs.len(), forward, wrap)
+        {
+            self.last_tab_wheel_switch = Some(now);
+            self.switch_to_tab(index, window, cx);
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn scroll_tab_strip_by(&mut self, delta: Pixels, cx: &mut Context<Self>) {
+        let offset = self.tab_strip_scroll_handle.offset();
+        let max_offset = self.tab_strip_scroll_handle.max_offset().x;
+        let x = (offset.x - delta).clamp(-max_offset, px(0.));
+        self.tab_strip_scroll_handle.set_offset(point(x, offset.y));
+        cx.notify();
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn schedule_tab_preview(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.tab_preview_index = None;
+        if index == self.active_tab_index {
+            self._tab_preview_task = None;
+            return;
+        }
+
+        self._tab_preview_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(TAB_PREVIEW_DELAY).await;
+            this.update(cx, |this, cx| {
+                if this.hovered_top_tab_index == Some(index) && index != this.active_tab_index {
+                    this.tab_preview_index = Some(index);
+                    cx.notify();
+                }
+            })
+            .ok();
+        }));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub(super) fn dismiss_tab_preview(&mut self, cx: &mut Context<Self>) {
+        self._tab_preview_task = None;
+        if self.tab_preview_index.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn render_tab_preview(&self, index: usize, cx: &App) -> Option<gpui::AnyElement> {
+        if self.tab_preview_index != Some(index) || index == self.active_tab_index {
+            return None;
+        }
+
+        let tab = self.tabs.get(index)?.read(cx);
+        let theme = cx.theme();
+        let title = if tab.title().is_empty() {
+            tab.url().to_string()
+        } else {
+            tab.title().to_string()
+        };
+        let url = tab.url().to_string();
+        let thumbnail = tab.thumbnail();
+        let radius = theme.component_radius().panel.unwrap_or(px(8.0));
+
+        Some(
+            deferred(
+                anchored()
+                    .anchor(Corner::TopLeft)
+                    .offset(point(px(0.), px(30.)))
+                    .snap_to_window_with_margin(px(8.))
+                    .child(
+                        v_flex()
+                            .id(("browser-tab-preview", index))
+                            .w(px(240.))
+                            .p_1p5()
+                            .gap_1()
+                            .rounded(radius)
+                            .bg(theme.colors().elevated_surface_background)
+                            .border_1()
+                            .border_color(theme.colors().border)
+                            .shadow_md()
+                            .when_some(thumbnail, |this, frame| {
+                                this.child(
+                                    div()
+                                        .w_full()
+                                        .h(px(135.))
+                                        .overflow_hidden()
+                                        .rounded(radius)
+                                        .child(
+                                            surface(frame).size_full().object_fit(ObjectFit::Cover),
+                                        ),
+                                )
+                            })
+                            .child(
+                                div()
+                                    .text_size(rems(0.75))
+                                    .text_color(theme.colors().text)
+                                    .child(title),
+                            )
+                            .child(
+                                div()
+                                    .overflow_hidden()
+                                    .whitespace_nowrap()
+                                    .text_ellipsis()
+                                    .text_size(rems(0.6875))
+                                    .text_color(theme.colors().text_muted)
+                                    .child(url),
+                            ),
+                    ),
+            )
+            .with_priority(1)
+            .into_any_element(),
+        )
+    }
+
     pub fn ensure_native_sidebar_panel(
         &mut self,
         cx: &mut Context<Self>,
@@ -729,7 +1254,7 @@ impl BrowserView {
                                         .children(row.into_iter().map(|index| {
                                             let tab = &self.tabs[index];
                                             let tab_data = tab.read(cx);
-                                            let favicon_url = tab_data.favicon_url();
+                                            let favicon_uri = tab_data.favicon_image_uri();
                                             let is_active = index == active_index;
                                             let is_hovered =
                                                 self.hovered_sidebar_tab_index == Some(index);
@@ -741,7 +1266,7 @@ impl BrowserView {
                                                 SharedString::from(format!(
                                                     "sidebar-tab-favicon-{index}"
                                                 )),
-                                                favicon_url,
+                                                favicon_uri.as_deref(),
                                                 cx,
                                             );
 
@@ -843,7 +1368,7 @@ impl BrowserView {
                         |(index, tab)| {
                             let tab_data = tab.read(cx);
                             let title = tab_data.title().to_string();
-                            let favicon_url = tab_data.favicon_url();
+                            let favicon_uri = tab_data.favicon_image_uri();
                             let is_pinned = tab_data.is_pinned();
                             let is_active = index == active_index;
                             let is_hovered = self.hovered_sidebar_tab_index == Some(index);
@@ -854,7 +1379,7 @@ impl BrowserView {
 
                             let favicon_element = render_tab_favicon(
                                 SharedString::from(format!("sidebar-tab-favicon-{index}")),
-                                favicon_url,
+                                favicon_uri.as_deref(),
                                 cx,
                             );
 
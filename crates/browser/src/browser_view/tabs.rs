@@ -1,23 +1,34 @@
+use crate::browser_settings::{BrowserCloseLastTab, BrowserSettings};
 use crate::cef_instance::CefInstance;
+use crate::events::BrowserTabOpenTarget;
 use crate::session::SerializedTab;
 use crate::tab::BrowserTab;
 use gpui::{App, AppContext as _, Context, Entity, Task, Window};
+use settings::Settings as _;
 use std::time::Duration;
+use workspace_modes::ModeId;
 
 use super::{
-    BrowserView, CloseTab, MAX_CLOSED_TABS, NewTab, NextTab, PendingTabOpenRequest, PreviousTab,
-    ReopenClosedTab,
+    ActivateLastTab, ActivateTab, BrowserView, CloseTab, MAX_CLOSED_TABS, NewTab, NextTab,
+    PendingTabOpenRequest, PinTab, PreviousTab, ReopenClosedTab, TogglePinTab, UnpinTab,
+    text_to_url,
 };
 #[cfg(not(target_os = "macos"))]
 use super::{TabBarMode, ToggleSidebar};
 
 impl BrowserView {
     pub(crate) fn add_tab(&mut self, cx: &mut Context<Self>) {
-        let tab = cx.new(|cx| BrowserTab::new(cx));
+        if let Some(new_tab_url) = configured_new_tab_url(cx) {
+            self.open_url(&new_tab_url, cx);
+            return;
+        }
+
+        let site_permissions = self.site_permissions.clone();
+        let site_user_agents = self.site_user_agents.clone();
+        let tab = cx.new(|cx| BrowserTab::new(site_permissions, site_user_agents, cx));
         self.configure_tab_request_context(&tab, cx);
 
-        let subscription = cx.subscribe(&tab, Self::handle_tab_event);
-        self._subscriptions.push(subscription);
+        self.subscribe_to_tab(&tab, cx);
 
         self.tabs.push(tab);
         self.set_active_tab_index(self.tabs.len() - 1);
@@ -32,15 +43,16 @@ impl BrowserView {
             self.message_pump_started,
             self.last_viewport
         );
+        let site_permissions = self.site_permissions.clone();
+        let site_user_agents = self.site_user_agents.clone();
         let tab = cx.new(|cx| {
-            let mut tab = BrowserTab::new(cx);
+            let mut tab = BrowserTab::new(site_permissions, site_user_agents, cx);
             tab.set_new_tab_page(false);
             tab.set_pending_url(url.to_string());
             tab
         });
         self.configure_tab_request_context(&tab, cx);
-        let subscription = cx.subscribe(&tab, Self::handle_tab_event);
-        self._subscriptions.push(subscription);
+        self.subscribe_to_tab(&tab, cx);
 
         let tab_ref = tab.clone();
         self.tabs.push(tab);
@@ -58,16 +70,38 @@ impl BrowserView {
         cx.notify();
     }
 
+    /// Opens text typed or linked from elsewhere in Glass, normalizing bare
+    /// domains and search terms the same way the new tab page does.
+    pub fn open_url_from_text(&mut self, text: &str, background: bool, cx: &mut Context<Self>) {
+        let url = text_to_url(text.trim(), cx);
+        if background {
+            self.queue_tab_open(url, BrowserTabOpenTarget::Background, cx);
+            return;
+        }
+
+        if let Some(tab) = self.active_tab().cloned() {
+            tab.update(cx, |tab, cx| {
+                tab.navigate(&url, cx);
+                tab.set_focus(true);
+            });
+            self.pending_toolbar_sync = true;
+            cx.notify();
+        } else {
+            self.open_url(&url, cx);
+        }
+    }
+
     fn create_tab_for_url(&mut self, url: &str, cx: &mut Context<Self>) -> Entity<BrowserTab> {
+        let site_permissions = self.site_permissions.clone();
+        let site_user_agents = self.site_user_agents.clone();
         let tab = cx.new(|cx| {
-            let mut tab = BrowserTab::new(cx);
+            let mut tab = BrowserTab::new(site_permissions, site_user_agents, cx);
             tab.set_new_tab_page(false);
             tab.set_pending_url(url.to_string());
             tab
         });
         self.configure_tab_request_context(&tab, cx);
-        let subscription = cx.subscribe(&tab, Self::handle_tab_event);
-        self._subscriptions.push(subscription);
+        self.subscribe_to_tab(&tab, cx);
         tab
     }
 
@@ -93,9 +127,10 @@ impl BrowserView {
         match request.target {
             crate::events::BrowserTabOpenTarget::Foreground => {
                 if let Some(old_tab) = self.active_tab().cloned() {
-                    old_tab.update(cx, |tab, _| {
+                    old_tab.update(cx, |tab, cx| {
                         tab.set_focus(false);
                         tab.set_hidden(true);
+                        tab.set_background(true, cx);
                     });
                 }
 
@@ -168,6 +203,7 @@ impl BrowserView {
             return;
         };
 
+        new_tab.update(cx, |tab, cx| tab.set_background(false, cx));
         if new_tab.read(cx).is_new_tab_page() {
             return;
         }
@@ -234,14 +270,20 @@ impl BrowserView {
         }
 
         if let Some(old_tab) = self.active_tab().cloned() {
-            self.clear_find_for_tab_switch(&old_tab, window, cx);
-            old_tab.update(cx, |tab, _| {
+            old_tab.update(cx, |tab, cx| {
+                tab.capture_thumbnail(cx);
+                tab.capture_scroll_position();
                 tab.set_focus(false);
                 tab.set_hidden(true);
+                tab.set_background(true, cx);
             });
         }
 
         self.set_active_tab_index(index);
+        #[cfg(not(target_os = "macos"))]
+        self.dismiss_tab_preview(cx);
+        #[cfg(not(target_os = "macos"))]
+        self.scroll_tab_strip_to_active_tab(cx);
 
         if let Some(new_tab) = self.active_tab() {
             let is_suspended = new_tab.read(cx).is_suspended();
@@ -256,16 +298,17 @@ impl BrowserView {
                 has_browser,
                 is_suspended,
             );
+            new_tab.update(cx, |tab, cx| tab.set_background(false, cx));
 
             if is_suspended {
                 let new_tab = new_tab.clone();
                 let (width, height, scale_factor) = self.current_dimensions(window);
                 let url = new_tab.read(cx).url().to_string();
-                new_tab.update(cx, |tab, _| {
+                new_tab.update(cx, |tab, cx| {
                     tab.set_scale_factor(scale_factor);
                     tab.set_size(width, height);
                     // Resume un-hides and un-mutes; the page is still loaded
-                    tab.resume();
+                    tab.resume(cx);
                     if !tab.has_browser() && width > 0 && height > 0 {
                         log::warn!(
                             "[browser::tabs] suspended tab had no browser, creating: {}",
@@ -281,6 +324,7 @@ impl BrowserView {
                     }
                     tab.set_focus(true);
                     tab.invalidate();
+                    tab.restore_scroll_position();
                 });
             } else if !is_new_tab_page {
                 let has_pending = new_tab.read(cx).has_pending_url();
@@ -292,6 +336,7 @@ impl BrowserView {
                         tab.take_pending_url();
                         tab.set_hidden(false);
                         tab.set_focus(true);
+                        tab.restore_scroll_position();
                     });
                 } else {
                     let (width, height, scale_factor) = self.current_dimensions(window);
@@ -311,6 +356,7 @@ impl BrowserView {
                         }
                         tab.set_hidden(false);
                         tab.set_focus(true);
+                        tab.restore_scroll_position();
                     });
                 }
             }
@@ -417,6 +463,7 @@ impl BrowserView {
             is_new_tab_page: tab.is_new_tab_page(),
             is_pinned: tab.is_pinned(),
             favicon_url: tab.favicon_url().map(|s| s.to_string()),
+            scroll_position: tab.scroll_position().cloned(),
         });
         if self.closed_tabs.len() > MAX_CLOSED_TABS {
             self.closed_tabs.remove(0);
@@ -429,30 +476,35 @@ impl BrowserView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Some(tab) = self.active_tab() {
-            if tab.read(cx).is_pinned() {
+        let Some(tab) = self.active_tab().cloned() else {
+            return;
+        };
+        let outcome = close_active_tab_outcome(
+            self.tabs.len(),
+            tab.read(cx).is_pinned(),
+            BrowserSettings::get_global(cx).close_last_tab,
+        );
+        match outcome {
+            CloseActiveTabOutcome::SuspendPinnedTab => {
                 self.close_pinned_tab(window, cx);
                 return;
             }
+            CloseActiveTabOutcome::CloseWindow => {
+                window.dispatch_action(Box::new(workspace::CloseWindow), cx);
+                return;
+            }
+            CloseActiveTabOutcome::CloseTab | CloseActiveTabOutcome::ReplaceWithNewTab => {}
         }
 
-        if let Some(tab) = self.active_tab().cloned() {
-            self.clear_find_for_tab_switch(&tab, window, cx);
-        }
-
-        if let Some(tab) = self.active_tab().cloned() {
-            self.push_closed_tab(&tab, cx);
-        }
-
-        if let Some(tab) = self.active_tab().cloned() {
-            tab.update(cx, |tab, _| {
-                tab.close_browser();
-            });
-        }
+        self.push_closed_tab(&tab, cx);
+        tab.update(cx, |tab, _| {
+            tab.close_browser();
+        });
 
-        if self.tabs.len() <= 1 {
+        if outcome == CloseActiveTabOutcome::ReplaceWithNewTab {
             self.tabs.pop();
             self.set_active_tab_index(0);
+            self.discard_incognito_session(cx);
             self.add_tab(cx);
 
             self.update_toolbar_active_tab(window, cx);
@@ -464,7 +516,8 @@ impl BrowserView {
         }
 
         let closed_index = self.active_tab_index;
-        self.tabs.remove(closed_index);
+        let closed_tab = self.tabs.remove(closed_index);
+        self.tab_subscriptions.remove(&closed_tab.entity_id());
 
         if closed_index >= self.tabs.len() {
             self.set_active_tab_index(self.tabs.len() - 1);
@@ -492,12 +545,10 @@ impl BrowserView {
             self.active_tab_index,
         );
 
-        self.clear_find_for_tab_switch(&tab, window, cx);
-
         // Suspend hides and mutes the browser, leaving the page fully loaded
         // so all cookies, localStorage, and session state are preserved.
-        tab.update(cx, |tab, _| {
-            tab.suspend();
+        tab.update(cx, |tab, cx| {
+            tab.suspend(cx);
         });
 
         let next_index = self.find_next_unpinned_tab(cx);
@@ -545,10 +596,24 @@ impl BrowserView {
         let title = closed.title.clone();
         let favicon_url = closed.favicon_url.clone();
 
-        let tab = cx.new(|cx| BrowserTab::new_with_state(url, title, false, favicon_url, cx));
+        let scroll_position = closed.scroll_position.clone();
+        let site_permissions = self.site_permissions.clone();
+        let site_user_agents = self.site_user_agents.clone();
+        let tab = cx.new(|cx| {
+            let mut tab = BrowserTab::new_with_state(
+                url,
+                title,
+                false,
+                favicon_url,
+                site_permissions,
+                site_user_agents,
+                cx,
+            );
+            tab.set_restored_scroll_position(scroll_position);
+            tab
+        });
         self.configure_tab_request_context(&tab, cx);
-        let subscription = cx.subscribe(&tab, Self::handle_tab_event);
-        self._subscriptions.push(subscription);
+        self.subscribe_to_tab(&tab, cx);
         self.tabs.push(tab.clone());
         self.set_active_tab_index(self.tabs.len() - 1);
 
@@ -565,11 +630,11 @@ impl BrowserView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if self.tabs.len() <= 1 {
-            return;
+        if let Some(next_index) =
+            adjacent_tab_index(self.active_tab_index, self.tabs.len(), true, true)
+        {
+            self.switch_to_tab(next_index, window, cx);
         }
-        let next_index = (self.active_tab_index + 1) % self.tabs.len();
-        self.switch_to_tab(next_index, window, cx);
     }
 
     pub(super) fn handle_previous_tab(
@@ -578,31 +643,88 @@ impl BrowserView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if self.tabs.len() <= 1 {
-            return;
+        if let Some(previous_index) =
+            adjacent_tab_index(self.active_tab_index, self.tabs.len(), false, true)
+        {
+            self.switch_to_tab(previous_index, window, cx);
         }
-        let previous_index = if self.active_tab_index == 0 {
-            self.tabs.len() - 1
-        } else {
-            self.active_tab_index - 1
+    }
+
+    pub(super) fn handle_activate_tab(
+        &mut self,
+        action: &ActivateTab,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.switch_to_tab(action.0, window, cx);
+    }
+
+    pub(super) fn handle_activate_last_tab(
+        &mut self,
+        _: &ActivateLastTab,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(last_index) = self.tabs.len().checked_sub(1) else {
+            return;
         };
-        self.switch_to_tab(previous_index, window, cx);
+        self.switch_to_tab(last_index, window, cx);
     }
 
     #[cfg(not(target_os = "macos"))]
     pub(crate) fn toggle_sidebar(&mut self, cx: &mut Context<Self>) {
-        match self.tab_bar_mode {
-            TabBarMode::Horizontal => {
-                self.tab_bar_mode = TabBarMode::Sidebar;
-                self.sidebar_collapsed = false;
-                self.set_sidebar_visibility(true, cx);
-            }
-            TabBarMode::Sidebar => {
-                self.tab_bar_mode = TabBarMode::Horizontal;
-                self.sidebar_collapsed = false;
-                self.set_sidebar_visibility(false, cx);
-            }
+        let tab_bar_mode = match self.tab_bar_mode {
+            TabBarMode::Horizontal => TabBarMode::Sidebar,
+            TabBarMode::Sidebar => TabBarMode::Horizontal,
+        };
+        self.set_tab_bar_mode(tab_bar_mode, cx);
+    }
+
+    pub(super) fn sync_default_zoom_with_settings(&mut self, cx: &mut Context<Self>) {
+        let browser_settings = BrowserSettings::get_global(cx);
+        if self.default_zoom == browser_settings.default_zoom {
+            return;
         }
+        self.default_zoom = browser_settings.default_zoom;
+        let zoom_level = browser_settings.default_zoom_level();
+        for tab in &self.tabs {
+            tab.update(cx, |tab, _| tab.set_default_zoom_level(zoom_level));
+        }
+    }
+
+    pub(super) fn sync_background_throttling_with_settings(&mut self, cx: &mut Context<Self>) {
+        let throttle_background_tabs = BrowserSettings::get_global(cx).throttle_background_tabs;
+        if self.throttle_background_tabs == throttle_background_tabs {
+            return;
+        }
+        self.throttle_background_tabs = throttle_background_tabs;
+        for tab in &self.tabs {
+            tab.update(cx, |tab, _| {
+                tab.set_throttle_in_background(throttle_background_tabs)
+            });
+        }
+    }
+
+    /// Switches to the `browser.tab_layout` layout when the setting changes,
+    /// leaving a layout picked with `ToggleSidebar` alone otherwise.
+    #[cfg(not(target_os = "macos"))]
+    pub(super) fn sync_tab_layout_with_settings(&mut self, cx: &mut Context<Self>) {
+        let tab_layout = BrowserSettings::get_global(cx).tab_layout;
+        if self.tab_layout == tab_layout {
+            return;
+        }
+        self.tab_layout = tab_layout;
+        self.set_tab_bar_mode(tab_layout.into(), cx);
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn set_tab_bar_mode(&mut self, tab_bar_mode: TabBarMode, cx: &mut Context<Self>) {
+        if self.tab_bar_mode == tab_bar_mode {
+            return;
+        }
+        self.tab_bar_mode = tab_bar_mode;
+        self.sidebar_collapsed = false;
+        self.set_sidebar_visibility(tab_bar_mode == TabBarMode::Sidebar, cx);
         self.hovered_top_tab_index = None;
         self.hovered_top_tab_close_index = None;
         self.hovered_top_new_tab_button = false;
@@ -634,12 +756,6 @@ impl BrowserView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if index == self.active_tab_index {
-            if let Some(tab) = self.active_tab().cloned() {
-                self.clear_find_for_tab_switch(&tab, window, cx);
-            }
-        }
-
         self.close_tab_at_inner(index, cx);
         self.update_toolbar_active_tab(window, cx);
     }
@@ -668,7 +784,8 @@ impl BrowserView {
             return;
         }
 
-        self.tabs.remove(index);
+        let closed_tab = self.tabs.remove(index);
+        self.tab_subscriptions.remove(&closed_tab.entity_id());
 
         if index < self.active_tab_index {
             self.set_active_tab_index(self.active_tab_index - 1);
@@ -679,6 +796,98 @@ impl BrowserView {
             self.activate_tab_for_close(cx);
         }
 
+        self.clear_tab_hover_state(index, cx);
+        self.sync_bookmark_bar_visibility(cx);
+        self.schedule_save(cx);
+        cx.notify();
+    }
+
+    /// Moves the tab at `index` into a new Glass window in browser mode. The tab
+    /// keeps its browser, so its URL and back/forward history come along.
+    pub fn detach_tab_to_new_window(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index >= self.tabs.len() || self.is_incognito_window {
+            return;
+        }
+        let Some(app_state) = workspace::AppState::global(cx).upgrade() else {
+            return;
+        };
+
+        let was_active = index == self.active_tab_index;
+        let tab = self.tabs.remove(index);
+        self.tab_subscriptions.remove(&tab.entity_id());
+        tab.update(cx, |tab, _| {
+            tab.set_focus(false);
+            tab.set_hidden(true);
+        });
+
+        if self.tabs.is_empty() {
+            self.set_active_tab_index(0);
+            self.add_tab(cx);
+        } else if index < self.active_tab_index {
+            self.set_active_tab_index(self.active_tab_index - 1);
+        } else if was_active {
+            if self.active_tab_index >= self.tabs.len() {
+                self.set_active_tab_index(self.tabs.len() - 1);
+            }
+            self.activate_tab_for_close(cx);
+        }
+
+        self.clear_tab_hover_state(index, cx);
+        self.pending_toolbar_sync = true;
+        self.sync_bookmark_bar_visibility(cx);
+        self.schedule_save(cx);
+        cx.notify();
+
+        workspace::open_new(
+            workspace::OpenOptions::default(),
+            app_state,
+            cx,
+            move |workspace, window, cx| {
+                workspace.switch_to_mode(ModeId::BROWSER, window, cx);
+                if let Some(browser_view) = crate::workspace_browser_view(workspace) {
+                    browser_view.update(cx, |browser_view, cx| {
+                        browser_view.adopt_tab(tab, cx);
+                        browser_view.update_toolbar_active_tab(window, cx);
+                    });
+                }
+            },
+        )
+        .detach_and_log_err(cx);
+    }
+
+    /// Takes ownership of a tab detached from another window, replacing the
+    /// blank new tab page a freshly opened window starts with.
+    fn adopt_tab(&mut self, tab: Entity<BrowserTab>, cx: &mut Context<Self>) {
+        if let Some(index) = self.tabs.iter().position(|existing| {
+            let existing = existing.read(cx);
+            !existing.is_pinned() && existing.is_new_tab_page()
+        }) {
+            let new_tab_page = self.tabs.remove(index);
+            new_tab_page.update(cx, |tab, _| tab.close_browser());
+            self.tab_subscriptions.remove(&new_tab_page.entity_id());
+        }
+        self.subscribe_to_tab(&tab, cx);
+
+        self.tabs.push(tab.clone());
+        self.sort_tabs_pinned_first(cx);
+        let index = self
+            .tabs
+            .iter()
+            .position(|existing| existing == &tab)
+            .unwrap_or(0);
+        self.set_active_tab_index(index);
+        tab.update(cx, |tab, cx| {
+            tab.set_background(false, cx);
+            tab.set_hidden(false);
+            tab.set_focus(true);
+        });
+        self.pending_toolbar_sync = true;
+        self.sync_bookmark_bar_visibility(cx);
+        self.schedule_save(cx);
+        cx.notify();
+    }
+
+    fn clear_tab_hover_state(&mut self, index: usize, cx: &mut Context<Self>) {
         if self.hovered_top_tab_index == Some(index) {
             self.hovered_top_tab_index = None;
         }
@@ -686,6 +895,8 @@ impl BrowserView {
             self.hovered_top_tab_close_index = None;
         }
         #[cfg(not(target_os = "macos"))]
+        self.dismiss_tab_preview(cx);
+        #[cfg(not(target_os = "macos"))]
         if self.hovered_sidebar_tab_index == Some(index) {
             self.hovered_sidebar_tab_index = None;
         }
@@ -698,16 +909,42 @@ impl BrowserView {
                 sidebar_panel.clear_hover_state(cx);
             });
         }
-
-        self.sync_bookmark_bar_visibility(cx);
-        self.schedule_save(cx);
-        cx.notify();
     }
 
     pub(super) fn sort_tabs_pinned_first(&mut self, cx: &App) {
         self.tabs.sort_by_key(|tab| !tab.read(cx).is_pinned());
     }
 
+    pub(super) fn handle_pin_tab(&mut self, _: &PinTab, _: &mut Window, cx: &mut Context<Self>) {
+        self.pin_tab_at(self.active_tab_index, cx);
+    }
+
+    pub(super) fn handle_unpin_tab(
+        &mut self,
+        _: &UnpinTab,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.unpin_tab_at(self.active_tab_index, cx);
+    }
+
+    pub(super) fn handle_toggle_pin_tab(
+        &mut self,
+        _: &TogglePinTab,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let index = self.active_tab_index;
+        let Some(tab) = self.tabs.get(index) else {
+            return;
+        };
+        if tab.read(cx).is_pinned() {
+            self.unpin_tab_at(index, cx);
+        } else {
+            self.pin_tab_at(index, cx);
+        }
+    }
+
     pub(super) fn pin_tab_at(&mut self, index: usize, cx: &mut Context<Self>) {
         if index >= self.tabs.len() {
             return;
@@ -750,6 +987,8 @@ impl BrowserView {
         cx.notify();
     }
 
+    /// Closes every unpinned tab except the one at `keep_index`, which becomes
+    /// the active tab. Pinned tabs always stay open.
     pub(super) fn close_other_tabs_at(&mut self, keep_index: usize, cx: &mut Context<Self>) {
         if keep_index >= self.tabs.len() {
             return;
@@ -775,3 +1014,115 @@ impl BrowserView {
         cx.notify();
     }
 }
+
+/// The homepage new tabs open to, or `None` when they should show the built-in
+/// new tab page.
+fn configured_new_tab_url(cx: &App) -> Option<String> {
+    let new_tab_url = BrowserSettings::get_global(cx)
+        .new_tab_url
+        .as_deref()?
+        .trim();
+    if new_tab_url.is_empty() || new_tab_url == "glass://newtab" {
+        return None;
+    }
+    Some(text_to_url(new_tab_url, cx))
+}
+
+/// The tab next to `active_index` in the given direction, or `None` when there
+/// is nowhere to go without wrapping.
+pub(super) fn adjacent_tab_index(
+    active_index: usize,
+    tab_count: usize,
+    forward: bool,
+    wrap: bool,
+) -> Option<usize> {
+    if tab_count <= 1 {
+        return None;
+    }
+    if forward {
+        if active_index + 1 < tab_count {
+            Some(active_index + 1)
+        } else {
+            wrap.then_some(0)
+        }
+    } else if active_index > 0 {
+        Some(active_index - 1)
+    } else {
+        wrap.then_some(tab_count - 1)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CloseActiveTabOutcome {
+    /// Pinned tabs are never closed from the keyboard. They are suspended and
+    /// the next unpinned tab is activated instead.
+    SuspendPinnedTab,
+    CloseTab,
+    ReplaceWithNewTab,
+    CloseWindow,
+}
+
+fn close_active_tab_outcome(
+    tab_count: usize,
+    active_tab_is_pinned: bool,
+    close_last_tab: BrowserCloseLastTab,
+) -> CloseActiveTabOutcome {
+    if active_tab_is_pinned {
+        CloseActiveTabOutcome::SuspendPinnedTab
+    } else if tab_count > 1 {
+        CloseActiveTabOutcome::CloseTab
+    } else {
+        match close_last_tab {
+            BrowserCloseLastTab::NewTab => CloseActiveTabOutcome::ReplaceWithNewTab,
+            BrowserCloseLastTab::CloseWindow => CloseActiveTabOutcome::CloseWindow,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closing_the_last_tab_follows_the_close_last_tab_setting() {
+        assert_eq!(
+            close_active_tab_outcome(1, false, BrowserCloseLastTab::NewTab),
+            CloseActiveTabOutcome::ReplaceWithNewTab
+        );
+        assert_eq!(
+            close_active_tab_outcome(1, false, BrowserCloseLastTab::CloseWindow),
+            CloseActiveTabOutcome::CloseWindow
+        );
+        assert_eq!(
+            close_active_tab_outcome(3, false, BrowserCloseLastTab::CloseWindow),
+            CloseActiveTabOutcome::CloseTab
+        );
+    }
+
+    #[test]
+    fn test_adjacent_tab_index_only_wraps_when_asked() {
+        assert_eq!(adjacent_tab_index(0, 3, true, false), Some(1));
+        assert_eq!(adjacent_tab_index(2, 3, true, false), None);
+        assert_eq!(adjacent_tab_index(2, 3, true, true), Some(0));
+        assert_eq!(adjacent_tab_index(0, 3, false, false), None);
+        assert_eq!(adjacent_tab_index(0, 3, false, true), Some(2));
+        assert_eq!(adjacent_tab_index(0, 1, true, true), None);
+    }
+
+    #[test]
+    fn test_pinned_tabs_are_suspended_instead_of_closed() {
+        for close_last_tab in [
+            BrowserCloseLastTab::NewTab,
+            BrowserCloseLastTab::CloseWindow,
+        ] {
+            assert_eq!(
+                close_active_tab_outcome(1, true, close_last_tab),
+                CloseActiveTabOutcome::SuspendPinnedTab
+            );
+            assert_eq!(
+                close_active_tab_outcome(4, true, close_last_tab),
+                CloseActiveTabOutcome::SuspendPinnedTab
+            );
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use gpui::{App, Context};
+use settings::Settings as _;
+
+use super::BrowserView;
+use crate::browser_settings::BrowserSettings;
+use crate::page_language::{page_origin, primary_language_subtag, translation_url};
+
+impl BrowserView {
+    /// The language the active page should be offered translation from. This
+    /// is `None` once the page is translated, when it is already in the user's
+    /// language, or when the prompt was dismissed for its origin.
+    pub fn translation_offer_language(&self, cx: &App) -> Option<String> {
+        let tab = self.active_tab()?.read(cx);
+        if tab.is_new_tab_page() || tab.is_translated() {
+            return None;
+        }
+        let page_language = tab.detected_language()?;
+        if target_language(cx)? == page_language {
+            return None;
+        }
+        if page_origin(tab.url())
+            .is_some_and(|origin| self.dismissed_translation_origins.contains(&origin))
+        {
+            return None;
+        }
+        Some(page_language.to_string())
+    }
+
+    pub fn translate_active_tab(&mut self, cx: &mut Context<Self>) {
+        let Some(target_language) = target_language(cx) else {
+            return;
+        };
+        let Some(tab) = self.active_tab().cloned() else {
+            return;
+        };
+        let template = BrowserSettings::get_global(cx).translation_url.clone();
+        tab.update(cx, |tab, cx| {
+            let translated_url = translation_url(&template, &target_language, tab.url());
+            tab.translate(&translated_url, cx);
+        });
+        self.pending_toolbar_sync = true;
+        cx.notify();
+    }
+
+    /// Stops offering translation for the active page's origin until Glass
+    /// restarts.
+    pub fn dismiss_translation_offer(&mut self, cx: &mut Context<Self>) {
+        let Some(origin) = self
+            .active_tab()
+            .and_then(|tab| page_origin(tab.read(cx).url()))
+        else {
+            return;
+        };
+        self.dismissed_translation_origins.insert(origin);
+        cx.notify();
+    }
+}
+
+/// The language pages are translated into: the configured
+/// `browser.translation_language`, or the system locale when unset.
+fn target_language(cx: &App) -> Option<String> {
+    BrowserSettings::get_global(cx)
+        .translation_language
+        .clone()
+        .or_else(sys_locale::get_locale)
+        .as_deref()
+        .and_then(primary_language_subtag)
+}
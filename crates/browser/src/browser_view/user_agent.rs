@@ -0,0 +1,236 @@
+use editor::{Editor, actions::SelectAll as EditorSelectAll};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, WeakEntity,
+    Window,
+};
+use ui::prelude::*;
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use super::BrowserView;
+use crate::session;
+use crate::site_permissions::origin_for_url;
+use crate::tab::BrowserTab;
+use crate::user_agent::UserAgentPreset;
+
+pub(crate) fn set_custom_user_agent(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(browser_view) = crate::workspace_browser_view(workspace) else {
+        return;
+    };
+    let Some(tab) = browser_view.read(cx).active_tab().cloned() else {
+        return;
+    };
+    let browser_view = browser_view.downgrade();
+    workspace.toggle_modal(window, cx, |window, cx| {
+        CustomUserAgentModal::new(browser_view, tab, window, cx)
+    });
+}
+
+impl BrowserView {
+    /// Changes the user agent in the scope the tab already uses: the site-wide
+    /// override when its origin has one, otherwise the tab's own.
+    pub(super) fn set_user_agent_for_tab(
+        &mut self,
+        tab: &Entity<BrowserTab>,
+        user_agent: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let origin = origin_for_url(tab.read(cx).url());
+        let has_site_override = tab.read(cx).site_user_agent().is_some();
+        match origin {
+            Some(origin) if has_site_override => {
+                self.set_site_user_agent(&origin, user_agent, cx);
+            }
+            _ => tab.update(cx, |tab, cx| tab.set_user_agent(user_agent, cx)),
+        }
+        cx.notify();
+    }
+
+    /// Moves the tab's user agent to a site-wide override for its origin, or
+    /// turns the site-wide override back into a tab-only one.
+    pub(super) fn toggle_site_wide_user_agent(
+        &mut self,
+        tab: &Entity<BrowserTab>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(origin) = origin_for_url(tab.read(cx).url()) else {
+            return;
+        };
+        let site_user_agent = tab.read(cx).site_user_agent();
+        match site_user_agent {
+            Some(user_agent) => {
+                tab.update(cx, |tab, cx| tab.set_user_agent(Some(user_agent), cx));
+                self.set_site_user_agent(&origin, None, cx);
+            }
+            None => {
+                let user_agent = tab.read(cx).user_agent();
+                tab.update(cx, |tab, cx| tab.set_user_agent(None, cx));
+                self.set_site_user_agent(&origin, user_agent, cx);
+            }
+        }
+    }
+
+    fn set_site_user_agent(
+        &mut self,
+        origin: &str,
+        user_agent: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        self.site_user_agents
+            .update(|overrides| overrides.set_for_origin(origin, user_agent));
+        for tab in &self.tabs {
+            tab.update(cx, |tab, cx| tab.refresh_user_agent(cx));
+        }
+        if let Some(json) = self.site_user_agents.serialize() {
+            cx.background_spawn(async move {
+                session::save_user_agent_overrides(json).await.log_err();
+            })
+            .detach();
+        }
+        cx.notify();
+    }
+}
+
+/// Adds the "User Agent" submenu to the page context menu.
+pub(super) fn user_agent_submenu(
+    menu: ui::ContextMenu,
+    browser_view: WeakEntity<BrowserView>,
+    tab: Entity<BrowserTab>,
+    cx: &App,
+) -> ui::ContextMenu {
+    let current_user_agent = tab.read(cx).user_agent();
+    let origin = origin_for_url(tab.read(cx).url());
+    let is_site_wide = tab.read(cx).site_user_agent().is_some();
+
+    menu.submenu("User Agent", move |mut menu, _window, _cx| {
+        let choices = std::iter::once(("Default", None)).chain(
+            UserAgentPreset::ALL
+                .into_iter()
+                .map(|preset| (preset.label(), Some(preset.user_agent()))),
+        );
+        for (label, user_agent) in choices {
+            let browser_view = browser_view.clone();
+            let tab = tab.clone();
+            menu = menu.toggleable_entry(
+                label,
+                current_user_agent.as_deref() == user_agent,
+                IconPosition::Start,
+                None,
+                move |_window, cx| {
+                    browser_view
+                        .update(cx, |browser_view, cx| {
+                            browser_view.set_user_agent_for_tab(
+                                &tab,
+                                user_agent.map(str::to_string),
+                                cx,
+                            );
+                        })
+                        .ok();
+                },
+            );
+        }
+        menu = menu.entry("Custom…", None, |window, cx| {
+            window.dispatch_action(Box::new(super::SetCustomUserAgent), cx);
+        });
+
+        if let Some(origin) = &origin
+            && (current_user_agent.is_some() || is_site_wide)
+        {
+            let browser_view = browser_view.clone();
+            let tab = tab.clone();
+            menu = menu.separator().toggleable_entry(
+                format!("Use for All of {origin}"),
+                is_site_wide,
+                IconPosition::Start,
+                None,
+                move |_window, cx| {
+                    browser_view
+                        .update(cx, |browser_view, cx| {
+                            browser_view.toggle_site_wide_user_agent(&tab, cx);
+                        })
+                        .ok();
+                },
+            );
+        }
+        menu
+    })
+}
+
+pub struct CustomUserAgentModal {
+    browser_view: WeakEntity<BrowserView>,
+    tab: Entity<BrowserTab>,
+    editor: Entity<Editor>,
+}
+
+impl ModalView for CustomUserAgentModal {}
+
+impl EventEmitter<DismissEvent> for CustomUserAgentModal {}
+
+impl Focusable for CustomUserAgentModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl CustomUserAgentModal {
+    fn new(
+        browser_view: WeakEntity<BrowserView>,
+        tab: Entity<BrowserTab>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let current_user_agent = tab.read(cx).user_agent().unwrap_or_default();
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Leave empty to use the default user agent", window, cx);
+            editor.set_text(current_user_agent, window, cx);
+            editor.select_all(&EditorSelectAll, window, cx);
+            editor
+        });
+        Self {
+            browser_view,
+            tab,
+            editor,
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let user_agent = self.editor.read(cx).text(cx).trim().to_string();
+        let user_agent = (!user_agent.is_empty()).then_some(user_agent);
+        let tab = self.tab.clone();
+        self.browser_view
+            .update(cx, |browser_view, cx| {
+                browser_view.set_user_agent_for_tab(&tab, user_agent, cx);
+            })
+            .ok();
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for CustomUserAgentModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("CustomUserAgentModal")
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .w(rems(34.))
+            .elevation_3(cx)
+            .child(
+                h_flex()
+                    .px_3()
+                    .py_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new("Custom User Agent")),
+            )
+            .child(div().px_3().py_2().child(self.editor.clone()))
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+
+use gpui::{AnyWindowHandle, App, Context, PromptLevel, Window};
+use util::ResultExt as _;
+use workspace::{CloseWindow, MultiWorkspace, Workspace, WorkspaceItemKind};
+use workspace_modes::ModeId;
+
+use super::BrowserView;
+use crate::session::SerializedTab;
+
+pub(crate) fn merge_all_browser_windows(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let current_window = window.window_handle();
+    let source_windows = browser_windows_to_merge(current_window, cx);
+    if source_windows.is_empty() {
+        return;
+    }
+    if crate::workspace_browser_view(workspace)
+        .is_some_and(|browser_view| browser_view.read(cx).is_incognito_window())
+    {
+        return;
+    }
+
+    let answer = window.prompt(
+        PromptLevel::Warning,
+        "Merge tabs from all browser windows into this window?",
+        Some("Tabs are moved here and windows left without other content are closed."),
+        &["Merge Windows", "Cancel"],
+        cx,
+    );
+    cx.spawn_in(window, async move |workspace, cx| {
+        if answer.await.log_err() != Some(0) {
+            return anyhow::Ok(());
+        }
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.switch_to_mode(ModeId::BROWSER, window, cx);
+            let Some(target) = crate::workspace_browser_view(workspace) else {
+                return;
+            };
+
+            let mut merged_tabs = Vec::new();
+            let mut emptied_windows = Vec::new();
+            for source_window in source_windows {
+                let Some(multi_workspace) = source_window.downcast::<MultiWorkspace>() else {
+                    continue;
+                };
+                let emptied = multi_workspace
+                    .update(cx, |multi_workspace, _window, cx| {
+                        let mut emptied = true;
+                        for workspace in multi_workspace.workspaces() {
+                            if let Some(browser_view) =
+                                crate::workspace_browser_view(workspace.read(cx))
+                            {
+                                merged_tabs.extend(browser_view.update(cx, |browser_view, cx| {
+                                    browser_view.take_tabs_for_merge(cx)
+                                }));
+                            }
+                            emptied &= has_only_browser_items(workspace.read(cx), cx);
+                        }
+                        emptied
+                    })
+                    .log_err()
+                    .unwrap_or(false);
+                if emptied {
+                    emptied_windows.push(multi_workspace);
+                }
+            }
+
+            target.update(cx, |browser_view, cx| {
+                browser_view.merge_tabs(merged_tabs, cx);
+                browser_view.update_toolbar_active_tab(window, cx);
+            });
+
+            for multi_workspace in emptied_windows {
+                multi_workspace
+                    .update(cx, |multi_workspace, window, cx| {
+                        multi_workspace.close_window(&CloseWindow, window, cx);
+                    })
+                    .log_err();
+            }
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+fn browser_windows_to_merge(current_window: AnyWindowHandle, cx: &App) -> Vec<AnyWindowHandle> {
+    cx.windows()
+        .into_iter()
+        .filter(|window| *window != current_window)
+        .filter(|window| {
+            window
+                .downcast::<MultiWorkspace>()
+                .and_then(|multi_workspace| multi_workspace.read(cx).ok())
+                .is_some_and(|multi_workspace| {
+                    multi_workspace.workspaces().iter().any(|workspace| {
+                        crate::workspace_browser_view(workspace.read(cx)).is_some_and(
+                            |browser_view| !browser_view.read(cx).is_incognito_window(),
+                        )
+                    })
+                })
+        })
+        .collect()
+}
+
+fn has_only_browser_items(workspace: &Workspace, cx: &App) -> bool {
+    workspace
+        .items(cx)
+        .all(|item| item.workspace_item_kind(cx) == Some(WorkspaceItemKind::Browser))
+}
+
+impl BrowserView {
+    /// Removes every tab from this view so it can be moved into another window,
+    /// leaving a fresh new tab page behind.
+    fn take_tabs_for_merge(&mut self, cx: &mut Context<Self>) -> Vec<SerializedTab> {
+        if self.is_incognito_window {
+            return Vec::new();
+        }
+
+        let tabs = std::mem::take(&mut self.tabs);
+        self.tab_subscriptions.clear();
+        let serialized_tabs = tabs
+            .iter()
+            .map(|tab| {
+                tab.update(cx, |tab, _| {
+                    let serialized_tab = SerializedTab {
+                        url: tab.url().to_string(),
+                        title: tab.title().to_string(),
+                        is_new_tab_page: tab.is_new_tab_page(),
+                        is_pinned: tab.is_pinned(),
+                        favicon_url: tab.favicon_url().map(|url| url.to_string()),
+                        scroll_position: tab.scroll_position().cloned(),
+                    };
+                    tab.close_browser();
+                    serialized_tab
+                })
+            })
+            .collect();
+
+        self.set_active_tab_index(0);
+        self.add_tab(cx);
+        self.sync_bookmark_bar_visibility(cx);
+        cx.notify();
+        serialized_tabs
+    }
+
+    fn merge_tabs(&mut self, incoming: Vec<SerializedTab>, cx: &mut Context<Self>) {
+        let active_tab_id = self.active_tab().map(|tab| tab.entity_id());
+        let existing_urls: HashSet<String> = self
+            .tabs
+            .iter()
+            .filter(|tab| !tab.read(cx).is_new_tab_page())
+            .map(|tab| tab.read(cx).url().to_string())
+            .collect();
+
+        let (new_tabs, urls_to_pin) = plan_tab_merge(&existing_urls, incoming);
+        for tab in &self.tabs {
+            let pin = {
+                let tab = tab.read(cx);
+                !tab.is_pinned() && !tab.is_new_tab_page() && urls_to_pin.contains(tab.url())
+            };
+            if pin {
+                tab.update(cx, |tab, _| tab.set_pinned(true));
+            }
+        }
+        for serialized_tab in &new_tabs {
+            self.push_serialized_tab(serialized_tab, cx);
+        }
+
+        self.sort_tabs_pinned_first(cx);
+        if let Some(index) = active_tab_id.and_then(|active_tab_id| {
+            self.tabs
+                .iter()
+                .position(|tab| tab.entity_id() == active_tab_id)
+        }) {
+            self.set_active_tab_index(index);
+        }
+        self.pending_toolbar_sync = true;
+        self.sync_bookmark_bar_visibility(cx);
+        self.schedule_save(cx);
+        cx.notify();
+    }
+}
+
+/// Returns the incoming tabs that should be added, with duplicate URLs collapsed
+/// into one tab that stays pinned if any of its copies was, along with the URLs
+/// of already-open tabs that need to become pinned.
+fn plan_tab_merge(
+    existing_urls: &HashSet<String>,
+    incoming: Vec<SerializedTab>,
+) -> (Vec<SerializedTab>, HashSet<String>) {
+    let mut new_tabs: Vec<SerializedTab> = Vec::new();
+    let mut urls_to_pin = HashSet::new();
+    for tab in incoming {
+        if tab.is_new_tab_page {
+            continue;
+        }
+        if existing_urls.contains(&tab.url) {
+            if tab.is_pinned {
+                urls_to_pin.insert(tab.url);
+            }
+            continue;
+        }
+        if let Some(duplicate) = new_tabs.iter_mut().find(|new_tab| new_tab.url == tab.url) {
+            duplicate.is_pinned |= tab.is_pinned;
+            continue;
+        }
+        new_tabs.push(tab);
+    }
+    (new_tabs, urls_to_pin)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::plan_tab_merge;
+    use crate::session::SerializedTab;
+
+    fn tab(url: &str, is_pinned: bool) -> SerializedTab {
+        SerializedTab {
+            url: url.to_string(),
+            title: url.to_string(),
+            is_new_tab_page: false,
+            is_pinned,
+            favicon_url: None,
+            scroll_position: None,
+        }
+    }
+
+    #[test]
+    fn merging_collapses_duplicate_urls_and_keeps_pins() {
+        let existing_urls = HashSet::from(["https://a.test/".to_string()]);
+        let new_tab_page = SerializedTab {
+            is_new_tab_page: true,
+            ..tab("", false)
+        };
+        let (new_tabs, urls_to_pin) = plan_tab_merge(
+            &existing_urls,
+            vec![
+                tab("https://a.test/", true),
+                tab("https://b.test/", false),
+                new_tab_page,
+                tab("https://b.test/", true),
+                tab("https://c.test/", false),
+            ],
+        );
+
+        let new_tabs: Vec<_> = new_tabs
+            .iter()
+            .map(|tab| (tab.url.as_str(), tab.is_pinned))
+            .collect();
+        assert_eq!(
+            new_tabs,
+            vec![("https://b.test/", true), ("https://c.test/", false)]
+        );
+        assert_eq!(urls_to_pin, HashSet::from(["https://a.test/".to_string()]));
+    }
+}
@@ -17,11 +17,16 @@ use crate::events::EventSender;
 use crate::find_handler::{FindHandlerBuilder, OsrFindHandler};
 use crate::life_span_handler::{LifeSpanHandlerBuilder, OsrLifeSpanHandler};
 use crate::load_handler::{LoadHandlerBuilder, OsrLoadHandler};
+use crate::media_playback::extract_media_playback_from_message;
 use crate::page_chrome::extract_page_chrome_from_message;
+use crate::page_language::extract_page_language_from_message;
 use crate::permission_handler::{OsrPermissionHandler, PermissionHandlerBuilder};
 use crate::render_handler::{OsrRenderHandler, RenderHandlerBuilder, RenderState};
 use crate::request_handler::{OsrRequestHandler, RequestHandlerBuilder};
+use crate::scroll_position::extract_scroll_position_from_message;
+use crate::site_permissions::SitePermissions;
 use crate::text_input::extract_text_input_state_from_message;
+use crate::user_agent::TabUserAgent;
 use parking_lot::Mutex;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -169,6 +174,37 @@ wrap_client! {
                 return 1;
             }
 
+            if let Some(scroll_position) = extract_scroll_position_from_message(message) {
+                if let Some(scroll_position) = scroll_position {
+                    let _ = self
+                        .event_sender
+                        .send(crate::events::BrowserEvent::ScrollPositionChanged(
+                            scroll_position,
+                        ));
+                }
+                return 1;
+            }
+
+            if let Some(page_language) = extract_page_language_from_message(message) {
+                let _ = self
+                    .event_sender
+                    .send(crate::events::BrowserEvent::PageLanguageDetected(
+                        page_language,
+                    ));
+                return 1;
+            }
+
+            if let Some(media_playback) = extract_media_playback_from_message(message) {
+                if let Some(media_playback) = media_playback {
+                    let _ = self
+                        .event_sender
+                        .send(crate::events::BrowserEvent::MediaPlaybackChanged(
+                            media_playback,
+                        ));
+                }
+                return 1;
+            }
+
             let Some(page_chrome) = extract_page_chrome_from_message(message) else {
                 return 0;
             };
@@ -182,17 +218,32 @@ wrap_client! {
 }
 
 impl ClientBuilder {
-    pub fn build(render_state: Arc<Mutex<RenderState>>, event_sender: EventSender) -> cef::Client {
-        Self::build_inner(render_state, event_sender, KeyboardHandlerBuilder::build())
+    pub fn build(
+        render_state: Arc<Mutex<RenderState>>,
+        event_sender: EventSender,
+        site_permissions: SitePermissions,
+        user_agent: TabUserAgent,
+    ) -> cef::Client {
+        Self::build_inner(
+            render_state,
+            event_sender,
+            site_permissions,
+            user_agent,
+            KeyboardHandlerBuilder::build(),
+        )
     }
 
     pub fn build_for_popup(
         render_state: Arc<Mutex<RenderState>>,
         event_sender: EventSender,
+        site_permissions: SitePermissions,
+        user_agent: TabUserAgent,
     ) -> cef::Client {
         Self::build_inner(
             render_state,
             event_sender,
+            site_permissions,
+            user_agent,
             PopupKeyboardHandlerBuilder::build(),
         )
     }
@@ -200,17 +251,23 @@ impl ClientBuilder {
     fn build_inner(
         render_state: Arc<Mutex<RenderState>>,
         event_sender: EventSender,
+        site_permissions: SitePermissions,
+        user_agent: TabUserAgent,
         keyboard_handler: cef::KeyboardHandler,
     ) -> cef::Client {
         let render_handler = OsrRenderHandler::new(render_state, event_sender.clone());
         let load_handler = OsrLoadHandler::new(event_sender.clone());
         let display_handler = OsrDisplayHandler::new(event_sender.clone());
-        let life_span_handler = OsrLifeSpanHandler::new(event_sender.clone());
-        let request_handler = OsrRequestHandler::new(event_sender.clone());
+        let life_span_handler = OsrLifeSpanHandler::new(
+            event_sender.clone(),
+            site_permissions.clone(),
+            user_agent.site_user_agents().clone(),
+        );
+        let request_handler = OsrRequestHandler::new(event_sender.clone(), user_agent);
         let download_handler = OsrDownloadHandler::new(event_sender.clone());
         let find_handler = OsrFindHandler::new(event_sender.clone());
         let context_menu_handler = OsrContextMenuHandler::new(event_sender.clone());
-        let permission_handler = OsrPermissionHandler::new();
+        let permission_handler = OsrPermissionHandler::new(event_sender.clone(), site_permissions);
         Self::new(
             RenderHandlerBuilder::build(render_handler),
             LoadHandlerBuilder::build(load_handler),
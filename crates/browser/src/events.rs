@@ -4,7 +4,11 @@
 //! on the main/foreground thread via a channel.
 
 use crate::context_menu_handler::ContextMenuContext;
+use crate::media_playback::MediaPlayback;
 use crate::page_chrome::PageChrome;
+use crate::page_security::PageSecurity;
+use crate::scroll_position::ScrollPosition;
+use crate::site_permissions::PermissionRequest;
 use crate::text_input::BrowserTextInputState;
 use cef::WindowOpenDisposition;
 use std::sync::mpsc;
@@ -32,6 +36,32 @@ pub struct DownloadUpdatedEvent {
     pub is_interrupted: bool,
 }
 
+/// The request that loaded a tab's main frame, as seen before CEF's network
+/// stack adds cookies and default headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MainFrameRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl MainFrameRequest {
+    pub fn to_curl_command(&self) -> String {
+        let mut command = format!("curl {}", shell_quote(&self.url));
+        if !self.method.eq_ignore_ascii_case("GET") {
+            command.push_str(&format!(" -X {}", shell_quote(&self.method)));
+        }
+        for (name, value) in &self.headers {
+            command.push_str(&format!(" -H {}", shell_quote(&format!("{name}: {value}"))));
+        }
+        command
+    }
+}
+
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BrowserTabOpenTarget {
     Foreground,
@@ -133,11 +163,17 @@ pub enum BrowserEvent {
         context: ContextMenuContext,
     },
     OpenTargetRequested(OpenTargetRequest),
+    MainFrameRequest(MainFrameRequest),
     FaviconUrlChanged(Vec<String>),
     PageChromeChanged(Option<PageChrome>),
+    PageLanguageDetected(Option<String>),
+    MediaPlaybackChanged(MediaPlayback),
+    PageSecurityChanged(Option<PageSecurity>),
+    ScrollPositionChanged(ScrollPosition),
     TextInputStateChanged(BrowserTextInputState),
     FindResult(FindResultEvent),
     DownloadUpdated(DownloadUpdatedEvent),
+    PermissionRequested(PermissionRequest),
 }
 
 pub type EventSender = mpsc::Sender<BrowserEvent>;
@@ -149,7 +185,41 @@ pub fn event_channel() -> (EventSender, EventReceiver) {
 
 #[cfg(test)]
 mod tests {
-    use super::{BrowserTabOpenTarget, OpenDisposition};
+    use super::{BrowserTabOpenTarget, MainFrameRequest, OpenDisposition};
+
+    #[test]
+    fn get_requests_become_plain_curl_commands() {
+        let request = MainFrameRequest {
+            method: "GET".into(),
+            url: "https://example.com/api?q=1&page=2".into(),
+            headers: Vec::new(),
+        };
+        assert_eq!(
+            request.to_curl_command(),
+            "curl 'https://example.com/api?q=1&page=2'"
+        );
+    }
+
+    #[test]
+    fn curl_commands_include_method_and_quoted_headers() {
+        let request = MainFrameRequest {
+            method: "POST".into(),
+            url: "https://example.com/it's".into(),
+            headers: vec![
+                ("Referer".into(), "https://example.com/".into()),
+                (
+                    "Content-Type".into(),
+                    "application/x-www-form-urlencoded".into(),
+                ),
+            ],
+        };
+        assert_eq!(
+            request.to_curl_command(),
+            "curl 'https://example.com/it'\\''s' -X 'POST' \
+             -H 'Referer: https://example.com/' \
+             -H 'Content-Type: application/x-www-form-urlencoded'"
+        );
+    }
 
     #[test]
     fn tab_like_dispositions_are_app_managed() {
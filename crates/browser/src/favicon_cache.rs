@@ -0,0 +1,158 @@
+//! On-disk favicon cache keyed by page origin.
+//!
+//! Tabs that share an origin resolve to the same cache file, so the tab strip
+//! can render favicons from disk instead of re-fetching them on every render.
+
+use anyhow::{Context as _, Result, bail};
+use futures::AsyncReadExt as _;
+use http_client::{AsyncBody, HttpClient};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_EXTENSION: &str = "ico";
+
+pub(crate) fn favicons_dir() -> PathBuf {
+    paths::data_dir().join("browser_favicons")
+}
+
+pub(crate) fn max_age(favicon_cache_days: u64) -> Duration {
+    Duration::from_secs(favicon_cache_days.saturating_mul(24 * 60 * 60))
+}
+
+/// Returns the cache file for the origin of `page_url`, keeping the image
+/// extension of `favicon_url` so the platform can infer the image format.
+pub(crate) fn cache_path(page_url: &str, favicon_url: &str) -> Option<PathBuf> {
+    let origin = url::Url::parse(page_url).ok()?.origin();
+    if !origin.is_tuple() {
+        return None;
+    }
+    let key = origin
+        .ascii_serialization()
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() || character == '.' || character == '-' {
+                character
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    Some(favicons_dir().join(format!("{key}.{}", favicon_extension(favicon_url))))
+}
+
+fn favicon_extension(favicon_url: &str) -> &'static str {
+    let path = url::Url::parse(favicon_url)
+        .map(|url| url.path().to_ascii_lowercase())
+        .unwrap_or_default();
+    ["png", "ico", "svg", "gif", "jpg", "jpeg", "webp"]
+        .into_iter()
+        .find(|extension| path.ends_with(&format!(".{extension}")))
+        .unwrap_or(DEFAULT_EXTENSION)
+}
+
+pub(crate) fn is_fresh(path: &Path, max_age: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age <= max_age)
+}
+
+pub(crate) async fn fetch(
+    http_client: Arc<dyn HttpClient>,
+    favicon_url: String,
+    destination: PathBuf,
+) -> Result<PathBuf> {
+    let mut response = http_client
+        .get(&favicon_url, AsyncBody::default(), true)
+        .await
+        .with_context(|| format!("requesting favicon {favicon_url}"))?;
+
+    let mut body = Vec::new();
+    response
+        .body_mut()
+        .read_to_end(&mut body)
+        .await
+        .with_context(|| format!("reading favicon {favicon_url}"))?;
+
+    if !response.status().is_success() {
+        bail!(
+            "favicon status error {} for {favicon_url}",
+            response.status().as_u16()
+        );
+    }
+    if body.is_empty() {
+        bail!("empty favicon response for {favicon_url}");
+    }
+
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let temporary_path = destination.with_extension("download");
+    std::fs::write(&temporary_path, &body)?;
+    std::fs::rename(&temporary_path, &destination)?;
+    Ok(destination)
+}
+
+pub(crate) fn remove_expired_entries(max_age: Duration) -> Result<()> {
+    let entries = match std::fs::read_dir(favicons_dir()) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(error.into()),
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if !is_fresh(&path, max_age) {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabs_on_the_same_origin_share_a_cache_entry() {
+        let first = cache_path("https://example.com/a", "https://example.com/favicon.ico");
+        let second = cache_path(
+            "https://example.com/b?query=1",
+            "https://example.com/favicon.ico",
+        );
+        assert!(first.is_some());
+        assert_eq!(first, second);
+
+        let other_origin = cache_path("https://other.example.com/", "https://example.com/x.ico");
+        assert_ne!(first, other_origin);
+    }
+
+    #[test]
+    fn cache_path_keeps_the_favicon_extension() {
+        let path = cache_path("https://example.com", "https://cdn.example.com/icon.PNG").and_then(
+            |path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            },
+        );
+        assert_eq!(path.as_deref(), Some("https___example.com.png"));
+
+        let path = cache_path("https://example.com", "https://example.com/icon?size=32").and_then(
+            |path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            },
+        );
+        assert_eq!(path.as_deref(), Some("https___example.com.ico"));
+    }
+
+    #[test]
+    fn opaque_origins_are_not_cached() {
+        assert_eq!(
+            cache_path("glass://newtab", "https://example.com/x.ico"),
+            None
+        );
+        assert_eq!(cache_path("not a url", "https://example.com/x.ico"), None);
+    }
+}
@@ -1,10 +1,37 @@
+use crate::browser_settings::{BrowserHistorySuggestionDetail, BrowserSettings};
 use crate::session;
-use fuzzy::StringMatchCandidate;
-use gpui::{BackgroundExecutor, Context, EventEmitter};
-use std::sync::atomic::AtomicBool;
-use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::Result;
+use gpui::{App, BackgroundExecutor, Context, EventEmitter, Subscription, Task};
+use settings::{Settings as _, SettingsStore};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use util::ResultExt as _;
 
-const MAX_ENTRIES: usize = 2000;
+const DEFAULT_MAX_ENTRIES: usize = 50_000;
+
+const COMMON_DOMAINS: &[&str] = &[
+    "google.com",
+    "youtube.com",
+    "github.com",
+    "gitlab.com",
+    "wikipedia.org",
+    "reddit.com",
+    "amazon.com",
+    "x.com",
+    "facebook.com",
+    "instagram.com",
+    "linkedin.com",
+    "netflix.com",
+    "stackoverflow.com",
+    "news.ycombinator.com",
+    "docs.rs",
+    "crates.io",
+    "apple.com",
+    "microsoft.com",
+    "openai.com",
+    "twitch.tv",
+];
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct HistoryEntry {
@@ -19,28 +46,180 @@ pub struct HistoryMatch {
     pub url: String,
     pub title: String,
     pub score: f64,
+    /// Whether this is a well-known domain suggested because nothing in
+    /// history matched, rather than a page the user has visited.
+    pub is_suggested_domain: bool,
+    /// When the page was last visited, in milliseconds since the Unix epoch.
+    /// Zero for suggested domains.
+    pub last_visited_ms: u64,
+}
+
+impl HistoryMatch {
+    /// The detail shown beside this match in omnibox suggestions, as the
+    /// `browser.history_suggestion_detail` setting asks.
+    pub fn suggestion_detail(&self, domain: &str, cx: &App) -> String {
+        self.detail(
+            domain,
+            BrowserSettings::get_global(cx).history_suggestion_detail,
+            SystemTime::now(),
+        )
+    }
+
+    fn detail(
+        &self,
+        domain: &str,
+        detail: BrowserHistorySuggestionDetail,
+        now: SystemTime,
+    ) -> String {
+        if self.is_suggested_domain {
+            return domain.to_string();
+        }
+        let visited = format_visit_age(self.last_visited_ms, now);
+        match detail {
+            BrowserHistorySuggestionDetail::Domain => domain.to_string(),
+            BrowserHistorySuggestionDetail::LastVisited => visited,
+            BrowserHistorySuggestionDetail::DomainAndLastVisited => {
+                format!("{domain} · {visited}")
+            }
+        }
+    }
+}
+
+/// How long ago a visit happened, in the compact form omnibox suggestions
+/// have room for, such as "2h ago".
+fn format_visit_age(last_visited_ms: u64, now: SystemTime) -> String {
+    let now_ms = now
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let minutes = now_ms.saturating_sub(last_visited_ms) / 60_000;
+    let hours = minutes / 60;
+    let days = hours / 24;
+    if minutes == 0 {
+        String::from("just now")
+    } else if hours == 0 {
+        format!("{minutes}m ago")
+    } else if days == 0 {
+        format!("{hours}h ago")
+    } else if days < 7 {
+        format!("{days}d ago")
+    } else if days < 30 {
+        format!("{}w ago", days / 7)
+    } else if days < 365 {
+        format!("{}mo ago", days / 30)
+    } else {
+        format!("{}y ago", days / 365)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TopSite {
+    pub origin: String,
+    pub host: String,
+    pub visit_count: u32,
+    pub last_visited_ms: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryClearRange {
+    LastHour,
+    LastDay,
+    LastWeek,
+    AllTime,
+}
+
+impl HistoryClearRange {
+    pub const ALL: [Self; 4] = [Self::LastHour, Self::LastDay, Self::LastWeek, Self::AllTime];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::LastHour => "Last hour",
+            Self::LastDay => "Last 24 hours",
+            Self::LastWeek => "Last 7 days",
+            Self::AllTime => "All time",
+        }
+    }
+
+    pub fn since(&self) -> Option<SystemTime> {
+        let duration = match self {
+            Self::LastHour => Duration::from_secs(60 * 60),
+            Self::LastDay => Duration::from_secs(24 * 60 * 60),
+            Self::LastWeek => Duration::from_secs(7 * 24 * 60 * 60),
+            Self::AllTime => return None,
+        };
+        SystemTime::now().checked_sub(duration)
+    }
 }
 
 pub struct BrowserHistory {
     entries: Vec<HistoryEntry>,
+    hidden_top_sites: Vec<String>,
+    max_entries: usize,
+    is_ephemeral: bool,
+    /// Whether visits were recorded since the entries were last handed out
+    /// for saving.
+    has_unsaved_visits: bool,
+    _save_history_task: Option<Task<()>>,
+    _save_hidden_top_sites_task: Option<Task<()>>,
+    _settings_subscription: Option<Subscription>,
 }
 
 impl EventEmitter<()> for BrowserHistory {}
 
 impl BrowserHistory {
     pub fn new(cx: &mut Context<Self>) -> Self {
+        let settings_subscription = cx.observe_global::<SettingsStore>(|this, cx| {
+            let max_entries = max_entries_setting(cx);
+            if this.max_entries != max_entries {
+                this.max_entries = max_entries;
+                let entry_count = this.entries.len();
+                this.prune();
+                if this.entries.len() != entry_count {
+                    cx.notify();
+                    this.save(cx);
+                }
+            }
+        });
         let mut this = Self {
             entries: Vec::new(),
+            hidden_top_sites: session::restore_hidden_top_sites().unwrap_or_default(),
+            max_entries: max_entries_setting(cx),
+            is_ephemeral: false,
+            has_unsaved_visits: false,
+            _save_history_task: None,
+            _save_hidden_top_sites_task: None,
+            _settings_subscription: Some(settings_subscription),
         };
         if let Some(entries) = session::restore_history() {
-            this.entries = entries;
+            let restored_count = entries.len();
+            this.entries = collapse_duplicate_entries(entries);
+            this.prune();
+            // Older versions could store a URL more than once; write the
+            // collapsed history back so the duplicates are gone for good.
+            if this.entries.len() != restored_count {
+                this.save(cx);
+            }
         }
         cx.notify();
         this
     }
 
+    /// Creates a history that never reads from or writes to disk, for incognito windows.
+    pub fn new_ephemeral() -> Self {
+        Self {
+            entries: Vec::new(),
+            hidden_top_sites: Vec::new(),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            is_ephemeral: true,
+            has_unsaved_visits: false,
+            _save_history_task: None,
+            _save_hidden_top_sites_task: None,
+            _settings_subscription: None,
+        }
+    }
+
     pub fn record_visit(&mut self, url: &str, title: &str) {
-        if url.is_empty() || url == "about:blank" {
+        if self.is_ephemeral || url.is_empty() || url == "about:blank" {
             return;
         }
 
@@ -48,6 +227,7 @@ impl BrowserHistory {
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_millis() as u64)
             .unwrap_or(0);
+        self.has_unsaved_visits = true;
 
         if let Some(entry) = self.entries.iter_mut().find(|e| e.url == url) {
             entry.visit_count += 1;
@@ -63,14 +243,13 @@ impl BrowserHistory {
                 last_visited_ms: now_ms,
             });
 
-            if self.entries.len() > MAX_ENTRIES {
-                // Evict the least recently visited entry
+            if self.entries.len() > self.max_entries {
                 if let Some(oldest_index) = self
                     .entries
                     .iter()
                     .enumerate()
-                    .min_by_key(|(_, e)| e.last_visited_ms)
-                    .map(|(i, _)| i)
+                    .min_by_key(|(_, entry)| eviction_key(entry))
+                    .map(|(index, _)| index)
                 {
                     self.entries.swap_remove(oldest_index);
                 }
@@ -86,17 +265,250 @@ impl BrowserHistory {
         self.entries.clear();
     }
 
+    /// Removes entries last visited at or after `since`, or every entry when `since` is
+    /// `None`, and writes the result to disk right away.
+    pub fn clear_range(&mut self, since: Option<SystemTime>, cx: &mut Context<Self>) {
+        match since {
+            Some(since) => {
+                let since_ms = since
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0);
+                self.entries
+                    .retain(|entry| entry.last_visited_ms < since_ms);
+            }
+            None => self.entries.clear(),
+        }
+        cx.notify();
+        self.save(cx);
+    }
+
+    /// Parses history written by `export_json`. Large exports take a while to
+    /// parse, so call this on a background thread.
+    pub fn read_json(reader: impl Read) -> Result<Vec<HistoryEntry>> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    /// Merges entries read by `read_json` into this history. URLs that are
+    /// already present keep a single entry with the higher visit count and the
+    /// most recent visit, so importing the same file twice changes nothing.
+    /// Returns how many new URLs were added.
+    pub fn import_entries(&mut self, imported: Vec<HistoryEntry>, cx: &mut Context<Self>) -> usize {
+        let added = self.merge_entries(imported);
+        cx.notify();
+        self.save(cx);
+        added
+    }
+
+    fn merge_entries(&mut self, imported: Vec<HistoryEntry>) -> usize {
+        let mut index_by_url: HashMap<String, usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.url.clone(), index))
+            .collect();
+        let mut added = 0;
+        for imported_entry in imported {
+            if imported_entry.url.is_empty() {
+                continue;
+            }
+            if let Some(entry) = index_by_url
+                .get(&imported_entry.url)
+                .and_then(|index| self.entries.get_mut(*index))
+            {
+                entry.visit_count = entry.visit_count.max(imported_entry.visit_count);
+                if imported_entry.last_visited_ms > entry.last_visited_ms {
+                    entry.last_visited_ms = imported_entry.last_visited_ms;
+                    if !imported_entry.title.is_empty() {
+                        entry.title = imported_entry.title;
+                    }
+                }
+            } else {
+                index_by_url.insert(imported_entry.url.clone(), self.entries.len());
+                self.entries.push(imported_entry);
+                added += 1;
+            }
+        }
+
+        self.prune();
+        added
+    }
+
+    /// Drops the oldest, least visited entries beyond the retention cap.
+    fn prune(&mut self) {
+        if self.entries.len() <= self.max_entries {
+            return;
+        }
+        self.entries
+            .sort_by_key(|entry| std::cmp::Reverse(eviction_key(entry)));
+        self.entries.truncate(self.max_entries);
+    }
+
+    /// Serializes the entries if visits were recorded since the last call, so
+    /// periodic session saves don't rewrite an unchanged history.
+    pub fn take_unsaved_json(&mut self) -> Option<String> {
+        if !std::mem::take(&mut self.has_unsaved_visits) {
+            return None;
+        }
+        self.serialize()
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        if self.is_ephemeral {
+            return;
+        }
+
+        self.has_unsaved_visits = false;
+        let json = self.serialize();
+        self._save_history_task = Some(cx.spawn(async move |this, cx| {
+            if let Some(json) = json {
+                session::save_history(json).await.log_err();
+            }
+            this.update(cx, |this, _| {
+                this._save_history_task.take();
+            })
+            .ok();
+        }));
+    }
+
+    /// Writes `entries` as a JSON array that `read_json` can read back.
+    pub fn export_json(entries: &[HistoryEntry], writer: impl Write) -> Result<()> {
+        serde_json::to_writer_pretty(writer, entries)?;
+        Ok(())
+    }
+
+    /// Writes `entries` as CSV with a `url,title,last_visited_ms,visit_count`
+    /// header row.
+    pub fn export_csv(entries: &[HistoryEntry], mut writer: impl Write) -> Result<()> {
+        writeln!(writer, "url,title,last_visited_ms,visit_count")?;
+        for entry in entries {
+            writeln!(
+                writer,
+                "{},{},{},{}",
+                csv_field(&entry.url),
+                csv_field(&entry.title),
+                entry.last_visited_ms,
+                entry.visit_count
+            )?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Aggregates visits per origin, most visited first. Origins removed with
+    /// `hide_top_site` are excluded.
+    pub fn top_sites(&self, limit: usize) -> Vec<TopSite> {
+        let mut sites_by_origin: HashMap<String, TopSite> = HashMap::default();
+        for entry in &self.entries {
+            let Ok(url) = url::Url::parse(&entry.url) else {
+                continue;
+            };
+            if !matches!(url.scheme(), "http" | "https") {
+                continue;
+            }
+            let Some(host) = url.host_str() else {
+                continue;
+            };
+            let origin = url.origin().ascii_serialization();
+            if self.hidden_top_sites.contains(&origin) {
+                continue;
+            }
+
+            let site = sites_by_origin
+                .entry(origin.clone())
+                .or_insert_with(|| TopSite {
+                    origin,
+                    host: host.strip_prefix("www.").unwrap_or(host).to_string(),
+                    visit_count: 0,
+                    last_visited_ms: 0,
+                });
+            site.visit_count += entry.visit_count;
+            site.last_visited_ms = site.last_visited_ms.max(entry.last_visited_ms);
+        }
+
+        let mut sites: Vec<TopSite> = sites_by_origin.into_values().collect();
+        sites.sort_by(|a, b| {
+            b.visit_count
+                .cmp(&a.visit_count)
+                .then(b.last_visited_ms.cmp(&a.last_visited_ms))
+        });
+        sites.truncate(limit);
+        sites
+    }
+
+    pub fn hide_top_site(&mut self, origin: &str, cx: &mut Context<Self>) {
+        if self.hidden_top_sites.iter().any(|hidden| hidden == origin) {
+            return;
+        }
+        self.hidden_top_sites.push(origin.to_string());
+        cx.notify();
+
+        if self.is_ephemeral {
+            return;
+        }
+
+        let json = serde_json::to_string(&self.hidden_top_sites).log_err();
+        self._save_hidden_top_sites_task = Some(cx.spawn(async move |this, cx| {
+            if let Some(json) = json {
+                session::save_hidden_top_sites(json).await.log_err();
+            }
+            this.update(cx, |this, _| {
+                this._save_hidden_top_sites_task.take();
+            })
+            .ok();
+        }));
+    }
+
     pub fn serialize(&self) -> Option<String> {
         serde_json::to_string(&self.entries).ok()
     }
 
+    /// Returns the text the omnibox should inline-complete `query` to, based on
+    /// the most visited history entry whose URL starts with it. The completion
+    /// stops at the end of the domain unless the query already reaches into
+    /// the path, and it always keeps the user's typed prefix verbatim.
+    pub fn inline_completion(entries: &[HistoryEntry], query: &str) -> Option<String> {
+        if query.is_empty() || query.contains(char::is_whitespace) {
+            return None;
+        }
+        let query_has_www = strip_prefix_ignoring_case(query, "www.").is_some();
+
+        entries
+            .iter()
+            .filter_map(|entry| {
+                let url = strip_prefix_ignoring_case(&entry.url, "https://")
+                    .or_else(|| strip_prefix_ignoring_case(&entry.url, "http://"))?;
+                let url = if query_has_www {
+                    url
+                } else {
+                    strip_prefix_ignoring_case(url, "www.").unwrap_or(url)
+                };
+                let host = &url[..url.find('/').unwrap_or(url.len())];
+                let remainder = strip_prefix_ignoring_case(host, query)
+                    .filter(|remainder| !remainder.is_empty())
+                    .or_else(|| {
+                        strip_prefix_ignoring_case(url.trim_end_matches('/'), query)
+                            .filter(|remainder| !remainder.is_empty())
+                    })?;
+                Some((remainder, entry))
+            })
+            .max_by(|(_, a), (_, b)| {
+                a.visit_count
+                    .cmp(&b.visit_count)
+                    .then(a.last_visited_ms.cmp(&b.last_visited_ms))
+            })
+            .map(|(remainder, _)| format!("{query}{remainder}"))
+    }
+
+    /// Ranks `entries` against `query` on the background executor. See
+    /// `rank_entries` for how results are scored.
     pub async fn search(
         entries: Vec<HistoryEntry>,
         query: String,
         max_results: usize,
         executor: BackgroundExecutor,
     ) -> Vec<HistoryMatch> {
-        if query.is_empty() {
+        if query.trim().is_empty() {
             return Vec::new();
         }
 
@@ -105,76 +517,599 @@ impl BrowserHistory {
             .map(|d| d.as_millis() as u64)
             .unwrap_or(0);
 
-        let candidates: Vec<StringMatchCandidate> = entries
-            .iter()
-            .enumerate()
-            .map(|(id, entry)| {
-                StringMatchCandidate::new(id, &format!("{} {}", entry.title, entry.url))
+        executor
+            .spawn(async move { rank_entries(&entries, &query, max_results, now_ms) })
+            .await
+    }
+
+    /// Domains from the `browser.suggested_domains` setting and the built-in
+    /// list that start with `query`, offered when history has no matches.
+    /// Empty when `browser.suggest_domains` is off.
+    pub fn domain_suggestions(query: &str, max_results: usize, cx: &App) -> Vec<HistoryMatch> {
+        let settings = BrowserSettings::get_global(cx);
+        if !settings.suggest_domains {
+            return Vec::new();
+        }
+        match_domains(&settings.suggested_domains, query, max_results)
+    }
+}
+
+/// Configured domains come first, then the built-in ones, both in list order
+/// so the most popular sites win. A leading scheme or `www.` in `query` is
+/// ignored.
+fn match_domains(extra_domains: &[String], query: &str, max_results: usize) -> Vec<HistoryMatch> {
+    let query = query.trim().to_lowercase();
+    let query = query
+        .split_once("://")
+        .map_or(query.as_str(), |(_, rest)| rest);
+    let query = query.strip_prefix("www.").unwrap_or(query);
+    if query.is_empty() || query.contains(char::is_whitespace) {
+        return Vec::new();
+    }
+
+    let mut results: Vec<HistoryMatch> = Vec::new();
+    let domains = extra_domains
+        .iter()
+        .map(|domain| domain.trim().to_lowercase())
+        .chain(COMMON_DOMAINS.iter().map(|domain| domain.to_string()));
+    for domain in domains {
+        if results.len() >= max_results {
+            break;
+        }
+        let domain = domain.strip_prefix("www.").unwrap_or(&domain).to_string();
+        if !domain.starts_with(query) || results.iter().any(|result| result.title == domain) {
+            continue;
+        }
+        results.push(HistoryMatch {
+            url: format!("https://{domain}"),
+            title: domain,
+            score: 0.0,
+            is_suggested_domain: true,
+            last_visited_ms: 0,
+        });
+    }
+    results
+}
+
+/// Splits `query` on whitespace and scores each entry by how many tokens it
+/// matches, where they match, and how often and recently it was visited.
+/// Tokens found in the host outrank ones found in the title, which outrank
+/// ones found in the path, and earlier matches score higher within each.
+/// Tokens that aren't substrings can still match as an in-order subsequence
+/// of the host, so `gh` finds `github.com`. Entries matching no token are
+/// left out.
+fn rank_entries(
+    entries: &[HistoryEntry],
+    query: &str,
+    max_results: usize,
+    now_ms: u64,
+) -> Vec<HistoryMatch> {
+    let tokens: Vec<String> = query
+        .split_whitespace()
+        .map(|token| token.to_lowercase())
+        .collect();
+    if tokens.is_empty() {
+        return Vec::new();
+    }
+
+    let mut results: Vec<HistoryMatch> = entries
+        .iter()
+        .filter_map(|entry| {
+            let url = entry.url.to_lowercase();
+            let without_scheme = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+            let (host, path) = without_scheme
+                .split_once('/')
+                .map_or((without_scheme, ""), |(host, path)| (host, path));
+            let host = host.strip_prefix("www.").unwrap_or(host);
+            let title = entry.title.to_lowercase();
+
+            let mut matched_tokens = 0;
+            let mut match_score = 0.0;
+            for token in &tokens {
+                let token_score = substring_score(host, token, 3.0)
+                    .or_else(|| substring_score(&title, token, 2.0))
+                    .or_else(|| substring_score(path, token, 1.0))
+                    .or_else(|| is_subsequence(token, host).then_some(1.5));
+                if let Some(token_score) = token_score {
+                    matched_tokens += 1;
+                    match_score += token_score;
+                }
+            }
+            if matched_tokens == 0 {
+                return None;
+            }
+
+            let age_hours = now_ms.saturating_sub(entry.last_visited_ms) as f64 / 3_600_000.0;
+            let recency_bonus = 0.5 / (1.0 + age_hours / 24.0);
+            let frequency_bonus = 0.5 * (entry.visit_count as f64).ln_1p() / 10.0_f64.ln_1p();
+
+            Some(HistoryMatch {
+                url: entry.url.clone(),
+                title: entry.title.clone(),
+                score: 10.0 * matched_tokens as f64 + match_score + recency_bonus + frequency_bonus,
+                is_suggested_domain: false,
+                last_visited_ms: entry.last_visited_ms,
             })
-            .collect();
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    results.truncate(max_results);
+    results
+}
+
+/// The rest of `text` after `prefix`, compared without case one character at
+/// a time. Lowercasing can change a string's byte length, so this never
+/// slices one string by offsets taken from the other.
+pub fn strip_prefix_ignoring_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let mut text_chars = text.chars();
+    for prefix_char in prefix.chars() {
+        let text_char = text_chars.next()?;
+        if !text_char.to_lowercase().eq(prefix_char.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(text_chars.as_str())
+}
+
+/// Whether every word of `query` appears, ignoring case, in `title` or `url`.
+/// Used to suggest open tabs and bookmarks, which are few enough that they
+/// aren't ranked.
+pub fn matches_all_words(query: &str, title: &str, url: &str) -> bool {
+    let title = title.to_lowercase();
+    let url = url.to_lowercase();
+    let mut words = query.split_whitespace().map(str::to_lowercase).peekable();
+    words.peek().is_some() && words.all(|word| title.contains(&word) || url.contains(&word))
+}
+
+/// Scores a substring match of `token` in `haystack`, up to `weight`, losing
+/// up to half of it the further into `haystack` the match starts.
+fn substring_score(haystack: &str, token: &str, weight: f64) -> Option<f64> {
+    let position = haystack.find(token)?;
+    let depth = position as f64 / haystack.len().max(1) as f64;
+    Some(weight * (1.0 - 0.5 * depth))
+}
+
+fn is_subsequence(token: &str, haystack: &str) -> bool {
+    let mut haystack_chars = haystack.chars();
+    token
+        .chars()
+        .all(|token_char| haystack_chars.any(|character| character == token_char))
+}
+
+fn max_entries_setting(cx: &App) -> usize {
+    usize::try_from(BrowserSettings::get_global(cx).max_history_entries).unwrap_or(usize::MAX)
+}
+
+/// Entries with the smallest key are evicted first: the least recently visited,
+/// with ties going to the one visited fewer times.
+fn eviction_key(entry: &HistoryEntry) -> (u64, u32) {
+    (entry.last_visited_ms, entry.visit_count)
+}
+
+/// Merges entries that share a URL into one, summing their visit counts and
+/// keeping the most recent visit and its title.
+fn collapse_duplicate_entries(entries: Vec<HistoryEntry>) -> Vec<HistoryEntry> {
+    let mut index_by_url: HashMap<String, usize> = HashMap::default();
+    let mut collapsed: Vec<HistoryEntry> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        match index_by_url.get(&entry.url) {
+            Some(&index) => {
+                let existing = &mut collapsed[index];
+                existing.visit_count = existing.visit_count.saturating_add(entry.visit_count);
+                if entry.last_visited_ms > existing.last_visited_ms {
+                    existing.last_visited_ms = entry.last_visited_ms;
+                    if !entry.title.is_empty() {
+                        existing.title = entry.title;
+                    }
+                }
+            }
+            None => {
+                index_by_url.insert(entry.url.clone(), collapsed.len());
+                collapsed.push(entry);
+            }
+        }
+    }
+    collapsed
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str, visit_count: u32, last_visited_ms: u64) -> HistoryEntry {
+        HistoryEntry {
+            url: url.to_string(),
+            title: String::new(),
+            visit_count,
+            last_visited_ms,
+        }
+    }
+
+    fn history(entries: Vec<HistoryEntry>, hidden_top_sites: Vec<String>) -> BrowserHistory {
+        BrowserHistory {
+            entries,
+            hidden_top_sites,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            is_ephemeral: false,
+            has_unsaved_visits: false,
+            _save_history_task: None,
+            _save_hidden_top_sites_task: None,
+            _settings_subscription: None,
+        }
+    }
+
+    #[test]
+    fn test_top_sites_aggregates_visits_per_origin() {
+        let history = history(
+            vec![
+                entry("https://github.com/zed-industries", 3, 10),
+                entry("https://github.com/", 2, 20),
+                entry("https://www.example.com/docs", 4, 30),
+                entry("glass://newtab", 50, 40),
+                entry("http://localhost:3000/", 1, 50),
+            ],
+            Vec::new(),
+        );
+
+        let sites = history.top_sites(10);
+        let origins: Vec<&str> = sites.iter().map(|site| site.origin.as_str()).collect();
+        assert_eq!(
+            origins,
+            vec![
+                "https://github.com",
+                "https://www.example.com",
+                "http://localhost:3000",
+            ]
+        );
+        assert_eq!(sites[0].visit_count, 5);
+        assert_eq!(sites[0].last_visited_ms, 20);
+        assert_eq!(sites[1].host, "example.com");
+        assert_eq!(history.top_sites(1).len(), 1);
+    }
+
+    #[test]
+    fn test_ephemeral_history_does_not_record_visits() {
+        let mut history = BrowserHistory::new_ephemeral();
+        history.record_visit("https://example.com/", "Example");
+        assert!(history.entries().is_empty());
+        assert!(history.top_sites(10).is_empty());
+    }
+
+    #[test]
+    fn test_unchanged_history_is_not_serialized_again() {
+        let mut history = history(Vec::new(), Vec::new());
+        assert!(history.take_unsaved_json().is_none());
+
+        history.record_visit("https://example.com/", "Example");
+        assert!(history.take_unsaved_json().is_some());
+        assert!(history.take_unsaved_json().is_none());
+    }
+
+    #[test]
+    fn test_top_sites_skips_hidden_origins() {
+        let history = history(
+            vec![
+                entry("https://github.com/", 5, 10),
+                entry("https://example.com/", 1, 20),
+            ],
+            vec!["https://github.com".to_string()],
+        );
+
+        let sites = history.top_sites(10);
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].origin, "https://example.com");
+    }
+
+    #[test]
+    fn test_inline_completion_prefers_most_visited_domain() {
+        let entries = vec![
+            entry("https://www.github.com/zed-industries/zed", 3, 10),
+            entry("https://gitlab.com/", 1, 20),
+            entry("https://github.com/", 5, 30),
+        ];
+
+        assert_eq!(
+            BrowserHistory::inline_completion(&entries, "git").as_deref(),
+            Some("github.com")
+        );
+        assert_eq!(
+            BrowserHistory::inline_completion(&entries, "GitL").as_deref(),
+            Some("GitLab.com")
+        );
+        assert_eq!(
+            BrowserHistory::inline_completion(&entries, "github.com/z").as_deref(),
+            Some("github.com/zed-industries/zed")
+        );
+        assert_eq!(
+            BrowserHistory::inline_completion(&entries, "github.com/zed-industries/zed"),
+            None
+        );
+        assert_eq!(BrowserHistory::inline_completion(&entries, "rust"), None);
+        assert_eq!(BrowserHistory::inline_completion(&entries, "git hub"), None);
+    }
+
+    #[test]
+    fn test_inline_completion_handles_non_ascii_text() {
+        let entries = vec![
+            entry("https://münchen.de/", 2, 10),
+            entry("https://example.com/Straße/Übersicht", 1, 20),
+        ];
+
+        assert_eq!(
+            BrowserHistory::inline_completion(&entries, "MÜN").as_deref(),
+            Some("MÜNchen.de")
+        );
+        assert_eq!(
+            BrowserHistory::inline_completion(&entries, "example.com/STRASSE"),
+            None
+        );
+        assert_eq!(
+            BrowserHistory::inline_completion(&entries, "example.com/straße/").as_deref(),
+            Some("example.com/straße/Übersicht")
+        );
+    }
+
+    #[test]
+    fn test_strip_prefix_ignoring_case() {
+        assert_eq!(
+            strip_prefix_ignoring_case("GitHub.com", "git"),
+            Some("Hub.com")
+        );
+        assert_eq!(
+            strip_prefix_ignoring_case("Übersicht", "üb"),
+            Some("ersicht")
+        );
+        assert_eq!(strip_prefix_ignoring_case("ab", "abc"), None);
+        assert_eq!(strip_prefix_ignoring_case("İstanbul", "i"), None);
+    }
+
+    #[test]
+    fn test_export_csv_quotes_fields() {
+        let mut entries = vec![entry("https://example.com/?a=1,2", 2, 30)];
+        entries[0].title = "Say \"hi\"".to_string();
+
+        let mut output = Vec::new();
+        BrowserHistory::export_csv(&entries, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "url,title,last_visited_ms,visit_count\n\"https://example.com/?a=1,2\",\"Say \"\"hi\"\"\",30,2\n"
+        );
+    }
 
-        let cancel_flag = AtomicBool::new(false);
-        let matches = fuzzy::match_strings(
-            &candidates,
-            &query,
-            false,
-            true,
-            max_results * 3, // over-fetch so we can re-rank
-            &cancel_flag,
-            executor,
+    #[test]
+    fn test_merge_entries_does_not_duplicate_urls() {
+        let mut history = history(
+            vec![
+                entry("https://github.com/", 5, 10),
+                entry("https://example.com/", 1, 20),
+            ],
+            Vec::new(),
+        );
+
+        let mut exported = Vec::new();
+        BrowserHistory::export_json(
+            &[
+                entry("https://github.com/", 2, 40),
+                entry("https://example.com/", 3, 15),
+                entry("https://zed.dev/", 1, 50),
+            ],
+            &mut exported,
         )
-        .await;
+        .unwrap();
+        let imported = BrowserHistory::read_json(exported.as_slice()).unwrap();
 
-        let query_lower = query.to_lowercase();
+        assert_eq!(history.merge_entries(imported.clone()), 1);
+        assert_eq!(history.merge_entries(imported), 0);
 
-        let mut results: Vec<HistoryMatch> = matches
-            .into_iter()
-            .filter_map(|m| {
-                let entry = entries.get(m.candidate_id)?;
-                let fuzzy_score = m.score;
-
-                // Recency bonus: 0.0-0.3 based on age
-                let age_ms = now_ms.saturating_sub(entry.last_visited_ms);
-                let age_hours = age_ms as f64 / 3_600_000.0;
-                let recency_bonus = 0.3 * (1.0 / (1.0 + age_hours / 24.0));
-
-                // Frequency bonus: 0.0-0.2 on log scale
-                let frequency_bonus = 0.2 * (entry.visit_count as f64).ln_1p() / 10.0_f64.ln_1p();
-
-                // Prefix bonus: 0.5 if URL starts with query
-                let prefix_bonus = if entry.url.to_lowercase().starts_with(&query_lower)
-                    || entry
-                        .url
-                        .to_lowercase()
-                        .strip_prefix("https://")
-                        .is_some_and(|u| u.starts_with(&query_lower))
-                    || entry
-                        .url
-                        .to_lowercase()
-                        .strip_prefix("http://")
-                        .is_some_and(|u| u.starts_with(&query_lower))
-                {
-                    0.5
-                } else {
-                    0.0
-                };
+        let summary: Vec<(&str, u32, u64)> = history
+            .entries()
+            .iter()
+            .map(|entry| (entry.url.as_str(), entry.visit_count, entry.last_visited_ms))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("https://github.com/", 5, 40),
+                ("https://example.com/", 3, 20),
+                ("https://zed.dev/", 1, 50),
+            ]
+        );
+    }
 
-                let final_score = fuzzy_score + recency_bonus + frequency_bonus + prefix_bonus;
+    #[test]
+    fn test_collapse_duplicate_entries_sums_visits() {
+        let mut newer = entry("https://github.com/", 2, 30);
+        newer.title = "GitHub".to_string();
+        let collapsed = collapse_duplicate_entries(vec![
+            entry("https://github.com/", 3, 10),
+            entry("https://example.com/", 1, 20),
+            newer,
+        ]);
 
-                Some(HistoryMatch {
-                    url: entry.url.clone(),
-                    title: entry.title.clone(),
-                    score: final_score,
-                })
-            })
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[0].url, "https://github.com/");
+        assert_eq!(collapsed[0].visit_count, 5);
+        assert_eq!(collapsed[0].last_visited_ms, 30);
+        assert_eq!(collapsed[0].title, "GitHub");
+    }
+
+    #[test]
+    fn test_prune_evicts_oldest_least_visited_entries() {
+        let mut history = history(
+            vec![
+                entry("https://a.com/", 1, 10),
+                entry("https://b.com/", 9, 10),
+                entry("https://c.com/", 1, 30),
+            ],
+            Vec::new(),
+        );
+        history.max_entries = 2;
+        history.prune();
+
+        let urls: Vec<&str> = history
+            .entries()
+            .iter()
+            .map(|entry| entry.url.as_str())
             .collect();
+        assert_eq!(urls, vec!["https://c.com/", "https://b.com/"]);
+    }
 
-        results.sort_by(|a, b| {
-            b.score
-                .partial_cmp(&a.score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-        results.truncate(max_results);
-        results
+    fn titled_entry(url: &str, title: &str, visit_count: u32) -> HistoryEntry {
+        HistoryEntry {
+            title: title.to_string(),
+            ..entry(url, visit_count, 0)
+        }
+    }
+
+    #[test]
+    fn test_rank_entries_matches_every_token() {
+        let entries = vec![
+            titled_entry(
+                "https://www.rust-lang.org/",
+                "Rust Programming Language",
+                20,
+            ),
+            titled_entry(
+                "https://github.com/zed-industries/zed/issues",
+                "Issues · zed-industries/zed",
+                5,
+            ),
+            titled_entry(
+                "https://github.com/rust-lang/rust/issues",
+                "Issues · rust-lang/rust",
+                1,
+            ),
+        ];
+
+        let urls = |query: &str| -> Vec<String> {
+            rank_entries(&entries, query, 8, 0)
+                .into_iter()
+                .map(|result| result.url)
+                .collect()
+        };
+
+        assert_eq!(
+            urls("gh rust issues").first().map(String::as_str),
+            Some("https://github.com/rust-lang/rust/issues")
+        );
+        assert_eq!(
+            urls("rust").first().map(String::as_str),
+            Some("https://www.rust-lang.org/")
+        );
+        assert!(urls("kotlin").is_empty());
+        assert!(urls("   ").is_empty());
+    }
+
+    #[test]
+    fn test_rank_entries_prefers_host_matches_over_path_matches() {
+        let entries = vec![
+            titled_entry("https://example.com/docs/guides/zed", "", 1),
+            titled_entry("https://zed.dev/", "", 1),
+        ];
+
+        let results = rank_entries(&entries, "zed", 8, 0);
+        assert_eq!(results[0].url, "https://zed.dev/");
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_match_domains_prefers_configured_domains() {
+        let extra_domains = vec![
+            "gitea.example.com".to_string(),
+            "www.github.com".to_string(),
+        ];
+        let urls: Vec<String> = match_domains(&extra_domains, "https://www.git", 8)
+            .into_iter()
+            .map(|result| result.url)
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://gitea.example.com",
+                "https://github.com",
+                "https://gitlab.com"
+            ]
+        );
+        assert!(match_domains(&[], "git hub", 8).is_empty());
+        assert!(match_domains(&[], "", 8).is_empty());
+    }
+
+    #[test]
+    fn test_suggestion_detail_shows_time_since_last_visit() {
+        let now = UNIX_EPOCH + Duration::from_secs(10 * 24 * 60 * 60);
+        let now_ms = 10 * 24 * 60 * 60 * 1000;
+        let history_match = HistoryMatch {
+            url: "https://github.com/".to_string(),
+            title: "GitHub".to_string(),
+            score: 1.0,
+            is_suggested_domain: false,
+            last_visited_ms: now_ms - 2 * 60 * 60 * 1000,
+        };
+
+        assert_eq!(
+            history_match.detail("github.com", BrowserHistorySuggestionDetail::Domain, now),
+            "github.com"
+        );
+        assert_eq!(
+            history_match.detail(
+                "github.com",
+                BrowserHistorySuggestionDetail::LastVisited,
+                now
+            ),
+            "2h ago"
+        );
+        assert_eq!(
+            history_match.detail(
+                "github.com",
+                BrowserHistorySuggestionDetail::DomainAndLastVisited,
+                now
+            ),
+            "github.com · 2h ago"
+        );
+
+        let suggested_domain = HistoryMatch {
+            is_suggested_domain: true,
+            last_visited_ms: 0,
+            ..history_match
+        };
+        assert_eq!(
+            suggested_domain.detail(
+                "github.com",
+                BrowserHistorySuggestionDetail::LastVisited,
+                now
+            ),
+            "github.com"
+        );
+
+        assert_eq!(format_visit_age(now_ms - 30_000, now), "just now");
+        assert_eq!(
+            format_visit_age(now_ms - 9 * 24 * 60 * 60 * 1000, now),
+            "1w ago"
+        );
+    }
+
+    #[test]
+    fn test_matches_all_words() {
+        assert!(matches_all_words(
+            "zed docs",
+            "Zed Docs",
+            "https://zed.dev/docs"
+        ));
+        assert!(matches_all_words("ZED", "", "https://zed.dev/"));
+        assert!(!matches_all_words("zed github", "Zed", "https://zed.dev/"));
+        assert!(!matches_all_words("  ", "Zed", "https://zed.dev/"));
     }
 }
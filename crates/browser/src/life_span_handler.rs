@@ -7,8 +7,10 @@
 use crate::client::ClientBuilder;
 use crate::events::{BrowserEvent, EventSender, OpenDisposition, OpenTargetRequest};
 use crate::render_handler::RenderState;
+use crate::site_permissions::{self, SitePermissions};
+use crate::user_agent::{SiteUserAgents, TabUserAgent};
 use cef::{
-    Browser, ImplLifeSpanHandler, LifeSpanHandler, WrapLifeSpanHandler, rc::Rc as _,
+    Browser, ImplBrowser, ImplLifeSpanHandler, LifeSpanHandler, WrapLifeSpanHandler, rc::Rc as _,
     wrap_life_span_handler,
 };
 use parking_lot::Mutex;
@@ -17,17 +19,32 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct OsrLifeSpanHandler {
     sender: EventSender,
+    site_permissions: SitePermissions,
+    site_user_agents: SiteUserAgents,
 }
 
 impl OsrLifeSpanHandler {
-    pub fn new(sender: EventSender) -> Self {
-        Self { sender }
+    pub fn new(
+        sender: EventSender,
+        site_permissions: SitePermissions,
+        site_user_agents: SiteUserAgents,
+    ) -> Self {
+        Self {
+            sender,
+            site_permissions,
+            site_user_agents,
+        }
     }
 
-    fn popup_client() -> cef::Client {
+    fn popup_client(&self) -> cef::Client {
         let render_state = Arc::new(Mutex::new(RenderState::default()));
         let (popup_sender, _popup_receiver) = crate::events::event_channel();
-        ClientBuilder::build_for_popup(render_state, popup_sender)
+        ClientBuilder::build_for_popup(
+            render_state,
+            popup_sender,
+            self.site_permissions.clone(),
+            TabUserAgent::new(self.site_user_agents.clone()),
+        )
     }
 }
 
@@ -94,7 +111,7 @@ wrap_life_span_handler! {
             }
 
             if let Some(client) = client {
-                *client = Some(OsrLifeSpanHandler::popup_client());
+                *client = Some(self.handler.popup_client());
             }
 
             if let Some(url) = target_url {
@@ -112,7 +129,10 @@ wrap_life_span_handler! {
             0 // Allow close
         }
 
-        fn on_before_close(&self, _browser: Option<&mut Browser>) {
+        fn on_before_close(&self, browser: Option<&mut Browser>) {
+            if let Some(browser) = browser {
+                site_permissions::forget_browser_permission_requests(browser.identifier());
+            }
         }
     }
 }
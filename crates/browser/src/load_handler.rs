@@ -4,6 +4,7 @@
 //! URL and title tracking is handled by the DisplayHandler instead.
 
 use crate::events::{BrowserEvent, EventSender};
+use crate::page_security;
 use cef::{Browser, ImplLoadHandler, LoadHandler, WrapLoadHandler, rc::Rc as _, wrap_load_handler};
 
 #[derive(Clone)]
@@ -25,7 +26,7 @@ wrap_load_handler! {
     impl LoadHandler {
         fn on_loading_state_change(
             &self,
-            _browser: Option<&mut Browser>,
+            browser: Option<&mut Browser>,
             is_loading: ::std::os::raw::c_int,
             can_go_back: ::std::os::raw::c_int,
             can_go_forward: ::std::os::raw::c_int,
@@ -35,6 +36,13 @@ wrap_load_handler! {
                 can_go_back: can_go_back != 0,
                 can_go_forward: can_go_forward != 0,
             });
+            if is_loading == 0
+                && let Some(browser) = browser
+            {
+                let _ = self.handler.sender.send(BrowserEvent::PageSecurityChanged(
+                    page_security::read_page_security(browser),
+                ));
+            }
         }
 
         fn on_load_error(
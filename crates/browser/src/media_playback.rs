@@ -0,0 +1,146 @@
+//! Media Playback Detection
+//!
+//! The render process reports whether the page is playing sound, from an
+//! `<audio>` or `<video>` element or a running Web Audio context, and whether
+//! it holds a live WebRTC or WebSocket connection, so such tabs keep running
+//! while they are in the background.
+
+use cef::{CefString, ImplListValue, ImplProcessMessage, ProcessMessage};
+use serde::Deserialize;
+
+pub(crate) const MEDIA_PLAYBACK_MESSAGE_NAME: &str = "glass.media_playback";
+pub(crate) const MEDIA_PLAYBACK_BRIDGE_NAME: &str = "__glassReportMediaPlayback";
+
+pub(crate) const MEDIA_PLAYBACK_OBSERVER_SCRIPT: &str = r#"
+(function () {
+  if (window.__glassMediaPlaybackInstalled) return;
+  window.__glassMediaPlaybackInstalled = true;
+
+  const bridge = window.__glassReportMediaPlayback;
+  if (typeof bridge !== 'function') return;
+
+  const audioContexts = new Set();
+  const peerConnections = new Set();
+  let openSocketCount = 0;
+
+  let lastReport = null;
+  const report = () => {
+    const mediaAudible = Array.from(document.querySelectorAll('audio, video')).some(
+      (media) => !media.paused && !media.ended && !media.muted && media.volume > 0
+    );
+    const webAudioAudible = Array.from(audioContexts).some(
+      (context) => context.state === 'running'
+    );
+    const audible = mediaAudible || webAudioAudible;
+    const connected = openSocketCount > 0 || Array.from(peerConnections).some(
+      (connection) => connection.connectionState === 'connecting'
+        || connection.connectionState === 'connected'
+    );
+    const nextReport = JSON.stringify({ audible, connected });
+    if (nextReport === lastReport) return;
+    lastReport = nextReport;
+    bridge(nextReport);
+  };
+
+  const track = (name, onCreate) => {
+    const Original = window[name];
+    if (typeof Original !== 'function') return;
+    window[name] = class extends Original {
+      constructor(...args) {
+        super(...args);
+        onCreate(this);
+      }
+    };
+  };
+
+  track('AudioContext', (context) => {
+    audioContexts.add(context);
+    context.addEventListener('statechange', () => {
+      if (context.state === 'closed') audioContexts.delete(context);
+      report();
+    });
+    report();
+  });
+  track('RTCPeerConnection', (connection) => {
+    peerConnections.add(connection);
+    connection.addEventListener('connectionstatechange', () => {
+      if (connection.connectionState === 'closed') peerConnections.delete(connection);
+      report();
+    });
+  });
+  track('WebSocket', (socket) => {
+    let isOpen = false;
+    socket.addEventListener('open', () => {
+      isOpen = true;
+      openSocketCount += 1;
+      report();
+    });
+    socket.addEventListener('close', () => {
+      if (!isOpen) return;
+      isOpen = false;
+      openSocketCount -= 1;
+      report();
+    });
+  });
+
+  for (const type of ['play', 'playing', 'pause', 'ended', 'volumechange', 'emptied']) {
+    document.addEventListener(type, report, true);
+  }
+  report();
+})();
+"#;
+
+/// What keeps a background tab from being frozen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct MediaPlayback {
+    #[serde(default, rename = "audible")]
+    pub is_audible: bool,
+    #[serde(default, rename = "connected")]
+    pub has_live_connection: bool,
+}
+
+pub(crate) fn parse_media_playback_payload(payload: &str) -> Option<MediaPlayback> {
+    serde_json::from_str(payload).ok()
+}
+
+/// Returns `None` for other messages, and `Some(None)` for a malformed
+/// media playback payload.
+pub(crate) fn extract_media_playback_from_message(
+    message: &mut ProcessMessage,
+) -> Option<Option<MediaPlayback>> {
+    if CefString::from(&message.name()).to_string() != MEDIA_PLAYBACK_MESSAGE_NAME {
+        return None;
+    }
+
+    let args = message.argument_list()?;
+    let payload = CefString::from(&args.string(0)).to_string();
+    Some(parse_media_playback_payload(&payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_media_playback_payloads() {
+        assert_eq!(
+            parse_media_playback_payload(r#"{"audible":true}"#),
+            Some(MediaPlayback {
+                is_audible: true,
+                has_live_connection: false,
+            })
+        );
+        assert_eq!(
+            parse_media_playback_payload(r#"{"audible":false,"connected":true}"#),
+            Some(MediaPlayback {
+                is_audible: false,
+                has_live_connection: true,
+            })
+        );
+        assert_eq!(
+            parse_media_playback_payload("{}"),
+            Some(MediaPlayback::default())
+        );
+        assert_eq!(parse_media_playback_payload("not json"), None);
+    }
+}
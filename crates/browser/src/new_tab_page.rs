@@ -1,11 +1,18 @@
 use crate::browser_view::BrowserView;
-use crate::history::HistoryMatch;
+use crate::history::{HistoryMatch, TopSite};
 use editor::Editor;
 use gpui::{
     App, Entity, Focusable, InteractiveElement, IntoElement, ParentElement, SharedString, Styled,
     Window, div, prelude::*, px, rems,
 };
-use ui::{Color, Icon, IconName, IconSize, h_flex, prelude::*, v_flex};
+use ui::{
+    Color, Icon, IconButton, IconButtonShape, IconName, IconSize, Tooltip, h_flex, prelude::*,
+    v_flex,
+};
+
+pub(crate) const TOP_SITES_LIMIT: usize = 8;
+pub(crate) const RECENTLY_CLOSED_LIMIT: usize = 5;
+const TOP_SITES_PER_ROW: usize = 4;
 
 pub fn render_new_tab_page(
     browser_view: Entity<BrowserView>,
@@ -13,6 +20,8 @@ pub fn render_new_tab_page(
     search_text: String,
     suggestions: Vec<HistoryMatch>,
     selected_index: Option<usize>,
+    top_sites: Vec<TopSite>,
+    recently_closed: Vec<(String, String)>,
     is_incognito_window: bool,
     window: &mut Window,
     cx: &mut gpui::Context<BrowserView>,
@@ -68,7 +77,7 @@ pub fn render_new_tab_page(
         None
     } else {
         Some(render_search_menu(
-            browser_view,
+            browser_view.clone(),
             search_text,
             suggestions,
             selected_index,
@@ -151,10 +160,172 @@ pub fn render_new_tab_page(
                         })
                         .child(search_box)
                         .when_some(dropdown, |this, dropdown| this.child(dropdown)),
-                ),
+                )
+                .when(!top_sites.is_empty(), |this| {
+                    this.child(render_top_sites(browser_view.clone(), top_sites, cx))
+                })
+                .when(!recently_closed.is_empty(), |this| {
+                    this.child(render_recently_closed(browser_view, recently_closed, cx))
+                }),
         )
 }
 
+fn render_top_sites(
+    browser_view: Entity<BrowserView>,
+    top_sites: Vec<TopSite>,
+    cx: &App,
+) -> impl IntoElement {
+    let theme = cx.theme();
+    let radius = theme.component_radius().tab.unwrap_or(px(8.0));
+    let mut rows = Vec::new();
+    let mut tiles = top_sites.into_iter().enumerate().peekable();
+
+    while tiles.peek().is_some() {
+        let row = tiles.by_ref().take(TOP_SITES_PER_ROW).map(|(index, site)| {
+            let navigate_view = browser_view.clone();
+            let remove_view = browser_view.clone();
+            let url = site.origin.clone();
+            let origin = site.origin;
+            let group_name = SharedString::from(format!("new-tab-top-site-{index}"));
+
+            v_flex()
+                .id(("new-tab-top-site", index))
+                .group(group_name.clone())
+                .relative()
+                .flex_1()
+                .min_w_0()
+                .items_center()
+                .gap_2()
+                .p_3()
+                .rounded(radius)
+                .cursor_pointer()
+                .hover(|style| style.bg(theme.colors().ghost_element_hover))
+                .on_click(move |_, window, cx| {
+                    let url = url.clone();
+                    navigate_view.update(cx, |browser_view, cx| {
+                        browser_view.navigate_new_tab_search_result(url, window, cx);
+                    });
+                })
+                .child(
+                    div()
+                        .size(px(40.0))
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .rounded_full()
+                        .bg(theme.colors().element_background)
+                        .child(Icon::new(IconName::Globe).color(Color::Muted)),
+                )
+                .child(
+                    div()
+                        .w_full()
+                        .overflow_hidden()
+                        .whitespace_nowrap()
+                        .text_ellipsis()
+                        .text_center()
+                        .text_size(rems(0.75))
+                        .text_color(theme.colors().text)
+                        .child(site.host),
+                )
+                .child(
+                    div()
+                        .absolute()
+                        .top_1()
+                        .right_1()
+                        .visible_on_hover(group_name)
+                        .child(
+                            IconButton::new(("new-tab-remove-top-site", index), IconName::Close)
+                                .shape(IconButtonShape::Square)
+                                .icon_size(IconSize::XSmall)
+                                .tooltip(Tooltip::text("Remove from Top Sites"))
+                                .on_click(move |_, _, cx| {
+                                    cx.stop_propagation();
+                                    remove_view.update(cx, |browser_view, cx| {
+                                        browser_view.remove_top_site(&origin, cx);
+                                    });
+                                }),
+                        ),
+                )
+                .into_any_element()
+        });
+        rows.push(h_flex().w_full().gap_2().children(row).into_any_element());
+    }
+
+    v_flex()
+        .w_full()
+        .gap_1()
+        .child(section_heading("Top Sites", cx))
+        .children(rows)
+}
+
+fn render_recently_closed(
+    browser_view: Entity<BrowserView>,
+    recently_closed: Vec<(String, String)>,
+    cx: &App,
+) -> impl IntoElement {
+    let theme = cx.theme();
+    let rows = recently_closed
+        .into_iter()
+        .enumerate()
+        .map(|(index, (url, title))| {
+            let browser_view = browser_view.clone();
+            let title = if title.is_empty() { url.clone() } else { title };
+            let domain = extract_domain(&url);
+
+            h_flex()
+                .id(("new-tab-recently-closed", index))
+                .w_full()
+                .px_3()
+                .py_1p5()
+                .gap_3()
+                .rounded(theme.component_radius().tab.unwrap_or(px(8.0)))
+                .cursor_pointer()
+                .hover(|style| style.bg(theme.colors().ghost_element_hover))
+                .on_click(move |_, window, cx| {
+                    let url = url.clone();
+                    browser_view.update(cx, |browser_view, cx| {
+                        browser_view.navigate_new_tab_search_result(url, window, cx);
+                    });
+                })
+                .child(
+                    Icon::new(IconName::RotateCcw)
+                        .size(IconSize::Small)
+                        .color(Color::Muted),
+                )
+                .child(
+                    div()
+                        .flex_1()
+                        .min_w_0()
+                        .overflow_hidden()
+                        .whitespace_nowrap()
+                        .text_ellipsis()
+                        .text_size(rems(0.8125))
+                        .text_color(theme.colors().text)
+                        .child(title),
+                )
+                .child(
+                    div()
+                        .text_size(rems(0.75))
+                        .text_color(theme.colors().text_muted)
+                        .child(domain),
+                )
+        });
+
+    v_flex()
+        .w_full()
+        .gap_1()
+        .child(section_heading("Recently Closed", cx))
+        .children(rows)
+}
+
+fn section_heading(label: &'static str, cx: &App) -> impl IntoElement {
+    div()
+        .px_3()
+        .text_size(rems(0.6875))
+        .text_color(cx.theme().colors().text_muted)
+        .child(label)
+}
+
 fn render_search_menu(
     browser_view: Entity<BrowserView>,
     search_text: String,
@@ -213,7 +384,9 @@ fn render_search_menu(
             } else {
                 SharedString::from(suggestion.title.clone())
             };
-            let detail = SharedString::from(extract_domain(&suggestion.url));
+            let detail = SharedString::from(
+                suggestion.suggestion_detail(&extract_domain(&suggestion.url), cx),
+            );
             rows.push(
                 render_search_row(
                     browser_view.clone(),
@@ -1,10 +1,13 @@
+use crate::browser_settings::BrowserSettings;
 use crate::history::{BrowserHistory, HistoryMatch};
-use editor::{Editor, actions::SelectAll};
+use crate::url_input::{looks_like_url, search_engine_name, search_url_for_query, text_to_url};
+use editor::{Editor, MultiBufferOffset, SelectionEffects, actions::SelectAll};
 use gpui::{
     App, Bounds, Context, Corner, Entity, EventEmitter, FocusHandle, Focusable, IntoElement,
     ParentElement, Pixels, Render, SharedString, Styled, Subscription, Task, Window, anchored,
     canvas, deferred, div, native_image_view, point, prelude::*, px,
 };
+use settings::Settings as _;
 use std::time::Duration;
 use ui::{Icon, IconName, IconSize, h_flex, prelude::*, v_flex};
 
@@ -19,14 +22,12 @@ pub enum OmniboxSuggestion {
 }
 
 impl OmniboxSuggestion {
-    fn url_or_search(&self) -> String {
+    fn url_or_search(&self, cx: &App) -> String {
         match self {
             OmniboxSuggestion::HistoryItem { url, .. } => url.clone(),
-            OmniboxSuggestion::RawUrl(url) => text_to_url(url),
+            OmniboxSuggestion::RawUrl(url) => text_to_url(url, cx),
             OmniboxSuggestion::SearchQuery(query) => {
-                let encoded: String =
-                    url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
-                format!("https://www.google.com/search?q={}", encoded)
+                search_url_for_query(query, &BrowserSettings::get_global(cx).search_url)
             }
         }
     }
@@ -37,9 +38,15 @@ pub struct Omnibox {
     history: Entity<BrowserHistory>,
     content_focus_handle: FocusHandle,
     suggestions: Vec<OmniboxSuggestion>,
-    selected_index: usize,
+    /// The suggestion row highlighted with the arrow keys. Enter navigates
+    /// the typed text while nothing is highlighted.
+    selected_suggestion_index: Option<usize>,
     is_open: bool,
     suppress_search: bool,
+    /// The text as the user last typed it, without any inline completion.
+    typed_text: String,
+    /// Set after a deletion, so removing a completion leaves the literal text.
+    suppress_inline_completion: bool,
     navigation_started: bool,
     current_page_url: String,
     pending_search: Option<Task<()>>,
@@ -62,7 +69,7 @@ impl Omnibox {
             editor
         });
 
-        let buffer_subscription = cx.subscribe(&url_editor, Self::on_editor_event);
+        let buffer_subscription = cx.subscribe_in(&url_editor, window, Self::on_editor_event);
         let focus_subscription =
             cx.on_focus(&url_editor.focus_handle(cx), window, Self::on_editor_focus);
         let blur_subscription =
@@ -73,9 +80,11 @@ impl Omnibox {
             history,
             content_focus_handle,
             suggestions: Vec::new(),
-            selected_index: 0,
+            selected_suggestion_index: None,
             is_open: false,
             suppress_search: false,
+            typed_text: String::new(),
+            suppress_inline_completion: false,
             navigation_started: false,
             current_page_url: String::new(),
             pending_search: None,
@@ -88,6 +97,7 @@ impl Omnibox {
         let display_url = display_url(url);
         self.navigation_started = false;
         self.current_page_url = display_url.clone();
+        self.typed_text = display_url.clone();
         self.close_dropdown(cx);
         self.suppress_search = true;
         self.url_editor.update(cx, |editor, cx| {
@@ -107,8 +117,9 @@ impl Omnibox {
 
     fn on_editor_event(
         &mut self,
-        _editor: Entity<Editor>,
+        editor: &Entity<Editor>,
         event: &editor::EditorEvent,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         if matches!(event, editor::EditorEvent::BufferEdited) {
@@ -116,11 +127,37 @@ impl Omnibox {
                 self.suppress_search = false;
                 return;
             }
-            self.schedule_search(cx);
+            let text = editor.read(cx).text(cx);
+            self.suppress_inline_completion =
+                text.len() <= self.typed_text.len() && self.typed_text.starts_with(&text);
+            self.typed_text = text;
+            self.schedule_search(window, cx);
         }
     }
 
+    /// Appends the rest of `completion` after the typed text and selects it,
+    /// so typing over it refines the match and Enter opens the full URL.
+    fn show_inline_completion(
+        &mut self,
+        completion: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let typed_length = self.typed_text.len();
+        let completion_length = completion.len();
+        self.suppress_search = true;
+        self.url_editor.update(cx, |editor, cx| {
+            editor.set_text(completion, window, cx);
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |selections| {
+                selections.select_ranges([
+                    MultiBufferOffset(typed_length)..MultiBufferOffset(completion_length)
+                ]);
+            });
+        });
+    }
+
     fn on_editor_focus(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.typed_text = self.url_editor.read(cx).text(cx);
         self.url_editor.update(cx, |editor, cx| {
             editor.select_all(&SelectAll, window, cx);
         });
@@ -142,7 +179,7 @@ impl Omnibox {
         }
     }
 
-    fn schedule_search(&mut self, cx: &mut Context<Self>) {
+    fn schedule_search(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let query = self.url_editor.read(cx).text(cx);
 
         if query.is_empty() || query == self.current_page_url {
@@ -154,8 +191,9 @@ impl Omnibox {
         }
 
         let executor = cx.background_executor().clone();
+        let max_suggestions = BrowserSettings::get_global(cx).max_suggestions;
 
-        self.pending_search = Some(cx.spawn(async move |this, cx| {
+        self.pending_search = Some(cx.spawn_in(window, async move |this, cx| {
             cx.background_executor()
                 .timer(Duration::from_millis(100))
                 .await;
@@ -185,10 +223,28 @@ impl Omnibox {
                 .ok()
                 .unwrap_or_default();
 
-            let history_matches =
-                BrowserHistory::search(entries, query_for_search.clone(), 8, executor).await;
-
-            let _ = this.update(cx, |this, cx| {
+            let inline_completion = BrowserHistory::inline_completion(&entries, &query_for_search);
+            let history_matches = BrowserHistory::search(
+                entries,
+                query_for_search.clone(),
+                max_suggestions,
+                executor,
+            )
+            .await;
+
+            let _ = this.update_in(cx, |this, window, cx| {
+                if let Some(completion) = inline_completion
+                    && !this.suppress_inline_completion
+                    && this.typed_text == query_for_search
+                    && this.url_editor.read(cx).text(cx) == query_for_search
+                {
+                    this.show_inline_completion(completion, window, cx);
+                }
+                let history_matches = if history_matches.is_empty() {
+                    BrowserHistory::domain_suggestions(&query_for_search, max_suggestions, cx)
+                } else {
+                    history_matches
+                };
                 this.build_suggestions(query_for_search, history_matches);
                 this.pending_search = None;
                 cx.notify();
@@ -213,47 +269,56 @@ impl Omnibox {
             });
         }
 
-        self.selected_index = 0;
+        self.selected_suggestion_index = None;
         self.is_open = true;
     }
 
     fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
-        if self.is_open && !self.suggestions.is_empty() {
-            let index = self
-                .selected_index
-                .min(self.suggestions.len().saturating_sub(1));
-            let url = self.suggestions[index].url_or_search();
+        if self.is_open
+            && let Some(suggestion) = self
+                .selected_suggestion_index
+                .and_then(|index| self.suggestions.get(index))
+        {
+            let url = suggestion.url_or_search(cx);
             self.navigate(url, window, cx);
             return;
         }
 
-        // Fallback: if dropdown is not open, just navigate to whatever is in the editor
         let text = self.url_editor.read(cx).text(cx);
         if text.is_empty() {
             return;
         }
 
-        let url = text_to_url(&text);
+        let url = text_to_url(&text, cx);
 
         self.navigate(url, window, cx);
     }
 
     fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_open && self.selected_suggestion_index.take().is_some() {
+            cx.notify();
+            return;
+        }
+
         self.close_dropdown(cx);
         self.navigation_started = false;
         let current_page_url = self.current_page_url.clone();
-        if self.url_editor.read(cx).text(cx) != current_page_url {
-            self.suppress_search = true;
-            self.url_editor.update(cx, |editor, cx| {
-                editor.set_text(current_page_url, window, cx);
-            });
+        if self.url_editor.read(cx).text(cx) == current_page_url {
+            window.focus(&self.content_focus_handle, cx);
+            return;
         }
+
+        self.suppress_search = true;
+        self.url_editor.update(cx, |editor, cx| {
+            editor.set_text(current_page_url, window, cx);
+            editor.select_all(&SelectAll, window, cx);
+        });
     }
 
     fn close_dropdown(&mut self, cx: &mut Context<Self>) {
         self.suggestions.clear();
         self.is_open = false;
-        self.selected_index = 0;
+        self.selected_suggestion_index = None;
         self.pending_search = None;
         cx.notify();
     }
@@ -274,11 +339,8 @@ impl Omnibox {
         if !self.is_open || self.suggestions.is_empty() {
             return;
         }
-        if self.selected_index == 0 {
-            self.selected_index = self.suggestions.len() - 1;
-        } else {
-            self.selected_index -= 1;
-        }
+        self.selected_suggestion_index =
+            previous_suggestion_index(self.selected_suggestion_index, self.suggestions.len());
         cx.notify();
     }
 
@@ -291,7 +353,8 @@ impl Omnibox {
         if !self.is_open || self.suggestions.is_empty() {
             return;
         }
-        self.selected_index = (self.selected_index + 1) % self.suggestions.len();
+        self.selected_suggestion_index =
+            next_suggestion_index(self.selected_suggestion_index, self.suggestions.len());
         cx.notify();
     }
 
@@ -303,7 +366,7 @@ impl Omnibox {
             .iter()
             .enumerate()
             .map(|(index, suggestion)| {
-                let is_selected = index == self.selected_index;
+                let is_selected = self.selected_suggestion_index == Some(index);
                 let (leading_icon, title, subtitle) = match suggestion {
                     OmniboxSuggestion::HistoryItem { url, title, .. } => {
                         let display_title: SharedString = if title.is_empty() {
@@ -338,7 +401,8 @@ impl Omnibox {
                             query.clone()
                         };
                         let display: SharedString =
-                            format!("Search Google for \"{}\"", truncated).into();
+                            format!("Search {} for \"{}\"", search_engine_name(cx), truncated)
+                                .into();
                         (
                             Icon::new(IconName::MagnifyingGlass)
                                 .size(IconSize::Small)
@@ -369,9 +433,9 @@ impl Omnibox {
                     .on_mouse_down(
                         gpui::MouseButton::Left,
                         cx.listener(move |this, _, window, cx| {
-                            this.selected_index = index;
+                            this.selected_suggestion_index = Some(index);
                             if let Some(suggestion) = this.suggestions.get(index) {
-                                let url = suggestion.url_or_search();
+                                let url = suggestion.url_or_search(cx);
                                 this.navigate(url, window, cx);
                             }
                         }),
@@ -490,102 +554,44 @@ impl Render for Omnibox {
     }
 }
 
-fn looks_like_url(input: &str) -> bool {
-    if input.starts_with("http://") || input.starts_with("https://") {
-        return true;
-    }
-    // Contains :// scheme
-    if input.contains("://") {
-        return true;
-    }
-
-    if input.chars().any(char::is_whitespace) {
-        return false;
+fn display_url(url: &str) -> String {
+    if url == "glass://newtab" {
+        return String::new();
     }
 
-    let Ok(url) = url::Url::parse(&format!("http://{input}")) else {
-        return false;
-    };
-
-    let Some(host) = url.host_str() else {
-        return false;
-    };
-
-    host.eq_ignore_ascii_case("localhost")
-        || host.contains('.')
-        || host.parse::<std::net::IpAddr>().is_ok()
-        || (url.port().is_some() && !host.contains('.'))
+    url.to_string()
 }
 
-fn should_use_http_by_default(input: &str) -> bool {
-    let Ok(url) = url::Url::parse(&format!("http://{input}")) else {
-        return false;
-    };
-
-    let Some(host) = url.host_str() else {
-        return false;
-    };
-
-    if host.eq_ignore_ascii_case("localhost") {
-        return true;
-    }
-
-    if let Ok(address) = host.parse::<std::net::IpAddr>() {
-        return address.is_loopback();
+/// Moving down from no highlight starts at the first row, and moving past
+/// the last row wraps to the first.
+fn next_suggestion_index(selected: Option<usize>, count: usize) -> Option<usize> {
+    if count == 0 {
+        return None;
     }
-
-    url.port().is_some() && !host.contains('.')
+    Some(selected.map_or(0, |index| (index + 1) % count))
 }
 
-fn text_to_url(text: &str) -> String {
-    if text.starts_with("http://") || text.starts_with("https://") {
-        return text.to_string();
-    }
-
-    if !looks_like_url(text) {
-        let encoded: String = url::form_urlencoded::byte_serialize(text.as_bytes()).collect();
-        return format!("https://www.google.com/search?q={encoded}");
-    }
-
-    if should_use_http_by_default(text) {
-        format!("http://{text}")
-    } else {
-        format!("https://{text}")
+/// Moving up from no highlight or the first row wraps to the last row.
+fn previous_suggestion_index(selected: Option<usize>, count: usize) -> Option<usize> {
+    if count == 0 {
+        return None;
     }
-}
-
-fn display_url(url: &str) -> String {
-    if url == "glass://newtab" {
-        return String::new();
-    }
-
-    url.to_string()
+    Some(match selected {
+        Some(0) | None => count - 1,
+        Some(index) => index - 1,
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{looks_like_url, text_to_url};
+    use super::*;
 
     #[test]
-    fn localhost_inputs_are_treated_as_urls() {
-        assert!(looks_like_url("localhost"));
-        assert!(looks_like_url("localhost:3000"));
-        assert_eq!(text_to_url("localhost"), "http://localhost");
-        assert_eq!(text_to_url("localhost:3000"), "http://localhost:3000");
-    }
-
-    #[test]
-    fn regular_domains_default_to_https() {
-        assert!(looks_like_url("example.com"));
-        assert_eq!(text_to_url("example.com"), "https://example.com");
-    }
-
-    #[test]
-    fn plain_queries_still_search() {
-        assert!(!looks_like_url("rust ownership"));
-        assert_eq!(
-            text_to_url("rust ownership"),
-            "https://www.google.com/search?q=rust+ownership"
-        );
+    fn arrow_keys_wrap_through_suggestions() {
+        assert_eq!(next_suggestion_index(None, 3), Some(0));
+        assert_eq!(next_suggestion_index(Some(2), 3), Some(0));
+        assert_eq!(previous_suggestion_index(None, 3), Some(2));
+        assert_eq!(previous_suggestion_index(Some(1), 3), Some(0));
+        assert_eq!(next_suggestion_index(None, 0), None);
     }
 }
@@ -0,0 +1,184 @@
+//! Page Capture
+//!
+//! Takes screenshots of a page through the DevTools protocol, which can
+//! render the whole document rather than only the painted viewport.
+
+use anyhow::{Context as _, Result, anyhow};
+use base64::Engine as _;
+use cef::{
+    Browser, DevToolsMessageObserver, ImplBrowserHost, ImplDevToolsMessageObserver,
+    WrapDevToolsMessageObserver, rc::Rc as _, wrap_dev_tools_message_observer,
+};
+use futures::StreamExt as _;
+use futures::channel::mpsc;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// Chromium can't render a single capture taller than its maximum texture
+/// size, so longer pages are cut off at this height.
+const MAX_CAPTURE_HEIGHT: f64 = 16384.0;
+
+/// Starts well above the IDs CEF assigns to `ExecuteDevToolsMethod` calls.
+static NEXT_MESSAGE_ID: AtomicI32 = AtomicI32::new(1_000_000);
+
+struct DevToolsMethodResult {
+    message_id: i32,
+    success: bool,
+    result: Vec<u8>,
+}
+
+#[derive(Clone)]
+struct OsrDevToolsMessageObserver {
+    sender: mpsc::UnboundedSender<DevToolsMethodResult>,
+}
+
+wrap_dev_tools_message_observer! {
+    struct DevToolsMessageObserverBuilder {
+        observer: OsrDevToolsMessageObserver,
+    }
+
+    impl DevToolsMessageObserver {
+        fn on_dev_tools_method_result(
+            &self,
+            _browser: Option<&mut Browser>,
+            message_id: ::std::os::raw::c_int,
+            success: ::std::os::raw::c_int,
+            result: *const u8,
+            result_size: usize,
+        ) {
+            let result = if result.is_null() {
+                Vec::new()
+            } else {
+                // CEF owns the buffer only for the duration of this callback.
+                unsafe { std::slice::from_raw_parts(result, result_size) }.to_vec()
+            };
+            self.observer
+                .sender
+                .unbounded_send(DevToolsMethodResult {
+                    message_id,
+                    success: success != 0,
+                    result,
+                })
+                .ok();
+        }
+    }
+}
+
+/// Sends DevTools methods to one browser and waits for their results. The
+/// observer is unregistered when the session is dropped.
+pub(crate) struct DevToolsSession {
+    host: cef::BrowserHost,
+    results: mpsc::UnboundedReceiver<DevToolsMethodResult>,
+    _registration: cef::Registration,
+}
+
+impl DevToolsSession {
+    pub(crate) fn attach(host: &cef::BrowserHost) -> Option<Self> {
+        let (sender, results) = mpsc::unbounded();
+        let mut observer =
+            DevToolsMessageObserverBuilder::new(OsrDevToolsMessageObserver { sender });
+        let registration = host.add_dev_tools_message_observer(Some(&mut observer))?;
+        Some(Self {
+            host: host.clone(),
+            results,
+            _registration: registration,
+        })
+    }
+
+    pub(crate) async fn call(&mut self, method: &str, params: Value) -> Result<Value> {
+        let message_id = NEXT_MESSAGE_ID.fetch_add(1, Ordering::Relaxed);
+        let message = json!({ "id": message_id, "method": method, "params": params }).to_string();
+        if self.host.send_dev_tools_message(Some(message.as_bytes())) == 0 {
+            return Err(anyhow!("failed to send {method}"));
+        }
+
+        while let Some(result) = self.results.next().await {
+            if result.message_id != message_id {
+                continue;
+            }
+            let value: Value = serde_json::from_slice(&result.result)
+                .with_context(|| format!("malformed {method} result"))?;
+            if !result.success {
+                let error = value
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown error");
+                return Err(anyhow!("{method} failed: {error}"));
+            }
+            return Ok(value);
+        }
+        Err(anyhow!("the page closed before {method} finished"))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LayoutMetrics {
+    css_content_size: ContentSize,
+}
+
+#[derive(Deserialize)]
+struct ContentSize {
+    width: f64,
+    height: f64,
+}
+
+/// `Page.captureScreenshot` parameters. Full-page captures clip to the
+/// document's size, reported by `Page.getLayoutMetrics`.
+pub(crate) fn screenshot_params(layout_metrics: Option<&Value>) -> Result<Value> {
+    let Some(layout_metrics) = layout_metrics else {
+        return Ok(json!({ "format": "png" }));
+    };
+    let metrics = LayoutMetrics::deserialize(layout_metrics)
+        .context("malformed Page.getLayoutMetrics result")?;
+    let size = metrics.css_content_size;
+    Ok(json!({
+        "format": "png",
+        "captureBeyondViewport": true,
+        "clip": {
+            "x": 0,
+            "y": 0,
+            "width": size.width.ceil(),
+            "height": size.height.ceil().min(MAX_CAPTURE_HEIGHT),
+            "scale": 1,
+        },
+    }))
+}
+
+/// Decodes the PNG a `Page.captureScreenshot` result carries as base64.
+pub(crate) fn decode_screenshot(result: &Value) -> Result<Vec<u8>> {
+    let data = result
+        .get("data")
+        .and_then(Value::as_str)
+        .context("screenshot result has no image data")?;
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .context("screenshot image data is not valid base64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_page_screenshots_clip_to_the_document() {
+        let metrics =
+            json!({ "cssContentSize": { "x": 0, "y": 0, "width": 1280.4, "height": 40000 } });
+        let params = screenshot_params(Some(&metrics)).unwrap();
+        assert_eq!(params["clip"]["width"], 1281.0);
+        assert_eq!(params["clip"]["height"], MAX_CAPTURE_HEIGHT);
+        assert_eq!(params["captureBeyondViewport"], true);
+
+        assert_eq!(screenshot_params(None).unwrap(), json!({ "format": "png" }));
+    }
+
+    #[test]
+    fn decodes_screenshot_data() {
+        assert_eq!(
+            decode_screenshot(&json!({ "data": "iVBORw==" })).unwrap(),
+            vec![0x89, b'P', b'N', b'G']
+        );
+        assert!(decode_screenshot(&json!({})).is_err());
+    }
+}
@@ -9,6 +9,15 @@ use cef::{
 use gpui::{Hsla, Rgba};
 use serde::Deserialize;
 
+use crate::media_playback::{
+    MEDIA_PLAYBACK_BRIDGE_NAME, MEDIA_PLAYBACK_MESSAGE_NAME, MEDIA_PLAYBACK_OBSERVER_SCRIPT,
+};
+use crate::page_language::{
+    PAGE_LANGUAGE_BRIDGE_NAME, PAGE_LANGUAGE_MESSAGE_NAME, PAGE_LANGUAGE_OBSERVER_SCRIPT,
+};
+use crate::scroll_position::{
+    SCROLL_POSITION_BRIDGE_NAME, SCROLL_POSITION_MESSAGE_NAME, SCROLL_POSITION_OBSERVER_SCRIPT,
+};
 use crate::text_input::send_text_input_state;
 
 pub(crate) const PAGE_CHROME_MESSAGE_NAME: &str = "glass.page_chrome";
@@ -337,12 +346,16 @@ pub(crate) fn extract_page_chrome_from_message(
     parse_page_chrome_payload(&payload).map(Some)
 }
 
+/// A V8 function that forwards its string argument to the browser process as
+/// a process message named `message_name`.
 #[derive(Clone)]
-struct PageChromeBridgeV8Handler;
+struct BridgeV8Handler {
+    message_name: &'static str,
+}
 
 wrap_v8_handler! {
-    struct PageChromeBridgeV8HandlerBuilder {
-        handler: PageChromeBridgeV8Handler,
+    struct BridgeV8HandlerBuilder {
+        handler: BridgeV8Handler,
     }
 
     impl V8Handler {
@@ -361,7 +374,7 @@ wrap_v8_handler! {
                 .map(|value| CefString::from(&value.string_value()).to_string())
                 .unwrap_or_default();
             let Some(message) =
-                process_message_create(Some(&CefString::from(PAGE_CHROME_MESSAGE_NAME)))
+                process_message_create(Some(&CefString::from(self.handler.message_name)))
             else {
                 let _ = exception;
                 return 0;
@@ -391,9 +404,55 @@ wrap_v8_handler! {
     }
 }
 
-impl PageChromeBridgeV8HandlerBuilder {
-    fn build() -> V8Handler {
-        Self::new(PageChromeBridgeV8Handler)
+impl BridgeV8HandlerBuilder {
+    fn build(message_name: &'static str) -> V8Handler {
+        Self::new(BridgeV8Handler { message_name })
+    }
+}
+
+/// Exposes `bridge_name` on the context's global object as a function that
+/// posts to `message_name`, then evaluates `script`, which is expected to
+/// call the bridge.
+fn install_bridge_script(
+    context: &mut V8Context,
+    bridge_name: &str,
+    message_name: &'static str,
+    script: &str,
+    script_url: &str,
+) {
+    let mut handler = BridgeV8HandlerBuilder::build(message_name);
+    let Some(mut bridge) =
+        v8_value_create_function(Some(&CefString::from(bridge_name)), Some(&mut handler))
+    else {
+        return;
+    };
+
+    let Some(global) = context.global() else {
+        return;
+    };
+
+    global.set_value_bykey(
+        Some(&CefString::from(bridge_name)),
+        Some(&mut bridge),
+        Default::default(),
+    );
+
+    let mut result = None;
+    let mut eval_exception = None::<cef::V8Exception>;
+    if context.eval(
+        Some(&CefString::from(script)),
+        Some(&CefString::from(script_url)),
+        0,
+        Some(&mut result),
+        Some(&mut eval_exception),
+    ) == 0
+    {
+        if let Some(eval_exception) = eval_exception {
+            log::warn!(
+                "[browser::page_chrome] Failed to install {script_url}: {}",
+                CefString::from(&eval_exception.message()).to_string()
+            );
+        }
     }
 }
 
@@ -420,41 +479,34 @@ wrap_render_process_handler! {
                 return;
             }
 
-            let mut handler = PageChromeBridgeV8HandlerBuilder::build();
-            let Some(mut bridge) = v8_value_create_function(
-                Some(&CefString::from(PAGE_CHROME_BRIDGE_NAME)),
-                Some(&mut handler),
-            ) else {
-                return;
-            };
-
-            let Some(global) = context.global() else {
-                return;
-            };
-
-            global.set_value_bykey(
-                Some(&CefString::from(PAGE_CHROME_BRIDGE_NAME)),
-                Some(&mut bridge),
-                Default::default(),
+            install_bridge_script(
+                context,
+                PAGE_CHROME_BRIDGE_NAME,
+                PAGE_CHROME_MESSAGE_NAME,
+                PAGE_CHROME_OBSERVER_SCRIPT,
+                "glass://page_chrome.js",
+            );
+            install_bridge_script(
+                context,
+                SCROLL_POSITION_BRIDGE_NAME,
+                SCROLL_POSITION_MESSAGE_NAME,
+                SCROLL_POSITION_OBSERVER_SCRIPT,
+                "glass://scroll_position.js",
+            );
+            install_bridge_script(
+                context,
+                PAGE_LANGUAGE_BRIDGE_NAME,
+                PAGE_LANGUAGE_MESSAGE_NAME,
+                PAGE_LANGUAGE_OBSERVER_SCRIPT,
+                "glass://page_language.js",
+            );
+            install_bridge_script(
+                context,
+                MEDIA_PLAYBACK_BRIDGE_NAME,
+                MEDIA_PLAYBACK_MESSAGE_NAME,
+                MEDIA_PLAYBACK_OBSERVER_SCRIPT,
+                "glass://media_playback.js",
             );
-
-            let mut result = None;
-            let mut eval_exception = None::<cef::V8Exception>;
-            if context.eval(
-                Some(&CefString::from(PAGE_CHROME_OBSERVER_SCRIPT)),
-                Some(&CefString::from("glass://page_chrome.js")),
-                0,
-                Some(&mut result),
-                Some(&mut eval_exception),
-            ) == 0
-            {
-                if let Some(eval_exception) = eval_exception {
-                    log::warn!(
-                        "[browser::page_chrome] Failed to install page chrome observer: {}",
-                        CefString::from(&eval_exception.message()).to_string()
-                    );
-                }
-            }
         }
 
         fn on_focused_node_changed(
@@ -0,0 +1,128 @@
+//! Page Language Detection
+//!
+//! The render process reports the language a page declares once its document
+//! has been parsed, so the toolbar can offer to translate pages that are not
+//! in the user's language.
+
+use cef::{CefString, ImplListValue, ImplProcessMessage, ProcessMessage};
+use serde::Deserialize;
+
+pub(crate) const PAGE_LANGUAGE_MESSAGE_NAME: &str = "glass.page_language";
+pub(crate) const PAGE_LANGUAGE_BRIDGE_NAME: &str = "__glassReportPageLanguage";
+
+pub(crate) const PAGE_LANGUAGE_OBSERVER_SCRIPT: &str = r#"
+(function () {
+  if (window.__glassPageLanguageInstalled) return;
+  window.__glassPageLanguageInstalled = true;
+
+  const bridge = window.__glassReportPageLanguage;
+  if (typeof bridge !== 'function') return;
+
+  const report = () => {
+    const meta = document.querySelector('meta[http-equiv="content-language" i]');
+    const language = document.documentElement.lang
+      || (meta && meta.getAttribute('content'))
+      || null;
+    bridge(JSON.stringify({ language }));
+  };
+
+  if (document.readyState === 'loading') {
+    document.addEventListener('DOMContentLoaded', report, { once: true });
+  } else {
+    report();
+  }
+})();
+"#;
+
+#[derive(Deserialize)]
+struct PageLanguagePayload {
+    #[serde(default)]
+    language: Option<String>,
+}
+
+/// Returns the primary language a page declared, or `None` when it declared
+/// none or the payload is malformed.
+pub(crate) fn parse_page_language_payload(payload: &str) -> Option<String> {
+    let payload: PageLanguagePayload = serde_json::from_str(payload).ok()?;
+    primary_language_subtag(&payload.language?)
+}
+
+pub(crate) fn extract_page_language_from_message(
+    message: &mut ProcessMessage,
+) -> Option<Option<String>> {
+    if CefString::from(&message.name()).to_string() != PAGE_LANGUAGE_MESSAGE_NAME {
+        return None;
+    }
+
+    let args = message.argument_list()?;
+    let payload = CefString::from(&args.string(0)).to_string();
+    Some(parse_page_language_payload(&payload))
+}
+
+/// Reduces a BCP 47 tag such as `pt-BR` or a POSIX locale such as `de_DE.UTF-8`
+/// to its lowercase primary language, since regional variants of the same
+/// language don't need translating.
+pub(crate) fn primary_language_subtag(tag: &str) -> Option<String> {
+    let primary = tag.trim().split(['-', '_', '.']).next()?;
+    if !(2..=3).contains(&primary.len())
+        || !primary
+            .chars()
+            .all(|character| character.is_ascii_alphabetic())
+    {
+        return None;
+    }
+    Some(primary.to_ascii_lowercase())
+}
+
+/// Fills the `{language}` and `{url}` placeholders of a translation provider
+/// URL template.
+pub(crate) fn translation_url(template: &str, target_language: &str, page_url: &str) -> String {
+    let encoded_url: String = url::form_urlencoded::byte_serialize(page_url.as_bytes()).collect();
+    template
+        .replace("{language}", target_language)
+        .replace("{url}", &encoded_url)
+}
+
+/// The origin translation prompts are dismissed for, so that dismissing the
+/// prompt on one page covers the rest of the site.
+pub(crate) fn page_origin(page_url: &str) -> Option<String> {
+    let origin = url::Url::parse(page_url).ok()?.origin();
+    origin.is_tuple().then(|| origin.ascii_serialization())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_reported_page_languages() {
+        assert_eq!(
+            parse_page_language_payload(r#"{"language":"de-DE"}"#).as_deref(),
+            Some("de")
+        );
+        assert_eq!(parse_page_language_payload(r#"{"language":null}"#), None);
+        assert_eq!(parse_page_language_payload(""), None);
+        assert_eq!(
+            primary_language_subtag("pt_BR.UTF-8").as_deref(),
+            Some("pt")
+        );
+        assert_eq!(primary_language_subtag("not a language"), None);
+    }
+
+    #[test]
+    fn builds_translation_urls_from_templates() {
+        assert_eq!(
+            translation_url(
+                "https://translate.google.com/translate?sl=auto&tl={language}&u={url}",
+                "en",
+                "https://example.de/seite?a=1",
+            ),
+            "https://translate.google.com/translate?sl=auto&tl=en&u=https%3A%2F%2Fexample.de%2Fseite%3Fa%3D1"
+        );
+        assert_eq!(
+            page_origin("https://example.de/seite").as_deref(),
+            Some("https://example.de")
+        );
+        assert_eq!(page_origin("glass://newtab"), None);
+    }
+}
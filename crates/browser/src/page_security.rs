@@ -0,0 +1,175 @@
+//! Page Security
+//!
+//! Reads the SSL status of a browser's visible navigation entry so the
+//! toolbar can show whether the active page is secure.
+
+use cef::{
+    CefString, ImplBrowser, ImplBrowserHost, ImplNavigationEntry, ImplSslstatus,
+    ImplX509CertPrincipal, ImplX509Certificate,
+};
+
+const SSL_CONTENT_DISPLAYED_INSECURE: u32 = 1 << 0;
+const SSL_CONTENT_RAN_INSECURE: u32 = 1 << 1;
+
+/// Every `CERT_STATUS_*` error bit except the two revocation-check bits,
+/// which Chromium treats as non-fatal.
+const CERT_STATUS_ERROR_MASK: u32 = 0xFFCF;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PageSecurityState {
+    Secure,
+    MixedContent,
+    Insecure,
+    CertificateError,
+}
+
+impl PageSecurityState {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Secure => "Connection is secure",
+            Self::MixedContent => "Connection is not fully secure",
+            Self::Insecure => "Connection is not secure",
+            Self::CertificateError => "Certificate is not valid",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Secure => "Information you send to this site is private.",
+            Self::MixedContent => "Parts of this page were loaded over an insecure connection.",
+            Self::Insecure => "Don't enter passwords or payment details on this site.",
+            Self::CertificateError => "Someone may be trying to impersonate this site.",
+        }
+    }
+
+    /// SF Symbol shown in the toolbar.
+    pub fn icon(self) -> &'static str {
+        match self {
+            Self::Secure => "lock",
+            Self::MixedContent => "lock.trianglebadge.exclamationmark",
+            Self::Insecure => "lock.open",
+            Self::CertificateError => "exclamationmark.triangle",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CertificateSummary {
+    pub subject: String,
+    pub issuer: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageSecurity {
+    pub state: PageSecurityState,
+    pub certificate: Option<CertificateSummary>,
+}
+
+pub(crate) fn read_page_security(browser: &cef::Browser) -> Option<PageSecurity> {
+    let entry = browser.host()?.visible_navigation_entry()?;
+    let url = CefString::from(&entry.url()).to_string();
+    let status = entry.sslstatus();
+
+    let (is_secure_connection, content_status, cert_status) = match &status {
+        Some(status) => {
+            let content_status: &cef::sys::cef_ssl_content_status_t =
+                status.content_status().as_ref();
+            let cert_status: &cef::sys::cef_cert_status_t = status.cert_status().as_ref();
+            (
+                status.is_secure_connection() != 0,
+                content_status.0 as u32,
+                cert_status.0 as u32,
+            )
+        }
+        None => (false, 0, 0),
+    };
+    let state = security_state(&url, is_secure_connection, content_status, cert_status)?;
+
+    let certificate = status
+        .and_then(|status| status.x509_certificate())
+        .map(|certificate| CertificateSummary {
+            subject: certificate
+                .subject()
+                .map(|principal| CefString::from(&principal.display_name()).to_string())
+                .unwrap_or_default(),
+            issuer: certificate
+                .issuer()
+                .map(|principal| CefString::from(&principal.display_name()).to_string())
+                .unwrap_or_default(),
+        });
+
+    Some(PageSecurity { state, certificate })
+}
+
+/// Fallback for pages whose SSL status has not been read yet.
+pub(crate) fn security_state_for_url(url: &str) -> Option<PageSecurityState> {
+    url.starts_with("http://")
+        .then_some(PageSecurityState::Insecure)
+}
+
+fn security_state(
+    url: &str,
+    is_secure_connection: bool,
+    content_status: u32,
+    cert_status: u32,
+) -> Option<PageSecurityState> {
+    if url.starts_with("http://") {
+        return Some(PageSecurityState::Insecure);
+    }
+    if !url.starts_with("https://") {
+        return None;
+    }
+    if cert_status & CERT_STATUS_ERROR_MASK != 0 {
+        Some(PageSecurityState::CertificateError)
+    } else if !is_secure_connection {
+        Some(PageSecurityState::Insecure)
+    } else if content_status & (SSL_CONTENT_DISPLAYED_INSECURE | SSL_CONTENT_RAN_INSECURE) != 0 {
+        Some(PageSecurityState::MixedContent)
+    } else {
+        Some(PageSecurityState::Secure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn security_state_follows_scheme_and_ssl_status() {
+        assert_eq!(
+            security_state("http://example.com/", false, 0, 0),
+            Some(PageSecurityState::Insecure)
+        );
+        assert_eq!(
+            security_state("https://example.com/", true, 0, 0),
+            Some(PageSecurityState::Secure)
+        );
+        assert_eq!(
+            security_state(
+                "https://example.com/",
+                true,
+                SSL_CONTENT_DISPLAYED_INSECURE,
+                0
+            ),
+            Some(PageSecurityState::MixedContent)
+        );
+        assert_eq!(
+            security_state("https://expired.example.com/", true, 0, 1 << 1),
+            Some(PageSecurityState::CertificateError)
+        );
+        assert_eq!(security_state("glass://newtab", false, 0, 0), None);
+    }
+
+    #[test]
+    fn revocation_check_bits_are_not_certificate_errors() {
+        assert_eq!(
+            security_state(
+                "https://example.com/",
+                true,
+                0,
+                (1 << 4) | (1 << 5) | (1 << 16)
+            ),
+            Some(PageSecurityState::Secure)
+        );
+    }
+}
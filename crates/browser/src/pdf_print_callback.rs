@@ -0,0 +1,49 @@
+//! CEF PDF Print Callback
+//!
+//! Reports the result of `CefBrowserHost::PrintToPDF` back to the task that
+//! started it.
+
+use cef::{
+    CefString, ImplPdfPrintCallback, PdfPrintCallback, WrapPdfPrintCallback, rc::Rc as _,
+    wrap_pdf_print_callback,
+};
+use futures::channel::oneshot;
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct OsrPdfPrintCallback {
+    sender: Arc<Mutex<Option<oneshot::Sender<bool>>>>,
+}
+
+impl OsrPdfPrintCallback {
+    pub fn new(sender: oneshot::Sender<bool>) -> Self {
+        Self {
+            sender: Arc::new(Mutex::new(Some(sender))),
+        }
+    }
+}
+
+wrap_pdf_print_callback! {
+    pub struct PdfPrintCallbackBuilder {
+        callback: OsrPdfPrintCallback,
+    }
+
+    impl PdfPrintCallback {
+        fn on_pdf_print_finished(
+            &self,
+            _path: Option<&CefString>,
+            ok: ::std::os::raw::c_int,
+        ) {
+            if let Some(sender) = self.callback.sender.lock().take() {
+                sender.send(ok != 0).ok();
+            }
+        }
+    }
+}
+
+impl PdfPrintCallbackBuilder {
+    pub fn build(callback: OsrPdfPrintCallback) -> cef::PdfPrintCallback {
+        Self::new(callback)
+    }
+}
@@ -2,22 +2,42 @@
 //!
 //! Handles permission requests from web content. Grants protected media
 //! identifier permissions required for Widevine DRM playback on streaming
-//! platforms (HBO Max, Netflix, Disney+, etc).
+//! platforms (HBO Max, Netflix, Disney+, etc). Camera, microphone, location
+//! and notification requests follow the per-site decisions in
+//! `site_permissions`, asking the user when none has been saved.
 
+use crate::events::{BrowserEvent, EventSender};
+use crate::site_permissions::{self, PermissionRequest, RequestOutcome, SitePermissions};
 use cef::{
-    Browser, Frame, ImplMediaAccessCallback, ImplPermissionHandler, ImplPermissionPromptCallback,
-    MediaAccessCallback, PermissionHandler, PermissionPromptCallback, PermissionRequestResult,
-    WrapPermissionHandler, rc::Rc as _, wrap_permission_handler,
+    Browser, Frame, ImplBrowser, ImplMediaAccessCallback, ImplPermissionHandler,
+    ImplPermissionPromptCallback, MediaAccessCallback, PermissionHandler, PermissionPromptCallback,
+    PermissionRequestResult, WrapPermissionHandler, rc::Rc as _, wrap_permission_handler,
 };
 
 const PROTECTED_MEDIA_IDENTIFIER: u32 = 262144;
 
 #[derive(Clone)]
-pub struct OsrPermissionHandler;
+pub struct OsrPermissionHandler {
+    sender: EventSender,
+    site_permissions: SitePermissions,
+}
 
 impl OsrPermissionHandler {
-    pub fn new() -> Self {
-        Self
+    pub fn new(sender: EventSender, site_permissions: SitePermissions) -> Self {
+        Self {
+            sender,
+            site_permissions,
+        }
+    }
+
+    fn request_decision(&self, request: PermissionRequest) {
+        if let Err(error) = self
+            .sender
+            .send(BrowserEvent::PermissionRequested(request.clone()))
+        {
+            log::error!("[browser::permission] failed to send permission request: {error}");
+            site_permissions::resolve_permission_request(&request, false);
+        }
     }
 }
 
@@ -29,7 +49,7 @@ wrap_permission_handler! {
     impl PermissionHandler {
         fn on_request_media_access_permission(
             &self,
-            _browser: Option<&mut Browser>,
+            browser: Option<&mut Browser>,
             _frame: Option<&mut Frame>,
             requesting_origin: Option<&cef::CefString>,
             requested_permissions: u32,
@@ -43,15 +63,26 @@ wrap_permission_handler! {
                 origin,
                 requested_permissions,
             );
-            if let Some(callback) = callback {
-                callback.cont(requested_permissions);
+            let Some(callback) = callback else {
+                return 0;
+            };
+            match site_permissions::handle_media_access_request(
+                &self.handler.site_permissions,
+                browser.map(|browser| browser.identifier()),
+                &origin,
+                requested_permissions,
+                callback,
+            ) {
+                RequestOutcome::Handled => {}
+                RequestOutcome::NeedsPrompt(request) => self.handler.request_decision(request),
+                RequestOutcome::Unhandled => callback.cont(requested_permissions),
             }
             1
         }
 
         fn on_show_permission_prompt(
             &self,
-            _browser: Option<&mut Browser>,
+            browser: Option<&mut Browser>,
             prompt_id: u64,
             requesting_origin: Option<&cef::CefString>,
             requested_permissions: u32,
@@ -79,7 +110,24 @@ wrap_permission_handler! {
                 requested_permissions,
                 prompt_id,
             );
-            0
+            let Some(callback) = callback else {
+                return 0;
+            };
+            match site_permissions::handle_permission_prompt(
+                &self.handler.site_permissions,
+                browser.map(|browser| browser.identifier()),
+                &origin,
+                prompt_id,
+                requested_permissions,
+                callback,
+            ) {
+                RequestOutcome::Handled => 1,
+                RequestOutcome::NeedsPrompt(request) => {
+                    self.handler.request_decision(request);
+                    1
+                }
+                RequestOutcome::Unhandled => 0,
+            }
         }
 
         fn on_dismiss_permission_prompt(
@@ -93,6 +141,7 @@ wrap_permission_handler! {
                 prompt_id,
                 result,
             );
+            site_permissions::forget_permission_prompt(prompt_id);
         }
     }
 }
@@ -1,22 +1,33 @@
 //! CEF Request Handler
 //!
 //! Allows CEF to handle non-current-tab dispositions (new tab/window) so
-//! popup-based auth flows can use native opener semantics.
+//! popup-based auth flows can use native opener semantics, records the
+//! main frame's request for "Copy as cURL", and switches the user agent
+//! before a main frame navigation is sent.
 
-use crate::events::{BrowserEvent, EventSender, OpenDisposition, OpenTargetRequest};
+use crate::events::{
+    BrowserEvent, EventSender, MainFrameRequest, OpenDisposition, OpenTargetRequest,
+};
+use crate::user_agent::TabUserAgent;
 use cef::{
-    Browser, ImplRequestHandler, RequestHandler, WindowOpenDisposition, WrapRequestHandler,
-    rc::Rc as _, wrap_request_handler,
+    Browser, ImplBrowser, ImplFrame, ImplRequest, ImplRequestHandler, RequestHandler,
+    WindowOpenDisposition, WrapRequestHandler, rc::Rc as _, wrap_request_handler,
 };
 
+/// Request headers worth replaying from the command line. Cookies and the
+/// remaining defaults are added later by the network stack, so they aren't
+/// visible here.
+const CAPTURED_REQUEST_HEADERS: &[&str] = &["Accept", "Accept-Language", "Content-Type", "Origin"];
+
 #[derive(Clone)]
 pub struct OsrRequestHandler {
     sender: EventSender,
+    user_agent: TabUserAgent,
 }
 
 impl OsrRequestHandler {
-    pub fn new(sender: EventSender) -> Self {
-        Self { sender }
+    pub fn new(sender: EventSender, user_agent: TabUserAgent) -> Self {
+        Self { sender, user_agent }
     }
 }
 
@@ -26,6 +37,35 @@ wrap_request_handler! {
     }
 
     impl RequestHandler {
+        fn on_before_browse(
+            &self,
+            browser: Option<&mut Browser>,
+            frame: Option<&mut cef::Frame>,
+            request: Option<&mut cef::Request>,
+            _user_gesture: ::std::os::raw::c_int,
+            _is_redirect: ::std::os::raw::c_int,
+        ) -> ::std::os::raw::c_int {
+            let (Some(frame), Some(request)) = (frame, request) else {
+                return 0;
+            };
+
+            if frame.is_main() == 0 {
+                return 0;
+            }
+
+            if let Some(host) = browser.and_then(|browser| browser.host()) {
+                let url = cef::CefString::from(&request.url()).to_string();
+                self.handler.user_agent.apply(&host, &url);
+            }
+
+            let _ = self
+                .handler
+                .sender
+                .send(BrowserEvent::MainFrameRequest(main_frame_request(request)));
+
+            0
+        }
+
         fn on_open_urlfrom_tab(
             &self,
             _browser: Option<&mut Browser>,
@@ -64,6 +104,30 @@ wrap_request_handler! {
     }
 }
 
+fn main_frame_request(request: &cef::Request) -> MainFrameRequest {
+    let method = cef::CefString::from(&request.method()).to_string();
+    let url = cef::CefString::from(&request.url()).to_string();
+
+    let mut headers = Vec::new();
+    let referrer = cef::CefString::from(&request.referrer_url()).to_string();
+    if !referrer.is_empty() {
+        headers.push(("Referer".to_string(), referrer));
+    }
+    for name in CAPTURED_REQUEST_HEADERS {
+        let value = request.header_by_name(Some(&cef::CefString::from(*name)));
+        let value = cef::CefString::from(&value).to_string();
+        if !value.is_empty() {
+            headers.push((name.to_string(), value));
+        }
+    }
+
+    MainFrameRequest {
+        method,
+        url,
+        headers,
+    }
+}
+
 impl RequestHandlerBuilder {
     pub fn build(handler: OsrRequestHandler) -> cef::RequestHandler {
         Self::new(handler)
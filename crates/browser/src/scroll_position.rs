@@ -0,0 +1,118 @@
+//! Scroll Position Sync
+//!
+//! The render process reports the main frame's scroll offset whenever the
+//! page scrolls, so the browser process already knows where a tab was when it
+//! is deactivated and can scroll back once the tab is shown or reloaded.
+
+use cef::{CefString, ImplListValue, ImplProcessMessage, ProcessMessage};
+use serde::{Deserialize, Serialize};
+
+pub(crate) const SCROLL_POSITION_MESSAGE_NAME: &str = "glass.scroll_position";
+pub(crate) const SCROLL_POSITION_BRIDGE_NAME: &str = "__glassReportScrollPosition";
+
+pub(crate) const SCROLL_POSITION_OBSERVER_SCRIPT: &str = r#"
+(function () {
+  if (window.__glassScrollSyncInstalled) return;
+  window.__glassScrollSyncInstalled = true;
+
+  const bridge = window.__glassReportScrollPosition;
+  if (typeof bridge !== 'function') return;
+
+  let scheduled = false;
+
+  const report = () => {
+    scheduled = false;
+    bridge(JSON.stringify({
+      url: window.location.href,
+      x: window.scrollX,
+      y: window.scrollY,
+    }));
+  };
+
+  window.__glassFlushScrollPosition = report;
+  window.addEventListener('scroll', () => {
+    if (scheduled) return;
+    scheduled = true;
+    window.setTimeout(report, 150);
+  }, { passive: true });
+})();
+"#;
+
+const FLUSH_SCROLL_POSITION_SCRIPT: &str =
+    "window.__glassFlushScrollPosition && window.__glassFlushScrollPosition();";
+
+/// The scroll offset of a page, tied to the URL it was recorded on so that it
+/// is never applied to a different page.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScrollPosition {
+    pub url: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl ScrollPosition {
+    pub(crate) fn applies_to(&self, url: &str) -> bool {
+        self.url == url
+    }
+
+    /// `scrollTo` clamps to the page's current extent, so an offset recorded on
+    /// a longer version of the page lands on the nearest valid position.
+    pub(crate) fn restore_script(&self) -> String {
+        format!("window.scrollTo({}, {});", self.x, self.y)
+    }
+
+    pub(crate) fn flush_script() -> &'static str {
+        FLUSH_SCROLL_POSITION_SCRIPT
+    }
+}
+
+pub(crate) fn parse_scroll_position_payload(payload: &str) -> Option<ScrollPosition> {
+    let position: ScrollPosition = serde_json::from_str(payload).ok()?;
+    if !position.x.is_finite() || !position.y.is_finite() {
+        return None;
+    }
+    Some(position)
+}
+
+pub(crate) fn extract_scroll_position_from_message(
+    message: &mut ProcessMessage,
+) -> Option<Option<ScrollPosition>> {
+    if CefString::from(&message.name()).to_string() != SCROLL_POSITION_MESSAGE_NAME {
+        return None;
+    }
+
+    let args = message.argument_list()?;
+    let payload = CefString::from(&args.string(0)).to_string();
+    Some(parse_scroll_position_payload(&payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_reported_scroll_positions() {
+        assert_eq!(
+            parse_scroll_position_payload(r#"{"url":"https://example.com/","x":0,"y":1200.5}"#),
+            Some(ScrollPosition {
+                url: "https://example.com/".to_string(),
+                x: 0.0,
+                y: 1200.5,
+            })
+        );
+        assert_eq!(parse_scroll_position_payload(""), None);
+        assert_eq!(parse_scroll_position_payload(r#"{"x":0,"y":10}"#), None);
+    }
+
+    #[test]
+    fn scroll_positions_only_apply_to_their_own_page() {
+        let position = ScrollPosition {
+            url: "https://example.com/a".to_string(),
+            x: 0.0,
+            y: 480.0,
+        };
+        assert!(position.applies_to("https://example.com/a"));
+        assert!(!position.applies_to("https://example.com/b"));
+        assert_eq!(position.restore_script(), "window.scrollTo(0, 480);");
+    }
+}
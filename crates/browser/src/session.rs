@@ -1,5 +1,8 @@
 use crate::bookmarks::BookmarkStore;
 use crate::history::HistoryEntry;
+use crate::scroll_position::ScrollPosition;
+use crate::site_permissions::SitePermissionStore;
+use crate::user_agent::UserAgentOverrides;
 use db::kvp::GlobalKeyValueStore;
 use serde::{Deserialize, Serialize};
 use util::ResultExt as _;
@@ -7,17 +10,17 @@ use util::ResultExt as _;
 const BROWSER_TABS_KEY: &str = "browser_tabs";
 const BROWSER_PINNED_TABS_KEY: &str = "browser_pinned_tabs";
 const BROWSER_HISTORY_KEY: &str = "browser_history";
+const BROWSER_HIDDEN_TOP_SITES_KEY: &str = "browser_hidden_top_sites";
 const BROWSER_BOOKMARKS_KEY: &str = "browser_bookmarks";
 const BROWSER_DOWNLOADS_KEY: &str = "browser_downloads";
+const BROWSER_SITE_PERMISSIONS_KEY: &str = "browser_site_permissions";
+const BROWSER_USER_AGENT_OVERRIDES_KEY: &str = "browser_user_agent_overrides";
+const BROWSER_APP_WINDOW_BOUNDS_KEY_PREFIX: &str = "browser_app_window_bounds:";
 
 #[derive(Serialize, Deserialize)]
 pub struct SerializedBrowserTabs {
     pub tabs: Vec<SerializedTab>,
     pub active_index: usize,
-    #[serde(default)]
-    pub sidebar: bool,
-    #[serde(default)]
-    pub sidebar_visible: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -30,6 +33,18 @@ pub struct SerializedTab {
     pub is_pinned: bool,
     #[serde(default)]
     pub favicon_url: Option<String>,
+    #[serde(default)]
+    pub scroll_position: Option<ScrollPosition>,
+}
+
+/// Where a window opened with "Open as App" was last placed, in screen
+/// coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SerializedAppWindowBounds {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -88,6 +103,19 @@ pub async fn save_history(json: String) -> anyhow::Result<()> {
         .await
 }
 
+pub fn restore_hidden_top_sites() -> Option<Vec<String>> {
+    let json = GlobalKeyValueStore::global()
+        .read_kvp(BROWSER_HIDDEN_TOP_SITES_KEY)
+        .log_err()??;
+    serde_json::from_str(&json).log_err()
+}
+
+pub async fn save_hidden_top_sites(json: String) -> anyhow::Result<()> {
+    GlobalKeyValueStore::global()
+        .write_kvp(BROWSER_HIDDEN_TOP_SITES_KEY.to_string(), json)
+        .await
+}
+
 pub fn restore_bookmarks() -> Option<BookmarkStore> {
     let json = GlobalKeyValueStore::global()
         .read_kvp(BROWSER_BOOKMARKS_KEY)
@@ -113,3 +141,45 @@ pub async fn save_downloads(json: String) -> anyhow::Result<()> {
         .write_kvp(BROWSER_DOWNLOADS_KEY.to_string(), json)
         .await
 }
+
+pub fn restore_site_permissions() -> Option<SitePermissionStore> {
+    let json = GlobalKeyValueStore::global()
+        .read_kvp(BROWSER_SITE_PERMISSIONS_KEY)
+        .log_err()??;
+    serde_json::from_str(&json).log_err()
+}
+
+pub async fn save_site_permissions(json: String) -> anyhow::Result<()> {
+    GlobalKeyValueStore::global()
+        .write_kvp(BROWSER_SITE_PERMISSIONS_KEY.to_string(), json)
+        .await
+}
+
+pub fn restore_user_agent_overrides() -> Option<UserAgentOverrides> {
+    let json = GlobalKeyValueStore::global()
+        .read_kvp(BROWSER_USER_AGENT_OVERRIDES_KEY)
+        .log_err()??;
+    serde_json::from_str(&json).log_err()
+}
+
+pub async fn save_user_agent_overrides(json: String) -> anyhow::Result<()> {
+    GlobalKeyValueStore::global()
+        .write_kvp(BROWSER_USER_AGENT_OVERRIDES_KEY.to_string(), json)
+        .await
+}
+
+pub fn restore_app_window_bounds(app_key: &str) -> Option<SerializedAppWindowBounds> {
+    let json = GlobalKeyValueStore::global()
+        .read_kvp(&format!("{BROWSER_APP_WINDOW_BOUNDS_KEY_PREFIX}{app_key}"))
+        .log_err()??;
+    serde_json::from_str(&json).log_err()
+}
+
+pub async fn save_app_window_bounds(app_key: String, json: String) -> anyhow::Result<()> {
+    GlobalKeyValueStore::global()
+        .write_kvp(
+            format!("{BROWSER_APP_WINDOW_BOUNDS_KEY_PREFIX}{app_key}"),
+            json,
+        )
+        .await
+}
@@ -9,25 +9,38 @@
 //! counts before calling `cef::shutdown()` — regardless of whether
 //! GPUI has dropped the BrowserTab entities yet.
 
+use crate::browser_settings::BrowserSettings;
 use crate::client::ClientBuilder;
 use crate::context_menu_handler::ContextMenuContext;
 use crate::events::{
-    self, BrowserEvent, DownloadUpdatedEvent, EventReceiver, FindResultEvent, OpenTargetRequest,
+    self, BrowserEvent, BrowserTabOpenTarget, DownloadUpdatedEvent, EventReceiver, FindResultEvent,
+    MainFrameRequest, OpenTargetRequest,
 };
+use crate::media_playback::MediaPlayback;
+use crate::page_capture::{self, DevToolsSession};
 use crate::page_chrome::PageChrome;
+use crate::page_security::{self, PageSecurity, PageSecurityState};
+use crate::pdf_print_callback::{OsrPdfPrintCallback, PdfPrintCallbackBuilder};
 use crate::render_handler::RenderState;
+use crate::scroll_position::ScrollPosition;
+use crate::site_permissions::{self, PermissionRequest, SitePermissions, origin_for_url};
 use crate::text_input::BrowserTextInputState;
+use crate::user_agent::{SiteUserAgents, TabUserAgent};
 use anyhow::{Context as _, Result};
 use cef::{
-    ImplBrowser, ImplBrowserHost, ImplFrame, ImplRequestContext, KeyEvent, KeyEventType,
-    MouseButtonType,
+    ImplBrowser, ImplBrowserHost, ImplDictionaryValue, ImplFrame, ImplRequestContext, KeyEvent,
+    KeyEventType, MouseButtonType,
 };
 use core_video::pixel_buffer::CVPixelBuffer;
-use gpui::{Context, EventEmitter, Hsla};
+use futures::channel::oneshot;
+use gpui::{Context, EventEmitter, Hsla, Image, ImageFormat, Task};
 use parking_lot::Mutex;
+use settings::Settings as _;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 /// All live CEF browser handles, keyed by browser ID.
 ///
 /// BrowserTab stores only the integer ID and accesses the handle through
@@ -36,6 +49,10 @@ use std::sync::atomic::Ordering;
 /// ref counts before `cef::shutdown()` is called.
 static BROWSER_HANDLES: Mutex<Option<HashMap<i32, cef::Browser>>> = Mutex::new(None);
 
+/// Tabs are frozen only after this long in the background, so switching away
+/// briefly doesn't interrupt the page.
+const BACKGROUND_FREEZE_DELAY: Duration = Duration::from_secs(5 * 60);
+
 /// Force-close all tracked browsers and release their CEF handles.
 ///
 /// This takes every handle out of the global map, calls
@@ -80,18 +97,73 @@ pub(crate) fn close_all_browsers() -> usize {
 pub(crate) enum TabEvent {
     AddressChanged(String),
     TitleChanged,
-    LoadingStateChanged,
+    /// The tab's [`TabStatus`] changed. Carries the status from before the
+    /// change so subscribers can react to individual fields.
+    StatusChanged {
+        previous: TabStatus,
+    },
+    LoadFinished,
     PageChromeChanged,
+    PageLanguageChanged,
+    PageSecurityChanged,
     TextInputStateChanged(BrowserTextInputState),
     FrameReady,
     NavigateToUrl(String),
-    OpenNewTab(String),
+    OpenNewTab(String, BrowserTabOpenTarget),
     OpenTargetRequested(OpenTargetRequest),
     FaviconChanged,
-    LoadError { url: String, error_text: String },
-    ContextMenuOpen { context: ContextMenuContext },
+    LoadError {
+        url: String,
+        error_text: String,
+    },
+    ContextMenuOpen {
+        context: ContextMenuContext,
+    },
     FindResult(FindResultEvent),
     DownloadUpdated(DownloadUpdatedEvent),
+    PermissionRequested(PermissionRequest),
+}
+
+/// A tab's loading, navigation and audio state, kept in one place so the
+/// toolbar and tab strip read the same values.
+///
+/// Each field is driven by a CEF callback:
+/// - `is_loading`, `can_go_back` and `can_go_forward` come from
+///   `CefLoadHandler::OnLoadingStateChange`. `is_loading` is also set when
+///   Glass starts or stops a load itself.
+/// - `load_progress` comes from `CefDisplayHandler::OnLoadingProgressChange`.
+///   It resets to `0.0` when a load starts and is `1.0` once it finishes.
+/// - `is_muted` mirrors the last `CefBrowserHost::SetAudioMuted` call, which
+///   Glass makes while a pinned tab is suspended.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TabStatus {
+    pub is_loading: bool,
+    pub load_progress: f64,
+    pub is_muted: bool,
+    pub can_go_back: bool,
+    pub can_go_forward: bool,
+}
+
+/// A tab's find-in-page query and the position of its active match. The
+/// query outlives the find bar, so re-opening find restores it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FindState {
+    pub query: String,
+    pub is_open: bool,
+    pub match_count: i32,
+    pub active_match_ordinal: i32,
+}
+
+impl FindState {
+    /// The "active/total" counter shown next to the find query.
+    pub fn match_position_label(&self) -> String {
+        let match_count = self.match_count.max(0);
+        if match_count == 0 {
+            String::from("0/0")
+        } else {
+            format!("{}/{match_count}", self.active_match_ordinal.max(0))
+        }
+    }
 }
 
 pub struct BrowserTab {
@@ -101,27 +173,50 @@ pub struct BrowserTab {
     event_receiver: EventReceiver,
     url: String,
     title: String,
-    is_loading: bool,
-    can_go_back: bool,
-    can_go_forward: bool,
-    loading_progress: f64,
+    status: TabStatus,
     is_new_tab_page: bool,
     is_pinned: bool,
     favicon_url: Option<String>,
+    favicon_cache_path: Option<PathBuf>,
+    scroll_position: Option<ScrollPosition>,
+    restore_scroll_after_load: bool,
+    detected_language: Option<String>,
+    is_translated: bool,
     page_chrome: Option<PageChrome>,
+    page_security: Option<PageSecurity>,
+    thumbnail: Option<CVPixelBuffer>,
     text_input_state: BrowserTextInputState,
     pending_url: Option<String>,
     suspended_url: Option<String>,
     request_context: Option<cef::RequestContext>,
+    main_frame_request: Option<MainFrameRequest>,
+    user_agent: TabUserAgent,
+    default_zoom_level: f64,
+    throttle_in_background: bool,
+    background_since: Option<Instant>,
+    _background_freeze_timer: Option<Task<()>>,
+    media_playback: MediaPlayback,
+    is_frozen: bool,
+    find_state: FindState,
 }
 
 impl EventEmitter<TabEvent> for BrowserTab {}
 
 impl BrowserTab {
-    pub fn new(_cx: &mut Context<Self>) -> Self {
+    pub fn new(
+        site_permissions: SitePermissions,
+        site_user_agents: SiteUserAgents,
+        cx: &mut Context<Self>,
+    ) -> Self {
         let render_state = Arc::new(Mutex::new(RenderState::default()));
         let (sender, receiver) = events::event_channel();
-        let client = ClientBuilder::build(render_state.clone(), sender);
+        let user_agent = TabUserAgent::new(site_user_agents);
+        let client = ClientBuilder::build(
+            render_state.clone(),
+            sender,
+            site_permissions,
+            user_agent.clone(),
+        );
 
         Self {
             browser_id: None,
@@ -130,18 +225,31 @@ impl BrowserTab {
             event_receiver: receiver,
             url: String::from("glass://newtab"),
             title: String::from("New Tab"),
-            is_loading: false,
-            can_go_back: false,
-            can_go_forward: false,
-            loading_progress: 0.0,
+            status: TabStatus::default(),
             is_new_tab_page: true,
             is_pinned: false,
             favicon_url: None,
+            favicon_cache_path: None,
+            scroll_position: None,
+            restore_scroll_after_load: false,
+            detected_language: None,
+            is_translated: false,
             page_chrome: None,
+            page_security: None,
+            thumbnail: None,
             text_input_state: BrowserTextInputState::default(),
             pending_url: None,
             suspended_url: None,
             request_context: None,
+            main_frame_request: None,
+            user_agent,
+            default_zoom_level: BrowserSettings::get_global(cx).default_zoom_level(),
+            throttle_in_background: BrowserSettings::get_global(cx).throttle_background_tabs,
+            background_since: None,
+            _background_freeze_timer: None,
+            media_playback: MediaPlayback::default(),
+            is_frozen: false,
+            find_state: FindState::default(),
         }
     }
 
@@ -150,11 +258,19 @@ impl BrowserTab {
         title: String,
         is_new_tab_page: bool,
         favicon_url: Option<String>,
-        _cx: &mut Context<Self>,
+        site_permissions: SitePermissions,
+        site_user_agents: SiteUserAgents,
+        cx: &mut Context<Self>,
     ) -> Self {
         let render_state = Arc::new(Mutex::new(RenderState::default()));
         let (sender, receiver) = events::event_channel();
-        let client = ClientBuilder::build(render_state.clone(), sender);
+        let user_agent = TabUserAgent::new(site_user_agents);
+        let client = ClientBuilder::build(
+            render_state.clone(),
+            sender,
+            site_permissions,
+            user_agent.clone(),
+        );
 
         Self {
             browser_id: None,
@@ -163,18 +279,31 @@ impl BrowserTab {
             event_receiver: receiver,
             url,
             title,
-            is_loading: false,
-            can_go_back: false,
-            can_go_forward: false,
-            loading_progress: 0.0,
+            status: TabStatus::default(),
             is_new_tab_page,
             is_pinned: false,
             favicon_url,
+            favicon_cache_path: None,
+            scroll_position: None,
+            restore_scroll_after_load: false,
+            detected_language: None,
+            is_translated: false,
             page_chrome: None,
+            page_security: None,
+            thumbnail: None,
             text_input_state: BrowserTextInputState::default(),
             pending_url: None,
             suspended_url: None,
             request_context: None,
+            main_frame_request: None,
+            user_agent,
+            default_zoom_level: BrowserSettings::get_global(cx).default_zoom_level(),
+            throttle_in_background: BrowserSettings::get_global(cx).throttle_background_tabs,
+            background_since: None,
+            _background_freeze_timer: None,
+            media_playback: MediaPlayback::default(),
+            is_frozen: false,
+            find_state: FindState::default(),
         }
     }
 
@@ -188,11 +317,24 @@ impl BrowserTab {
                         continue;
                     }
                     self.page_chrome = None;
+                    if self.page_security.take().is_some() {
+                        cx.emit(TabEvent::PageSecurityChanged);
+                    }
                     if self.text_input_state != BrowserTextInputState::default() {
                         self.text_input_state = BrowserTextInputState::default();
                         cx.emit(TabEvent::TextInputStateChanged(self.text_input_state));
                     }
+                    if self
+                        .scroll_position
+                        .as_ref()
+                        .is_some_and(|position| !position.applies_to(&url))
+                    {
+                        self.scroll_position = None;
+                        self.restore_scroll_after_load = false;
+                    }
                     self.url.clone_from(&url);
+                    self.apply_user_agent();
+                    self.apply_default_zoom();
                     cx.emit(TabEvent::AddressChanged(url));
                     cx.emit(TabEvent::PageChromeChanged);
                 }
@@ -208,13 +350,36 @@ impl BrowserTab {
                     can_go_back,
                     can_go_forward,
                 } => {
-                    self.is_loading = is_loading;
-                    self.can_go_back = can_go_back;
-                    self.can_go_forward = can_go_forward;
-                    cx.emit(TabEvent::LoadingStateChanged);
+                    let mut status = self.status;
+                    let finished_loading = status.is_loading && !is_loading;
+                    if is_loading && !status.is_loading {
+                        status.load_progress = 0.0;
+                        if self.detected_language.take().is_some() {
+                            cx.emit(TabEvent::PageLanguageChanged);
+                        }
+                    }
+                    if finished_loading {
+                        status.load_progress = 1.0;
+                    }
+                    status.is_loading = is_loading;
+                    status.can_go_back = can_go_back;
+                    status.can_go_forward = can_go_forward;
+                    self.set_status(status, cx);
+                    if finished_loading {
+                        if std::mem::take(&mut self.restore_scroll_after_load) {
+                            self.restore_scroll_position();
+                        }
+                        cx.emit(TabEvent::LoadFinished);
+                    }
                 }
                 BrowserEvent::LoadingProgress(progress) => {
-                    self.loading_progress = progress;
+                    self.set_status(
+                        TabStatus {
+                            load_progress: progress,
+                            ..self.status
+                        },
+                        cx,
+                    );
                 }
                 BrowserEvent::FrameReady => {
                     cx.emit(TabEvent::FrameReady);
@@ -229,11 +394,18 @@ impl BrowserTab {
                 BrowserEvent::OpenTargetRequested(request) => {
                     cx.emit(TabEvent::OpenTargetRequested(request));
                 }
+                BrowserEvent::MainFrameRequest(request) => {
+                    if is_suspended {
+                        continue;
+                    }
+                    self.main_frame_request = Some(request);
+                }
                 BrowserEvent::FaviconUrlChanged(urls) => {
                     if is_suspended {
                         continue;
                     }
                     self.favicon_url = urls.into_iter().next();
+                    self.favicon_cache_path = None;
                     cx.emit(TabEvent::FaviconChanged);
                 }
                 BrowserEvent::PageChromeChanged(page_chrome) => {
@@ -245,6 +417,33 @@ impl BrowserTab {
                         cx.emit(TabEvent::PageChromeChanged);
                     }
                 }
+                BrowserEvent::PageSecurityChanged(page_security) => {
+                    if is_suspended {
+                        continue;
+                    }
+                    if self.page_security != page_security {
+                        self.page_security = page_security;
+                        cx.emit(TabEvent::PageSecurityChanged);
+                    }
+                }
+                BrowserEvent::PageLanguageDetected(language) => {
+                    if is_suspended {
+                        continue;
+                    }
+                    if self.detected_language != language {
+                        self.detected_language = language;
+                        cx.emit(TabEvent::PageLanguageChanged);
+                    }
+                }
+                BrowserEvent::MediaPlaybackChanged(media_playback) => {
+                    self.media_playback = media_playback;
+                }
+                BrowserEvent::ScrollPositionChanged(scroll_position) => {
+                    if is_suspended || !scroll_position.applies_to(&self.url) {
+                        continue;
+                    }
+                    self.scroll_position = Some(scroll_position);
+                }
                 BrowserEvent::TextInputStateChanged(text_input_state) => {
                     if is_suspended {
                         continue;
@@ -256,13 +455,19 @@ impl BrowserTab {
                     }
                 }
                 BrowserEvent::FindResult(result) => {
+                    self.find_state.match_count = result.count;
+                    self.find_state.active_match_ordinal = result.active_match_ordinal;
                     cx.emit(TabEvent::FindResult(result));
                 }
                 BrowserEvent::DownloadUpdated(update) => {
                     cx.emit(TabEvent::DownloadUpdated(update));
                 }
+                BrowserEvent::PermissionRequested(request) => {
+                    cx.emit(TabEvent::PermissionRequested(request));
+                }
             }
         }
+        self.apply_background_state();
     }
 
     pub fn create_browser(&mut self, initial_url: &str) -> Result<()> {
@@ -339,12 +544,17 @@ impl BrowserTab {
         self.with_host(|host| {
             host.was_resized();
         });
+        self.apply_user_agent();
+        self.apply_default_zoom();
+        self.apply_background_state();
 
         Ok(())
     }
 
     pub fn navigate(&mut self, url: &str, cx: &mut Context<Self>) {
+        self.is_translated = false;
         self.favicon_url = None;
+        self.favicon_cache_path = None;
         if self.browser_id.is_some() {
             let loaded = self
                 .with_browser(|browser| {
@@ -359,7 +569,14 @@ impl BrowserTab {
 
             if loaded {
                 self.url = url.to_string();
-                self.is_loading = true;
+                self.set_status(
+                    TabStatus {
+                        is_loading: true,
+                        load_progress: 0.0,
+                        ..self.status
+                    },
+                    cx,
+                );
             }
         } else {
             self.url = url.to_string();
@@ -375,7 +592,7 @@ impl BrowserTab {
         self.suspended_url.is_some()
     }
 
-    pub fn suspend(&mut self) {
+    pub fn suspend(&mut self, cx: &mut Context<Self>) {
         if self.suspended_url.is_some() {
             return;
         }
@@ -397,10 +614,10 @@ impl BrowserTab {
         // Just hide and mute — leave the page fully loaded so all cookies,
         // localStorage, sessionStorage, and JS state are preserved intact.
         self.set_hidden(true);
-        self.set_audio_muted(true);
+        self.set_audio_muted(true, cx);
     }
 
-    pub fn resume(&mut self) {
+    pub fn resume(&mut self, cx: &mut Context<Self>) {
         let Some(url) = self.suspended_url.take() else {
             return;
         };
@@ -443,12 +660,18 @@ impl BrowserTab {
         self.url = url;
         // Page is still loaded — just un-hide and un-mute
         self.set_hidden(false);
-        self.set_audio_muted(false);
+        self.set_audio_muted(false, cx);
     }
 
-    pub fn reload(&mut self) {
+    pub fn reload(&mut self, cx: &mut Context<Self>) {
         if self.with_browser(|browser| browser.reload()).is_some() {
-            self.is_loading = true;
+            self.set_status(
+                TabStatus {
+                    is_loading: true,
+                    ..self.status
+                },
+                cx,
+            );
         } else {
             log::trace!(
                 "[browser] reload called but no browser exists, url={}",
@@ -457,23 +680,148 @@ impl BrowserTab {
         }
     }
 
-    pub fn stop(&mut self) {
+    /// The user agent this tab's pages are loaded with: the tab's own
+    /// override, then its origin's, or `None` for the platform default.
+    pub fn user_agent(&self) -> Option<String> {
+        self.user_agent.for_url(&self.url)
+    }
+
+    /// The site-wide override for this tab's origin, if there is one.
+    pub fn site_user_agent(&self) -> Option<String> {
+        self.user_agent.site_override(&self.url)
+    }
+
+    /// Overrides the user agent for this tab only and reloads the page with it.
+    pub fn set_user_agent(&mut self, user_agent: Option<String>, cx: &mut Context<Self>) {
+        self.user_agent.set_tab_override(user_agent);
+        self.refresh_user_agent(cx);
+    }
+
+    /// Picks up a changed site-wide override, reloading if it applies here.
+    pub(crate) fn refresh_user_agent(&mut self, cx: &mut Context<Self>) {
+        if self.apply_user_agent() {
+            self.reload(cx);
+        }
+    }
+
+    fn apply_user_agent(&mut self) -> bool {
+        let mut changed = false;
+        self.with_host(|host| changed = self.user_agent.apply(host, &self.url));
+        changed
+    }
+
+    pub(crate) fn set_default_zoom_level(&mut self, zoom_level: f64) {
+        self.default_zoom_level = zoom_level;
+        self.apply_default_zoom();
+    }
+
+    /// CEF remembers zoom per host, so this is reapplied after each navigation.
+    fn apply_default_zoom(&self) {
+        let zoom_level = self.default_zoom_level;
+        self.with_host(|host| host.set_zoom_level(zoom_level));
+    }
+
+    /// Marks the tab as inactive. Tabs left in the background for a while are
+    /// frozen, which stops their timers, animations and network polling until
+    /// they are shown again, unless they are playing sound or holding a live
+    /// connection.
+    pub fn set_background(&mut self, is_background: bool, cx: &mut Context<Self>) {
+        if !is_background {
+            self.background_since = None;
+            self._background_freeze_timer = None;
+        } else if self.background_since.is_none() {
+            self.background_since = Some(Instant::now());
+            self._background_freeze_timer = Some(cx.spawn(async move |this, cx| {
+                cx.background_executor()
+                    .timer(BACKGROUND_FREEZE_DELAY)
+                    .await;
+                this.update(cx, |tab, _| tab.apply_background_state()).ok();
+            }));
+        }
+        self.apply_background_state();
+    }
+
+    pub(crate) fn set_throttle_in_background(&mut self, throttle_in_background: bool) {
+        self.throttle_in_background = throttle_in_background;
+        self.apply_background_state();
+    }
+
+    /// Whether the page is playing sound from a media element or Web Audio.
+    pub fn is_audible(&self) -> bool {
+        self.media_playback.is_audible
+    }
+
+    fn apply_background_state(&mut self) {
+        let should_freeze = self.throttle_in_background
+            && !self.media_playback.is_audible
+            && !self.media_playback.has_live_connection
+            && !self.is_new_tab_page
+            && self
+                .background_since
+                .is_some_and(|since| since.elapsed() >= BACKGROUND_FREEZE_DELAY);
+        if self.browser_id.is_none() || should_freeze == self.is_frozen {
+            return;
+        }
+        let state = if should_freeze { "frozen" } else { "active" };
+        self.with_host(|host| {
+            let Some(mut params) = cef::dictionary_value_create() else {
+                return;
+            };
+            params.set_string(
+                Some(&cef::CefString::from("state")),
+                Some(&cef::CefString::from(state)),
+            );
+            host.execute_dev_tools_method(
+                0,
+                Some(&cef::CefString::from("Page.setWebLifecycleState")),
+                Some(&mut params),
+            );
+        });
+        self.is_frozen = should_freeze;
+    }
+
+    pub fn stop_loading(&mut self, cx: &mut Context<Self>) {
         self.with_browser(|browser| browser.stop_load());
-        self.is_loading = false;
+        self.set_status(
+            TabStatus {
+                is_loading: false,
+                ..self.status
+            },
+            cx,
+        );
     }
 
     pub fn go_back(&mut self) {
-        if self.can_go_back {
+        if self.status.can_go_back {
+            self.is_translated = false;
             self.with_browser(|browser| browser.go_back());
         }
     }
 
     pub fn go_forward(&mut self) {
-        if self.can_go_forward {
+        if self.status.can_go_forward {
+            self.is_translated = false;
             self.with_browser(|browser| browser.go_forward());
         }
     }
 
+    /// The primary language the current page declared, such as `de`.
+    pub fn detected_language(&self) -> Option<&str> {
+        self.detected_language.as_deref()
+    }
+
+    pub fn is_translated(&self) -> bool {
+        self.is_translated
+    }
+
+    /// Loads the provider's translation of the current page. Links followed
+    /// from there stay on the provider, so the tab counts as translated until
+    /// the user navigates somewhere else themselves.
+    pub fn translate(&mut self, translated_url: &str, cx: &mut Context<Self>) {
+        self.navigate(translated_url, cx);
+        self.is_translated = true;
+    }
+
     pub fn copy(&self) {
         self.with_focused_frame(|frame| frame.copy());
     }
@@ -502,6 +850,52 @@ impl BrowserTab {
         self.with_focused_frame(|frame| frame.del());
     }
 
+    pub fn scroll_position(&self) -> Option<&ScrollPosition> {
+        self.scroll_position.as_ref()
+    }
+
+    /// Seeds the scroll position from a saved session. It is applied once the
+    /// page finishes loading.
+    pub(crate) fn set_restored_scroll_position(&mut self, position: Option<ScrollPosition>) {
+        self.restore_scroll_after_load = position.is_some();
+        self.scroll_position = position;
+    }
+
+    /// Asks the page to report its current scroll offset right away rather
+    /// than after the scroll throttle, so it is up to date when the tab is
+    /// deactivated.
+    pub fn capture_scroll_position(&self) {
+        self.execute_main_frame_javascript(ScrollPosition::flush_script());
+    }
+
+    /// Scrolls back to the last recorded offset for the current page, waiting
+    /// for the load to finish if the page is not ready yet.
+    pub fn restore_scroll_position(&mut self) {
+        let Some(position) = self
+            .scroll_position
+            .as_ref()
+            .filter(|position| position.applies_to(&self.url))
+        else {
+            return;
+        };
+        if self.status.is_loading || !self.has_browser() {
+            self.restore_scroll_after_load = true;
+            return;
+        }
+        let script = position.restore_script();
+        self.execute_main_frame_javascript(&script);
+    }
+
+    fn execute_main_frame_javascript(&self, code: &str) {
+        self.with_browser(|browser| {
+            if let Some(frame) = browser.main_frame() {
+                let code = cef::CefString::from(code);
+                let url = cef::CefString::from("");
+                frame.execute_java_script(Some(&code), Some(&url), 0);
+            }
+        });
+    }
+
     pub fn execute_javascript(&self, code: &str) {
         self.with_focused_frame(|frame| {
             let code = cef::CefString::from(code);
@@ -519,6 +913,129 @@ impl BrowserTab {
         });
     }
 
+    /// Opens the print dialog for the page. Does nothing on the new tab page.
+    pub fn print(&self) {
+        if self.is_new_tab_page {
+            return;
+        }
+        self.with_host(|host| host.print());
+    }
+
+    /// Writes the page to `path` as a PDF, resolving once the file has been
+    /// written.
+    pub fn save_as_pdf(&self, path: PathBuf, cx: &mut Context<Self>) -> Task<Result<()>> {
+        if self.is_new_tab_page {
+            return Task::ready(Err(anyhow::anyhow!(
+                "the new tab page can't be saved as a PDF"
+            )));
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        let pdf_path = cef::CefString::from(path.to_string_lossy().as_ref());
+        self.with_host(|host| {
+            let settings = cef::PdfPrintSettings::default();
+            let mut callback = PdfPrintCallbackBuilder::build(OsrPdfPrintCallback::new(sender));
+            host.print_to_pdf(Some(&pdf_path), Some(&settings), Some(&mut callback));
+        });
+
+        cx.background_spawn(async move {
+            // The sender is dropped without a result when the tab has no browser.
+            let succeeded = receiver.await.context("the page has no browser to print")?;
+            anyhow::ensure!(succeeded, "failed to write {}", path.display());
+            Ok(())
+        })
+    }
+
+    /// Renders the whole document as a PNG, not only the part in view.
+    pub fn capture_full_page(&self, cx: &mut Context<Self>) -> Task<Result<Arc<Image>>> {
+        self.capture_screenshot(true, cx)
+    }
+
+    /// Renders the part of the page currently in view as a PNG.
+    pub fn capture_viewport(&self, cx: &mut Context<Self>) -> Task<Result<Arc<Image>>> {
+        self.capture_screenshot(false, cx)
+    }
+
+    fn capture_screenshot(
+        &self,
+        full_page: bool,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Arc<Image>>> {
+        if self.is_new_tab_page {
+            return Task::ready(Err(anyhow::anyhow!("the new tab page can't be captured")));
+        }
+        let session = self
+            .with_browser(|browser| browser.host())
+            .flatten()
+            .and_then(|host| DevToolsSession::attach(&host));
+        let Some(mut session) = session else {
+            return Task::ready(Err(anyhow::anyhow!("the page has no browser to capture")));
+        };
+
+        cx.spawn(async move |_, cx| {
+            let layout_metrics = if full_page {
+                Some(
+                    session
+                        .call("Page.getLayoutMetrics", serde_json::json!({}))
+                        .await?,
+                )
+            } else {
+                None
+            };
+            let params = page_capture::screenshot_params(layout_metrics.as_ref())?;
+            let result = session.call("Page.captureScreenshot", params).await?;
+            drop(session);
+
+            // Full-page captures can be tens of megabytes of base64.
+            let bytes = cx
+                .background_spawn(async move { page_capture::decode_screenshot(&result) })
+                .await?;
+            Ok(Arc::new(Image::from_bytes(ImageFormat::Png, bytes)))
+        })
+    }
+
+    /// The page's web origin, such as `https://example.com`, or `None` for
+    /// pages that aren't served over HTTP.
+    pub fn origin(&self) -> Option<String> {
+        origin_for_url(&self.url)
+    }
+
+    /// Clears stored data and host-only cookies for the page's origin, then
+    /// reloads the page so it starts over without them.
+    pub fn clear_origin_data(&self, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let Some(origin) = self.origin() else {
+            return Task::ready(Err(anyhow::anyhow!(
+                "{} has no site data to clear",
+                self.url
+            )));
+        };
+        let session = self
+            .with_browser(|browser| browser.host())
+            .flatten()
+            .and_then(|host| DevToolsSession::attach(&host));
+        let Some(mut session) = session else {
+            return Task::ready(Err(anyhow::anyhow!("the page has no browser to clear")));
+        };
+
+        let url = self.url.clone();
+        cx.spawn(async move |this, cx| {
+            session
+                .call(
+                    "Storage.clearDataForOrigin",
+                    site_permissions::clear_origin_data_params(&origin),
+                )
+                .await?;
+            let cookies = session
+                .call("Network.getCookies", site_permissions::get_cookies_params(&url))
+                .await?;
+            for params in site_permissions::host_only_cookie_deletions(&url, &cookies) {
+                session.call("Network.deleteCookies", params).await?;
+            }
+            drop(session);
+            this.update(cx, |tab, cx| tab.reload(cx))
+        })
+    }
+
     pub fn set_size(&mut self, width: u32, height: u32) {
         {
             let mut state = self.render_state.lock();
@@ -552,10 +1069,17 @@ impl BrowserTab {
         });
     }
 
-    pub fn set_audio_muted(&self, muted: bool) {
+    pub fn set_audio_muted(&mut self, muted: bool, cx: &mut Context<Self>) {
         self.with_host(|host| {
             host.set_audio_muted(if muted { 1 } else { 0 });
         });
+        self.set_status(
+            TabStatus {
+                is_muted: muted,
+                ..self.status
+            },
+            cx,
+        );
     }
 
     pub fn find_in_page(&self, query: &str, forward: bool, match_case: bool, find_next: bool) {
@@ -576,6 +1100,41 @@ impl BrowserTab {
         });
     }
 
+    pub fn find_state(&self) -> &FindState {
+        &self.find_state
+    }
+
+    pub fn open_find(&mut self) {
+        self.find_state.is_open = true;
+    }
+
+    /// Replaces the query and searches for it from the top of the page.
+    pub fn set_find_query(&mut self, query: String) {
+        self.find_state.query = query;
+        self.find_state.match_count = 0;
+        self.find_state.active_match_ordinal = 0;
+        self.find_match(true, false);
+    }
+
+    /// Searches for the current query. With `find_next`, moves to the next
+    /// or previous match instead of restarting the search.
+    pub fn find_match(&self, forward: bool, find_next: bool) {
+        if self.find_state.query.is_empty() {
+            self.stop_finding(true);
+        } else {
+            self.find_in_page(&self.find_state.query, forward, false, find_next);
+        }
+    }
+
+    /// Hides the find bar and its highlights, keeping the query to restore
+    /// when find is opened again.
+    pub fn close_find(&mut self) {
+        self.stop_finding(true);
+        self.find_state.is_open = false;
+        self.find_state.match_count = 0;
+        self.find_state.active_match_ordinal = 0;
+    }
+
     pub fn ime_set_composition(&self, text: &str, selection_range: Option<std::ops::Range<usize>>) {
         self.with_host(|host| {
             let text = cef::CefString::from(text);
@@ -666,6 +1225,18 @@ impl BrowserTab {
         self.render_state.lock().current_frame.clone()
     }
 
+    /// Caches the last painted frame so the tab can be previewed while it's hidden.
+    pub fn capture_thumbnail(&mut self, cx: &mut Context<Self>) {
+        if let Some(frame) = self.current_frame() {
+            self.thumbnail = Some(frame);
+            cx.notify();
+        }
+    }
+
+    pub fn thumbnail(&self) -> Option<CVPixelBuffer> {
+        self.thumbnail.clone()
+    }
+
     pub fn url(&self) -> &str {
         &self.url
     }
@@ -674,22 +1245,53 @@ impl BrowserTab {
         &self.title
     }
 
+    pub fn status(&self) -> TabStatus {
+        self.status
+    }
+
+    fn set_status(&mut self, status: TabStatus, cx: &mut Context<Self>) {
+        if self.status == status {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.status, status);
+        cx.emit(TabEvent::StatusChanged { previous });
+    }
+
     pub fn is_loading(&self) -> bool {
-        self.is_loading
+        self.status.is_loading
+    }
+
+    /// The active page load's progress, from `0.0` to `1.0`.
+    pub fn load_progress(&self) -> f64 {
+        self.status.load_progress
     }
 
     pub fn can_go_back(&self) -> bool {
-        self.can_go_back
+        self.status.can_go_back
     }
 
     pub fn can_go_forward(&self) -> bool {
-        self.can_go_forward
+        self.status.can_go_forward
     }
 
     pub fn favicon_url(&self) -> Option<&str> {
         self.favicon_url.as_deref()
     }
 
+    pub(crate) fn set_favicon_cache_path(&mut self, path: Option<PathBuf>) {
+        self.favicon_cache_path = path;
+    }
+
+    /// The image to show for this tab's favicon, preferring the on-disk cache
+    /// over the page's favicon URL.
+    pub fn favicon_image_uri(&self) -> Option<String> {
+        self.favicon_cache_path
+            .as_deref()
+            .and_then(|path| url::Url::from_file_path(path).ok())
+            .map(String::from)
+            .or_else(|| self.favicon_url.clone())
+    }
+
     pub fn page_chrome_color(&self) -> Option<Hsla> {
         self.page_chrome.map(|page_chrome| page_chrome.color)
     }
@@ -698,6 +1300,44 @@ impl BrowserTab {
         self.text_input_state
     }
 
+    /// The request that loaded the current page, formatted as a cURL command.
+    /// Pages whose URL has changed since the load (e.g. via `history.pushState`)
+    /// fall back to a plain GET of the current URL.
+    pub fn current_request_as_curl(&self) -> Option<String> {
+        if self.is_new_tab_page || self.url.is_empty() || self.url.starts_with("glass://") {
+            return None;
+        }
+
+        match &self.main_frame_request {
+            Some(request) if request.url == self.url => Some(request.to_curl_command()),
+            _ => Some(
+                MainFrameRequest {
+                    method: "GET".to_string(),
+                    url: self.url.clone(),
+                    headers: Vec::new(),
+                }
+                .to_curl_command(),
+            ),
+        }
+    }
+
+    /// Certificate details for the page, once its load has finished.
+    pub fn page_security(&self) -> Option<&PageSecurity> {
+        self.page_security.as_ref()
+    }
+
+    /// `None` for the new tab page and for pages that aren't served over
+    /// http(s).
+    pub fn security_state(&self) -> Option<PageSecurityState> {
+        if self.is_new_tab_page {
+            return None;
+        }
+        self.page_security
+            .as_ref()
+            .map(|page_security| page_security.state)
+            .or_else(|| page_security::security_state_for_url(&self.url))
+    }
+
     pub fn is_new_tab_page(&self) -> bool {
         self.is_new_tab_page
     }
@@ -737,6 +1377,10 @@ impl BrowserTab {
 
     pub fn close_browser(&mut self) {
         self.page_chrome = None;
+        self.user_agent.forget_applied();
+        self.media_playback = MediaPlayback::default();
+        self.is_frozen = false;
+        self.find_state = FindState::default();
         if let Some(browser_id) = self.browser_id.take() {
             let browser = BROWSER_HANDLES
                 .lock()
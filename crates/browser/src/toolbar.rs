@@ -1,11 +1,13 @@
 use crate::BrowserView;
 use crate::omnibox::{Omnibox, OmniboxEvent};
+use crate::page_security::PageSecurityState;
+use crate::site_permissions::PermissionDecision;
 use crate::tab::{BrowserTab, TabEvent};
 use gpui::{
     App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement, Render, Subscription,
     WeakEntity, Window, native_icon_button,
 };
-use ui::{h_flex, prelude::*};
+use ui::{ContextMenu, IconButton, PopoverMenu, Tooltip, h_flex, prelude::*};
 use workspace::{
     ItemHandle, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView, WorkspaceItemKind,
 };
@@ -133,7 +135,9 @@ impl BrowserToolbar {
                             omnibox.set_url(&url, window, cx);
                         });
                     }
-                    TabEvent::LoadingStateChanged | TabEvent::TitleChanged => {
+                    TabEvent::StatusChanged { .. }
+                    | TabEvent::TitleChanged
+                    | TabEvent::PageSecurityChanged => {
                         cx.notify();
                     }
                     _ => {}
@@ -149,6 +153,19 @@ impl BrowserToolbar {
         cx.notify();
     }
 
+    fn toggle_bookmark(
+        &mut self,
+        _: &gpui::ClickEvent,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(browser_view) = self.browser_view.upgrade() {
+            browser_view.update(cx, |browser_view, cx| {
+                browser_view.toggle_bookmark_active_tab(cx);
+            });
+        }
+    }
+
     fn toggle_download_center(
         &mut self,
         _: &gpui::ClickEvent,
@@ -162,6 +179,92 @@ impl BrowserToolbar {
         }
     }
 
+    /// The active page's connection security, with its certificate and the
+    /// site's camera, microphone, location and notification decisions. Each
+    /// decision can be switched between Ask, Allow and Block.
+    fn render_security_menu(&self, cx: &App) -> impl IntoElement {
+        let security_state = self
+            .tab
+            .as_ref()
+            .and_then(|tab| tab.read(cx).security_state());
+        let (icon, icon_color) = match security_state {
+            Some(PageSecurityState::Secure) => (IconName::LockOutlined, Color::Muted),
+            Some(PageSecurityState::MixedContent) => (IconName::Warning, Color::Warning),
+            Some(PageSecurityState::CertificateError) => (IconName::Warning, Color::Error),
+            Some(PageSecurityState::Insecure) | None => (IconName::Info, Color::Muted),
+        };
+        let tool_tip = security_state.map_or("Site Information", PageSecurityState::label);
+
+        let browser_view = self.browser_view.clone();
+        let tab = self.tab.clone();
+        PopoverMenu::new("site-security")
+            .menu(move |window, cx| {
+                let browser_view = browser_view.upgrade()?;
+                let tab = tab.as_ref()?.read(cx);
+                let security_state = tab.security_state();
+                let certificate = tab
+                    .page_security()
+                    .and_then(|page_security| page_security.certificate.clone());
+                let site_permissions = browser_view.read(cx).active_site_permissions(cx);
+                let has_site_data = tab.origin().is_some();
+                Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    if let Some(security_state) = security_state {
+                        menu = menu
+                            .header(security_state.label())
+                            .label(security_state.description());
+                    }
+                    if let Some(certificate) = &certificate {
+                        menu = menu
+                            .label(format!("Issued to: {}", certificate.subject))
+                            .label(format!("Issued by: {}", certificate.issuer));
+                    }
+                    if let Some((origin, decisions)) = site_permissions.clone() {
+                        menu = menu.separator().header(format!("Permissions for {origin}"));
+                        for (permission, current_decision) in decisions {
+                            menu = menu.label(permission.label());
+                            for decision in [
+                                PermissionDecision::Ask,
+                                PermissionDecision::Allow,
+                                PermissionDecision::Deny,
+                            ] {
+                                let browser_view = browser_view.clone();
+                                let origin = origin.clone();
+                                menu = menu.toggleable_entry(
+                                    decision.label(),
+                                    decision == current_decision,
+                                    IconPosition::Start,
+                                    None,
+                                    move |_, cx| {
+                                        browser_view.update(cx, |browser_view, cx| {
+                                            browser_view.set_site_permission(
+                                                &origin, permission, decision, cx,
+                                            );
+                                        });
+                                    },
+                                );
+                            }
+                        }
+                    }
+                    if has_site_data {
+                        let browser_view = browser_view.clone();
+                        menu = menu.separator().entry("Clear Site Data", None, move |window, cx| {
+                            browser_view.update(cx, |browser_view, cx| {
+                                browser_view.clear_active_site_data(window, cx);
+                            });
+                        });
+                    }
+                    menu
+                }))
+            })
+            .trigger_with_tooltip(
+                IconButton::new("site-security-button", icon)
+                    .icon_size(IconSize::Small)
+                    .icon_color(icon_color),
+                Tooltip::text(tool_tip),
+            )
+            .attach(gpui::Corner::BottomLeft)
+    }
+
     fn go_back(&mut self, _: &gpui::ClickEvent, _window: &mut Window, cx: &mut Context<Self>) {
         if let Some(tab) = self.tab.clone() {
             tab.update(cx, |tab, _| {
@@ -180,16 +283,16 @@ impl BrowserToolbar {
 
     fn reload(&mut self, _: &gpui::ClickEvent, _window: &mut Window, cx: &mut Context<Self>) {
         if let Some(tab) = self.tab.clone() {
-            tab.update(cx, |tab, _| {
-                tab.reload();
+            tab.update(cx, |tab, cx| {
+                tab.reload(cx);
             });
         }
     }
 
     fn stop(&mut self, _: &gpui::ClickEvent, _window: &mut Window, cx: &mut Context<Self>) {
         if let Some(tab) = self.tab.clone() {
-            tab.update(cx, |tab, _| {
-                tab.stop();
+            tab.update(cx, |tab, cx| {
+                tab.stop_loading(cx);
             });
         }
     }
@@ -239,6 +342,10 @@ impl Render for BrowserToolbar {
             .tab
             .as_ref()
             .is_some_and(|tab| tab.read(cx).is_loading());
+        let is_bookmarked = self
+            .browser_view
+            .upgrade()
+            .is_some_and(|browser_view| browser_view.read(cx).is_active_tab_bookmarked(cx));
         let show_navigation_buttons = !is_new_tab_page;
         let show_omnibox = !is_new_tab_page;
         let show_downloads_button = true;
@@ -278,7 +385,19 @@ impl Render for BrowserToolbar {
                         .tooltip("Reload")
                 })
             })
-            .when(show_omnibox, |this| this.child(self.omnibox.clone()))
+            .when(show_omnibox, |this| {
+                this.child(self.render_security_menu(cx))
+                    .child(self.omnibox.clone())
+                    .child(if is_bookmarked {
+                        native_icon_button("bookmark", "star.fill")
+                            .on_click(cx.listener(Self::toggle_bookmark))
+                            .tooltip("Remove Bookmark")
+                    } else {
+                        native_icon_button("bookmark", "star")
+                            .on_click(cx.listener(Self::toggle_bookmark))
+                            .tooltip("Bookmark This Page")
+                    })
+            })
             .when(show_downloads_button, |this| {
                 this.child(
                     native_icon_button("downloads", "arrow.down.circle")
@@ -0,0 +1,306 @@
+use crate::browser_settings::BrowserSettings;
+use gpui::App;
+use settings::Settings as _;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+pub(crate) const DEFAULT_SEARCH_URL: &str = "https://www.google.com/search?q={query}";
+
+/// Query prefixes that search engines interpret as operators. Input using any
+/// of these is always searched, even when the operand looks like a domain.
+const SEARCH_OPERATORS: &[&str] = &[
+    "site:",
+    "inurl:",
+    "allinurl:",
+    "intitle:",
+    "allintitle:",
+    "intext:",
+    "allintext:",
+    "filetype:",
+    "ext:",
+    "related:",
+    "cache:",
+    "define:",
+    "before:",
+    "after:",
+];
+
+/// Turns text typed into the omnibox into the URL to navigate to, either by
+/// completing it with a scheme or by building a search URL from it with the
+/// configured search engine.
+pub fn text_to_url(text: &str, cx: &App) -> String {
+    url_for_text(text, &BrowserSettings::get_global(cx).search_url)
+}
+
+/// The search engine's results page for `query`, where `search_url` contains
+/// a `{query}` placeholder.
+pub(crate) fn search_url_for_query(query: &str, search_url: &str) -> String {
+    let encoded: String = url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
+    search_url.replace("{query}", &encoded)
+}
+
+/// The configured search engine's host, such as `google.com`, for labeling
+/// search suggestions.
+pub fn search_engine_name(cx: &App) -> String {
+    let search_url = &BrowserSettings::get_global(cx).search_url;
+    url::Url::parse(search_url)
+        .ok()
+        .and_then(|url| {
+            url.host_str()
+                .map(|host| host.trim_start_matches("www.").to_string())
+        })
+        .unwrap_or_else(|| search_url.clone())
+}
+
+fn url_for_text(text: &str, search_url: &str) -> String {
+    if text.starts_with("http://") || text.starts_with("https://") {
+        return text.to_string();
+    }
+
+    if !looks_like_url(text) {
+        return search_url_for_query(text, search_url);
+    }
+
+    let text = bracket_bare_ipv6(text);
+    if should_use_http_by_default(&text) {
+        format!("http://{text}")
+    } else {
+        format!("https://{text}")
+    }
+}
+
+pub(crate) fn looks_like_url(input: &str) -> bool {
+    if uses_search_operators(input) {
+        return false;
+    }
+
+    if input.starts_with("http://") || input.starts_with("https://") {
+        return true;
+    }
+
+    if input.contains("://") {
+        return true;
+    }
+
+    if input.chars().any(char::is_whitespace) {
+        return false;
+    }
+
+    let input = bracket_bare_ipv6(input);
+    let Ok(url) = url::Url::parse(&format!("http://{input}")) else {
+        return false;
+    };
+
+    match url.host() {
+        Some(url::Host::Domain(domain)) => {
+            is_localhost(domain) || domain.contains('.') || url.port().is_some()
+        }
+        // The URL parser also accepts numeric shorthands like "2024" as IPv4 hosts, so
+        // only treat input as an address when it was typed in dotted-quad form.
+        Some(url::Host::Ipv4(address)) => input.starts_with(&address.to_string()),
+        Some(url::Host::Ipv6(_)) => true,
+        None => false,
+    }
+}
+
+/// Bare IPv6 addresses such as "::1" are only valid in a URL inside brackets.
+fn bracket_bare_ipv6(input: &str) -> String {
+    if input.parse::<Ipv6Addr>().is_ok() {
+        format!("[{input}]")
+    } else {
+        input.to_string()
+    }
+}
+
+fn is_localhost(domain: &str) -> bool {
+    domain.eq_ignore_ascii_case("localhost") || domain.to_ascii_lowercase().ends_with(".localhost")
+}
+
+fn uses_search_operators(input: &str) -> bool {
+    input.split_whitespace().any(|token| {
+        if token.starts_with(['"', '-']) {
+            return true;
+        }
+
+        let token = token.trim_start_matches('+');
+        SEARCH_OPERATORS.iter().any(|operator| {
+            token
+                .get(..operator.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(operator))
+        })
+    })
+}
+
+fn should_use_http_by_default(input: &str) -> bool {
+    let Ok(url) = url::Url::parse(&format!("http://{input}")) else {
+        return false;
+    };
+
+    match url.host() {
+        Some(url::Host::Domain(domain)) => {
+            is_localhost(domain) || (url.port().is_some() && !domain.contains('.'))
+        }
+        Some(url::Host::Ipv4(address)) => is_local_address(IpAddr::V4(address)),
+        Some(url::Host::Ipv6(address)) => is_local_address(IpAddr::V6(address)),
+        None => false,
+    }
+}
+
+/// Loopback, private and link-local addresses rarely serve TLS, so they default to http.
+fn is_local_address(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => is_local_ipv4(address),
+        IpAddr::V6(address) => {
+            let first_segment = address.segments()[0];
+            let is_unique_local = first_segment & 0xfe00 == 0xfc00;
+            let is_link_local = first_segment & 0xffc0 == 0xfe80;
+            address.is_loopback()
+                || address.is_unspecified()
+                || is_unique_local
+                || is_link_local
+                || address.to_ipv4_mapped().is_some_and(is_local_ipv4)
+        }
+    }
+}
+
+fn is_local_ipv4(address: Ipv4Addr) -> bool {
+    address.is_loopback()
+        || address.is_private()
+        || address.is_link_local()
+        || address.is_unspecified()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DEFAULT_SEARCH_URL, looks_like_url, url_for_text};
+
+    fn text_to_url(text: &str) -> String {
+        url_for_text(text, DEFAULT_SEARCH_URL)
+    }
+
+    #[test]
+    fn localhost_inputs_are_treated_as_urls() {
+        assert!(looks_like_url("localhost"));
+        assert!(looks_like_url("localhost:5173"));
+        assert_eq!(text_to_url("localhost"), "http://localhost");
+        assert_eq!(text_to_url("localhost:5173"), "http://localhost:5173");
+    }
+
+    #[test]
+    fn local_hosts_with_ports_default_to_http() {
+        assert_eq!(
+            text_to_url("localhost:3000/api"),
+            "http://localhost:3000/api"
+        );
+        assert_eq!(
+            text_to_url("app.localhost:8080"),
+            "http://app.localhost:8080"
+        );
+        assert_eq!(text_to_url("devbox:8000"), "http://devbox:8000");
+        assert_eq!(text_to_url("example.com:8443"), "https://example.com:8443");
+    }
+
+    #[test]
+    fn ipv4_addresses_are_treated_as_urls() {
+        assert_eq!(text_to_url("127.0.0.1:8080"), "http://127.0.0.1:8080");
+        assert_eq!(text_to_url("192.168.1.20"), "http://192.168.1.20");
+        assert_eq!(
+            text_to_url("10.0.0.5:3000/health"),
+            "http://10.0.0.5:3000/health"
+        );
+        assert_eq!(text_to_url("169.254.1.1"), "http://169.254.1.1");
+        assert_eq!(text_to_url("8.8.8.8"), "https://8.8.8.8");
+    }
+
+    #[test]
+    fn ipv6_addresses_are_treated_as_urls() {
+        assert_eq!(text_to_url("::1"), "http://[::1]");
+        assert_eq!(text_to_url("[::1]:3000"), "http://[::1]:3000");
+        assert_eq!(text_to_url("fd00::1"), "http://[fd00::1]");
+        assert_eq!(text_to_url("fe80::1"), "http://[fe80::1]");
+        assert_eq!(
+            text_to_url("2606:4700:4700::1111"),
+            "https://[2606:4700:4700::1111]"
+        );
+    }
+
+    #[test]
+    fn bare_numbers_are_searched() {
+        assert!(!looks_like_url("2024"));
+        assert_eq!(text_to_url("2024"), "https://www.google.com/search?q=2024");
+    }
+
+    #[test]
+    fn regular_domains_default_to_https() {
+        assert!(looks_like_url("example.com"));
+        assert_eq!(text_to_url("example.com"), "https://example.com");
+    }
+
+    #[test]
+    fn plain_queries_still_search() {
+        assert!(!looks_like_url("rust async await"));
+        assert_eq!(
+            text_to_url("rust async await"),
+            "https://www.google.com/search?q=rust+async+await"
+        );
+        assert_eq!(
+            text_to_url("my file.txt"),
+            "https://www.google.com/search?q=my+file.txt"
+        );
+        assert_eq!(
+            text_to_url("define recursion"),
+            "https://www.google.com/search?q=define+recursion"
+        );
+    }
+
+    #[test]
+    fn test_search_operators_are_searched_verbatim() {
+        assert_eq!(
+            text_to_url("site:example.com"),
+            "https://www.google.com/search?q=site%3Aexample.com"
+        );
+        assert_eq!(
+            text_to_url("SITE:docs.rs tokio"),
+            "https://www.google.com/search?q=SITE%3Adocs.rs+tokio"
+        );
+        assert_eq!(
+            text_to_url("filetype:pdf"),
+            "https://www.google.com/search?q=filetype%3Apdf"
+        );
+        assert_eq!(
+            text_to_url("before:2024"),
+            "https://www.google.com/search?q=before%3A2024"
+        );
+        assert_eq!(
+            text_to_url("\"example.com\""),
+            "https://www.google.com/search?q=%22example.com%22"
+        );
+        assert_eq!(
+            text_to_url("-pinterest.com"),
+            "https://www.google.com/search?q=-pinterest.com"
+        );
+        assert_eq!(
+            text_to_url("site:https://example.com"),
+            "https://www.google.com/search?q=site%3Ahttps%3A%2F%2Fexample.com"
+        );
+    }
+
+    #[test]
+    fn test_operator_like_text_inside_urls_is_not_a_search() {
+        assert_eq!(
+            text_to_url("example.com/site:foo"),
+            "https://example.com/site:foo"
+        );
+    }
+
+    #[test]
+    fn searches_use_the_configured_search_engine() {
+        assert_eq!(
+            url_for_text("rust async", "https://duckduckgo.com/?q={query}&ia=web"),
+            "https://duckduckgo.com/?q=rust+async&ia=web"
+        );
+        assert_eq!(
+            url_for_text("example.com", "https://duckduckgo.com/?q={query}"),
+            "https://example.com"
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! User-agent Overrides
+//!
+//! Lets developers load pages with another browser's user-agent string,
+//! either for one tab or for every tab on an origin. Site-wide overrides are
+//! saved to the database; without one, tabs use CEF's platform user agent.
+
+use crate::session;
+use crate::site_permissions::origin_for_url;
+use cef::{ImplBrowserHost, ImplDictionaryValue};
+use gpui::App;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, LazyLock};
+use util::ResultExt as _;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UserAgentPreset {
+    Desktop,
+    Iphone,
+    Android,
+}
+
+impl UserAgentPreset {
+    pub const ALL: [Self; 3] = [Self::Desktop, Self::Iphone, Self::Android];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Desktop => "Desktop",
+            Self::Iphone => "iPhone",
+            Self::Android => "Android",
+        }
+    }
+
+    pub fn user_agent(self) -> &'static str {
+        match self {
+            Self::Desktop => {
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/145.0.0.0 Safari/537.36"
+            }
+            Self::Iphone => {
+                "Mozilla/5.0 (iPhone; CPU iPhone OS 18_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.0 Mobile/15E148 Safari/604.1"
+            }
+            Self::Android => {
+                "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/145.0.0.0 Mobile Safari/537.36"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserAgentOverrides {
+    origins: BTreeMap<String, String>,
+}
+
+impl UserAgentOverrides {
+    pub fn for_origin(&self, origin: &str) -> Option<&str> {
+        self.origins.get(origin).map(String::as_str)
+    }
+
+    /// An empty or missing user agent removes the origin's override.
+    pub fn set_for_origin(&mut self, origin: &str, user_agent: Option<String>) {
+        match user_agent.filter(|user_agent| !user_agent.trim().is_empty()) {
+            Some(user_agent) => {
+                self.origins.insert(origin.to_string(), user_agent);
+            }
+            None => {
+                self.origins.remove(origin);
+            }
+        }
+    }
+}
+
+static SAVED_USER_AGENT_OVERRIDES: LazyLock<Arc<Mutex<UserAgentOverrides>>> =
+    LazyLock::new(Arc::default);
+
+/// The site-wide overrides a browser window's tabs follow. Incognito windows
+/// get their own in-memory overrides, so they neither inherit saved overrides
+/// nor persist their own.
+#[derive(Clone)]
+pub struct SiteUserAgents {
+    overrides: Arc<Mutex<UserAgentOverrides>>,
+    is_saved: bool,
+}
+
+impl SiteUserAgents {
+    pub fn saved() -> Self {
+        Self {
+            overrides: SAVED_USER_AGENT_OVERRIDES.clone(),
+            is_saved: true,
+        }
+    }
+
+    pub fn in_memory() -> Self {
+        Self {
+            overrides: Arc::default(),
+            is_saved: false,
+        }
+    }
+
+    pub fn update<R>(&self, f: impl FnOnce(&mut UserAgentOverrides) -> R) -> R {
+        f(&mut self.overrides.lock())
+    }
+
+    /// The overrides as JSON to persist, or `None` for in-memory overrides.
+    pub(crate) fn serialize(&self) -> Option<String> {
+        if !self.is_saved {
+            return None;
+        }
+        serde_json::to_string(&*self.overrides.lock()).log_err()
+    }
+}
+
+/// Reads the saved overrides off the main thread at startup. Overrides set
+/// before they finish loading take precedence.
+pub(crate) fn load_saved_user_agent_overrides(cx: &mut App) {
+    cx.background_spawn(async move {
+        if let Some(saved) = session::restore_user_agent_overrides() {
+            let mut overrides = SAVED_USER_AGENT_OVERRIDES.lock();
+            for (origin, user_agent) in saved.origins {
+                overrides.origins.entry(origin).or_insert(user_agent);
+            }
+        }
+    })
+    .detach();
+}
+
+#[derive(Default)]
+struct TabUserAgentState {
+    tab_override: Option<String>,
+    applied: Option<String>,
+}
+
+/// A tab's user agent, shared with its request handler so that a navigation
+/// is sent with the user agent for its destination rather than the one for
+/// the page it leaves.
+#[derive(Clone)]
+pub(crate) struct TabUserAgent {
+    site_user_agents: SiteUserAgents,
+    state: Arc<Mutex<TabUserAgentState>>,
+}
+
+impl TabUserAgent {
+    pub fn new(site_user_agents: SiteUserAgents) -> Self {
+        Self {
+            site_user_agents,
+            state: Arc::default(),
+        }
+    }
+
+    pub fn site_user_agents(&self) -> &SiteUserAgents {
+        &self.site_user_agents
+    }
+
+    /// The tab's own override, then the override for the origin of `url`, or
+    /// `None` for the platform default.
+    pub fn for_url(&self, url: &str) -> Option<String> {
+        let tab_override = self.state.lock().tab_override.clone();
+        tab_override.or_else(|| self.site_override(url))
+    }
+
+    pub fn site_override(&self, url: &str) -> Option<String> {
+        let origin = origin_for_url(url)?;
+        self.site_user_agents
+            .update(|overrides| overrides.for_origin(&origin).map(str::to_string))
+    }
+
+    pub fn set_tab_override(&self, user_agent: Option<String>) {
+        self.state.lock().tab_override =
+            user_agent.filter(|user_agent| !user_agent.trim().is_empty());
+    }
+
+    pub fn forget_applied(&self) {
+        self.state.lock().applied = None;
+    }
+
+    /// Sets the user agent for `url` on the browser unless it is already in
+    /// effect, returning whether it changed.
+    pub fn apply(&self, host: &cef::BrowserHost, url: &str) -> bool {
+        let user_agent = self.for_url(url);
+        let mut state = self.state.lock();
+        if user_agent == state.applied {
+            return false;
+        }
+        let Some(mut params) = cef::dictionary_value_create() else {
+            return false;
+        };
+        // An empty string clears the override and restores the platform user agent.
+        params.set_string(
+            Some(&cef::CefString::from("userAgent")),
+            Some(&cef::CefString::from(
+                user_agent.as_deref().unwrap_or_default(),
+            )),
+        );
+        host.execute_dev_tools_method(
+            0,
+            Some(&cef::CefString::from("Emulation.setUserAgentOverride")),
+            Some(&mut params),
+        );
+        state.applied = user_agent;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_user_agents_remove_the_override() {
+        let mut overrides = UserAgentOverrides::default();
+        let origin = "https://m.example.com";
+        overrides.set_for_origin(origin, Some(UserAgentPreset::Iphone.user_agent().into()));
+        assert_eq!(
+            overrides.for_origin(origin),
+            Some(UserAgentPreset::Iphone.user_agent())
+        );
+
+        overrides.set_for_origin(origin, Some("  ".into()));
+        assert_eq!(overrides, UserAgentOverrides::default());
+    }
+
+    #[test]
+    fn tab_overrides_take_precedence_over_in_memory_site_overrides() {
+        let site_user_agents = SiteUserAgents::in_memory();
+        site_user_agents.update(|overrides| {
+            overrides.set_for_origin(
+                "https://m.example.com",
+                Some(UserAgentPreset::Iphone.user_agent().into()),
+            )
+        });
+        let tab_user_agent = TabUserAgent::new(site_user_agents.clone());
+        assert_eq!(
+            tab_user_agent
+                .for_url("https://m.example.com/inbox")
+                .as_deref(),
+            Some(UserAgentPreset::Iphone.user_agent())
+        );
+        assert_eq!(tab_user_agent.for_url("https://example.com/"), None);
+
+        tab_user_agent.set_tab_override(Some(UserAgentPreset::Android.user_agent().into()));
+        assert_eq!(
+            tab_user_agent
+                .for_url("https://m.example.com/inbox")
+                .as_deref(),
+            Some(UserAgentPreset::Android.user_agent())
+        );
+        assert_eq!(site_user_agents.serialize(), None);
+        assert_eq!(
+            SiteUserAgents::in_memory().update(|overrides| {
+                overrides
+                    .for_origin("https://m.example.com")
+                    .map(str::to_string)
+            }),
+            None
+        );
+    }
+}
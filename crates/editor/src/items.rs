@@ -801,68 +801,60 @@ impl SerializableItem for Editor {
                 mtime,
                 ..
             } => {
-                let opened_buffer = project.update(cx, |project, cx| {
-                    let (worktree, path) = project.find_worktree(&abs_path, cx)?;
-                    let project_path = ProjectPath {
-                        worktree_id: worktree.read(cx).id(),
-                        path: path,
-                    };
-                    Some(project.open_path(project_path, cx))
-                });
+                // Opening a missing path yields an empty new buffer, so files deleted since the
+                // last session would come back as blank tabs unless we drop them here. Unsaved
+                // contents are still restored, since they are the only remaining copy.
+                let skip_if_missing = contents.is_none() && project.read(cx).is_local();
+                let fs = project.read(cx).fs().clone();
+                window.spawn(cx, async move |cx| {
+                    if skip_if_missing && !fs.is_file(&abs_path).await {
+                        return Err(anyhow!(
+                            "Not restoring editor for {abs_path:?}: file no longer exists"
+                        ));
+                    }
 
-                match opened_buffer {
-                    Some(opened_buffer) => window.spawn(cx, async move |cx| {
-                        let (_, buffer) = opened_buffer
-                            .await
-                            .context("Failed to open path in project")?;
+                    let project_path = project.update(cx, |project, cx| {
+                        let (worktree, path) = project.find_worktree(&abs_path, cx)?;
+                        Some(ProjectPath {
+                            worktree_id: worktree.read(cx).id(),
+                            path,
+                        })
+                    });
 
-                        if let Some(contents) = contents {
-                            buffer.update(cx, |buffer, cx| {
-                                restore_serialized_buffer_contents(buffer, contents, mtime, cx);
-                            });
+                    let buffer = match project_path {
+                        Some(project_path) => {
+                            let (_, buffer) = project
+                                .update(cx, |project, cx| project.open_path(project_path, cx))
+                                .await
+                                .context("Failed to open path in project")?;
+                            buffer
                         }
-
-                        cx.update(|window, cx| {
-                            cx.new(|cx| {
-                                let mut editor =
-                                    Editor::for_buffer(buffer, Some(project), window, cx);
-
-                                editor.read_metadata_from_db(item_id, workspace_id, window, cx);
-                                editor
-                            })
-                        })
-                    }),
-                    None => {
                         // File is not in any worktree (e.g., opened as a standalone file).
                         // Open the buffer directly via the project rather than through
                         // workspace.open_abs_path(), which has the side effect of adding
                         // the item to a pane. The caller (deserialize_to) will add the
                         // returned item to the correct pane.
-                        window.spawn(cx, async move |cx| {
-                            let buffer = project
-                                .update(cx, |project, cx| project.open_local_buffer(&abs_path, cx))
-                                .await
-                                .with_context(|| {
-                                    format!("Failed to open buffer for {abs_path:?}")
-                                })?;
-
-                            if let Some(contents) = contents {
-                                buffer.update(cx, |buffer, cx| {
-                                    restore_serialized_buffer_contents(buffer, contents, mtime, cx);
-                                });
-                            }
-
-                            cx.update(|window, cx| {
-                                cx.new(|cx| {
-                                    let mut editor =
-                                        Editor::for_buffer(buffer, Some(project), window, cx);
-                                    editor.read_metadata_from_db(item_id, workspace_id, window, cx);
-                                    editor
-                                })
-                            })
-                        })
+                        None => project
+                            .update(cx, |project, cx| project.open_local_buffer(&abs_path, cx))
+                            .await
+                            .with_context(|| format!("Failed to open buffer for {abs_path:?}"))?,
+                    };
+
+                    if let Some(contents) = contents {
+                        buffer.update(cx, |buffer, cx| {
+                            restore_serialized_buffer_contents(buffer, contents, mtime, cx);
+                        });
                     }
-                }
+
+                    cx.update(|window, cx| {
+                        cx.new(|cx| {
+                            let mut editor = Editor::for_buffer(buffer, Some(project), window, cx);
+
+                            editor.read_metadata_from_db(item_id, workspace_id, window, cx);
+                            editor
+                        })
+                    })
+                })
             }
             SerializedEditor {
                 abs_path: None,
@@ -1922,6 +1914,73 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    async fn test_deserialize_skips_deleted_file(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_file(path!("/root/kept.rs"), Default::default())
+            .await;
+
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        let db = cx.update(|_, cx| workspace::WorkspaceDb::global(cx));
+        let editor_db = cx.update(|_, cx| EditorDb::global(cx));
+        let workspace_id = db.next_id().await.unwrap();
+
+        let deleted_item_id = 12000 as ItemId;
+        editor_db
+            .save_serialized_editor(
+                deleted_item_id,
+                workspace_id,
+                SerializedEditor {
+                    abs_path: Some(PathBuf::from(path!("/root/deleted.rs"))),
+                    contents: None,
+                    language: None,
+                    mtime: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let unsaved_item_id = 12001 as ItemId;
+        editor_db
+            .save_serialized_editor(
+                unsaved_item_id,
+                workspace_id,
+                SerializedEditor {
+                    abs_path: Some(PathBuf::from(path!("/root/deleted.rs"))),
+                    contents: Some("unsaved".to_string()),
+                    language: None,
+                    mtime: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let deleted = workspace
+            .update_in(cx, |workspace, window, cx| {
+                Editor::deserialize(
+                    project.clone(),
+                    workspace.weak_handle(),
+                    workspace_id,
+                    deleted_item_id,
+                    window,
+                    cx,
+                )
+            })
+            .await;
+        assert!(deleted.is_err());
+
+        let unsaved =
+            deserialize_editor(unsaved_item_id, workspace_id, workspace, project, cx).await;
+        unsaved.update(cx, |editor, cx| {
+            assert_eq!(editor.text(cx), "unsaved");
+        });
+    }
+
     // Regression test for https://github.com/zed-industries/zed/issues/35947
     // Verifies that deserializing a non-worktree editor does not add the item
     // to any pane as a side effect.
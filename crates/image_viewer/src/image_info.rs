@@ -38,6 +38,28 @@ impl ImageInfo {
     }
 }
 
+/// The name shown for `format` in image metadata summaries.
+pub fn image_format_label(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Png => "PNG",
+        ImageFormat::Jpeg => "JPEG",
+        ImageFormat::Gif => "GIF",
+        ImageFormat::WebP => "WebP",
+        ImageFormat::Tiff => "TIFF",
+        ImageFormat::Bmp => "BMP",
+        ImageFormat::Ico => "ICO",
+        ImageFormat::Avif => "AVIF",
+        ImageFormat::Pnm => "PNM",
+        ImageFormat::Tga => "TGA",
+        ImageFormat::Dds => "DDS",
+        ImageFormat::Hdr => "HDR",
+        ImageFormat::OpenExr => "OpenEXR",
+        ImageFormat::Farbfeld => "Farbfeld",
+        ImageFormat::Qoi => "QOI",
+        _ => "Unknown",
+    }
+}
+
 fn format_image_size(size: u64, image_unit_type: ImageFileSizeUnit) -> String {
     let use_decimal = matches!(image_unit_type, ImageFileSizeUnit::Decimal);
     format_file_size(size, use_decimal)
@@ -62,21 +84,13 @@ impl Render for ImageInfo {
                 colors.bits_per_pixel()
             ));
         }
+        if settings.show_color_details
+            && let Some(color_space) = metadata.color_space
+        {
+            components.push(color_space.label().to_string());
+        }
 
-        components.push(
-            match metadata.format {
-                ImageFormat::Png => "PNG",
-                ImageFormat::Jpeg => "JPEG",
-                ImageFormat::Gif => "GIF",
-                ImageFormat::WebP => "WebP",
-                ImageFormat::Tiff => "TIFF",
-                ImageFormat::Bmp => "BMP",
-                ImageFormat::Ico => "ICO",
-                ImageFormat::Avif => "Avif",
-                _ => "Unknown",
-            }
-            .to_string(),
-        );
+        components.push(image_format_label(metadata.format).to_string());
 
         div().child(Label::new(components.join(" • ")).size(LabelSize::Small))
     }
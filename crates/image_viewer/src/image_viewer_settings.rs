@@ -8,12 +8,19 @@ pub struct ImageViewerSettings {
     ///
     /// Default: "binary"
     pub unit: ImageFileSizeUnit,
+    /// Whether image metadata includes the color space and bit depth, when
+    /// the image provides them.
+    ///
+    /// Default: false
+    pub show_color_details: bool,
 }
 
 impl Settings for ImageViewerSettings {
     fn from_settings(content: &settings::SettingsContent) -> Self {
+        let image_viewer = content.image_viewer.clone().unwrap();
         Self {
-            unit: content.image_viewer.clone().unwrap().unit.unwrap(),
+            unit: image_viewer.unit.unwrap(),
+            show_color_details: image_viewer.show_color_details.unwrap(),
         }
     }
 }
@@ -9,7 +9,7 @@ use gpui::{
     App, AsyncApp, Context, Entity, EventEmitter, Img, Subscription, Task, WeakEntity, prelude::*,
 };
 pub use image::ImageFormat;
-use image::{ExtendedColorType, GenericImageView, ImageReader};
+use image::{DynamicImage, ExtendedColorType, GenericImageView, ImageDecoder as _, ImageReader};
 use language::{DiskState, File};
 use rpc::{AnyProtoClient, ErrorExt as _, TypedEnvelope, proto};
 use std::num::NonZeroU64;
@@ -61,6 +61,7 @@ pub struct ImageMetadata {
     pub height: u32,
     pub file_size: u64,
     pub colors: Option<ImageColorInfo>,
+    pub color_space: Option<ImageColorSpace>,
     pub format: ImageFormat,
 }
 
@@ -99,6 +100,94 @@ impl ImageColorInfo {
     }
 }
 
+/// A well-known color space, recognized from the name of an image's embedded
+/// ICC profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageColorSpace {
+    Srgb,
+    DisplayP3,
+    AdobeRgb,
+    ProPhotoRgb,
+    Rec2020,
+}
+
+impl ImageColorSpace {
+    pub fn from_icc_profile(profile: &[u8]) -> Option<Self> {
+        let description = icc_profile_description(profile)?.to_lowercase();
+        if description.contains("display p3") {
+            Some(Self::DisplayP3)
+        } else if description.contains("srgb") {
+            Some(Self::Srgb)
+        } else if description.contains("adobe rgb") {
+            Some(Self::AdobeRgb)
+        } else if description.contains("prophoto") {
+            Some(Self::ProPhotoRgb)
+        } else if description.contains("2020") {
+            Some(Self::Rec2020)
+        } else {
+            None
+        }
+    }
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Srgb => "sRGB",
+            Self::DisplayP3 => "Display P3",
+            Self::AdobeRgb => "Adobe RGB",
+            Self::ProPhotoRgb => "ProPhoto RGB",
+            Self::Rec2020 => "Rec. 2020",
+        }
+    }
+}
+
+/// Reads the profile description (`desc`) tag, which is ASCII in version 2
+/// profiles and UTF-16 in version 4 ones.
+fn icc_profile_description(profile: &[u8]) -> Option<String> {
+    const HEADER_LEN: usize = 128;
+    const TAG_ENTRY_LEN: usize = 12;
+
+    let tag_count = read_be_u32(profile, HEADER_LEN)? as usize;
+    (0..tag_count).find_map(|index| {
+        let entry = HEADER_LEN + 4 + index * TAG_ENTRY_LEN;
+        if profile.get(entry..entry + 4)? != b"desc" {
+            return None;
+        }
+        let offset = read_be_u32(profile, entry + 4)? as usize;
+        let size = read_be_u32(profile, entry + 8)? as usize;
+        let tag = profile.get(offset..offset.checked_add(size)?)?;
+        match tag.get(0..4)? {
+            b"desc" => {
+                let length = read_be_u32(tag, 8)? as usize;
+                let text = tag.get(12..12usize.checked_add(length)?)?;
+                Some(
+                    String::from_utf8_lossy(text)
+                        .trim_end_matches('\0')
+                        .to_string(),
+                )
+            }
+            b"mluc" => {
+                if read_be_u32(tag, 8)? == 0 {
+                    return None;
+                }
+                let length = read_be_u32(tag, 20)? as usize;
+                let string_offset = read_be_u32(tag, 24)? as usize;
+                let text = tag.get(string_offset..string_offset.checked_add(length)?)?;
+                let code_units: Vec<u16> = text
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                Some(String::from_utf16_lossy(&code_units))
+            }
+            _ => None,
+        }
+    })
+}
+
+fn read_be_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
 pub struct ImageItem {
     pub id: ImageId,
     pub file: Arc<worktree::File>,
@@ -113,7 +202,13 @@ impl ImageItem {
 
         let mut image_reader = ImageReader::new(std::io::Cursor::new(image_bytes));
         image_reader.set_format(image_format);
-        let image = image_reader.decode()?;
+        let mut decoder = image_reader.into_decoder()?;
+        let color_space = decoder
+            .icc_profile()
+            .log_err()
+            .flatten()
+            .and_then(|profile| ImageColorSpace::from_icc_profile(&profile));
+        let image = DynamicImage::from_decoder(decoder)?;
 
         let (width, height) = image.dimensions();
 
@@ -123,6 +218,7 @@ impl ImageItem {
             file_size: image_bytes.len() as u64,
             format: image_format,
             colors: ImageColorInfo::from_color_type(image.color()),
+            color_space,
         })
     }
 
@@ -907,3 +1003,60 @@ fn create_gpui_image(content: Vec<u8>) -> anyhow::Result<Arc<gpui::Image>> {
         content,
     )))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn icc_profile(description_tag: &[u8]) -> Vec<u8> {
+        let mut profile = vec![0; 128];
+        profile.extend_from_slice(&1u32.to_be_bytes());
+        profile.extend_from_slice(b"desc");
+        profile.extend_from_slice(&144u32.to_be_bytes());
+        profile.extend_from_slice(&(description_tag.len() as u32).to_be_bytes());
+        profile.extend_from_slice(description_tag);
+        profile
+    }
+
+    #[test]
+    fn test_color_space_from_v2_profile() {
+        let name = b"sRGB IEC61966-2.1\0";
+        let mut tag = b"desc\0\0\0\0".to_vec();
+        tag.extend_from_slice(&(name.len() as u32).to_be_bytes());
+        tag.extend_from_slice(name);
+
+        assert_eq!(
+            ImageColorSpace::from_icc_profile(&icc_profile(&tag)),
+            Some(ImageColorSpace::Srgb)
+        );
+    }
+
+    #[test]
+    fn test_color_space_from_v4_profile() {
+        let name: Vec<u8> = "Display P3"
+            .encode_utf16()
+            .flat_map(|code_unit| code_unit.to_be_bytes())
+            .collect();
+        let mut tag = b"mluc\0\0\0\0".to_vec();
+        tag.extend_from_slice(&1u32.to_be_bytes());
+        tag.extend_from_slice(&12u32.to_be_bytes());
+        tag.extend_from_slice(b"enUS");
+        tag.extend_from_slice(&(name.len() as u32).to_be_bytes());
+        tag.extend_from_slice(&28u32.to_be_bytes());
+        tag.extend_from_slice(&name);
+
+        assert_eq!(
+            ImageColorSpace::from_icc_profile(&icc_profile(&tag)),
+            Some(ImageColorSpace::DisplayP3)
+        );
+    }
+
+    #[test]
+    fn test_unrecognized_or_malformed_profiles_have_no_color_space() {
+        assert_eq!(ImageColorSpace::from_icc_profile(&[]), None);
+        assert_eq!(
+            ImageColorSpace::from_icc_profile(&icc_profile(b"desc")),
+            None
+        );
+    }
+}
@@ -240,15 +240,27 @@ impl TrustedWorktreesStore {
         worktree_store: &Entity<WorktreeStore>,
         cx: &App,
     ) -> bool {
+        self.restricted_worktree_count(worktree_store, cx) > 0
+    }
+
+    /// How many of a worktree store's open worktrees are restricted.
+    pub fn restricted_worktree_count(
+        &self,
+        worktree_store: &Entity<WorktreeStore>,
+        cx: &App,
+    ) -> usize {
         self.restricted
             .get(&worktree_store.downgrade())
-            .is_some_and(|restricted_worktrees| {
-                restricted_worktrees.iter().any(|restricted_worktree| {
-                    worktree_store
-                        .read(cx)
-                        .worktree_for_id(*restricted_worktree, cx)
-                        .is_some()
-                })
+            .map_or(0, |restricted_worktrees| {
+                restricted_worktrees
+                    .iter()
+                    .filter(|restricted_worktree| {
+                        worktree_store
+                            .read(cx)
+                            .worktree_for_id(**restricted_worktree, cx)
+                            .is_some()
+                    })
+                    .count()
             })
     }
 
@@ -1,16 +1,29 @@
-use anyhow::Result;
-use gpui::{App, Context, ScrollHandle, SharedString, Window};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Result, anyhow};
+use futures::{
+    StreamExt as _,
+    channel::mpsc::{UnboundedSender, unbounded},
+};
+use gpui::{App, Context, ScrollHandle, SharedString, Task, WeakEntity, Window};
 use project::DirectoryLister;
 use serde::Deserialize;
-use service_hub::{ServiceOperationRequest, ServiceProviderDescriptor, ServiceResourceRef};
+use service_hub::{
+    ServiceArtifactKind, ServiceArtifactRef, ServiceOperationRequest, ServiceProviderDescriptor,
+    ServiceResourceRef,
+};
 use ui::{
-    AnyElement, Button, ButtonSize, ButtonStyle, Color, Label, LabelSize, Severity, WithScrollbar,
-    h_flex, prelude::*, v_flex,
+    AnyElement, Button, ButtonSize, ButtonStyle, Color, Label, LabelSize, ProgressBar, Severity,
+    WithScrollbar, h_flex, prelude::*, v_flex,
 };
+use workspace::Workspace;
 
 use crate::{
     app_store_connect_auth::{AscAuthSummary, load_auth_status},
-    command_runner::{run_auth_action, run_json_operation},
+    command_runner::{run_auth_action, run_json_operation, run_streaming_json_operation},
     service_auth::{
         ServiceAuthFormState, ServiceAuthStatusSummary, ServiceAuthUiAction, ServiceAuthUiModel,
     },
@@ -23,6 +36,8 @@ use crate::{
 
 pub(crate) const APP_STORE_CONNECT_PROVIDER_ID: &str = "app-store-connect";
 
+const BUILD_PROCESSING_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
 pub(crate) fn build_app_store_connect_workspace_adapter(
     descriptor: ServiceProviderDescriptor,
     window: &mut Window,
@@ -66,6 +81,49 @@ struct AscBuildSummary {
     min_os_version: Option<String>,
 }
 
+impl AscBuildSummary {
+    fn is_processing(&self) -> bool {
+        self.processing_state.eq_ignore_ascii_case("PROCESSING")
+    }
+
+    fn status_label(&self) -> String {
+        processing_state_label(&self.processing_state)
+    }
+
+    fn status_color(&self) -> Color {
+        match self.processing_state.to_ascii_uppercase().as_str() {
+            "PROCESSING" => Color::Warning,
+            "VALID" => Color::Success,
+            "INVALID" | "FAILED" => Color::Error,
+            _ => Color::Muted,
+        }
+    }
+}
+
+fn processing_state_label(processing_state: &str) -> String {
+    match processing_state.to_ascii_uppercase().as_str() {
+        "PROCESSING" => "Processing".to_string(),
+        "VALID" => "Ready to Submit".to_string(),
+        "INVALID" => "Invalid".to_string(),
+        "FAILED" => "Failed".to_string(),
+        _ => processing_state.to_string(),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum AscUploadState {
+    Uploading {
+        file_name: String,
+        progress: Option<f32>,
+        message: Option<String>,
+    },
+    Succeeded {
+        file_name: String,
+        build_id: Option<String>,
+    },
+    Failed(String),
+}
+
 #[derive(Clone, Debug)]
 enum LoadState<T> {
     Loading,
@@ -80,6 +138,9 @@ pub(crate) struct AppStoreConnectWorkspaceProvider {
     apps_state: LoadState<Vec<AscAppSummary>>,
     builds_state: LoadState<Vec<AscBuildSummary>>,
     builds_scroll_handle: ScrollHandle,
+    builds_poll_task: Option<Task<()>>,
+    upload_state: Option<AscUploadState>,
+    upload_task: Option<Task<()>>,
 }
 
 impl AppStoreConnectWorkspaceProvider {
@@ -96,6 +157,9 @@ impl AppStoreConnectWorkspaceProvider {
             apps_state: LoadState::Loading,
             builds_state: LoadState::Ready(Vec::new()),
             builds_scroll_handle: ScrollHandle::new(),
+            builds_poll_task: None,
+            upload_state: None,
+            upload_task: None,
         }
     }
 
@@ -133,6 +197,7 @@ impl AppStoreConnectWorkspaceProvider {
         self.auth_state = LoadState::Loading;
         self.apps_state = LoadState::Loading;
         self.builds_state = LoadState::Ready(Vec::new());
+        self.builds_poll_task = None;
         cx.notify();
 
         cx.spawn_in(window, async move |this, cx| {
@@ -252,6 +317,18 @@ impl AppStoreConnectWorkspaceProvider {
         window: &mut Window,
         cx: &mut Context<ServicesPage>,
     ) -> AnyElement {
+        if let LoadState::Ready(summary) = &self.auth_state
+            && !summary.authenticated
+        {
+            return self
+                .render_empty_panel(
+                    "App Store Connect is not configured",
+                    "Authenticate with an App Store Connect API key (issuer ID, key ID, and private key) to load apps and build status.",
+                    cx,
+                )
+                .into_any_element();
+        }
+
         match state.navigation_id.as_str() {
             "builds" => self
                 .render_builds_content(state, window, cx)
@@ -277,15 +354,28 @@ impl AppStoreConnectWorkspaceProvider {
 
         state.selected_resource_id = Some(app.id.clone());
         self.builds_state = LoadState::Loading;
+        self.builds_poll_task = None;
         cx.notify();
 
+        self.fetch_builds(app, window, cx);
+    }
+
+    fn fetch_builds(
+        &mut self,
+        app: AscAppSummary,
+        window: &mut Window,
+        cx: &mut Context<ServicesPage>,
+    ) {
         cx.spawn_in(window, async move |this, cx| {
             let builds_result = cx
-                .background_spawn(async move { load_builds(&app).await })
+                .background_spawn({
+                    let app = app.clone();
+                    async move { load_builds(&app).await }
+                })
                 .await;
-            this.update_in(cx, |page, _window, cx| {
+            this.update_in(cx, |page, window, cx| {
                 with_app_store_connect_provider_mut(page, |pane, state| {
-                    if state.selected_resource_id.as_deref() != Some(app_id.as_str()) {
+                    if state.selected_resource_id.as_deref() != Some(app.id.as_str()) {
                         return;
                     }
 
@@ -293,6 +383,7 @@ impl AppStoreConnectWorkspaceProvider {
                         Ok(builds) => LoadState::Ready(builds),
                         Err(error) => LoadState::Error(error.to_string()),
                     };
+                    pane.schedule_builds_poll(app, window, cx);
                     cx.notify();
                 });
             })
@@ -301,6 +392,35 @@ impl AppStoreConnectWorkspaceProvider {
         .detach();
     }
 
+    /// Keeps refreshing the build list in the background while the latest
+    /// build is still being processed by App Store Connect.
+    fn schedule_builds_poll(
+        &mut self,
+        app: AscAppSummary,
+        window: &mut Window,
+        cx: &mut Context<ServicesPage>,
+    ) {
+        if !self
+            .selected_build()
+            .is_some_and(|build| build.is_processing())
+        {
+            self.builds_poll_task = None;
+            return;
+        }
+
+        self.builds_poll_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor()
+                .timer(BUILD_PROCESSING_POLL_INTERVAL)
+                .await;
+            this.update_in(cx, |page, window, cx| {
+                with_app_store_connect_provider_mut(page, |pane, _state| {
+                    pane.fetch_builds(app, window, cx);
+                });
+            })
+            .ok();
+        }));
+    }
+
     fn refresh_builds(
         &mut self,
         state: &mut ServicesPageState,
@@ -312,6 +432,142 @@ impl AppStoreConnectWorkspaceProvider {
         }
     }
 
+    fn pick_upload_artifact(
+        &mut self,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<ServicesPage>,
+    ) {
+        let prompt = workspace
+            .update(cx, |workspace, cx| {
+                workspace.prompt_for_open_path(
+                    gpui::PathPromptOptions {
+                        files: true,
+                        directories: false,
+                        multiple: false,
+                        prompt: Some(SharedString::from("Select an .ipa or .pkg to upload")),
+                    },
+                    DirectoryLister::Local(
+                        workspace.project().clone(),
+                        workspace.app_state().fs.clone(),
+                    ),
+                    window,
+                    cx,
+                )
+            })
+            .ok();
+
+        let Some(prompt) = prompt else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(path) = prompt
+                .await
+                .ok()
+                .flatten()
+                .and_then(|mut paths| paths.pop())
+            else {
+                return;
+            };
+
+            this.update_in(cx, |page, window, cx| {
+                with_app_store_connect_provider_mut(page, |pane, state| {
+                    pane.upload_build(state, path, window, cx);
+                });
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Uploads an `.ipa` or `.pkg` for the selected app. Progress reported by
+    /// the CLI is streamed into `upload_state`, and dropping `upload_task`
+    /// (on cancel or when the tab closes) kills the upload.
+    fn upload_build(
+        &mut self,
+        state: &ServicesPageState,
+        path: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<ServicesPage>,
+    ) {
+        let artifact = self
+            .selected_app(state)
+            .ok_or_else(|| anyhow!("Select an app before uploading a build."))
+            .and_then(|app| Ok((app, validate_upload_artifact(&path)?)));
+        let (app, artifact) = match artifact {
+            Ok(artifact) => artifact,
+            Err(error) => {
+                self.upload_task = None;
+                self.upload_state = Some(AscUploadState::Failed(error.to_string()));
+                cx.notify();
+                return;
+            }
+        };
+
+        let file_name = path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string_lossy().into_owned());
+        self.upload_state = Some(AscUploadState::Uploading {
+            file_name: file_name.clone(),
+            progress: None,
+            message: None,
+        });
+        cx.notify();
+
+        self.upload_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let (progress_tx, mut progress_rx) = unbounded();
+            let upload = cx.background_spawn({
+                let app = app.clone();
+                async move { upload_build_artifact(&app, artifact, progress_tx).await }
+            });
+
+            while let Some(line) = progress_rx.next().await {
+                this.update(cx, |page, cx| {
+                    with_app_store_connect_provider_mut(page, |pane, _state| {
+                        if let Some(AscUploadState::Uploading {
+                            progress, message, ..
+                        }) = &mut pane.upload_state
+                        {
+                            if let Some(percentage) = parse_upload_progress(&line) {
+                                *progress = Some(percentage);
+                            }
+                            *message = Some(line);
+                            cx.notify();
+                        }
+                    });
+                })
+                .ok();
+            }
+
+            let result = upload.await;
+            this.update_in(cx, |page, window, cx| {
+                with_app_store_connect_provider_mut(page, |pane, state| {
+                    pane.upload_state = Some(match result {
+                        Ok(build_id) => AscUploadState::Succeeded {
+                            file_name,
+                            build_id,
+                        },
+                        Err(error) => AscUploadState::Failed(error.to_string()),
+                    });
+                    pane.upload_task = None;
+                    if state.selected_resource_id.as_deref() == Some(app.id.as_str()) {
+                        pane.fetch_builds(app, window, cx);
+                    }
+                    cx.notify();
+                });
+            })
+            .ok();
+        }));
+    }
+
+    fn cancel_upload(&mut self, cx: &mut Context<ServicesPage>) {
+        self.upload_task = None;
+        self.upload_state = None;
+        cx.notify();
+    }
+
     fn show_authenticate_form(&mut self) {
         self.auth_form.show();
     }
@@ -612,7 +868,7 @@ impl AppStoreConnectWorkspaceProvider {
                                 .map(|build| format!("#{}", build.build_number))
                                 .unwrap_or_else(|| "None".to_string()),
                             selected_build
-                                .map(|build| build.processing_state.clone())
+                                .map(|build| build.status_label())
                                 .unwrap_or_else(|| "Select an app to inspect builds".to_string()),
                             cx,
                         ),
@@ -671,10 +927,9 @@ impl AppStoreConnectWorkspaceProvider {
                                             Label::new(format!("Build {}", build.build_number))
                                                 .size(LabelSize::Large),
                                         )
-                                        .child(self.render_detail_row(
-                                            "Status",
-                                            build.processing_state.clone(),
-                                        ))
+                                        .child(
+                                            self.render_detail_row("Status", build.status_label()),
+                                        )
                                         .child(self.render_detail_row(
                                             "Uploaded",
                                             build.uploaded_date.clone(),
@@ -708,6 +963,81 @@ impl AppStoreConnectWorkspaceProvider {
             })
     }
 
+    fn render_upload_status(&self, cx: &mut Context<ServicesPage>) -> Option<AnyElement> {
+        let upload_state = self.upload_state.as_ref()?;
+        let panel = v_flex()
+            .gap_2()
+            .p_4()
+            .rounded_lg()
+            .border_1()
+            .border_color(cx.theme().colors().border_variant)
+            .bg(cx.theme().colors().background);
+
+        let panel = match upload_state {
+            AscUploadState::Uploading {
+                file_name,
+                progress,
+                message,
+            } => panel
+                .child(
+                    h_flex()
+                        .justify_between()
+                        .items_center()
+                        .gap_3()
+                        .child(Label::new(format!("Uploading {file_name}…")))
+                        .child(
+                            Button::new("services-cancel-upload", "Cancel")
+                                .style(ButtonStyle::Subtle)
+                                .size(ButtonSize::Compact)
+                                .on_click(cx.listener(|page, _, _window, cx| {
+                                    with_app_store_connect_provider_mut(page, |pane, _state| {
+                                        pane.cancel_upload(cx);
+                                    });
+                                })),
+                        ),
+                )
+                .when_some(*progress, |panel, progress| {
+                    panel.child(ProgressBar::new(
+                        "services-upload-progress",
+                        progress,
+                        1.,
+                        cx,
+                    ))
+                })
+                .when_some(message.clone(), |panel, message| {
+                    panel.child(
+                        Label::new(message)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .single_line()
+                            .truncate(),
+                    )
+                }),
+            AscUploadState::Succeeded {
+                file_name,
+                build_id,
+            } => panel
+                .child(Label::new(format!("Uploaded {file_name}")).color(Color::Success))
+                .child(
+                    Label::new(match build_id {
+                        Some(build_id) => format!("Build ID {build_id}"),
+                        None => "App Store Connect is processing the new build.".to_string(),
+                    })
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+                ),
+            AscUploadState::Failed(error) => panel
+                .child(Label::new("Upload failed").color(Color::Error))
+                .child(
+                    Label::new(error.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+        };
+
+        Some(panel.into_any_element())
+    }
+
     fn render_builds_content(
         &self,
         state: &ServicesPageState,
@@ -715,6 +1045,7 @@ impl AppStoreConnectWorkspaceProvider {
         cx: &mut Context<ServicesPage>,
     ) -> impl IntoElement {
         let selected_app = self.selected_app(state);
+        let is_uploading = matches!(self.upload_state, Some(AscUploadState::Uploading { .. }));
         let content = match &self.builds_state {
             LoadState::Loading => Label::new("Loading builds…")
                 .color(Color::Muted)
@@ -744,11 +1075,14 @@ impl AppStoreConnectWorkspaceProvider {
                     let subtitle = match &build.expiration_date {
                         Some(expiration_date) => format!(
                             "{} · uploaded {} · expires {}",
-                            build.processing_state, build.uploaded_date, expiration_date
+                            build.status_label(),
+                            build.uploaded_date,
+                            expiration_date
                         ),
                         None => format!(
                             "{} · uploaded {}",
-                            build.processing_state, build.uploaded_date
+                            build.status_label(),
+                            build.uploaded_date
                         ),
                     };
 
@@ -775,9 +1109,9 @@ impl AppStoreConnectWorkspaceProvider {
                                         .size(LabelSize::Large),
                                 )
                                 .child(
-                                    Label::new(build.processing_state.clone())
+                                    Label::new(build.status_label())
                                         .size(LabelSize::Small)
-                                        .color(Color::Muted),
+                                        .color(build.status_color()),
                                 ),
                         )
                         .child(
@@ -825,17 +1159,42 @@ impl AppStoreConnectWorkspaceProvider {
                                 ),
                         )
                         .child(
-                            Button::new("services-refresh-builds", "Refresh Builds")
-                                .style(ButtonStyle::Outlined)
-                                .size(ButtonSize::Compact)
-                                .disabled(selected_app.is_none())
-                                .on_click(cx.listener(|page, _, window, cx| {
-                                    with_app_store_connect_provider_mut(page, |pane, state| {
-                                        pane.refresh_builds(state, window, cx);
-                                    });
-                                })),
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Button::new("services-upload-build", "Upload Build…")
+                                        .style(ButtonStyle::Outlined)
+                                        .size(ButtonSize::Compact)
+                                        .disabled(selected_app.is_none() || is_uploading)
+                                        .on_click(cx.listener(|page, _, window, cx| {
+                                            let workspace = page.workspace().clone();
+                                            with_app_store_connect_provider_mut(
+                                                page,
+                                                |pane, _state| {
+                                                    pane.pick_upload_artifact(
+                                                        workspace, window, cx,
+                                                    );
+                                                },
+                                            );
+                                        })),
+                                )
+                                .child(
+                                    Button::new("services-refresh-builds", "Refresh")
+                                        .style(ButtonStyle::Outlined)
+                                        .size(ButtonSize::Compact)
+                                        .disabled(selected_app.is_none())
+                                        .on_click(cx.listener(|page, _, window, cx| {
+                                            with_app_store_connect_provider_mut(
+                                                page,
+                                                |pane, state| {
+                                                    pane.refresh_builds(state, window, cx);
+                                                },
+                                            );
+                                        })),
+                                ),
                         ),
                 )
+                .children(self.render_upload_status(cx))
                 .child(
                     div()
                         .flex_1()
@@ -986,6 +1345,80 @@ struct AscBuildAttributes {
     min_os_version: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct AscUploadResponse {
+    #[serde(rename = "buildId")]
+    build_id: Option<String>,
+    data: Option<AscUploadRecord>,
+}
+
+#[derive(Deserialize)]
+struct AscUploadRecord {
+    id: String,
+}
+
+fn validate_upload_artifact(path: &Path) -> Result<ServiceArtifactRef> {
+    let kind = match path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("ipa") => ServiceArtifactKind::Ipa,
+        Some("pkg") => ServiceArtifactKind::Pkg,
+        _ => {
+            return Err(anyhow!(
+                "{} is not an .ipa or .pkg file.",
+                path.to_string_lossy()
+            ));
+        }
+    };
+    if !path.is_file() {
+        return Err(anyhow!("{} does not exist.", path.to_string_lossy()));
+    }
+
+    Ok(ServiceArtifactRef {
+        kind,
+        path: path.to_path_buf(),
+    })
+}
+
+/// Extracts a trailing percentage such as `Uploading… 42%` from a CLI progress line.
+fn parse_upload_progress(line: &str) -> Option<f32> {
+    let (before_percent, _) = line.rsplit_once('%')?;
+    let digits_start = before_percent
+        .rfind(|character: char| !character.is_ascii_digit() && character != '.')
+        .map_or(0, |index| index + 1);
+    let percentage: f32 = before_percent[digits_start..].parse().ok()?;
+    Some((percentage / 100.).clamp(0., 1.))
+}
+
+async fn upload_build_artifact(
+    app: &AscAppSummary,
+    artifact: ServiceArtifactRef,
+    progress_tx: UnboundedSender<String>,
+) -> Result<Option<String>> {
+    let response: AscUploadResponse = run_streaming_json_operation(
+        ServiceOperationRequest {
+            provider_id: APP_STORE_CONNECT_PROVIDER_ID.to_string(),
+            operation: "upload_build".to_string(),
+            resource: Some(ServiceResourceRef {
+                provider_id: APP_STORE_CONNECT_PROVIDER_ID.to_string(),
+                kind: "app".to_string(),
+                external_id: app.id.clone(),
+                label: app.name.clone(),
+            }),
+            artifact: Some(artifact),
+            input: Default::default(),
+        },
+        progress_tx,
+    )
+    .await?;
+
+    Ok(response
+        .build_id
+        .or_else(|| response.data.map(|record| record.id)))
+}
+
 async fn load_apps() -> Result<Vec<AscAppSummary>> {
     let response: AscAppsResponse = run_json_operation(ServiceOperationRequest {
         provider_id: APP_STORE_CONNECT_PROVIDER_ID.to_string(),
@@ -1050,3 +1483,32 @@ async fn load_builds(app: &AscAppSummary) -> Result<Vec<AscBuildSummary>> {
         })
         .collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{parse_upload_progress, processing_state_label, validate_upload_artifact};
+
+    #[test]
+    fn labels_build_processing_states() {
+        assert_eq!(processing_state_label("PROCESSING"), "Processing");
+        assert_eq!(processing_state_label("VALID"), "Ready to Submit");
+        assert_eq!(processing_state_label("INVALID"), "Invalid");
+        assert_eq!(processing_state_label("FAILED"), "Failed");
+        assert_eq!(processing_state_label("SOMETHING_NEW"), "SOMETHING_NEW");
+    }
+
+    #[test]
+    fn parses_upload_progress_percentages() {
+        assert_eq!(parse_upload_progress("Uploading build… 42%"), Some(0.42));
+        assert_eq!(parse_upload_progress("Uploading build… 100%"), Some(1.));
+        assert_eq!(parse_upload_progress("Verifying package"), None);
+    }
+
+    #[test]
+    fn rejects_unsupported_upload_artifacts() {
+        assert!(validate_upload_artifact(Path::new("/tmp/App.zip")).is_err());
+        assert!(validate_upload_artifact(Path::new("/nonexistent/App.ipa")).is_err());
+    }
+}
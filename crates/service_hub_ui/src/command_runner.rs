@@ -1,9 +1,13 @@
 use anyhow::{Context as _, Result, anyhow};
+use futures::{
+    AsyncBufReadExt as _, AsyncReadExt as _, StreamExt as _, channel::mpsc::UnboundedSender,
+    io::BufReader,
+};
 use serde::Deserialize;
 use service_hub::{
     ServiceAuthActionRequest, ServiceCommandPlan, ServiceHub, ServiceOperationRequest,
 };
-use util::command::new_command;
+use util::command::{Stdio, new_command};
 
 pub(crate) async fn run_auth_action(request: ServiceAuthActionRequest) -> Result<()> {
     let plan = ServiceHub::default()
@@ -24,6 +28,85 @@ where
         .with_context(|| "Failed to parse JSON output from App Store Connect CLI")
 }
 
+/// Runs an operation whose CLI reports progress on stderr, forwarding each
+/// stderr line to `progress_tx` as it arrives. The child process is killed if
+/// the returned future is dropped.
+pub(crate) async fn run_streaming_json_operation<T>(
+    request: ServiceOperationRequest,
+    progress_tx: UnboundedSender<String>,
+) -> Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let plan = ServiceHub::default()
+        .build_operation(&request)
+        .map_err(|error| anyhow!(error.to_string()))?;
+
+    let mut command = new_command(&plan.command);
+    command
+        .args(&plan.args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    if let Some(cwd) = &plan.cwd {
+        command.current_dir(cwd);
+    }
+    for (key, value) in &plan.env {
+        command.env(key, value);
+    }
+
+    let mut child = command
+        .spawn()
+        .with_context(|| format!("Failed to start `{}`", plan.command))?;
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("Failed to capture stdout for `{}`", plan.command))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| anyhow!("Failed to capture stderr for `{}`", plan.command))?;
+
+    let read_stdout = async {
+        let mut output = Vec::new();
+        stdout.read_to_end(&mut output).await?;
+        anyhow::Ok(output)
+    };
+    let read_stderr = async {
+        let mut lines = BufReader::new(stderr).lines();
+        let mut stderr_lines = Vec::new();
+        while let Some(line) = lines.next().await {
+            let line = line?;
+            progress_tx.unbounded_send(line.clone()).ok();
+            stderr_lines.push(line);
+        }
+        anyhow::Ok(stderr_lines)
+    };
+    let (stdout, stderr_lines) = futures::future::try_join(read_stdout, read_stderr).await?;
+    let status = child
+        .status()
+        .await
+        .with_context(|| format!("Failed while waiting for `{}`", plan.command))?;
+
+    if status.success() {
+        serde_json::from_slice(&stdout)
+            .with_context(|| "Failed to parse JSON output from App Store Connect CLI")
+    } else {
+        let stderr = stderr_lines.join("\n").trim().to_string();
+        let stdout = String::from_utf8_lossy(&stdout).trim().to_string();
+        Err(anyhow!(
+            "{}",
+            if !stderr.is_empty() {
+                stderr
+            } else if !stdout.is_empty() {
+                stdout
+            } else {
+                format!("`{}` exited unsuccessfully", plan.command)
+            }
+        ))
+    }
+}
+
 async fn run_command_plan(plan: ServiceCommandPlan) -> Result<Vec<u8>> {
     let mut command = new_command(&plan.command);
     command.args(&plan.args);
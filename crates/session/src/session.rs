@@ -6,14 +6,21 @@ pub struct Session {
     session_id: String,
     old_session_id: Option<String>,
     old_window_ids: Option<Vec<WindowId>>,
+    last_session_crashed: bool,
 }
 
 const SESSION_ID_KEY: &str = "session_id";
 const SESSION_WINDOW_STACK_KEY: &str = "session_window_stack";
+const CLEAN_SHUTDOWN_SESSION_ID_KEY: &str = "clean_shutdown_session_id";
 
 impl Session {
     pub async fn new(session_id: String, db: KeyValueStore) -> Self {
         let old_session_id = db.read_kvp(SESSION_ID_KEY).ok().flatten();
+        let clean_shutdown_session_id = db.read_kvp(CLEAN_SHUTDOWN_SESSION_ID_KEY).ok().flatten();
+        let last_session_crashed = last_session_crashed(
+            old_session_id.as_deref(),
+            clean_shutdown_session_id.as_deref(),
+        );
 
         db.write_kvp(SESSION_ID_KEY.to_string(), session_id.clone())
             .await
@@ -34,6 +41,7 @@ impl Session {
             session_id,
             old_session_id,
             old_window_ids,
+            last_session_crashed,
         }
     }
 
@@ -43,6 +51,7 @@ impl Session {
             session_id: uuid::Uuid::new_v4().to_string(),
             old_session_id: None,
             old_window_ids: None,
+            last_session_crashed: false,
         }
     }
 
@@ -52,6 +61,7 @@ impl Session {
             session_id: uuid::Uuid::new_v4().to_string(),
             old_session_id: Some(old_session_id),
             old_window_ids: None,
+            last_session_crashed: false,
         }
     }
 
@@ -105,12 +115,17 @@ impl AppSession {
     }
 
     fn app_will_quit(&mut self, cx: &mut Context<Self>) -> Task<()> {
-        if let Some(window_stack) = window_stack(cx) {
-            let db = KeyValueStore::global(cx);
-            cx.background_spawn(async move { store_window_stack(db, &window_stack).await })
-        } else {
-            Task::ready(())
-        }
+        let window_stack = window_stack(cx);
+        let session_id = self.session.session_id.clone();
+        let db = KeyValueStore::global(cx);
+        cx.background_spawn(async move {
+            if let Some(window_stack) = window_stack {
+                store_window_stack(db.clone(), &window_stack).await;
+            }
+            db.write_kvp(CLEAN_SHUTDOWN_SESSION_ID_KEY.to_string(), session_id)
+                .await
+                .log_err();
+        })
     }
 
     pub fn id(&self) -> &str {
@@ -129,6 +144,26 @@ impl AppSession {
     pub fn last_session_window_stack(&self) -> Option<Vec<WindowId>> {
         self.session.old_window_ids.clone()
     }
+
+    /// Whether the previous session ended without quitting, for example because
+    /// it crashed or was killed.
+    pub fn last_session_crashed(&self) -> bool {
+        self.session.last_session_crashed
+    }
+}
+
+/// A missing marker means the previous session predates clean-shutdown
+/// tracking, so it is treated as a clean shutdown rather than a crash.
+fn last_session_crashed(
+    old_session_id: Option<&str>,
+    clean_shutdown_session_id: Option<&str>,
+) -> bool {
+    match (old_session_id, clean_shutdown_session_id) {
+        (Some(old_session_id), Some(clean_shutdown_session_id)) => {
+            old_session_id != clean_shutdown_session_id
+        }
+        _ => false,
+    }
 }
 
 fn window_stack(cx: &App) -> Option<Vec<u64>> {
@@ -147,3 +182,17 @@ async fn store_window_stack(db: KeyValueStore, windows: &[u64]) {
             .log_err();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_session_crashed() {
+        assert!(!last_session_crashed(Some("previous"), Some("previous")));
+        assert!(last_session_crashed(Some("previous"), Some("older")));
+        assert!(!last_session_crashed(Some("previous"), None));
+        assert!(!last_session_crashed(None, Some("older")));
+        assert!(!last_session_crashed(None, None));
+    }
+}
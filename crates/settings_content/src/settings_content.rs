@@ -125,6 +125,9 @@ pub struct SettingsContent {
     /// Default: VSCode
     pub base_keymap: Option<BaseKeymapContent>,
 
+    /// The settings for the built-in browser.
+    pub browser: Option<BrowserSettingsContent>,
+
     /// Configuration for the collab panel visual settings.
     pub collaboration_panel: Option<PanelSettingsContent>,
 
@@ -771,6 +774,194 @@ pub enum LineIndicatorFormat {
     Long,
 }
 
+/// The settings for the built-in browser.
+#[with_fallible_options]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, Default, PartialEq)]
+pub struct BrowserSettingsContent {
+    /// How many days a cached favicon is reused before it is fetched again.
+    ///
+    /// Default: 7
+    pub favicon_cache_days: Option<u64>,
+    /// The language foreign pages are offered translation into, such as
+    /// `"en"` or `"pt-BR"`. Uses the system locale when unset.
+    ///
+    /// Default: null
+    pub translation_language: Option<String>,
+    /// The translation provider URL. `{language}` is replaced with the target
+    /// language and `{url}` with the page's URL.
+    ///
+    /// Default: "https://translate.google.com/translate?sl=auto&tl={language}&u={url}"
+    pub translation_url: Option<String>,
+    /// The page new tabs open to, such as a homepage or `"intranet.example.com"`.
+    /// Shows the built-in new tab page when unset or empty.
+    ///
+    /// Default: null
+    pub new_tab_url: Option<String>,
+    /// The search engine used for omnibox searches. `{query}` is replaced
+    /// with the search terms. URLs without `{query}` fall back to the default.
+    ///
+    /// Default: "https://www.google.com/search?q={query}"
+    pub search_url: Option<String>,
+    /// The zoom level pages open at, as a percentage between 25 and 500.
+    /// Values outside that range fall back to the default.
+    ///
+    /// Default: 100
+    pub default_zoom: Option<u32>,
+    /// Whether tabs left in the background for five minutes are frozen,
+    /// stopping their timers and animations until they are shown again. Tabs
+    /// playing sound or holding a WebRTC or WebSocket connection keep running.
+    ///
+    /// Default: true
+    pub throttle_background_tabs: Option<bool>,
+    /// How many URLs browsing history keeps before dropping the oldest, least
+    /// visited ones.
+    ///
+    /// Default: 50000
+    pub max_history_entries: Option<u64>,
+    /// Whether the omnibox suggests well-known domains, such as `github.com`
+    /// for `git`, when nothing in browsing history matches.
+    ///
+    /// Default: true
+    pub suggest_domains: Option<bool>,
+    /// Extra domains to suggest alongside the built-in list, such as
+    /// `"intranet.example.com"`.
+    ///
+    /// Default: []
+    pub suggested_domains: Option<Vec<String>>,
+    /// How many browsing history suggestions the omnibox shows. Values
+    /// outside 3 to 15 are clamped.
+    ///
+    /// Default: 4
+    pub max_suggestions: Option<u32>,
+    /// What omnibox history suggestions show beside their title: `"domain"`,
+    /// `"last_visited"` for how long ago the page was visited, such as
+    /// `"2h ago"`, or `"domain_and_last_visited"` for both.
+    ///
+    /// Default: "domain"
+    pub history_suggestion_detail: Option<BrowserHistorySuggestionDetail>,
+    /// URLs opened as pinned tabs every time Glass starts, whatever the
+    /// previous session held. A tab already showing one of them is reused.
+    ///
+    /// Default: []
+    pub startup_tabs: Option<Vec<String>>,
+    /// What the top tab strip does when tabs no longer fit: `"shrink"` narrows
+    /// tabs down to just their favicon, `"scroll"` keeps their width and
+    /// scrolls the strip horizontally.
+    ///
+    /// Default: "shrink"
+    pub tab_overflow: Option<BrowserTabOverflow>,
+    /// Whether scrolling the mouse wheel past the last tab in the top tab
+    /// strip wraps around to the first one, and vice versa.
+    ///
+    /// Default: false
+    pub tab_scroll_wraps: Option<bool>,
+    /// Where browser tabs are listed: `"top"` shows a tab strip above the
+    /// page, `"sidebar"` lists them vertically beside it. macOS always lists
+    /// tabs in the workspace sidebar.
+    ///
+    /// Default: "top"
+    pub tab_layout: Option<BrowserTabLayout>,
+    /// What closing the last browser tab does: `"new_tab"` replaces it with
+    /// a new tab page, `"close_window"` closes the window.
+    ///
+    /// Default: "new_tab"
+    pub close_last_tab: Option<BrowserCloseLastTab>,
+}
+
+#[with_fallible_options]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    Default,
+    PartialEq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserCloseLastTab {
+    /// Replaces the closed tab with a new tab page.
+    #[default]
+    NewTab,
+    /// Closes the window.
+    CloseWindow,
+}
+
+#[with_fallible_options]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    Default,
+    PartialEq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserTabLayout {
+    /// Shows tabs in a strip above the page.
+    #[default]
+    Top,
+    /// Lists tabs vertically in a sidebar beside the page.
+    Sidebar,
+}
+
+#[with_fallible_options]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    Default,
+    PartialEq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserTabOverflow {
+    /// Shrinks tabs to fit, down to just their favicon.
+    #[default]
+    Shrink,
+    /// Keeps tabs at a fixed width and scrolls the tab strip.
+    Scroll,
+}
+
+#[with_fallible_options]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    Default,
+    PartialEq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserHistorySuggestionDetail {
+    /// Shows the page's domain.
+    #[default]
+    Domain,
+    /// Shows how long ago the page was last visited.
+    LastVisited,
+    /// Shows the domain followed by how long ago the page was last visited.
+    DomainAndLastVisited,
+}
+
 /// The settings for the image viewer.
 #[with_fallible_options]
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, Default, PartialEq)]
@@ -779,6 +970,11 @@ pub struct ImageViewerSettingsContent {
     ///
     /// Default: "binary"
     pub unit: Option<ImageFileSizeUnit>,
+    /// Whether image metadata includes the color space and bit depth, when
+    /// the image provides them.
+    ///
+    /// Default: false
+    pub show_color_details: Option<bool>,
 }
 
 #[with_fallible_options]
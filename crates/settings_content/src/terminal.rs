@@ -124,6 +124,12 @@ pub struct TerminalSettingsContent {
     ///
     /// Default: true
     pub keep_selection_on_copy: Option<bool>,
+    /// Whether clicking an http(s) link in the terminal opens it in Glass's
+    /// browser mode instead of the system browser. Other schemes, such as
+    /// mailto: and file:, are always handed to the system.
+    ///
+    /// Default: true
+    pub open_links_in_glass: Option<bool>,
     /// Whether to show the terminal panel button in the dock header.
     ///
     /// Default: true
@@ -1,7 +1,8 @@
 use gpui::WindowButtonLayout;
 use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use settings_macros::{MergeFrom, with_fallible_options};
+use std::borrow::Cow;
 
 /// The layout of window control buttons as represented by user settings.
 ///
@@ -78,6 +79,68 @@ impl From<String> for WindowButtonLayoutContent {
     }
 }
 
+/// An editor status item that can be shown in the title bar toolbar.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TitleBarStatusItem {
+    Cursor,
+    Language,
+    Encoding,
+    LineEnding,
+    Toolchain,
+    Lsp,
+    Predictions,
+    Activity,
+}
+
+/// The title bar toolbar's status items, in order. Unknown entries are skipped
+/// with a warning, so a typo or an item from a newer version hides only itself.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, MergeFrom)]
+#[serde(transparent)]
+pub struct TitleBarStatusItems(pub Vec<TitleBarStatusItem>);
+
+impl<'de> Deserialize<'de> for TitleBarStatusItems {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let entries = Vec::<serde_json::Value>::deserialize(deserializer)?;
+        let items = entries
+            .iter()
+            .filter_map(|entry| match TitleBarStatusItem::deserialize(entry) {
+                Ok(item) => Some(item),
+                Err(error) => {
+                    log::warn!("Ignoring unknown title bar toolbar item {entry}: {error}");
+                    None
+                }
+            })
+            .collect();
+        Ok(Self(items))
+    }
+}
+
+impl JsonSchema for TitleBarStatusItems {
+    fn schema_name() -> Cow<'static, str> {
+        "TitleBarStatusItems".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        Vec::<TitleBarStatusItem>::json_schema(generator)
+    }
+}
+
 #[with_fallible_options]
 #[derive(Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug)]
 pub struct TitleBarSettingsContent {
@@ -113,6 +176,21 @@ pub struct TitleBarSettingsContent {
     ///
     /// Default: false
     pub show_menus: Option<bool>,
+    /// Whether to show the battery level and Glass's memory usage in the title bar (macOS only).
+    ///
+    /// Default: false
+    pub show_system_info: Option<bool>,
+    /// Whether the toolchain status item offers to select a toolchain when the
+    /// active buffer's language supports toolchains but none is selected.
+    ///
+    /// Default: true
+    pub show_toolchain_placeholder: Option<bool>,
+    /// Which editor status items appear in the title bar toolbar, and in what order.
+    ///
+    /// An empty list hides every status item.
+    ///
+    /// Default: ["cursor", "language", "toolchain", "encoding", "line_ending", "lsp", "predictions", "activity"]
+    pub toolbar_items: Option<TitleBarStatusItems>,
     /// The layout of window control buttons in the title bar (Linux only).
     ///
     /// This can be set to "platform_default" to follow the system configuration, or
@@ -122,3 +200,31 @@ pub struct TitleBarSettingsContent {
     /// Default: "platform_default"
     pub button_layout: Option<WindowButtonLayoutContent>,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{TitleBarSettingsContent, TitleBarStatusItem, TitleBarStatusItems};
+
+    #[test]
+    fn test_toolbar_items_skip_unknown_entries() {
+        let content = serde_json::from_value::<TitleBarSettingsContent>(json!({
+            "toolbar_items": ["lsp", "battery", "cursor", 3, "line_ending"]
+        }))
+        .unwrap();
+        assert_eq!(
+            content.toolbar_items,
+            Some(TitleBarStatusItems(vec![
+                TitleBarStatusItem::Lsp,
+                TitleBarStatusItem::Cursor,
+                TitleBarStatusItem::LineEnding,
+            ]))
+        );
+
+        let content =
+            serde_json::from_value::<TitleBarSettingsContent>(json!({ "toolbar_items": [] }))
+                .unwrap();
+        assert_eq!(content.toolbar_items, Some(TitleBarStatusItems::default()));
+    }
+}
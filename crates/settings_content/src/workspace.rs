@@ -49,6 +49,16 @@ pub struct WorkspaceSettingsContent {
     /// Values: empty_tab, last_workspace, last_session, launchpad
     /// Default: last_session
     pub restore_on_startup: Option<RestoreOnStartupBehavior>,
+    /// Whether to reopen each window in the mode (browser, editor, or terminal)
+    /// it was last left in. When disabled, windows always open in browser mode.
+    ///
+    /// Default: true
+    pub restore_last_mode: Option<bool>,
+    /// Whether to reopen editor mode's files and pane layout from the previous
+    /// session. When disabled, editor mode starts with an empty pane.
+    ///
+    /// Default: true
+    pub restore_editor_session: Option<bool>,
     /// Whether to attempt to restore previous file's state when opening it again.
     /// The state is stored per pane.
     /// When disabled, defaults are applied instead of the state restoration.
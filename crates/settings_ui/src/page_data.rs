@@ -216,7 +216,7 @@ fn general_page() -> SettingsPage {
         ]
     }
 
-    fn workspace_restoration_section() -> [SettingsPageItem; 3] {
+    fn workspace_restoration_section() -> [SettingsPageItem; 5] {
         [
             SettingsPageItem::SectionHeader("Workspace Restoration"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -253,6 +253,34 @@ fn general_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Restore Last Mode",
+                description: "Reopen each window in the mode it was last left in.",
+                field: Box::new(SettingField {
+                    json_path: Some("restore_last_mode"),
+                    pick: |settings_content| settings_content.workspace.restore_last_mode.as_ref(),
+                    write: |settings_content, value| {
+                        settings_content.workspace.restore_last_mode = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Restore Editor Session",
+                description: "Reopen editor mode's files and pane layout from the previous session.",
+                field: Box::new(SettingField {
+                    json_path: Some("restore_editor_session"),
+                    pick: |settings_content| {
+                        settings_content.workspace.restore_editor_session.as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content.workspace.restore_editor_session = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
         ]
     }
 
@@ -3121,7 +3149,7 @@ fn window_and_layout_page() -> SettingsPage {
         ]
     }
 
-    fn title_bar_section() -> [SettingsPageItem; 10] {
+    fn title_bar_section() -> [SettingsPageItem; 12] {
         [
             SettingsPageItem::SectionHeader("Title Bar"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -3288,6 +3316,50 @@ fn window_and_layout_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Show System Info",
+                description: "(macOS only) show the battery level and memory usage in the titlebar.",
+                field: Box::new(SettingField {
+                    json_path: Some("title_bar.show_system_info"),
+                    pick: |settings_content| {
+                        settings_content
+                            .title_bar
+                            .as_ref()?
+                            .show_system_info
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .title_bar
+                            .get_or_insert_default()
+                            .show_system_info = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Show Toolchain Placeholder",
+                description: "Offer to select a toolchain when the active language supports one but none is selected.",
+                field: Box::new(SettingField {
+                    json_path: Some("title_bar.show_toolchain_placeholder"),
+                    pick: |settings_content| {
+                        settings_content
+                            .title_bar
+                            .as_ref()?
+                            .show_toolchain_placeholder
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .title_bar
+                            .get_or_insert_default()
+                            .show_toolchain_placeholder = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
             SettingsPageItem::DynamicItem(DynamicItem {
                 discriminant: SettingItem {
                     files: USER,
@@ -5745,7 +5817,7 @@ fn terminal_page() -> SettingsPage {
         ]
     }
 
-    fn behavior_settings_section() -> [SettingsPageItem; 4] {
+    fn behavior_settings_section() -> [SettingsPageItem; 5] {
         [
             SettingsPageItem::SectionHeader("Behavior Settings"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -5806,6 +5878,28 @@ fn terminal_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Open Links In Glass",
+                description: "Whether clicking an http(s) link in the terminal opens it in browser mode instead of the system browser.",
+                field: Box::new(SettingField {
+                    json_path: Some("terminal.open_links_in_glass"),
+                    pick: |settings_content| {
+                        settings_content
+                            .terminal
+                            .as_ref()?
+                            .open_links_in_glass
+                            .as_ref()
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .terminal
+                            .get_or_insert_default()
+                            .open_links_in_glass = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
         ]
     }
 
@@ -7909,7 +8003,7 @@ fn language_settings_data() -> Box<[SettingsPageItem]> {
         ]
     }
 
-    fn global_only_miscellaneous_sub_section() -> [SettingsPageItem; 3] {
+    fn global_only_miscellaneous_sub_section() -> [SettingsPageItem; 4] {
         [
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Image Viewer",
@@ -7929,6 +8023,27 @@ fn language_settings_data() -> Box<[SettingsPageItem]> {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Image Color Details",
+                description: "Whether image metadata includes the color space and bit depth.",
+                field: Box::new(SettingField {
+                    json_path: Some("image_viewer.show_color_details"),
+                    pick: |settings_content| {
+                        settings_content
+                            .image_viewer
+                            .as_ref()
+                            .and_then(|image_viewer| image_viewer.show_color_details.as_ref())
+                    },
+                    write: |settings_content, value| {
+                        settings_content
+                            .image_viewer
+                            .get_or_insert_default()
+                            .show_color_details = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
             SettingsPageItem::SettingItem(SettingItem {
                 title: "Auto Replace Emoji Shortcode",
                 description: "Whether to automatically replace emoji shortcodes with emoji characters.",
@@ -2005,7 +2005,9 @@ impl Terminal {
                 let mouse_cell_index =
                     content_index_for_mouse(position, &self.last_content.terminal_bounds);
                 if let Some(link) = self.last_content.cells[mouse_cell_index].hyperlink() {
-                    cx.open_url(link.uri());
+                    cx.emit(Event::Open(MaybeNavigationTarget::Url(
+                        link.uri().to_string(),
+                    )));
                 } else if e.modifiers.secondary() {
                     self.events
                         .push_back(InternalEvent::FindHyperlink(position, true));
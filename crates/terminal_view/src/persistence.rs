@@ -6,7 +6,7 @@ use gpui::{AppContext as _, AsyncWindowContext, Axis, Entity, Task, WeakEntity};
 use project::Project;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use ui::{App, Context, Pixels, Window};
+use ui::{App, Context, Pixels, SharedString, Window};
 use util::ResultExt as _;
 
 use db::{
@@ -101,9 +101,23 @@ pub(crate) fn deserialize_terminal_panel(
                 let mut panel = TerminalPanel::new(workspace, window, cx);
                 panel.height = serialized_panel.height.map(|h| h.round());
                 panel.width = serialized_panel.width.map(|w| w.round());
+                panel.last_working_directory = serialized_panel.last_working_directory.clone();
                 panel
             })
         })?;
+        if !serialized_panel.sessions.is_empty() {
+            restore_terminal_sessions(
+                workspace,
+                project,
+                terminal_panel.clone(),
+                database_id,
+                &serialized_panel,
+                cx,
+            )
+            .await?;
+            return Ok(terminal_panel);
+        }
+
         match &serialized_panel.items {
             SerializedItems::NoSplits(item_ids) => {
                 let items = deserialize_terminal_views(
@@ -148,6 +162,68 @@ pub(crate) fn deserialize_terminal_panel(
     })
 }
 
+async fn restore_terminal_sessions(
+    workspace: WeakEntity<Workspace>,
+    project: Entity<Project>,
+    terminal_panel: Entity<TerminalPanel>,
+    database_id: WorkspaceId,
+    serialized_panel: &SerializedTerminalPanel,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let mut restored_session_ids = Vec::with_capacity(serialized_panel.sessions.len());
+    for serialized_session in &serialized_panel.sessions {
+        let Some((center_group, active_pane)) = deserialize_pane_group(
+            workspace.clone(),
+            project.clone(),
+            terminal_panel.clone(),
+            database_id,
+            &serialized_session.items,
+            cx,
+        )
+        .await
+        else {
+            restored_session_ids.push(None);
+            continue;
+        };
+
+        let is_first_session = restored_session_ids.iter().all(Option::is_none);
+        let title = SharedString::from(serialized_session.title.clone());
+        let pinned = serialized_session.pinned;
+        let session_id = terminal_panel.update(cx, |terminal_panel, cx| {
+            let center = PaneGroup::with_root(center_group);
+            let active_pane = active_pane.unwrap_or_else(|| center.first_pane());
+            if is_first_session {
+                terminal_panel.sync_current_session_manager(center, active_pane, cx);
+                terminal_panel.session_manager().update(cx, |manager, _| {
+                    let session_id = manager.current_session_id();
+                    manager.rename_session(session_id, title);
+                    manager.set_session_pinned(session_id, pinned);
+                    session_id
+                })
+            } else {
+                terminal_panel.session_manager().update(cx, |manager, _| {
+                    manager.push_restored_session(title, pinned, center, active_pane)
+                })
+            }
+        });
+        restored_session_ids.push(Some(session_id));
+    }
+
+    terminal_panel.update(cx, |terminal_panel, cx| {
+        if let Some(session_id) = restored_session_ids
+            .get(serialized_panel.active_session_index)
+            .copied()
+            .flatten()
+        {
+            terminal_panel
+                .session_manager()
+                .update(cx, |manager, _| manager.activate_session_by_id(session_id));
+        }
+        terminal_panel.refresh_current_session(cx);
+    });
+    Ok(())
+}
+
 fn populate_pane_items(
     pane: &mut Pane,
     items: Vec<Entity<TerminalView>>,
@@ -317,11 +393,28 @@ fn deserialize_terminal_views(
 
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct SerializedTerminalPanel {
+    /// The active tab's panes. Still written next to `sessions` so that builds
+    /// from before terminal tabs restore the active tab instead of an empty panel.
     pub items: SerializedItems,
     // A deprecated field, kept for backwards compatibility for the code before terminal splits were introduced.
     pub active_item_id: Option<u64>,
     pub width: Option<Pixels>,
     pub height: Option<Pixels>,
+    #[serde(default)]
+    pub last_working_directory: Option<PathBuf>,
+    /// Every terminal tab, in order. Older versions only stored the active tab, in `items`.
+    #[serde(default)]
+    pub sessions: Vec<SerializedTerminalSession>,
+    #[serde(default)]
+    pub active_session_index: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SerializedTerminalSession {
+    pub title: String,
+    #[serde(default)]
+    pub pinned: bool,
+    pub items: SerializedPaneGroup,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -517,3 +610,65 @@ impl TerminalDb {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(title: &str, pinned: bool, item_id: u64) -> SerializedTerminalSession {
+        SerializedTerminalSession {
+            title: title.to_string(),
+            pinned,
+            items: SerializedPaneGroup::Pane(SerializedPane {
+                active: true,
+                children: vec![item_id],
+                active_item: Some(item_id),
+                pinned_count: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_terminal_sessions_round_trip() {
+        let panel = SerializedTerminalPanel {
+            items: SerializedItems::NoSplits(vec![2]),
+            active_item_id: None,
+            width: None,
+            height: None,
+            last_working_directory: None,
+            sessions: vec![session("server", true, 1), session("tests", false, 2)],
+            active_session_index: 1,
+        };
+
+        let json = serde_json::to_string(&panel).unwrap();
+        let restored: SerializedTerminalPanel = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.active_session_index, 1);
+        let sessions = restored
+            .sessions
+            .iter()
+            .map(|session| {
+                let children = match &session.items {
+                    SerializedPaneGroup::Pane(pane) => pane.children.clone(),
+                    SerializedPaneGroup::Group { .. } => Vec::new(),
+                };
+                (session.title.as_str(), session.pinned, children)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sessions,
+            vec![("server", true, vec![1]), ("tests", false, vec![2])]
+        );
+    }
+
+    #[test]
+    fn test_panels_saved_before_terminal_tabs_still_load() {
+        let restored: SerializedTerminalPanel =
+            serde_json::from_str(r#"{"items":[7],"active_item_id":7,"width":null,"height":null}"#)
+                .unwrap();
+
+        assert!(restored.sessions.is_empty());
+        assert_eq!(restored.active_session_index, 0);
+        assert!(matches!(restored.items, SerializedItems::NoSplits(ids) if ids == vec![7]));
+    }
+}
@@ -0,0 +1,87 @@
+use editor::Editor;
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, WeakEntity};
+use menu::{Cancel, Confirm};
+use ui::{Headline, HeadlineSize, prelude::*};
+use util::ResultExt as _;
+use workspace::ModalView;
+
+use crate::terminal_panel::TerminalPanel;
+
+pub(crate) struct RenameTerminalTabModal {
+    terminal_panel: WeakEntity<TerminalPanel>,
+    session_id: u64,
+    current_title: SharedString,
+    editor: Entity<Editor>,
+}
+
+impl RenameTerminalTabModal {
+    pub(crate) fn new(
+        terminal_panel: WeakEntity<TerminalPanel>,
+        session_id: u64,
+        current_title: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text(current_title.clone(), window, cx);
+            editor.select_all(&editor::actions::SelectAll, window, cx);
+            editor
+        });
+        Self {
+            terminal_panel,
+            session_id,
+            current_title,
+            editor,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let new_title = self.editor.read(cx).text(cx);
+        let new_title = new_title.trim();
+        if !new_title.is_empty() && new_title != self.current_title.as_ref() {
+            let title = SharedString::from(new_title.to_string());
+            self.terminal_panel
+                .update(cx, |panel, cx| {
+                    panel.rename_session(self.session_id, title, cx)
+                })
+                .log_err();
+        }
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for RenameTerminalTabModal {}
+impl ModalView for RenameTerminalTabModal {}
+
+impl Focusable for RenameTerminalTabModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Render for RenameTerminalTabModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("RenameTerminalTabModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::Terminal).size(IconSize::XSmall))
+                    .child(Headline::new("Rename Terminal Tab").size(HeadlineSize::XSmall)),
+            )
+            .child(div().px_3().pb_3().w_full().child(self.editor.clone()))
+    }
+}
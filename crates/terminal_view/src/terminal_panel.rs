@@ -1,10 +1,18 @@
-use std::{cmp, path::PathBuf, process::ExitStatus, sync::Arc, time::Duration};
+use std::{
+    cmp,
+    path::{Path, PathBuf},
+    process::ExitStatus,
+    sync::Arc,
+    time::Duration,
+};
 
 use crate::{
     TerminalView, default_working_directory,
     persistence::{
-        SerializedItems, SerializedTerminalPanel, deserialize_terminal_panel, serialize_pane_group,
+        SerializedItems, SerializedTerminalPanel, SerializedTerminalSession,
+        deserialize_terminal_panel, serialize_pane_group,
     },
+    rename_terminal_tab_modal::RenameTerminalTabModal,
 };
 use breadcrumbs::Breadcrumbs;
 use collections::HashMap;
@@ -34,9 +42,10 @@ use workspace::{
     SwapPaneLeft, SwapPaneRight, SwapPaneUp, TerminalSessionCloseResult, TerminalSessionManager,
     ToggleZoom, Workspace, WorkspaceId, WorkspaceTerminalSession,
     dock::{DockPosition, Panel, PanelEvent, PanelHandle, PanelNavigationEntry},
-    item::SerializableItem,
+    item::{ItemHandle, SerializableItem},
     move_active_item, pane,
 };
+use workspace_modes::ModeId;
 
 use anyhow::{Result, anyhow};
 use zed_actions::assistant::InlineAssist;
@@ -49,7 +58,17 @@ actions!(
         /// Toggles the terminal panel.
         Toggle,
         /// Toggles focus on the terminal panel.
-        ToggleFocus
+        ToggleFocus,
+        /// Opens a new terminal tab with its own splits.
+        NewTerminalTab,
+        /// Closes the active terminal tab and activates its neighbor.
+        CloseTerminalTab,
+        /// Activates the next terminal tab.
+        NextTerminalTab,
+        /// Activates the previous terminal tab.
+        PreviousTerminalTab,
+        /// Renames the active terminal tab.
+        RenameTerminalTab
     ]
 );
 
@@ -70,6 +89,43 @@ pub fn init(cx: &mut App) {
                     }
                 }
             });
+            workspace.register_action(|workspace, _: &NewTerminalTab, window, cx| {
+                if let Some(panel) = workspace.panel::<TerminalPanel>(cx) {
+                    panel.update(cx, |panel, cx| panel.create_session(window, cx));
+                }
+            });
+            workspace.register_action(|workspace, _: &CloseTerminalTab, window, cx| {
+                if let Some(panel) = workspace.panel::<TerminalPanel>(cx) {
+                    panel.update(cx, |panel, cx| {
+                        let session_id = panel.current_session_id(cx);
+                        panel.close_session_by_id(session_id, window, cx);
+                    });
+                }
+            });
+            workspace.register_action(|workspace, _: &NextTerminalTab, window, cx| {
+                if let Some(panel) = workspace.panel::<TerminalPanel>(cx) {
+                    panel.update(cx, |panel, cx| {
+                        panel.activate_adjacent_session(true, window, cx)
+                    });
+                }
+            });
+            workspace.register_action(|workspace, _: &PreviousTerminalTab, window, cx| {
+                if let Some(panel) = workspace.panel::<TerminalPanel>(cx) {
+                    panel.update(cx, |panel, cx| {
+                        panel.activate_adjacent_session(false, window, cx)
+                    });
+                }
+            });
+            workspace.register_action(|workspace, _: &RenameTerminalTab, window, cx| {
+                let Some(panel) = workspace.panel::<TerminalPanel>(cx) else {
+                    return;
+                };
+                let session = panel.read(cx).current_session_snapshot(cx);
+                let panel = panel.downgrade();
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    RenameTerminalTabModal::new(panel, session.id, session.title, window, cx)
+                });
+            });
         },
     )
     .detach();
@@ -90,6 +146,9 @@ pub struct TerminalPanel {
     assistant_tab_bar_button: Option<AnyView>,
     session_manager: Entity<TerminalSessionManager>,
     active: bool,
+    /// Where the most recently closed or saved terminal was, so the next
+    /// terminal that fills an empty panel can resume there.
+    pub(crate) last_working_directory: Option<PathBuf>,
 }
 
 impl TerminalPanel {
@@ -124,6 +183,7 @@ impl TerminalPanel {
             assistant_tab_bar_button: None,
             session_manager,
             active: false,
+            last_working_directory: None,
         };
         terminal_panel.apply_tab_bar_buttons(&pane, cx);
         terminal_panel
@@ -150,6 +210,10 @@ impl TerminalPanel {
         }
     }
 
+    pub(crate) fn session_manager(&self) -> &Entity<TerminalSessionManager> {
+        &self.session_manager
+    }
+
     fn current_session_snapshot(&self, cx: &App) -> WorkspaceTerminalSession {
         self.session_manager.read(cx).current_session().clone()
     }
@@ -170,6 +234,12 @@ impl TerminalPanel {
             .collect()
     }
 
+    fn is_hosted_in_terminal_mode(&self, cx: &App) -> bool {
+        self.workspace
+            .upgrade()
+            .is_some_and(|workspace| workspace.read(cx).active_mode_id() == ModeId::TERMINAL)
+    }
+
     fn current_session_id(&self, cx: &App) -> u64 {
         self.session_manager.read(cx).current_session_id()
     }
@@ -280,6 +350,33 @@ impl TerminalPanel {
         Self::emit_navigation_updated(cx);
     }
 
+    fn activate_adjacent_session(
+        &mut self,
+        forward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(session_id) = self.session_manager.read(cx).adjacent_session_id(forward) else {
+            return;
+        };
+        self.activate_session_by_id(session_id, window, cx);
+    }
+
+    pub(crate) fn rename_session(
+        &mut self,
+        session_id: u64,
+        title: SharedString,
+        cx: &mut Context<Self>,
+    ) {
+        let renamed = self
+            .session_manager
+            .update(cx, |manager, _| manager.rename_session(session_id, title));
+        if renamed {
+            self.serialize(cx);
+            Self::emit_navigation_updated(cx);
+        }
+    }
+
     fn create_session(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(new_session) = self.create_empty_session(window, cx) {
             let new_session = self.session_manager.update(cx, |manager, _| {
@@ -315,18 +412,44 @@ impl TerminalPanel {
         }
 
         cx.defer_in(window, |this, window, cx| {
-            let Ok(kind) = this
-                .workspace
-                .update(cx, |workspace, cx| default_working_directory(workspace, cx))
-            else {
+            let Some(workspace) = this.workspace.upgrade() else {
                 return;
             };
+            let working_directory = this.initial_working_directory(workspace.read(cx), cx);
 
-            this.add_terminal_shell(kind, RevealStrategy::Always, window, cx)
+            this.add_terminal_shell(working_directory, RevealStrategy::Always, window, cx)
                 .detach_and_log_err(cx);
         });
     }
 
+    /// Resumes the last terminal's directory while it is still inside the
+    /// project directory. Otherwise new terminals follow the project, so
+    /// switching the active worktree redirects them without touching open ones.
+    fn initial_working_directory(&self, workspace: &Workspace, cx: &App) -> Option<PathBuf> {
+        let default_directory = default_working_directory(workspace, cx);
+        match (&self.last_working_directory, &default_directory) {
+            (Some(last_directory), Some(project_directory))
+                if last_directory.starts_with(project_directory)
+                    && is_worktree_directory(workspace, last_directory, cx) =>
+            {
+                Some(last_directory.clone())
+            }
+            _ => default_directory,
+        }
+    }
+
+    fn record_working_directory(&mut self, item: &dyn ItemHandle, cx: &App) {
+        if let Some(working_directory) = item.downcast::<TerminalView>().and_then(|terminal_view| {
+            terminal_view
+                .read(cx)
+                .terminal()
+                .read(cx)
+                .working_directory()
+        }) {
+            self.last_working_directory = Some(working_directory);
+        }
+    }
+
     pub(crate) fn apply_tab_bar_buttons(
         &self,
         terminal_pane: &Entity<Pane>,
@@ -494,7 +617,9 @@ impl TerminalPanel {
             let cleanup_task = workspace.update_in(&mut cx, |workspace, window, cx| {
                 let alive_item_ids = terminal_panel
                     .read(cx)
-                    .current_panes(cx)
+                    .session_manager
+                    .read(cx)
+                    .navigation_panes()
                     .into_iter()
                     .flat_map(|pane: Entity<Pane>| pane.read(cx).items())
                     .map(|item| item.item_id().as_u64() as ItemId)
@@ -542,7 +667,8 @@ impl TerminalPanel {
                 self.serialize(cx);
                 cx.notify();
             }
-            pane::Event::RemovedItem { .. } => {
+            pane::Event::RemovedItem { item } => {
+                self.record_working_directory(item.as_ref(), cx);
                 self.serialize(cx);
                 cx.notify();
             }
@@ -554,10 +680,17 @@ impl TerminalPanel {
                     pane_count_before_removal
                 });
                 if pane_count_before_removal == 1 {
-                    self.current_active_pane(cx).update(cx, |pane, cx| {
+                    let active_pane = self.current_active_pane(cx);
+                    active_pane.update(cx, |pane, cx| {
                         pane.set_zoomed(false, cx);
                     });
-                    cx.emit(PanelEvent::Close);
+                    // Terminal Mode has nothing else to show, so it keeps the empty
+                    // pane and its "New Terminal" prompt instead of closing the panel.
+                    if self.is_hosted_in_terminal_mode(cx) {
+                        active_pane.focus_handle(cx).focus(window, cx);
+                    } else {
+                        cx.emit(PanelEvent::Close);
+                    }
                 } else if let Some(focus_on_pane) = focus_on_pane
                     .as_ref()
                     .cloned()
@@ -1153,14 +1286,37 @@ impl TerminalPanel {
                 .timer(Duration::from_millis(50))
                 .await;
             let terminal_panel = terminal_panel.upgrade()?;
-            let items = terminal_panel.update(cx, |terminal_panel, cx| {
-                let current_session = terminal_panel.current_session_snapshot(cx);
-                SerializedItems::WithSplits(serialize_pane_group(
-                    &current_session.center,
-                    &current_session.active_pane,
-                    cx,
-                ))
-            });
+            let (items, sessions, active_session_index, last_working_directory) = terminal_panel
+                .update(cx, |terminal_panel, cx| {
+                    let current_session = terminal_panel.current_session_snapshot(cx);
+                    if let Some(active_item) = current_session.active_pane.read(cx).active_item() {
+                        terminal_panel.record_working_directory(active_item.as_ref(), cx);
+                    }
+                    let items = SerializedItems::WithSplits(serialize_pane_group(
+                        &current_session.center,
+                        &current_session.active_pane,
+                        cx,
+                    ));
+                    let all_sessions = terminal_panel.session_manager.read(cx).sessions().to_vec();
+                    let active_session_index = all_sessions
+                        .iter()
+                        .position(|session| session.id == current_session.id)
+                        .unwrap_or_default();
+                    let sessions = all_sessions
+                        .iter()
+                        .map(|session| SerializedTerminalSession {
+                            title: session.title.to_string(),
+                            pinned: session.pinned,
+                            items: serialize_pane_group(&session.center, &session.active_pane, cx),
+                        })
+                        .collect::<Vec<_>>();
+                    (
+                        items,
+                        sessions,
+                        active_session_index,
+                        terminal_panel.last_working_directory.clone(),
+                    )
+                });
             cx.background_spawn(
                 async move {
                     kvp.write_kvp(
@@ -1170,6 +1326,9 @@ impl TerminalPanel {
                             active_item_id: None,
                             height,
                             width,
+                            last_working_directory,
+                            sessions,
+                            active_session_index,
                         })?,
                     )
                     .await?;
@@ -1399,6 +1558,21 @@ pub fn prepare_task_for_spawn(
     }
 }
 
+/// Checks the project's worktree snapshot rather than the local filesystem, so
+/// this doesn't block the main thread and also holds for remote projects.
+fn is_worktree_directory(workspace: &Workspace, path: &Path, cx: &App) -> bool {
+    workspace
+        .project()
+        .read(cx)
+        .find_worktree(path, cx)
+        .is_some_and(|(worktree, relative_path)| {
+            worktree
+                .read(cx)
+                .entry_for_path(&relative_path)
+                .is_some_and(|entry| entry.is_dir())
+        })
+}
+
 fn is_enabled_in_workspace(workspace: &Workspace, cx: &App) -> bool {
     workspace.project().read(cx).supports_terminal(cx)
 }
@@ -1427,6 +1601,7 @@ pub fn new_terminal_pane(
         pane.display_nav_history_buttons(None);
         pane.set_should_display_tab_bar(|_, _| true);
         pane.set_zoom_out_on_close(false);
+        pane.set_empty_state_label("New Terminal");
 
         let split_closure_terminal_panel = terminal_panel.downgrade();
         pane.set_can_split(Some(Arc::new(move |pane, dragged_item, _window, cx| {
@@ -1,4 +1,5 @@
 mod persistence;
+mod rename_terminal_tab_modal;
 pub mod terminal_element;
 pub mod terminal_panel;
 mod terminal_path_like_target;
@@ -16,7 +17,7 @@ use gpui::{
 use itertools::Itertools;
 use menu;
 use persistence::TerminalDb;
-use project::{Project, ProjectEntryId, search::SearchQuery};
+use project::{Project, ProjectEntryId, Worktree, search::SearchQuery};
 use schemars::JsonSchema;
 use serde::Deserialize;
 use settings::{Settings, SettingsStore, TerminalBlink, WorkingDirectory};
@@ -1073,7 +1074,9 @@ fn subscribe_for_terminal_events(
                 }
 
                 Event::Open(maybe_navigation_target) => match maybe_navigation_target {
-                    MaybeNavigationTarget::Url(url) => cx.open_url(url),
+                    MaybeNavigationTarget::Url(url) => {
+                        open_terminal_url(&workspace, url, window, cx)
+                    }
                     MaybeNavigationTarget::PathLike(path_like_target) => open_path_like_target(
                         &workspace,
                         terminal_view,
@@ -1094,6 +1097,31 @@ fn subscribe_for_terminal_events(
     vec![terminal_subscription, terminal_events_subscription]
 }
 
+fn open_terminal_url(
+    workspace: &WeakEntity<Workspace>,
+    url: &str,
+    window: &mut Window,
+    cx: &mut Context<TerminalView>,
+) {
+    let is_web_url = ["http://", "https://"].iter().any(|scheme| {
+        url.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    });
+    if is_web_url
+        && TerminalSettings::get_global(cx).open_links_in_glass
+        && let Some(workspace) = workspace.upgrade()
+    {
+        workspace.update(cx, |workspace, cx| {
+            workspace
+                .open_url_in_browser(url, false, window, cx)
+                .log_err();
+        });
+        return;
+    }
+
+    cx.open_url(url);
+}
+
 fn regex_search_for_query(query: &SearchQuery) -> Option<RegexSearch> {
     let str = query.as_str();
     if query.is_regex() {
@@ -1965,19 +1993,29 @@ pub(crate) fn default_working_directory(workspace: &Workspace, cx: &App) -> Opti
     directory.or_else(dirs::home_dir)
 }
 
+/// Prefers the worktree picked in the title bar over the one containing the
+/// active entry.
 fn current_project_directory(workspace: &Workspace, cx: &App) -> Option<PathBuf> {
+    let project = workspace.project().read(cx);
     workspace
-        .project()
-        .read(cx)
-        .active_project_directory(cx)
-        .as_deref()
-        .map(Path::to_path_buf)
+        .active_worktree_override()
+        .and_then(|worktree_id| project.worktree_for_id(worktree_id, cx))
+        .and_then(|worktree| worktree_directory(worktree.read(cx)))
+        .or_else(|| {
+            project
+                .active_project_directory(cx)
+                .as_deref()
+                .map(Path::to_path_buf)
+        })
         .or_else(|| first_project_directory(workspace, cx))
 }
 
 ///Gets the first project's home directory, or the home directory
 fn first_project_directory(workspace: &Workspace, cx: &App) -> Option<PathBuf> {
-    let worktree = workspace.worktrees(cx).next()?.read(cx);
+    worktree_directory(workspace.worktrees(cx).next()?.read(cx))
+}
+
+fn worktree_directory(worktree: &Worktree) -> Option<PathBuf> {
     let worktree_path = worktree.abs_path();
     if worktree.root_entry()?.is_dir() {
         Some(worktree_path.to_path_buf())
@@ -2139,6 +2177,26 @@ mod tests {
         });
     }
 
+    // Worktree override set in the title bar -> that worktree, even with another active entry
+    #[gpui::test]
+    async fn worktree_override_takes_precedence(cx: &mut TestAppContext) {
+        let (project, workspace) = init_test(cx).await;
+
+        let (wt1, _entry1) = create_folder_wt(project.clone(), "/root1/", cx).await;
+        let (wt2, entry2) = create_folder_wt(project.clone(), "/root2/", cx).await;
+        insert_active_entry_for(wt2, entry2, project.clone(), cx);
+
+        workspace.update(cx, |workspace, cx| {
+            let worktree_id = wt1.read(cx).id();
+            workspace.set_active_worktree_override(Some(worktree_id), cx);
+        });
+
+        cx.update(|cx| {
+            let res = default_working_directory(workspace.read(cx), cx);
+            assert_eq!(res, Some(Path::new("/root1/").to_path_buf()));
+        });
+    }
+
     // active_entry_directory: No active entry -> returns None (used by CurrentFileDirectory)
     #[gpui::test]
     async fn active_entry_directory_no_active_entry(cx: &mut TestAppContext) {
@@ -5,7 +5,8 @@ use crate::{
 use collections::HashMap;
 use derive_more::{Deref, DerefMut};
 use gpui::{
-    App, Context, Font, FontFallbacks, FontStyle, Global, Pixels, Subscription, Window, px,
+    App, Context, Font, FontFallbacks, FontStyle, Global, Pixels, Subscription, Window, WindowId,
+    px,
 };
 use refineable::Refineable;
 use schemars::JsonSchema;
@@ -17,6 +18,12 @@ use std::sync::Arc;
 const MIN_FONT_SIZE: Pixels = px(6.0);
 const MAX_FONT_SIZE: Pixels = px(100.0);
 const MIN_LINE_HEIGHT: f32 = 1.0;
+/// The smallest scale a window's UI can be set to.
+pub const MIN_UI_SCALE: f32 = 0.5;
+/// The largest scale a window's UI can be set to.
+pub const MAX_UI_SCALE: f32 = 2.0;
+/// How much increasing or decreasing the UI scale changes it by.
+pub const UI_SCALE_STEP: f32 = 0.1;
 
 #[derive(
     Debug,
@@ -188,6 +195,13 @@ pub(crate) struct UiFontSize(Pixels);
 
 impl Global for UiFontSize {}
 
+/// Scale factors for windows whose whole UI is zoomed. Windows without an
+/// entry are at 1.0.
+#[derive(Default)]
+struct WindowUiScales(HashMap<WindowId, f32>);
+
+impl Global for WindowUiScales {}
+
 /// In-memory override for the font size in the agent panel.
 #[derive(Default)]
 pub struct AgentFontSize(Pixels);
@@ -634,10 +648,46 @@ pub fn setup_ui_font(window: &mut Window, cx: &mut App) -> gpui::Font {
         (font, theme_settings.ui_font_size(cx))
     };
 
-    window.set_rem_size(ui_font_size);
+    window.set_rem_size(ui_font_size * ui_scale(window, cx));
     ui_font
 }
 
+/// Returns the scale applied to the whole UI of `window`, chrome included.
+pub fn ui_scale(window: &Window, cx: &App) -> f32 {
+    cx.try_global::<WindowUiScales>()
+        .and_then(|scales| scales.0.get(&window.window_handle().window_id()))
+        .copied()
+        .unwrap_or(1.0)
+}
+
+/// Scales the whole UI of the window with `window_id`, returning the scale
+/// after clamping it to the supported range.
+pub fn set_ui_scale(window_id: WindowId, scale: f32, cx: &mut App) -> f32 {
+    let scale = clamp_ui_scale(scale);
+    let scales = &mut cx.default_global::<WindowUiScales>().0;
+    if scale == 1.0 {
+        scales.remove(&window_id);
+    } else {
+        scales.insert(window_id, scale);
+    }
+    cx.refresh_windows();
+    scale
+}
+
+/// Forgets the UI scale of a window that has closed.
+pub fn remove_ui_scale(window_id: WindowId, cx: &mut App) {
+    if cx.has_global::<WindowUiScales>() {
+        cx.global_mut::<WindowUiScales>().0.remove(&window_id);
+    }
+}
+
+/// Ensures a UI scale is within the supported range, rounded to a whole step
+/// so repeated adjustments don't drift.
+pub fn clamp_ui_scale(scale: f32) -> f32 {
+    let steps = (scale / UI_SCALE_STEP).round();
+    (steps * UI_SCALE_STEP).clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+}
+
 /// Sets the adjusted UI font size.
 pub fn adjust_ui_font_size(cx: &mut App, f: impl FnOnce(Pixels) -> Pixels) {
     let ui_font_size = ThemeSettings::get_global(cx).ui_font_size(cx);
@@ -750,3 +800,16 @@ impl settings::Settings for ThemeSettings {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_ui_scale() {
+        assert!((clamp_ui_scale(1.0 + UI_SCALE_STEP + UI_SCALE_STEP) - 1.2).abs() < 1e-6);
+        assert!((clamp_ui_scale(1.04) - 1.0).abs() < 1e-6);
+        assert_eq!(clamp_ui_scale(0.1), MIN_UI_SCALE);
+        assert_eq!(clamp_ui_scale(5.0), MAX_UI_SCALE);
+    }
+}
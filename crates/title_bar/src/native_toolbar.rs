@@ -6,18 +6,25 @@ mod project_items;
 mod session_items;
 mod state;
 mod status;
+mod system_info;
 
 use crate::{TitleBar, show_menus, title_bar_settings::TitleBarSettings};
 use client::Status as ClientStatus;
 use gpui::{
-    Action, AnyElement, Context, IntoElement, NativeToolbar, NativeToolbarDisplayMode,
-    NativeToolbarItem, NativeToolbarSizeMode, Window,
+    Action, AnyElement, Context, IntoElement, NativeSearchFieldTarget, NativeToolbar,
+    NativeToolbarDisplayMode, NativeToolbarItem, NativeToolbarSizeMode, Window,
 };
 use settings::Settings;
+use std::time::Duration;
 use workspace::ToggleSidebar;
 use workspace_modes::ModeId;
 
 pub(crate) use state::NativeToolbarState;
+use state::{ConnectionStatus, ToolbarState, ToolbarStatusText, ToolbarUpdate};
+
+/// How long status text has to stay unchanged before the toolbar is rebuilt
+/// to show it.
+const STATUS_TEXT_REBUILD_DELAY: Duration = Duration::from_millis(300);
 
 impl TitleBar {
     pub(crate) fn render_macos_title_bar(
@@ -32,7 +39,7 @@ impl TitleBar {
 
         self.update_native_toolbar(window, cx);
         let button_layout = TitleBarSettings::get_global(cx).button_layout;
-        let background_color = if self.browser_surface_active(cx) {
+        let background_color = if self.browser_surface_active() {
             let active_url = self.active_tab_url(cx);
             let live_color = self.active_tab_page_chrome_color(cx);
 
@@ -65,30 +72,49 @@ impl TitleBar {
     }
 
     pub(crate) fn invalidate_native_toolbar(&mut self, cx: &mut Context<Self>) {
-        self.native_toolbar_state.last_toolbar_key.clear();
+        self.native_toolbar_state.last_toolbar_state = None;
         cx.notify();
     }
 
     fn update_native_toolbar(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let active_mode = self
-            .workspace
-            .upgrade()
-            .map(|workspace| workspace.read(cx).active_mode_id())
-            .unwrap_or(ModeId::BROWSER);
-        let is_browser_surface_active = self.browser_surface_active(cx);
+        let active_mode = self.active_mode;
+        let is_browser_surface_active = self.browser_surface_active();
         let is_terminal_mode = active_mode == ModeId::TERMINAL;
         let is_new_tab_page = is_browser_surface_active && self.active_tab_is_new_tab_page(cx);
-        let title_bar_settings = *TitleBarSettings::get_global(cx);
+        let is_loading = is_browser_surface_active && self.active_tab_is_loading(cx);
+        let is_bookmarked = is_browser_surface_active && self.active_tab_is_bookmarked(cx);
+        let browser_tabs_sidebar_visible = is_browser_surface_active
+            && self
+                .workspace
+                .upgrade()
+                .is_some_and(|workspace| workspace.read(cx).browser_tabs_sidebar_visible(cx));
+        let translation_offer_language = if is_browser_surface_active {
+            self.active_tab_translation_offer_language(cx)
+        } else {
+            None
+        };
+        let security_state = if is_browser_surface_active {
+            self.active_tab_security_state(cx)
+        } else {
+            None
+        };
+        let (can_go_back, can_go_forward) = if is_browser_surface_active {
+            self.active_tab_navigation_state(cx)
+        } else {
+            (false, false)
+        };
+        let title_bar_settings = TitleBarSettings::get_global(cx).clone();
 
         self.sync_omnibox_url(cx);
         self.refresh_status_data(window, cx);
+        self.sync_system_info_refresh(title_bar_settings.show_system_info, cx);
 
         if self.native_toolbar_state.omnibox_panel_dirty {
             self.native_toolbar_state.omnibox_panel_dirty = false;
             if self.native_toolbar_state.omnibox_focused
                 && !self.native_toolbar_state.omnibox_text.is_empty()
             {
-                self.show_search_suggestion_menu(window);
+                self.show_search_suggestion_menu(window, cx);
             }
         }
 
@@ -96,7 +122,10 @@ impl TitleBar {
             return;
         }
 
-        let has_restricted_worktrees = self.has_restricted_worktrees(cx);
+        let restricted_worktree_count = self.restricted_worktree_count(cx);
+        self.native_toolbar_state.is_busy = self.project_is_busy(cx);
+        self.native_toolbar_state.edit_predictions_enabled = self.edit_predictions_enabled(cx);
+        let language_server_count = self.project.read(cx).language_server_statuses(cx).count();
         let is_remote = self.project.read(cx).is_via_remote_server();
         let user = self.user_store.read(cx).current_user();
         let user_login = user
@@ -104,40 +133,90 @@ impl TitleBar {
             .map(|user| user.github_login.to_string())
             .unwrap_or_default();
         let show_update = self.update_version.read(cx).show_update_in_menu_bar();
-        let connection_status_key = match &*self.client.status().borrow() {
-            ClientStatus::ConnectionError => "connection_error",
-            ClientStatus::ConnectionLost => "connection_lost",
-            ClientStatus::Reauthenticating => "reauthenticating",
-            ClientStatus::Reconnecting => "reconnecting",
-            ClientStatus::ReconnectionError { .. } => "reconnection_error",
-            ClientStatus::UpgradeRequired => "upgrade_required",
-            _ => "ok",
+        let connection_status = match &*self.client.status().borrow() {
+            ClientStatus::ConnectionError => ConnectionStatus::ConnectionError,
+            ClientStatus::ConnectionLost => ConnectionStatus::ConnectionLost,
+            ClientStatus::Reauthenticating => ConnectionStatus::Reauthenticating,
+            ClientStatus::Reconnecting => ConnectionStatus::Reconnecting,
+            ClientStatus::ReconnectionError { .. } => ConnectionStatus::ReconnectionError,
+            ClientStatus::UpgradeRequired => ConnectionStatus::UpgradeRequired,
+            _ => ConnectionStatus::Connected,
         };
-        let toolbar_key = format!(
-            "{}:{}:{}:{}:{}:{}:{}:{}:{}:{}:{}:{}:{}:{:?}:{:?}:{:?}:{:?}",
-            active_mode.0,
+        let terminal_title = if is_terminal_mode {
+            self.active_terminal_title(cx)
+                .map(|title| title.to_string())
+        } else {
+            None
+        };
+        let download_percent = if is_browser_surface_active {
+            self.active_download_percent(cx)
+        } else {
+            None
+        };
+        let toolbar_state = ToolbarState {
+            active_mode,
             is_browser_surface_active,
-            "",
-            "",
-            self.native_toolbar_state.omnibox_text,
+            omnibox_text: self.native_toolbar_state.omnibox_text.clone(),
             is_new_tab_page,
-            has_restricted_worktrees,
+            browser_tabs_sidebar_visible,
+            can_go_back,
+            can_go_forward,
+            is_loading,
+            is_bookmarked,
+            translation_offer_language: translation_offer_language.clone(),
+            security_state,
+            restricted_worktree_count,
             is_remote,
-            title_bar_settings.show_project_items,
-            title_bar_settings.show_branch_name,
+            show_project_items: title_bar_settings.show_project_items,
+            show_branch_name: title_bar_settings.show_branch_name,
             user_login,
-            connection_status_key,
+            connection_status,
+            is_reconnecting: self.native_toolbar_state.reconnect_task.is_some(),
             show_update,
-            self.native_toolbar_state.status_encoding,
-            self.native_toolbar_state.status_line_ending,
-            self.native_toolbar_state.status_toolchain,
-            self.native_toolbar_state.status_image_info,
-        );
+            status_encoding: self.native_toolbar_state.status_encoding.clone(),
+            status_line_ending: self.native_toolbar_state.status_line_ending.clone(),
+            status_toolchain: self.native_toolbar_state.status_toolchain.clone(),
+            status_image_info: self.native_toolbar_state.status_image_info.clone(),
+            status_image_loading: self.native_toolbar_state.status_image_loading,
+            has_status_cursor: self.native_toolbar_state.status_cursor.is_some(),
+            status_language: self.native_toolbar_state.status_language.clone(),
+            has_status_file_path: self.native_toolbar_state.status_file_path.is_some(),
+            has_status_system_info: self.native_toolbar_state.status_system_info.is_some(),
+            has_terminal_title: terminal_title.is_some(),
+            is_busy: self.native_toolbar_state.is_busy,
+            language_server_count,
+            failed_language_server_count: self.native_toolbar_state.failed_language_servers.len(),
+            edit_predictions_enabled: self.native_toolbar_state.edit_predictions_enabled,
+            download_percent,
+            toolbar_items: title_bar_settings.toolbar_items.clone(),
+        };
 
-        if self.native_toolbar_state.last_toolbar_key == toolbar_key {
-            return;
+        let status_text = ToolbarStatusText {
+            cursor: self.native_toolbar_state.status_cursor.clone(),
+            file_path: self.native_toolbar_state.status_file_path.clone(),
+            is_dirty: self.native_toolbar_state.status_is_dirty,
+            terminal_title: terminal_title.clone(),
+            system_info: self.native_toolbar_state.status_system_info.clone(),
+        };
+
+        match self
+            .native_toolbar_state
+            .update_toolbar_state(toolbar_state, status_text)
+        {
+            ToolbarUpdate::Unchanged => return,
+            ToolbarUpdate::DeferRebuild => {
+                self.native_toolbar_state.status_text_rebuild_task =
+                    Some(cx.spawn(async move |this, cx| {
+                        cx.background_executor()
+                            .timer(STATUS_TEXT_REBUILD_DELAY)
+                            .await;
+                        this.update(cx, |title_bar, cx| title_bar.invalidate_native_toolbar(cx))
+                            .ok();
+                    }));
+                return;
+            }
+            ToolbarUpdate::Rebuild => {}
         }
-        self.native_toolbar_state.last_toolbar_key = toolbar_key;
 
         let mut toolbar = NativeToolbar::new("glass.main.toolbar")
             .title("Glass")
@@ -168,37 +247,62 @@ impl TitleBar {
             toolbar = toolbar.item(item);
         }
 
+        if !is_browser_surface_active && !is_terminal_mode {
+            if let Some(file_path) = self.native_toolbar_state.status_file_path.clone() {
+                toolbar = toolbar.item(
+                    self.build_file_path_item(file_path, self.native_toolbar_state.status_is_dirty),
+                );
+            }
+        }
+
+        if let Some(terminal_title) = terminal_title {
+            toolbar = toolbar.item(self.build_terminal_title_item(terminal_title));
+        }
+
         toolbar = toolbar.item(NativeToolbarItem::FlexibleSpace);
 
         if is_browser_surface_active {
+            toolbar =
+                toolbar.item(self.build_browser_tabs_sidebar_item(browser_tabs_sidebar_visible));
+
             if !is_new_tab_page {
                 toolbar = toolbar
-                    .item(self.build_back_item())
-                    .item(self.build_forward_item())
-                    .item(self.build_reload_item())
-                    .item(self.build_omnibox_item());
+                    .item(self.build_back_item(can_go_back))
+                    .item(self.build_forward_item(can_go_forward))
+                    .item(self.build_reload_item(is_loading))
+                    .item(self.build_security_item(security_state))
+                    .item(self.build_omnibox_item())
+                    .item(self.build_bookmark_item(is_bookmarked));
+
+                if let Some(language) = translation_offer_language {
+                    toolbar = toolbar
+                        .item(self.build_translate_item(&language))
+                        .item(self.build_dismiss_translation_item());
+                }
             }
 
             toolbar = toolbar
                 .item(NativeToolbarItem::FlexibleSpace)
-                .item(self.build_downloads_item());
+                .item(self.build_downloads_item(download_percent));
         }
 
         if !is_browser_surface_active && !is_terminal_mode {
-            if let Some(toolchain) = self.native_toolbar_state.status_toolchain.clone() {
-                toolbar = toolbar.item(self.build_toolchain_item(toolchain));
-            }
-            if let Some(encoding) = self.native_toolbar_state.status_encoding.clone() {
-                toolbar = toolbar.item(self.build_encoding_item(encoding));
-            }
-            if let Some(line_ending) = self.native_toolbar_state.status_line_ending.clone() {
-                toolbar = toolbar.item(self.build_line_ending_item(line_ending));
+            for status_item in &title_bar_settings.toolbar_items {
+                if let Some(item) = self.build_status_item(*status_item, cx) {
+                    toolbar = toolbar.item(item);
+                }
             }
             if let Some(image_info) = self.native_toolbar_state.status_image_info.clone() {
                 toolbar = toolbar.item(self.build_image_info_item(image_info));
+            } else if self.native_toolbar_state.status_image_loading {
+                toolbar = toolbar.item(self.build_image_info_loading_item());
             }
         }
 
+        if let Some(system_info) = self.native_toolbar_state.status_system_info.clone() {
+            toolbar = toolbar.item(self.build_system_info_item(system_info));
+        }
+
         if let Some(item) = self.build_connection_status_item(cx) {
             toolbar = toolbar.item(item);
         }
@@ -216,5 +320,13 @@ impl TitleBar {
             .item(self.build_user_menu_item(&user, cx));
 
         window.set_native_toolbar(Some(toolbar));
+
+        if std::mem::take(&mut self.native_toolbar_state.omnibox_refocus_pending) {
+            window.focus_native_search_field(
+                NativeSearchFieldTarget::ToolbarItem("glass.omnibox".into()),
+                true,
+            );
+            self.native_toolbar_state.omnibox_focused = true;
+        }
     }
 }
@@ -1,13 +1,78 @@
-use browser::{self, BrowserView};
+use browser::{
+    self, BrowserDownloadState, BrowserSettings, BrowserView, PageSecurityState,
+    PermissionDecision,
+    history::{HistoryClearRange, strip_prefix_ignoring_case},
+    search_engine_name, text_to_url,
+};
 use gpui::{
     App, Context, Entity, Hsla, NativePopover, NativePopoverAnchor, NativePopoverBehavior,
     NativePopoverClickableRow, NativePopoverContentItem, NativeSearchFieldTarget,
     NativeSearchSuggestionMenu, Window,
 };
+use settings::Settings as _;
 
 use crate::TitleBar;
 use workspace_modes::ModeId;
 
+const MAX_OPEN_TAB_SUGGESTIONS: usize = 3;
+const MAX_BOOKMARK_SUGGESTIONS: usize = 3;
+
+const SUGGESTION_PANEL_WIDTH: f64 = 450.0;
+const SUGGESTION_PANEL_PADDING: f64 = 32.0;
+const SUGGESTION_PANEL_MAX_HEIGHT: f64 = 640.0;
+const SUGGESTION_ROW_HEIGHT: f64 = 28.0;
+const SUGGESTION_HEADING_HEIGHT: f64 = 28.0;
+const SUGGESTION_SEPARATOR_HEIGHT: f64 = 12.0;
+
+/// What choosing an omnibox suggestion does.
+#[derive(Clone)]
+pub(super) enum OmniboxRowTarget {
+    Navigate(String),
+    SwitchToTab(u64),
+}
+
+struct OmniboxSection {
+    heading: Option<&'static str>,
+    rows: Vec<OmniboxRow>,
+}
+
+struct OmniboxRow {
+    title: String,
+    icon: &'static str,
+    detail: String,
+    target: OmniboxRowTarget,
+}
+
+/// The rows found for each omnibox section, before they are grouped.
+#[derive(Default)]
+struct OmniboxCandidates {
+    autocomplete: Option<OmniboxRow>,
+    open_tabs: Vec<OmniboxRow>,
+    bookmarks: Vec<OmniboxRow>,
+    history_heading: &'static str,
+    history: Vec<OmniboxRow>,
+    search: Option<OmniboxRow>,
+}
+
+impl OmniboxCandidates {
+    /// The sections in the order they are shown. Sections without rows are
+    /// left out, so the panel never shows an empty heading or a separator
+    /// with nothing beneath it.
+    fn into_sections(self) -> Vec<OmniboxSection> {
+        [
+            (None, self.autocomplete.into_iter().collect()),
+            (Some("Open Tabs"), self.open_tabs),
+            (Some("Bookmarks"), self.bookmarks),
+            (Some(self.history_heading), self.history),
+            (Some("Search"), self.search.into_iter().collect()),
+        ]
+        .into_iter()
+        .filter(|(_, rows)| !rows.is_empty())
+        .map(|(heading, rows)| OmniboxSection { heading, rows })
+        .collect()
+    }
+}
+
 impl TitleBar {
     fn browser_view(&self, cx: &App) -> Option<Entity<BrowserView>> {
         let workspace = self.workspace.upgrade()?;
@@ -26,6 +91,48 @@ impl TitleBar {
             .unwrap_or(false)
     }
 
+    /// Returns whether the active tab can navigate back and forward. Both are
+    /// `false` on the new tab page.
+    pub(super) fn active_tab_navigation_state(&self, cx: &App) -> (bool, bool) {
+        self.browser_view(cx)
+            .and_then(|browser_view| {
+                let tab = browser_view.read(cx).active_tab()?.read(cx);
+                if tab.is_new_tab_page() {
+                    return None;
+                }
+                Some((tab.can_go_back(), tab.can_go_forward()))
+            })
+            .unwrap_or((false, false))
+    }
+
+    pub(super) fn active_tab_is_loading(&self, cx: &App) -> bool {
+        self.browser_view(cx)
+            .and_then(|browser_view| {
+                let browser_view = browser_view.read(cx);
+                browser_view
+                    .active_tab()
+                    .map(|tab| tab.read(cx).is_loading())
+            })
+            .unwrap_or(false)
+    }
+
+    pub(super) fn active_tab_security_state(&self, cx: &App) -> Option<PageSecurityState> {
+        let browser_view = self.browser_view(cx)?;
+        let browser_view = browser_view.read(cx);
+        browser_view.active_tab()?.read(cx).security_state()
+    }
+
+    pub(super) fn active_tab_is_bookmarked(&self, cx: &App) -> bool {
+        self.browser_view(cx)
+            .is_some_and(|browser_view| browser_view.read(cx).is_active_tab_bookmarked(cx))
+    }
+
+    pub(super) fn active_tab_translation_offer_language(&self, cx: &App) -> Option<String> {
+        self.browser_view(cx)?
+            .read(cx)
+            .translation_offer_language(cx)
+    }
+
     pub(super) fn active_tab_page_chrome_color(&self, cx: &App) -> Option<Hsla> {
         self.browser_view(cx).and_then(|browser_view| {
             let browser_view = browser_view.read(cx);
@@ -36,12 +143,13 @@ impl TitleBar {
     }
 
     pub(super) fn active_tab_url(&self, cx: &App) -> Option<String> {
-        self.browser_view(cx).and_then(|browser_view| {
-            let browser_view = browser_view.read(cx);
-            browser_view
-                .active_tab()
-                .map(|tab| tab.read(cx).url().to_string())
-        })
+        let tab_info = self.browser_view(cx)?.read(cx).active_tab_info(cx)?;
+        Some(tab_info.url)
+    }
+
+    pub(super) fn active_download_percent(&self, cx: &App) -> Option<u8> {
+        let progress = self.browser_view(cx)?.read(cx).active_download_progress()?;
+        Some((progress * 100.0).round() as u8)
     }
 
     pub(super) fn sync_omnibox_url(&mut self, cx: &mut App) {
@@ -49,16 +157,40 @@ impl TitleBar {
             return;
         }
 
-        if let Some(url) = self.browser_view(cx).and_then(|browser_view| {
-            let browser_view = browser_view.read(cx);
-            browser_view
-                .active_tab()
-                .map(|tab| tab.read(cx).url().to_string())
-        }) {
-            let omnibox_text = display_omnibox_text(&url);
-            if self.native_toolbar_state.omnibox_text != omnibox_text {
-                self.native_toolbar_state.omnibox_text = omnibox_text;
-            }
+        if let Some(omnibox_text) = self.active_tab_omnibox_text(cx)
+            && self.native_toolbar_state.omnibox_text != omnibox_text
+        {
+            self.native_toolbar_state.omnibox_text = omnibox_text;
+        }
+    }
+
+    fn active_tab_omnibox_text(&self, cx: &App) -> Option<String> {
+        let tab_info = self.browser_view(cx)?.read(cx).active_tab_info(cx)?;
+        Some(display_omnibox_text(&tab_info.url))
+    }
+
+    /// Escape reverts edited omnibox text to the active tab's URL and keeps the
+    /// field focused; pressing it again once the URL is showing blurs the field.
+    pub(super) fn cancel_omnibox_editing(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        window.dismiss_native_search_suggestion_menu();
+        let is_showing_current_url = self
+            .active_tab_omnibox_text(cx)
+            .is_none_or(|omnibox_text| omnibox_text == self.native_toolbar_state.omnibox_text);
+
+        self.native_toolbar_state.omnibox_focused = false;
+        self.native_toolbar_state.omnibox_selected_index = None;
+        self.native_toolbar_state.omnibox_inline_completion = None;
+        self.native_toolbar_state.omnibox_suggestions.clear();
+        self.sync_omnibox_url(cx);
+
+        if is_showing_current_url {
+            window.blur_native_field_editor();
+            cx.notify();
+        } else {
+            // The native field only picks up new text when the toolbar is rebuilt,
+            // which drops its focus, so focus it again once the rebuild happens.
+            self.native_toolbar_state.omnibox_refocus_pending = true;
+            self.invalidate_native_toolbar(cx);
         }
     }
 
@@ -66,10 +198,11 @@ impl TitleBar {
         if text.is_empty() {
             return;
         }
-        let url = text_to_url(text);
+        let url = text_to_url(text, cx);
         self.native_toolbar_state.omnibox_text = url.clone();
         self.native_toolbar_state.omnibox_focused = false;
         self.native_toolbar_state.omnibox_suggestions.clear();
+        self.native_toolbar_state.omnibox_inline_completion = None;
 
         if let Some(browser_view) = self.browser_view(cx) {
             browser_view.update(cx, |browser_view, cx| {
@@ -84,112 +217,198 @@ impl TitleBar {
         cx.notify();
     }
 
-    pub(super) fn omnibox_row_count(&self) -> usize {
-        let mut count = self.native_toolbar_state.omnibox_suggestions.len();
-        if !self.native_toolbar_state.omnibox_text.is_empty() {
-            count += 1;
-        }
-        count
+    /// Keeps the inline completion consistent with an edit of the omnibox text.
+    /// Deleting characters drops the completion so the literal text is
+    /// submitted, while typing characters that match it keeps it in place
+    /// until the next history search refines it.
+    pub(super) fn update_inline_completion_for_edit(&mut self, previous_text: &str) {
+        let text = &self.native_toolbar_state.omnibox_text;
+        let is_deletion =
+            text.len() < previous_text.len() && previous_text.starts_with(text.as_str());
+        self.native_toolbar_state.omnibox_completion_suppressed = is_deletion;
+
+        let completion = self.native_toolbar_state.omnibox_inline_completion.take();
+        self.native_toolbar_state.omnibox_inline_completion =
+            completion.filter(|_| !is_deletion).and_then(|completion| {
+                let remainder = strip_prefix_ignoring_case(&completion, text)?;
+                (!remainder.is_empty()).then(|| format!("{text}{remainder}"))
+            });
     }
 
-    pub(super) fn url_for_selected_row(&self, index: usize) -> Option<String> {
-        let mut current = 0;
-        if !self.native_toolbar_state.omnibox_text.is_empty() {
-            if current == index {
-                return Some(text_to_url(&self.native_toolbar_state.omnibox_text));
-            }
-            current += 1;
+    /// The omnibox suggestion panel's rows, grouped into sections in the
+    /// order they are shown.
+    fn omnibox_sections(&self, cx: &App) -> Vec<OmniboxSection> {
+        let query = self.native_toolbar_state.omnibox_text.as_str();
+        let mut candidates = OmniboxCandidates::default();
+
+        candidates.autocomplete = self
+            .native_toolbar_state
+            .omnibox_inline_completion
+            .clone()
+            .map(|completion| OmniboxRow {
+                title: completion.clone(),
+                icon: "arrow.up.right",
+                detail: "Autocomplete".to_string(),
+                target: OmniboxRowTarget::Navigate(completion),
+            });
+
+        if let Some(browser_view) = self.browser_view(cx) {
+            let browser_view = browser_view.read(cx);
+            candidates.open_tabs = browser_view
+                .open_tab_matches(query, MAX_OPEN_TAB_SUGGESTIONS, cx)
+                .into_iter()
+                .map(|open_tab| OmniboxRow {
+                    title: display_title(&open_tab.title, &open_tab.url),
+                    icon: "square.on.square",
+                    detail: "Switch to Tab".to_string(),
+                    target: OmniboxRowTarget::SwitchToTab(open_tab.tab_id),
+                })
+                .collect();
+            candidates.bookmarks = browser_view
+                .bookmark_matches(query, MAX_BOOKMARK_SUGGESTIONS, cx)
+                .into_iter()
+                .map(|bookmark| OmniboxRow {
+                    title: display_title(&bookmark.title, &bookmark.url),
+                    icon: "bookmark",
+                    detail: extract_domain(&bookmark.url),
+                    target: OmniboxRowTarget::Navigate(bookmark.url),
+                })
+                .collect();
         }
 
-        for suggestion in &self.native_toolbar_state.omnibox_suggestions {
-            if current == index {
-                return Some(suggestion.url.clone());
-            }
-            current += 1;
+        let suggestions = &self.native_toolbar_state.omnibox_suggestions;
+        candidates.history_heading = if suggestions
+            .iter()
+            .all(|suggestion| suggestion.is_suggested_domain)
+        {
+            "Suggestions"
+        } else {
+            "History"
+        };
+        candidates.history = suggestions
+            .iter()
+            .take(BrowserSettings::get_global(cx).max_suggestions)
+            .map(|suggestion| OmniboxRow {
+                title: display_title(&suggestion.title, &suggestion.url),
+                icon: if suggestion.is_suggested_domain {
+                    "globe"
+                } else {
+                    "clock"
+                },
+                detail: suggestion.suggestion_detail(&extract_domain(&suggestion.url), cx),
+                target: OmniboxRowTarget::Navigate(suggestion.url.clone()),
+            })
+            .collect();
+
+        if !query.is_empty() {
+            candidates.search = Some(OmniboxRow {
+                title: format!("Search \"{query}\""),
+                icon: "magnifyingglass",
+                detail: search_engine_name(cx).to_string(),
+                target: OmniboxRowTarget::Navigate(text_to_url(query, cx)),
+            });
         }
 
-        None
+        candidates.into_sections()
     }
 
-    pub(super) fn show_search_suggestion_menu(&self, window: &mut Window) {
-        if self.omnibox_row_count() == 0 {
+    pub(super) fn omnibox_row_count(&self, cx: &App) -> usize {
+        self.omnibox_sections(cx)
+            .iter()
+            .map(|section| section.rows.len())
+            .sum()
+    }
+
+    pub(super) fn target_for_selected_row(
+        &self,
+        index: usize,
+        cx: &App,
+    ) -> Option<OmniboxRowTarget> {
+        self.omnibox_sections(cx)
+            .into_iter()
+            .flat_map(|section| section.rows)
+            .nth(index)
+            .map(|row| row.target)
+    }
+
+    pub(super) fn open_omnibox_target(
+        &mut self,
+        target: OmniboxRowTarget,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match target {
+            OmniboxRowTarget::Navigate(url) => self.navigate_omnibox(&url, cx),
+            OmniboxRowTarget::SwitchToTab(tab_id) => {
+                self.native_toolbar_state.omnibox_focused = false;
+                self.native_toolbar_state.omnibox_suggestions.clear();
+                self.native_toolbar_state.omnibox_inline_completion = None;
+                if let Some(browser_view) = self.browser_view(cx) {
+                    browser_view.update(cx, |browser_view, cx| {
+                        browser_view.activate_navigation_entry(tab_id, window, cx);
+                    });
+                }
+                self.sync_omnibox_url(cx);
+                cx.notify();
+            }
+        }
+    }
+
+    pub(super) fn show_search_suggestion_menu(&self, window: &mut Window, cx: &App) {
+        let sections = self.omnibox_sections(cx);
+        if sections.is_empty() {
             window.dismiss_native_search_suggestion_menu();
             return;
         }
 
-        let workspace = self.workspace.clone();
         let selected = self.native_toolbar_state.omnibox_selected_index;
+        let panel_height = suggestion_panel_height(&sections);
         let mut items: Vec<NativePopoverContentItem> = Vec::new();
-        let mut row_count = 0usize;
         let mut row_index = 0usize;
-        let has_search_row = !self.native_toolbar_state.omnibox_text.is_empty();
 
-        if has_search_row {
-            let query = self.native_toolbar_state.omnibox_text.clone();
-            let search_workspace = workspace.clone();
-            items.push(
-                NativePopoverClickableRow::new(format!("Search \"{}\"", query))
-                    .icon("magnifyingglass")
-                    .detail("Google")
-                    .selected(selected == Some(row_index))
-                    .on_click(move |window, cx| {
-                        window.dismiss_native_search_suggestion_menu();
-                        let url = text_to_url(&query);
-                        if let Some(workspace) = search_workspace.upgrade()
-                            && let Some(title_bar) = workspace
-                                .read(cx)
-                                .titlebar_item()
-                                .and_then(|item| item.downcast::<TitleBar>().ok())
-                        {
-                            title_bar.update(cx, |title_bar, cx| {
-                                title_bar.navigate_omnibox(&url, cx);
-                            });
-                        }
-                    })
-                    .into(),
-            );
-            row_count += 1;
-            row_index += 1;
-            items.push(NativePopoverContentItem::separator());
-        }
-
-        items.push(NativePopoverContentItem::heading("History"));
-        for suggestion in &self.native_toolbar_state.omnibox_suggestions {
-            let url = suggestion.url.clone();
-            let navigate_workspace = workspace.clone();
-            let title = if suggestion.title.is_empty() {
-                suggestion.url.clone()
-            } else {
-                suggestion.title.clone()
-            };
-            let detail = extract_domain(&suggestion.url);
-            items.push(
-                NativePopoverClickableRow::new(title)
-                    .icon("clock")
-                    .detail(detail)
-                    .selected(selected == Some(row_index))
-                    .on_click(move |window, cx| {
-                        window.dismiss_native_search_suggestion_menu();
-                        if let Some(workspace) = navigate_workspace.upgrade()
-                            && let Some(title_bar) = workspace
-                                .read(cx)
-                                .titlebar_item()
-                                .and_then(|item| item.downcast::<TitleBar>().ok())
-                        {
-                            title_bar.update(cx, |title_bar, cx| {
-                                title_bar.navigate_omnibox(&url, cx);
-                            });
-                        }
-                    })
-                    .into(),
-            );
-            row_count += 1;
-            row_index += 1;
+        for (section_index, section) in sections.into_iter().enumerate() {
+            if section_index > 0 {
+                items.push(NativePopoverContentItem::separator());
+            }
+            if let Some(heading) = section.heading {
+                items.push(NativePopoverContentItem::heading(heading));
+            }
+            for row in section.rows {
+                // The autocomplete row is what Enter opens until another row
+                // is selected.
+                let is_selected = if section.heading.is_none() {
+                    selected.is_none_or(|selected| selected == row_index)
+                } else {
+                    selected == Some(row_index)
+                };
+                let workspace = self.workspace.clone();
+                let target = row.target;
+                items.push(
+                    NativePopoverClickableRow::new(row.title)
+                        .icon(row.icon)
+                        .detail(row.detail)
+                        .selected(is_selected)
+                        .on_click(move |window, cx| {
+                            window.dismiss_native_search_suggestion_menu();
+                            if let Some(workspace) = workspace.upgrade()
+                                && let Some(title_bar) = workspace
+                                    .read(cx)
+                                    .titlebar_item()
+                                    .and_then(|item| item.downcast::<TitleBar>().ok())
+                            {
+                                title_bar.update(cx, |title_bar, cx| {
+                                    title_bar.open_omnibox_target(target.clone(), window, cx);
+                                });
+                            }
+                        })
+                        .into(),
+                );
+                row_index += 1;
+            }
         }
 
-        let content_height =
-            (row_count as f64 * 28.0) + 28.0 + if has_search_row { 12.0 } else { 0.0 } + 32.0;
-        let menu = NativeSearchSuggestionMenu::new(450.0, content_height.min(400.0)).items(items);
+        let menu =
+            NativeSearchSuggestionMenu::new(SUGGESTION_PANEL_WIDTH, panel_height).items(items);
         window.update_native_search_suggestion_menu(
             menu,
             NativeSearchFieldTarget::ToolbarItem("glass.omnibox".into()),
@@ -229,46 +448,257 @@ impl TitleBar {
             row_count += 1;
         } else {
             for download in downloads {
-                let browser_view = browser_view.clone();
                 let detail = if download.is_incognito {
                     format!("{} • Incognito", download.status_text)
                 } else {
                     download.status_text.clone()
                 };
 
-                if download.is_complete && download.full_path.is_some() {
-                    let id = download.id;
+                match (download.state, download.full_path.is_some()) {
+                    (BrowserDownloadState::Completed, true) => {
+                        let id = download.id;
+                        let open_browser_view = browser_view.clone();
+                        items.push(
+                            NativePopoverClickableRow::new(download.display_name)
+                                .icon("arrow.down.doc")
+                                .detail(detail)
+                                .on_click(move |window, cx| {
+                                    window.dismiss_native_popover();
+                                    open_browser_view.update(cx, |browser_view, cx| {
+                                        browser_view.open_download_with_system(id, cx);
+                                    });
+                                })
+                                .into(),
+                        );
+                        let reveal_browser_view = browser_view.clone();
+                        items.push(
+                            NativePopoverClickableRow::new("Show in Finder")
+                                .icon("folder")
+                                .on_click(move |window, cx| {
+                                    window.dismiss_native_popover();
+                                    reveal_browser_view.update(cx, |browser_view, cx| {
+                                        browser_view.reveal_download_in_finder(id, cx);
+                                    });
+                                })
+                                .into(),
+                        );
+                        row_count += 1;
+                    }
+                    (BrowserDownloadState::Failed, _) => {
+                        items.push(NativePopoverContentItem::small_label(format!(
+                            "{} — {} ({})",
+                            download.display_name, detail, download.url
+                        )));
+                    }
+                    _ => {
+                        items.push(NativePopoverContentItem::small_label(format!(
+                            "{} — {}",
+                            download.display_name, detail
+                        )));
+                    }
+                }
+                row_count += 1;
+            }
+        }
+
+        window.dismiss_native_popover();
+        window.show_native_popover(
+            NativePopover::new(360.0, (row_count as f64 * 28.0 + 32.0).clamp(120.0, 360.0))
+                .behavior(NativePopoverBehavior::Transient)
+                .items(items),
+            NativePopoverAnchor::ToolbarItem("glass.browser.downloads".into()),
+        );
+    }
+
+    pub(super) fn show_security_panel(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(browser_view) = self.browser_view(cx) else {
+            return;
+        };
+        let Some(tab) = browser_view.read(cx).active_tab().cloned() else {
+            return;
+        };
+        let title_bar = cx.entity().downgrade();
+
+        let tab = tab.read(cx);
+        let mut items = Vec::new();
+        match tab.security_state() {
+            Some(security_state) => {
+                items.push(NativePopoverContentItem::heading(security_state.label()));
+                items.push(NativePopoverContentItem::small_label(
+                    security_state.description().to_string(),
+                ));
+            }
+            None => items.push(NativePopoverContentItem::heading("Site Information")),
+        }
+        if let Some(certificate) = tab
+            .page_security()
+            .and_then(|page_security| page_security.certificate.as_ref())
+        {
+            items.push(NativePopoverContentItem::separator());
+            items.push(NativePopoverContentItem::small_label(format!(
+                "Issued to: {}",
+                certificate.subject
+            )));
+            items.push(NativePopoverContentItem::small_label(format!(
+                "Issued by: {}",
+                certificate.issuer
+            )));
+        }
+        items.push(NativePopoverContentItem::separator());
+        items.push(
+            NativePopoverClickableRow::new("Site Permissions")
+                .icon("hand.raised")
+                .on_click(move |window, cx| {
+                    title_bar
+                        .update(cx, |title_bar, cx| {
+                            title_bar.show_site_permissions_panel(window, cx);
+                        })
+                        .ok();
+                })
+                .into(),
+        );
+        if let Some(origin) = tab.origin() {
+            let browser_view = browser_view.downgrade();
+            items.push(
+                NativePopoverClickableRow::new("Clear Site Data")
+                    .icon("trash")
+                    .detail(origin)
+                    .on_click(move |window, cx| {
+                        window.dismiss_native_popover();
+                        browser_view
+                            .update(cx, |browser_view, cx| {
+                                browser_view.clear_active_site_data(window, cx);
+                            })
+                            .ok();
+                    })
+                    .into(),
+            );
+        }
+
+        let row_count = items.len();
+        window.dismiss_native_popover();
+        window.show_native_popover(
+            NativePopover::new(320.0, row_count as f64 * 28.0 + 32.0)
+                .behavior(NativePopoverBehavior::Transient)
+                .items(items),
+            NativePopoverAnchor::ToolbarItem("glass.browser.security".into()),
+        );
+    }
+
+    pub(super) fn show_site_permissions_panel(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(browser_view) = self.browser_view(cx) else {
+            return;
+        };
+        let title_bar = cx.entity().downgrade();
+
+        let mut items = Vec::new();
+        match browser_view.read(cx).active_site_permissions(cx) {
+            Some((origin, decisions)) => {
+                items.push(NativePopoverContentItem::heading(origin.clone()));
+                items.push(NativePopoverContentItem::small_label(
+                    "Click a permission to switch between Ask, Allow and Block.".to_string(),
+                ));
+                for (permission, decision) in decisions {
+                    let origin = origin.clone();
+                    let browser_view = browser_view.clone();
+                    let title_bar = title_bar.clone();
+                    let next_decision = match decision {
+                        PermissionDecision::Ask => PermissionDecision::Allow,
+                        PermissionDecision::Allow => PermissionDecision::Deny,
+                        PermissionDecision::Deny => PermissionDecision::Ask,
+                    };
                     items.push(
-                        NativePopoverClickableRow::new(download.display_name)
-                            .icon("arrow.down.doc")
-                            .detail(detail)
+                        NativePopoverClickableRow::new(permission.label())
+                            .icon(site_permission_icon(decision))
+                            .detail(decision.label())
                             .on_click(move |window, cx| {
-                                window.dismiss_native_popover();
                                 browser_view.update(cx, |browser_view, cx| {
-                                    browser_view.open_download_with_system(id, cx);
+                                    browser_view.set_site_permission(
+                                        &origin,
+                                        permission,
+                                        next_decision,
+                                        cx,
+                                    );
                                 });
+                                title_bar
+                                    .update(cx, |title_bar, cx| {
+                                        title_bar.show_site_permissions_panel(window, cx);
+                                    })
+                                    .ok();
                             })
                             .into(),
                     );
-                } else {
-                    items.push(NativePopoverContentItem::small_label(format!(
-                        "{} — {}",
-                        download.display_name, detail
-                    )));
                 }
-                row_count += 1;
+            }
+            None => {
+                items.push(NativePopoverContentItem::heading("Site Permissions"));
+                items.push(NativePopoverContentItem::small_label(
+                    "This page has no site permissions.".to_string(),
+                ));
             }
         }
 
+        let row_count = items.len();
         window.dismiss_native_popover();
         window.show_native_popover(
-            NativePopover::new(360.0, (row_count as f64 * 28.0 + 32.0).clamp(120.0, 360.0))
+            NativePopover::new(300.0, row_count as f64 * 28.0 + 32.0)
                 .behavior(NativePopoverBehavior::Transient)
                 .items(items),
-            NativePopoverAnchor::ToolbarItem("glass.browser.downloads".into()),
+            NativePopoverAnchor::ToolbarItem("glass.browser.security".into()),
         );
     }
 
+    pub(super) fn show_clear_history_panel(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let title_bar = cx.entity().downgrade();
+        let mut items = vec![NativePopoverContentItem::heading("Clear Browsing History")];
+        for range in HistoryClearRange::ALL {
+            let title_bar = title_bar.clone();
+            items.push(
+                NativePopoverClickableRow::new(range.label())
+                    .icon("clock")
+                    .on_click(move |window, cx| {
+                        window.dismiss_native_popover();
+                        title_bar
+                            .update(cx, |title_bar, cx| {
+                                title_bar.clear_browsing_history(range, cx);
+                            })
+                            .ok();
+                    })
+                    .into(),
+            );
+        }
+
+        let row_count = items.len();
+        window.dismiss_native_popover();
+        window.show_native_popover(
+            NativePopover::new(240.0, row_count as f64 * 28.0 + 32.0)
+                .behavior(NativePopoverBehavior::Transient)
+                .items(items),
+            NativePopoverAnchor::ToolbarItem("glass.user_menu".into()),
+        );
+    }
+
+    fn clear_browsing_history(&mut self, range: HistoryClearRange, cx: &mut Context<Self>) {
+        if let Some(browser_view) = self.browser_view(cx) {
+            browser_view.update(cx, |browser_view, cx| {
+                browser_view.clear_history_range(range.since(), cx);
+            });
+        }
+
+        self.native_toolbar_state.omnibox_suggestions.clear();
+        self.native_toolbar_state.omnibox_selected_index = None;
+        cx.notify();
+    }
+
+    /// Treats a programmatic focus of the omnibox like the start of typing, so
+    /// the suggestion panel opens for the URL that is already in the field.
+    pub(crate) fn begin_omnibox_editing(&mut self, cx: &mut Context<Self>) {
+        self.native_toolbar_state.omnibox_focused = true;
+        self.native_toolbar_state.omnibox_selected_index = None;
+        self.native_toolbar_state.omnibox_suggestions.clear();
+        self.search_history(self.native_toolbar_state.omnibox_text.clone(), cx);
+    }
+
     pub(super) fn search_history(&mut self, query: String, cx: &mut Context<Self>) {
         let Some(entries) = self
             .browser_view(cx)
@@ -277,17 +707,33 @@ impl TitleBar {
             return;
         };
 
+        let inline_completion = if self.native_toolbar_state.omnibox_completion_suppressed {
+            None
+        } else {
+            browser::history::BrowserHistory::inline_completion(&entries, &query)
+        };
+        let max_suggestions = BrowserSettings::get_global(cx).max_suggestions;
         let executor = cx.background_executor().clone();
         let requested_query = query.clone();
         cx.spawn(async move |this, cx| {
             let matches =
-                browser::history::BrowserHistory::search(entries, query, 8, executor).await;
+                browser::history::BrowserHistory::search(entries, query, max_suggestions, executor)
+                    .await;
             let _ = cx.update(|cx| {
                 let _ = this.update(cx, |title_bar, cx| {
                     if title_bar.native_toolbar_state.omnibox_text != requested_query {
                         return;
                     }
-                    title_bar.native_toolbar_state.omnibox_suggestions = matches;
+                    title_bar.native_toolbar_state.omnibox_suggestions = if matches.is_empty() {
+                        browser::history::BrowserHistory::domain_suggestions(
+                            &requested_query,
+                            max_suggestions,
+                            cx,
+                        )
+                    } else {
+                        matches
+                    };
+                    title_bar.native_toolbar_state.omnibox_inline_completion = inline_completion;
                     title_bar.native_toolbar_state.omnibox_panel_dirty = true;
                     cx.notify();
                 });
@@ -297,17 +743,6 @@ impl TitleBar {
     }
 }
 
-fn text_to_url(text: &str) -> String {
-    if text.starts_with("http://") || text.starts_with("https://") {
-        text.to_string()
-    } else if text.contains('.') && !text.contains(' ') {
-        format!("https://{text}")
-    } else {
-        let encoded: String = url::form_urlencoded::byte_serialize(text.as_bytes()).collect();
-        format!("https://www.google.com/search?q={encoded}")
-    }
-}
-
 fn display_omnibox_text(url: &str) -> String {
     if url == "glass://newtab" {
         String::new()
@@ -316,6 +751,33 @@ fn display_omnibox_text(url: &str) -> String {
     }
 }
 
+/// The suggestion panel's height for `sections`, laid out with a separator
+/// between each pair of sections, so it fits however many rows are shown.
+fn suggestion_panel_height(sections: &[OmniboxSection]) -> f64 {
+    let row_count = sections
+        .iter()
+        .map(|section| section.rows.len())
+        .sum::<usize>();
+    let heading_count = sections
+        .iter()
+        .filter(|section| section.heading.is_some())
+        .count();
+    let separator_count = sections.len().saturating_sub(1);
+    let content_height = SUGGESTION_PANEL_PADDING
+        + row_count as f64 * SUGGESTION_ROW_HEIGHT
+        + heading_count as f64 * SUGGESTION_HEADING_HEIGHT
+        + separator_count as f64 * SUGGESTION_SEPARATOR_HEIGHT;
+    content_height.min(SUGGESTION_PANEL_MAX_HEIGHT)
+}
+
+fn display_title(title: &str, url: &str) -> String {
+    if title.is_empty() {
+        url.to_string()
+    } else {
+        title.to_string()
+    }
+}
+
 fn extract_domain(url: &str) -> String {
     url.strip_prefix("https://")
         .or_else(|| url.strip_prefix("http://"))
@@ -325,3 +787,116 @@ fn extract_domain(url: &str) -> String {
         .unwrap_or(url)
         .to_string()
 }
+
+fn site_permission_icon(decision: PermissionDecision) -> &'static str {
+    match decision {
+        PermissionDecision::Ask => "questionmark.circle",
+        PermissionDecision::Allow => "checkmark.circle",
+        PermissionDecision::Deny => "xmark.circle",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(index: usize) -> OmniboxRow {
+        OmniboxRow {
+            title: format!("Row {index}"),
+            icon: "clock",
+            detail: String::new(),
+            target: OmniboxRowTarget::Navigate(format!("https://example.com/{index}")),
+        }
+    }
+
+    fn rows(row_count: usize) -> Vec<OmniboxRow> {
+        (0..row_count).map(row).collect()
+    }
+
+    fn section(heading: Option<&'static str>, row_count: usize) -> OmniboxSection {
+        OmniboxSection {
+            heading,
+            rows: rows(row_count),
+        }
+    }
+
+    fn headings(sections: &[OmniboxSection]) -> Vec<Option<&'static str>> {
+        sections.iter().map(|section| section.heading).collect()
+    }
+
+    #[test]
+    fn test_omnibox_sections_keep_a_fixed_order() {
+        let sections = OmniboxCandidates {
+            autocomplete: Some(row(0)),
+            open_tabs: rows(2),
+            bookmarks: rows(1),
+            history_heading: "History",
+            history: rows(4),
+            search: Some(row(0)),
+        }
+        .into_sections();
+        assert_eq!(
+            headings(&sections),
+            [
+                None,
+                Some("Open Tabs"),
+                Some("Bookmarks"),
+                Some("History"),
+                Some("Search"),
+            ]
+        );
+        assert_eq!(sections[1].rows.len(), 2);
+        assert_eq!(sections[3].rows.len(), 4);
+    }
+
+    #[test]
+    fn test_omnibox_sections_drop_empty_sections() {
+        let sections = OmniboxCandidates {
+            history_heading: "Suggestions",
+            history: rows(3),
+            search: Some(row(0)),
+            ..OmniboxCandidates::default()
+        }
+        .into_sections();
+        assert_eq!(headings(&sections), [Some("Suggestions"), Some("Search")]);
+        // One separator between the two remaining sections, none for the
+        // dropped ones.
+        assert_eq!(
+            suggestion_panel_height(&sections),
+            32.0 + 2.0 * 28.0 + 4.0 * 28.0 + 12.0
+        );
+
+        assert!(
+            OmniboxCandidates {
+                history_heading: "History",
+                ..OmniboxCandidates::default()
+            }
+            .into_sections()
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_suggestion_panel_height() {
+        assert_eq!(
+            suggestion_panel_height(&[section(Some("History"), 4)]),
+            32.0 + 28.0 + 4.0 * 28.0
+        );
+        assert_eq!(
+            suggestion_panel_height(&[
+                section(None, 1),
+                section(Some("History"), 4),
+                section(Some("Search"), 1),
+            ]),
+            32.0 + 2.0 * 28.0 + 6.0 * 28.0 + 2.0 * 12.0
+        );
+        assert_eq!(
+            suggestion_panel_height(&[
+                section(Some("Open Tabs"), 3),
+                section(Some("Bookmarks"), 3),
+                section(Some("History"), 15),
+            ]),
+            SUGGESTION_PANEL_MAX_HEIGHT
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use browser::BrowserView;
+use browser::{BrowserView, PageSecurityState};
 use gpui::{
-    NativeToolbarItem, NativeToolbarSearchEvent, NativeToolbarSearchField, SharedString, px,
+    Action as _, NativeToolbarButton, NativeToolbarClickEvent, NativeToolbarItem,
+    NativeToolbarSearchEvent, NativeToolbarSearchField, SharedString, px,
 };
 use workspace_modes::ModeId;
 
@@ -33,10 +34,14 @@ impl TitleBar {
                     {
                         let text = event.text.clone();
                         title_bar.update(cx, |title_bar, cx| {
-                            title_bar.native_toolbar_state.omnibox_text = text.clone();
+                            let previous_text = std::mem::replace(
+                                &mut title_bar.native_toolbar_state.omnibox_text,
+                                text.clone(),
+                            );
+                            title_bar.update_inline_completion_for_edit(&previous_text);
                             title_bar.native_toolbar_state.omnibox_selected_index = None;
                             title_bar.native_toolbar_state.omnibox_suggestions.clear();
-                            title_bar.show_search_suggestion_menu(window);
+                            title_bar.show_search_suggestion_menu(window, cx);
                             title_bar.search_history(text, cx);
                             cx.notify();
                         });
@@ -54,13 +59,22 @@ impl TitleBar {
                         title_bar.update(cx, |title_bar, cx| {
                             if let Some(selected) =
                                 title_bar.native_toolbar_state.omnibox_selected_index
-                                && let Some(url) = title_bar.url_for_selected_row(selected)
+                                && let Some(target) =
+                                    title_bar.target_for_selected_row(selected, cx)
                             {
                                 title_bar.native_toolbar_state.omnibox_selected_index = None;
-                                title_bar.navigate_omnibox(&url, cx);
+                                title_bar.open_omnibox_target(target, window, cx);
                                 return;
                             }
                             title_bar.native_toolbar_state.omnibox_selected_index = None;
+                            if let Some(completion) = title_bar
+                                .native_toolbar_state
+                                .omnibox_inline_completion
+                                .clone()
+                            {
+                                title_bar.navigate_omnibox(&completion, cx);
+                                return;
+                            }
                             title_bar.navigate_omnibox(&text, cx);
                         });
                     }
@@ -73,7 +87,7 @@ impl TitleBar {
                             .and_then(|item| item.downcast::<TitleBar>().ok())
                     {
                         title_bar.update(cx, |title_bar, cx| {
-                            let total = title_bar.omnibox_row_count();
+                            let total = title_bar.omnibox_row_count(cx);
                             if total == 0 {
                                 return;
                             }
@@ -83,7 +97,7 @@ impl TitleBar {
                                     None => 0,
                                 },
                             );
-                            title_bar.show_search_suggestion_menu(window);
+                            title_bar.show_search_suggestion_menu(window, cx);
                             cx.notify();
                         });
                     }
@@ -96,7 +110,7 @@ impl TitleBar {
                             .and_then(|item| item.downcast::<TitleBar>().ok())
                     {
                         title_bar.update(cx, |title_bar, cx| {
-                            let total = title_bar.omnibox_row_count();
+                            let total = title_bar.omnibox_row_count(cx);
                             if total == 0 {
                                 return;
                             }
@@ -106,14 +120,12 @@ impl TitleBar {
                                     Some(index) => index - 1,
                                 },
                             );
-                            title_bar.show_search_suggestion_menu(window);
+                            title_bar.show_search_suggestion_menu(window, cx);
                             cx.notify();
                         });
                     }
                 })
                 .on_cancel(move |_event, window, cx| {
-                    window.dismiss_native_search_suggestion_menu();
-                    window.blur_native_field_editor();
                     if let Some(workspace) = workspace_for_cancel.upgrade()
                         && let Some(title_bar) = workspace
                             .read(cx)
@@ -121,10 +133,21 @@ impl TitleBar {
                             .and_then(|item| item.downcast::<TitleBar>().ok())
                     {
                         title_bar.update(cx, |title_bar, cx| {
-                            title_bar.native_toolbar_state.omnibox_focused = false;
-                            title_bar.native_toolbar_state.omnibox_selected_index = None;
-                            cx.notify();
+                            if title_bar
+                                .native_toolbar_state
+                                .omnibox_selected_index
+                                .take()
+                                .is_some()
+                            {
+                                title_bar.show_search_suggestion_menu(window, cx);
+                                cx.notify();
+                                return;
+                            }
+                            title_bar.cancel_omnibox_editing(window, cx);
                         });
+                    } else {
+                        window.dismiss_native_search_suggestion_menu();
+                        window.blur_native_field_editor();
                     }
                 })
                 .on_begin_editing(move |_event, _window, cx| {
@@ -150,6 +173,7 @@ impl TitleBar {
                         title_bar.update(cx, |title_bar, cx| {
                             title_bar.native_toolbar_state.omnibox_focused = false;
                             title_bar.native_toolbar_state.omnibox_selected_index = None;
+                            title_bar.native_toolbar_state.omnibox_inline_completion = None;
                             cx.notify();
                         });
                     }
@@ -157,12 +181,13 @@ impl TitleBar {
         )
     }
 
-    pub(crate) fn build_back_item(&self) -> NativeToolbarItem {
+    pub(crate) fn build_back_item(&self, enabled: bool) -> NativeToolbarItem {
         let workspace = self.workspace.clone();
-        self.build_simple_action_button(
+        self.build_action_button(
             "glass.browser.back",
             "chevron.left",
             "Go Back",
+            enabled,
             move |_window, cx| {
                 if let Some(workspace) = workspace.upgrade() {
                     let browser_view = workspace
@@ -181,12 +206,13 @@ impl TitleBar {
         )
     }
 
-    pub(crate) fn build_forward_item(&self) -> NativeToolbarItem {
+    pub(crate) fn build_forward_item(&self, enabled: bool) -> NativeToolbarItem {
         let workspace = self.workspace.clone();
-        self.build_simple_action_button(
+        self.build_action_button(
             "glass.browser.forward",
             "chevron.right",
             "Go Forward",
+            enabled,
             move |_window, cx| {
                 if let Some(workspace) = workspace.upgrade() {
                     let browser_view = workspace
@@ -205,12 +231,87 @@ impl TitleBar {
         )
     }
 
-    pub(crate) fn build_reload_item(&self) -> NativeToolbarItem {
+    /// Builds the reload button, which turns into a stop button while the
+    /// active tab is loading.
+    pub(crate) fn build_reload_item(&self, is_loading: bool) -> NativeToolbarItem {
+        let workspace = self.workspace.clone();
+        let (id, icon, tool_tip) = if is_loading {
+            ("glass.browser.stop", "xmark", "Stop Loading")
+        } else {
+            ("glass.browser.reload", "arrow.clockwise", "Reload")
+        };
+        self.build_simple_action_button(id, icon, tool_tip, move |_window, cx| {
+            if let Some(workspace) = workspace.upgrade() {
+                let browser_view = workspace
+                    .read(cx)
+                    .get_mode_view(ModeId::BROWSER)
+                    .and_then(|view| view.downcast::<BrowserView>().ok());
+                if let Some(browser_view) = browser_view {
+                    browser_view.update(cx, |browser_view, cx| {
+                        if let Some(tab) = browser_view.active_tab() {
+                            if is_loading {
+                                tab.update(cx, |tab, cx| tab.stop_loading(cx));
+                            } else {
+                                tab.update(cx, |tab, cx| tab.reload(cx));
+                            }
+                        }
+                        cx.notify();
+                    });
+                }
+            }
+        })
+    }
+
+    pub(crate) fn build_bookmark_item(&self, is_bookmarked: bool) -> NativeToolbarItem {
+        let workspace = self.workspace.clone();
+        let (icon, tool_tip) = if is_bookmarked {
+            ("star.fill", "Remove Bookmark")
+        } else {
+            ("star", "Bookmark This Page")
+        };
+        self.build_simple_action_button("glass.browser.bookmark", icon, tool_tip, move |_, cx| {
+            if let Some(workspace) = workspace.upgrade() {
+                let browser_view = workspace
+                    .read(cx)
+                    .get_mode_view(ModeId::BROWSER)
+                    .and_then(|view| view.downcast::<BrowserView>().ok());
+                if let Some(browser_view) = browser_view {
+                    browser_view.update(cx, |browser_view, cx| {
+                        browser_view.toggle_bookmark_active_tab(cx);
+                    });
+                }
+            }
+        })
+    }
+
+    pub(crate) fn build_translate_item(&self, language: &str) -> NativeToolbarItem {
+        let workspace = self.workspace.clone();
+        NativeToolbarItem::Button(
+            NativeToolbarButton::new("glass.browser.translate", "Translate")
+                .tool_tip(format!("Translate This Page ({language})"))
+                .icon("translate")
+                .on_click(move |_: &NativeToolbarClickEvent, _window, cx| {
+                    if let Some(workspace) = workspace.upgrade() {
+                        let browser_view = workspace
+                            .read(cx)
+                            .get_mode_view(ModeId::BROWSER)
+                            .and_then(|view| view.downcast::<BrowserView>().ok());
+                        if let Some(browser_view) = browser_view {
+                            browser_view.update(cx, |browser_view, cx| {
+                                browser_view.translate_active_tab(cx);
+                            });
+                        }
+                    }
+                }),
+        )
+    }
+
+    pub(crate) fn build_dismiss_translation_item(&self) -> NativeToolbarItem {
         let workspace = self.workspace.clone();
         self.build_simple_action_button(
-            "glass.browser.reload",
-            "arrow.clockwise",
-            "Reload",
+            "glass.browser.dismiss_translation",
+            "xmark",
+            "Don't Offer Translation for This Site",
             move |_window, cx| {
                 if let Some(workspace) = workspace.upgrade() {
                     let browser_view = workspace
@@ -219,9 +320,7 @@ impl TitleBar {
                         .and_then(|view| view.downcast::<BrowserView>().ok());
                     if let Some(browser_view) = browser_view {
                         browser_view.update(cx, |browser_view, cx| {
-                            if let Some(tab) = browser_view.active_tab() {
-                                tab.update(cx, |tab, _| tab.reload());
-                            }
+                            browser_view.dismiss_translation_offer(cx);
                         });
                     }
                 }
@@ -229,24 +328,75 @@ impl TitleBar {
         )
     }
 
-    pub(crate) fn build_downloads_item(&self) -> NativeToolbarItem {
-        let workspace = self.workspace.clone();
+    /// Toggles the browser's tab list in the sidebar, unlike `glass.sidebar.toggle`
+    /// which collapses whichever sidebar section is showing.
+    pub(crate) fn build_browser_tabs_sidebar_item(&self, is_visible: bool) -> NativeToolbarItem {
+        let (icon, tool_tip) = if is_visible {
+            ("rectangle.stack.fill", "Hide Tab Sidebar")
+        } else {
+            ("rectangle.stack", "Show Tab Sidebar")
+        };
         self.build_simple_action_button(
-            "glass.browser.downloads",
-            "arrow.down.circle",
-            "Downloads",
-            move |window, cx| {
-                if let Some(workspace) = workspace.upgrade()
-                    && let Some(title_bar) = workspace
-                        .read(cx)
-                        .titlebar_item()
-                        .and_then(|item| item.downcast::<TitleBar>().ok())
-                {
-                    title_bar.update(cx, |title_bar, cx| {
-                        title_bar.show_downloads_panel(window, cx);
-                    });
-                }
-            },
+            "glass.browser.tabs_sidebar",
+            icon,
+            tool_tip,
+            |window, cx| window.dispatch_action(browser::ToggleSidebar.boxed_clone(), cx),
+        )
+    }
+
+    pub(crate) fn build_security_item(
+        &self,
+        security_state: Option<PageSecurityState>,
+    ) -> NativeToolbarItem {
+        let workspace = self.workspace.clone();
+        let (icon, tool_tip) = match security_state {
+            Some(security_state) => (security_state.icon(), security_state.label()),
+            None => ("info.circle", "Site Information"),
+        };
+        NativeToolbarItem::Button(
+            NativeToolbarButton::new("glass.browser.security", "")
+                .tool_tip(tool_tip)
+                .icon(icon)
+                .on_click(move |_: &NativeToolbarClickEvent, window, cx| {
+                    if let Some(workspace) = workspace.upgrade()
+                        && let Some(title_bar) = workspace
+                            .read(cx)
+                            .titlebar_item()
+                            .and_then(|item| item.downcast::<TitleBar>().ok())
+                    {
+                        title_bar.update(cx, |title_bar, cx| {
+                            title_bar.show_security_panel(window, cx);
+                        });
+                    }
+                }),
+        )
+    }
+
+    pub(crate) fn build_downloads_item(&self, download_percent: Option<u8>) -> NativeToolbarItem {
+        let workspace = self.workspace.clone();
+        let (icon, tool_tip) = match download_percent {
+            Some(percent) => (
+                "arrow.down.circle.dotted",
+                format!("Downloading… {percent}%"),
+            ),
+            None => ("arrow.down.circle", "Downloads".to_string()),
+        };
+        NativeToolbarItem::Button(
+            NativeToolbarButton::new("glass.browser.downloads", "")
+                .tool_tip(tool_tip)
+                .icon(icon)
+                .on_click(move |_: &NativeToolbarClickEvent, window, cx| {
+                    if let Some(workspace) = workspace.upgrade()
+                        && let Some(title_bar) = workspace
+                            .read(cx)
+                            .titlebar_item()
+                            .and_then(|item| item.downcast::<TitleBar>().ok())
+                    {
+                        title_bar.update(cx, |title_bar, cx| {
+                            title_bar.show_downloads_panel(window, cx);
+                        });
+                    }
+                }),
         )
     }
 }
@@ -3,18 +3,141 @@ use std::sync::Arc;
 use client::User;
 use encoding_selector::Toggle as ToggleEncoding;
 use gpui::{
-    Action, App, NativeToolbarButton, NativeToolbarItem, NativeToolbarLabel,
-    NativeToolbarMenuButton, NativeToolbarMenuItem,
+    Action, App, NativeToolbarButton, NativeToolbarItem, NativeToolbarMenuButton,
+    NativeToolbarMenuItem,
 };
 use settings::Settings;
-use workspace::notifications::NotifyResultExt;
+use workspace::{RevealInProjectPanel, notifications::NotifyResultExt};
 
-use crate::{TitleBar, title_bar_settings::TitleBarSettings};
+use super::state::ToolchainStatus;
+use crate::{
+    TitleBar,
+    title_bar_settings::{TitleBarSettings, TitleBarStatusItem},
+};
+
+const IMAGE_INFO_ITEM_ID: &str = "glass.status.image_info";
 
 impl TitleBar {
-    pub(crate) fn build_toolchain_item(&self, toolchain: String) -> NativeToolbarItem {
+    pub(crate) fn build_status_item(
+        &self,
+        status_item: TitleBarStatusItem,
+        cx: &App,
+    ) -> Option<NativeToolbarItem> {
+        let state = &self.native_toolbar_state;
+        match status_item {
+            TitleBarStatusItem::Cursor => {
+                Some(self.build_cursor_item(state.status_cursor.clone()?))
+            }
+            TitleBarStatusItem::Language => {
+                Some(self.build_language_item(state.status_language.clone()?))
+            }
+            TitleBarStatusItem::Encoding => {
+                Some(self.build_encoding_item(state.status_encoding.clone()?))
+            }
+            TitleBarStatusItem::LineEnding => {
+                Some(self.build_line_ending_item(state.status_line_ending.clone()?))
+            }
+            TitleBarStatusItem::Toolchain => {
+                Some(self.build_toolchain_item(state.status_toolchain.clone()?))
+            }
+            TitleBarStatusItem::Lsp => Some(self.build_lsp_item(cx)),
+            TitleBarStatusItem::Predictions => Some(self.build_simple_action_button(
+                "glass.status.predictions",
+                if state.edit_predictions_enabled {
+                    "sparkles"
+                } else {
+                    "sparkle"
+                },
+                if state.edit_predictions_enabled {
+                    "Edit Predictions: On"
+                } else {
+                    "Edit Predictions: Off"
+                },
+                |window, cx| {
+                    window.dispatch_action(edit_prediction_ui::ToggleMenu.boxed_clone(), cx)
+                },
+            )),
+            TitleBarStatusItem::Activity => Some(self.build_activity_item(state.is_busy)),
+        }
+    }
+
+    fn build_lsp_item(&self, cx: &App) -> NativeToolbarItem {
+        let failed_count = self.native_toolbar_state.failed_language_servers.len();
+        let running_count = self.project.read(cx).language_server_statuses(cx).count();
+        NativeToolbarItem::Button(
+            NativeToolbarButton::new("glass.status.lsp", "")
+                .tool_tip(language_servers_summary(running_count, failed_count))
+                .icon(if failed_count > 0 {
+                    "exclamationmark.triangle"
+                } else {
+                    "bolt"
+                })
+                .on_click(|_, window, cx| {
+                    window.dispatch_action(language_tools::lsp_button::ToggleMenu.boxed_clone(), cx)
+                }),
+        )
+    }
+
+    fn build_activity_item(&self, is_busy: bool) -> NativeToolbarItem {
+        let (icon, tool_tip) = if is_busy {
+            (
+                "arrow.triangle.2.circlepath",
+                "Language Servers Working — View Logs",
+            )
+        } else {
+            ("doc.text.magnifyingglass", "View Logs")
+        };
+        self.build_simple_action_button("glass.status.activity", icon, tool_tip, |window, cx| {
+            window.dispatch_action(workspace::OpenLog.boxed_clone(), cx)
+        })
+    }
+
+    pub(crate) fn build_language_item(&self, language: String) -> NativeToolbarItem {
+        NativeToolbarItem::Button(
+            NativeToolbarButton::new("glass.status.language", language)
+                .tool_tip("Select Language")
+                .on_click(|_, window, cx| {
+                    window.dispatch_action(language_selector::Toggle.boxed_clone(), cx);
+                }),
+        )
+    }
+
+    pub(crate) fn build_file_path_item(
+        &self,
+        file_path: String,
+        is_dirty: bool,
+    ) -> NativeToolbarItem {
+        let label = if is_dirty {
+            format!("{file_path} \u{2022}")
+        } else {
+            file_path
+        };
         NativeToolbarItem::Button(
-            NativeToolbarButton::new("glass.status.toolchain", toolchain)
+            NativeToolbarButton::new("glass.status.file_path", label)
+                .tool_tip("Reveal in Project Panel")
+                .on_click(|_, window, cx| {
+                    window.dispatch_action(RevealInProjectPanel::default().boxed_clone(), cx);
+                }),
+        )
+    }
+
+    pub(crate) fn build_cursor_item(&self, cursor: String) -> NativeToolbarItem {
+        NativeToolbarItem::Button(
+            NativeToolbarButton::new("glass.status.cursor", cursor)
+                .tool_tip("Go to Line/Column")
+                .on_click(|_, window, cx| {
+                    window.dispatch_action(editor::actions::ToggleGoToLine.boxed_clone(), cx);
+                }),
+        )
+    }
+
+    pub(crate) fn build_toolchain_item(&self, toolchain: ToolchainStatus) -> NativeToolbarItem {
+        let label = match toolchain {
+            ToolchainStatus::Active(name) => name,
+            ToolchainStatus::Unselected { term } => format!("Select {term}"),
+        };
+        NativeToolbarItem::Button(
+            NativeToolbarButton::new("glass.status.toolchain", label)
                 .tool_tip("Select Toolchain")
                 .on_click(|_, window, cx| {
                     window.dispatch_action(toolchain_selector::Select.boxed_clone(), cx);
@@ -43,10 +166,34 @@ impl TitleBar {
     }
 
     pub(crate) fn build_image_info_item(&self, image_info: String) -> NativeToolbarItem {
-        NativeToolbarItem::Label(NativeToolbarLabel::new(
-            "glass.status.image_info",
-            image_info,
-        ))
+        let workspace = self.workspace.clone();
+        NativeToolbarItem::Button(
+            NativeToolbarButton::new(IMAGE_INFO_ITEM_ID, image_info)
+                .tool_tip("Image Details")
+                .on_click(move |_, window, cx| {
+                    if let Some(workspace) = workspace.upgrade()
+                        && let Some(title_bar) = workspace
+                            .read(cx)
+                            .titlebar_item()
+                            .and_then(|item| item.downcast::<TitleBar>().ok())
+                    {
+                        title_bar.update(cx, |title_bar, cx| {
+                            title_bar.show_image_details_popover(IMAGE_INFO_ITEM_ID, window, cx);
+                        });
+                    }
+                }),
+        )
+    }
+
+    /// Stands in for the image info item while the image's metadata is still
+    /// being read.
+    pub(crate) fn build_image_info_loading_item(&self) -> NativeToolbarItem {
+        NativeToolbarItem::Button(
+            NativeToolbarButton::new(IMAGE_INFO_ITEM_ID, "Loading…")
+                .tool_tip("Reading Image Metadata")
+                .icon("hourglass")
+                .enabled(false),
+        )
     }
 
     pub(crate) fn build_sign_in_item(&self) -> NativeToolbarItem {
@@ -98,6 +245,12 @@ impl TitleBar {
         menu_items.push(NativeToolbarMenuItem::action("Themes…").icon("paintbrush"));
         menu_items.push(NativeToolbarMenuItem::action("Icon Themes…").icon("photo"));
         menu_items.push(NativeToolbarMenuItem::action("Extensions").icon("puzzlepiece.extension"));
+        menu_items.push(NativeToolbarMenuItem::separator());
+        menu_items.push(
+            NativeToolbarMenuItem::action("Clear Browsing History…").icon("clock.arrow.circlepath"),
+        );
+        menu_items
+            .push(NativeToolbarMenuItem::action("Export History…").icon("square.and.arrow.up"));
         if signed_in {
             menu_items.push(NativeToolbarMenuItem::separator());
             menu_items.push(
@@ -124,6 +277,7 @@ impl TitleBar {
         }
 
         let client = self.client.clone();
+        let workspace = self.workspace.clone();
         NativeToolbarItem::MenuButton(menu_button.on_select(move |event, window, cx| {
             let show_update_offset = usize::from(show_update);
             let signed_in_offset = usize::from(signed_in);
@@ -151,7 +305,20 @@ impl TitleBar {
                     cx,
                 ),
                 4 => window.dispatch_action(zed_actions::Extensions::default().boxed_clone(), cx),
-                5 if signed_in => {
+                5 => {
+                    if let Some(workspace) = workspace.upgrade()
+                        && let Some(title_bar) = workspace
+                            .read(cx)
+                            .titlebar_item()
+                            .and_then(|item| item.downcast::<TitleBar>().ok())
+                    {
+                        title_bar.update(cx, |title_bar, cx| {
+                            title_bar.show_clear_history_panel(window, cx);
+                        });
+                    }
+                }
+                6 => window.dispatch_action(browser::ExportHistory.boxed_clone(), cx),
+                7 if signed_in => {
                     let client = client.clone();
                     window
                         .spawn(cx, async move |mut cx| {
@@ -164,3 +331,31 @@ impl TitleBar {
         }))
     }
 }
+
+fn language_servers_summary(running_count: usize, failed_count: usize) -> String {
+    let noun = if running_count == 1 {
+        "language server"
+    } else {
+        "language servers"
+    };
+    if failed_count == 0 {
+        format!("{running_count} {noun} running")
+    } else {
+        format!("{running_count} {noun} running, {failed_count} failed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_servers_summary() {
+        assert_eq!(language_servers_summary(0, 0), "0 language servers running");
+        assert_eq!(language_servers_summary(1, 0), "1 language server running");
+        assert_eq!(
+            language_servers_summary(2, 1),
+            "2 language servers running, 1 failed"
+        );
+    }
+}
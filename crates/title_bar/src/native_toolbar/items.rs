@@ -1,22 +1,44 @@
 use gpui::{
     Action, App, NativeToolbarButton, NativeToolbarClickEvent, NativeToolbarControlGroup,
     NativeToolbarGroupControlRepresentation, NativeToolbarGroupEvent, NativeToolbarGroupOption,
-    NativeToolbarItem, Window,
+    NativeToolbarItem, NativeToolbarLabel, Window,
 };
+use settings::Settings;
 use workspace_chrome::{mode_index, mode_label, mode_sf_symbol};
 use workspace_modes::{ModeId, SwitchToBrowserMode, SwitchToEditorMode, SwitchToTerminalMode};
 
 use crate::TitleBar;
 
 impl TitleBar {
-    pub(crate) fn has_restricted_worktrees(&self, cx: &App) -> bool {
+    pub(crate) fn restricted_worktree_count(&self, cx: &App) -> usize {
         project::trusted_worktrees::TrustedWorktrees::try_get_global(cx)
             .map(|trusted_worktrees| {
                 trusted_worktrees
                     .read(cx)
-                    .has_restricted_worktrees(&self.project.read(cx).worktree_store(), cx)
+                    .restricted_worktree_count(&self.project.read(cx).worktree_store(), cx)
             })
-            .unwrap_or(false)
+            .unwrap_or(0)
+    }
+
+    /// Whether any language server is reporting work in progress, such as
+    /// indexing or diagnostics that have yet to be published.
+    pub(crate) fn project_is_busy(&self, cx: &App) -> bool {
+        self.project
+            .read(cx)
+            .language_server_statuses(cx)
+            .any(|(_, status)| {
+                !status.pending_work.is_empty() || status.has_pending_diagnostic_updates
+            })
+    }
+
+    /// Whether edit predictions are shown for files without a more specific
+    /// language override, matching the "All Files" toggle in the predictions menu.
+    pub(crate) fn edit_predictions_enabled(&self, cx: &App) -> bool {
+        let language_settings = language::language_settings::all_language_settings(None, cx);
+        !project::DisableAiSettings::get_global(cx).disable_ai
+            && language_settings.edit_predictions.provider
+                != language::language_settings::EditPredictionProvider::None
+            && language_settings.show_edit_predictions(None, cx)
     }
 
     pub(super) fn build_simple_action_button(
@@ -25,15 +47,34 @@ impl TitleBar {
         icon: &'static str,
         tool_tip: &'static str,
         on_click: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> NativeToolbarItem {
+        self.build_action_button(id, icon, tool_tip, true, on_click)
+    }
+
+    pub(super) fn build_action_button(
+        &self,
+        id: &'static str,
+        icon: &'static str,
+        tool_tip: &'static str,
+        enabled: bool,
+        on_click: impl Fn(&mut Window, &mut App) + 'static,
     ) -> NativeToolbarItem {
         NativeToolbarItem::Button(
             NativeToolbarButton::new(id, "")
                 .tool_tip(tool_tip)
                 .icon(icon)
+                .enabled(enabled)
                 .on_click(move |_: &NativeToolbarClickEvent, window, cx| on_click(window, cx)),
         )
     }
 
+    pub(crate) fn build_terminal_title_item(&self, terminal_title: String) -> NativeToolbarItem {
+        NativeToolbarItem::Label(NativeToolbarLabel::new(
+            "glass.terminal.title",
+            terminal_title,
+        ))
+    }
+
     pub(crate) fn build_mode_switcher_item(&self, active_mode: ModeId) -> NativeToolbarItem {
         let workspace = self.workspace.clone();
         NativeToolbarItem::ControlGroup(
@@ -1,18 +1,83 @@
-use crate::TitleBar;
-use gpui::{Action, App, NativeToolbarButton, NativeToolbarItem};
+use crate::{TitleBar, restricted_mode_label};
+use gpui::{
+    Action, App, Context, NativePopover, NativePopoverAnchor, NativePopoverBehavior,
+    NativePopoverClickableRow, NativePopoverContentItem, NativeToolbarButton, NativeToolbarItem,
+    Window,
+};
 use workspace::ToggleWorktreeSecurity;
 
+const RESTRICTED_MODE_ITEM_ID: &str = "glass.restricted_mode";
+
 impl TitleBar {
     pub(crate) fn build_restricted_mode_item(&self, cx: &App) -> Option<NativeToolbarItem> {
-        self.has_restricted_worktrees(cx).then(|| {
+        let restricted_worktree_count = self.restricted_worktree_count(cx);
+        let workspace = self.workspace.clone();
+        (restricted_worktree_count > 0).then(|| {
             NativeToolbarItem::Button(
-                NativeToolbarButton::new("glass.restricted_mode", "Restricted Mode")
-                    .tool_tip("Manage Worktree Trust")
-                    .icon("exclamationmark.shield")
-                    .on_click(|_, window, cx| {
-                        window.dispatch_action(ToggleWorktreeSecurity.boxed_clone(), cx);
-                    }),
+                NativeToolbarButton::new(
+                    RESTRICTED_MODE_ITEM_ID,
+                    restricted_mode_label(restricted_worktree_count),
+                )
+                .tool_tip("Manage Worktree Trust")
+                .icon("exclamationmark.shield")
+                .on_click(move |_, window, cx| {
+                    if let Some(workspace) = workspace.upgrade()
+                        && let Some(title_bar) = workspace
+                            .read(cx)
+                            .titlebar_item()
+                            .and_then(|item| item.downcast::<TitleBar>().ok())
+                    {
+                        title_bar.update(cx, |title_bar, cx| {
+                            title_bar.show_restricted_worktrees_popover(window, cx);
+                        });
+                    }
+                }),
             )
         })
     }
+
+    fn show_restricted_worktrees_popover(&self, window: &mut Window, cx: &mut Context<Self>) {
+        window.dismiss_native_popover();
+        let restricted_worktrees = self.restricted_worktrees(cx);
+        if restricted_worktrees.is_empty() {
+            return;
+        }
+
+        let row_count = restricted_worktrees.len() + 2;
+        let mut items = vec![NativePopoverContentItem::heading("Restricted Worktrees")];
+        for (worktree_id, abs_path) in restricted_worktrees {
+            let title_bar = cx.entity().downgrade();
+            items.push(
+                NativePopoverClickableRow::new(abs_path.to_string_lossy().into_owned())
+                    .icon("checkmark.shield")
+                    .detail("Trust")
+                    .on_click(move |window, cx| {
+                        if let Some(title_bar) = title_bar.upgrade() {
+                            title_bar.update(cx, |title_bar, cx| {
+                                title_bar.trust_worktree(worktree_id, cx);
+                                title_bar.show_restricted_worktrees_popover(window, cx);
+                            });
+                        }
+                    })
+                    .into(),
+            );
+        }
+        items.push(NativePopoverContentItem::separator());
+        items.push(
+            NativePopoverClickableRow::new("Manage Worktree Trust…")
+                .icon("gearshape")
+                .on_click(|window, cx| {
+                    window.dismiss_native_popover();
+                    window.dispatch_action(ToggleWorktreeSecurity.boxed_clone(), cx);
+                })
+                .into(),
+        );
+
+        window.show_native_popover(
+            NativePopover::new(360.0, row_count as f64 * 28.0 + 32.0)
+                .behavior(NativePopoverBehavior::Transient)
+                .items(items),
+            NativePopoverAnchor::ToolbarItem(RESTRICTED_MODE_ITEM_ID.into()),
+        );
+    }
 }
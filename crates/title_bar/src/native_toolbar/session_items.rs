@@ -1,20 +1,43 @@
 use client::Status as ClientStatus;
-use gpui::{App, NativeToolbarButton, NativeToolbarItem};
+use gpui::{App, Context, NativeToolbarButton, NativeToolbarItem};
+use util::{ConnectionResult, ResultExt as _};
 
 use crate::TitleBar;
 
 impl TitleBar {
     pub(crate) fn build_connection_status_item(&self, _cx: &App) -> Option<NativeToolbarItem> {
         match &*self.client.status().borrow() {
-            ClientStatus::ConnectionError
+            status @ (ClientStatus::ConnectionError
             | ClientStatus::ConnectionLost
             | ClientStatus::Reauthenticating
             | ClientStatus::Reconnecting
-            | ClientStatus::ReconnectionError { .. } => Some(NativeToolbarItem::Button(
-                NativeToolbarButton::new("glass.connection_status", "Disconnected")
-                    .tool_tip("Disconnected")
-                    .icon("wifi.exclamationmark"),
-            )),
+            | ClientStatus::ReconnectionError { .. }) => {
+                let is_reconnecting = self.native_toolbar_state.reconnect_task.is_some()
+                    || matches!(
+                        status,
+                        ClientStatus::Reconnecting | ClientStatus::Reauthenticating
+                    );
+                let workspace = self.workspace.clone();
+                Some(NativeToolbarItem::Button(
+                    NativeToolbarButton::new("glass.connection_status", "Disconnected")
+                        .tool_tip(if is_reconnecting {
+                            "Reconnecting..."
+                        } else {
+                            "Disconnected. Click to Reconnect"
+                        })
+                        .icon("wifi.exclamationmark")
+                        .on_click(move |_, _, cx| {
+                            if let Some(workspace) = workspace.upgrade()
+                                && let Some(title_bar) = workspace
+                                    .read(cx)
+                                    .titlebar_item()
+                                    .and_then(|item| item.downcast::<TitleBar>().ok())
+                            {
+                                title_bar.update(cx, |title_bar, cx| title_bar.reconnect(cx));
+                            }
+                        }),
+                ))
+            }
             ClientStatus::UpgradeRequired => Some(NativeToolbarItem::Button(
                 NativeToolbarButton::new("glass.connection_status", "Update Required")
                     .tool_tip("Please Update to Collaborate")
@@ -37,4 +60,25 @@ impl TitleBar {
             },
         )
     }
+
+    /// Attempts to reconnect right away instead of waiting for the client's
+    /// backoff. Clicks while an attempt is in flight are ignored.
+    fn reconnect(&mut self, cx: &mut Context<Self>) {
+        if self.native_toolbar_state.reconnect_task.is_some() {
+            return;
+        }
+
+        let client = self.client.clone();
+        self.native_toolbar_state.reconnect_task = Some(cx.spawn(async move |this, cx| {
+            if let ConnectionResult::Result(result) = client.connect(true, cx).await {
+                result.log_err();
+            }
+            this.update(cx, |this, cx| {
+                this.native_toolbar_state.reconnect_task = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
 }
@@ -1,5 +1,25 @@
-use browser::history::HistoryMatch;
-use gpui::{Hsla, Subscription};
+use std::collections::{HashMap, HashSet};
+
+use browser::{PageSecurityState, history::HistoryMatch};
+use client::proto;
+use gpui::{Hsla, Subscription, Task};
+use language::{LanguageServerId, LanguageServerName};
+use project::LspStoreEvent;
+use workspace_modes::ModeId;
+
+use crate::title_bar_settings::TitleBarStatusItem;
+
+/// The toolchain status item. `None` in the toolbar state means toolchains
+/// don't apply to the active buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ToolchainStatus {
+    Active(String),
+    /// The language has toolchains, such as Python virtual environments, but
+    /// none is selected. `term` is what the language calls its toolchains.
+    Unselected {
+        term: String,
+    },
+}
 
 #[derive(Default)]
 pub(crate) struct NativeToolbarState {
@@ -8,13 +28,541 @@ pub(crate) struct NativeToolbarState {
     pub(crate) omnibox_panel_dirty: bool,
     pub(crate) omnibox_suggestions: Vec<HistoryMatch>,
     pub(crate) omnibox_selected_index: Option<usize>,
-    pub(crate) last_toolbar_key: String,
+    pub(crate) omnibox_inline_completion: Option<String>,
+    pub(crate) omnibox_completion_suppressed: bool,
+    pub(crate) omnibox_refocus_pending: bool,
+    pub(crate) last_toolbar_state: Option<ToolbarState>,
+    pub(crate) last_status_text: Option<ToolbarStatusText>,
+    pub(crate) pending_status_text: Option<ToolbarStatusText>,
+    pub(crate) status_text_rebuild_task: Option<Task<()>>,
     pub(crate) status_encoding: Option<String>,
     pub(crate) status_line_ending: Option<String>,
-    pub(crate) status_toolchain: Option<String>,
+    pub(crate) status_toolchain: Option<ToolchainStatus>,
     pub(crate) status_image_info: Option<String>,
+    pub(crate) status_image_loading: bool,
+    pub(crate) status_cursor: Option<String>,
+    pub(crate) status_language: Option<String>,
+    pub(crate) status_file_path: Option<String>,
+    /// Whether the active editor has unsaved changes.
+    pub(crate) status_is_dirty: bool,
+    pub(crate) status_system_info: Option<String>,
+    pub(crate) is_busy: bool,
+    pub(crate) edit_predictions_enabled: bool,
+    pub(crate) failed_language_servers: HashSet<LanguageServerName>,
+    pub(crate) language_server_names: HashMap<LanguageServerId, LanguageServerName>,
+    pub(crate) system_info_task: Option<Task<()>>,
+    pub(crate) reconnect_task: Option<Task<()>>,
     pub(crate) active_editor_subscription: Option<Subscription>,
     pub(crate) active_image_subscription: Option<Subscription>,
     pub(crate) active_page_chrome_color: Option<Hsla>,
     pub(crate) active_page_chrome_url: Option<String>,
 }
+
+/// What the native toolbar needs after its inputs are recorded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ToolbarUpdate {
+    Unchanged,
+    Rebuild,
+    /// Only status text changed. The caller schedules a rebuild once the text
+    /// settles, replacing any rebuild it scheduled earlier.
+    DeferRebuild,
+}
+
+impl NativeToolbarState {
+    /// Records the latest toolbar inputs and compares them with the ones the
+    /// native toolbar was last built from.
+    pub(crate) fn update_toolbar_state(
+        &mut self,
+        toolbar_state: ToolbarState,
+        status_text: ToolbarStatusText,
+    ) -> ToolbarUpdate {
+        if self.last_toolbar_state.as_ref() != Some(&toolbar_state) {
+            self.last_toolbar_state = Some(toolbar_state);
+            self.last_status_text = Some(status_text);
+            self.pending_status_text = None;
+            self.status_text_rebuild_task = None;
+            return ToolbarUpdate::Rebuild;
+        }
+        if self.last_status_text.as_ref() == Some(&status_text) {
+            self.pending_status_text = None;
+            self.status_text_rebuild_task = None;
+            return ToolbarUpdate::Unchanged;
+        }
+        if self.pending_status_text.as_ref() == Some(&status_text) {
+            return ToolbarUpdate::Unchanged;
+        }
+        self.pending_status_text = Some(status_text);
+        ToolbarUpdate::DeferRebuild
+    }
+
+    /// Tracks which language servers failed to start or report an error
+    /// health status, returning whether that set changed. A removed server
+    /// no longer counts as failed.
+    pub(crate) fn record_language_server_update(&mut self, event: &LspStoreEvent) -> bool {
+        let (name, status_update) = match event {
+            LspStoreEvent::LanguageServerAdded(id, name, _) => {
+                self.language_server_names.insert(*id, name.clone());
+                return false;
+            }
+            LspStoreEvent::LanguageServerRemoved(id) => {
+                return self
+                    .language_server_names
+                    .remove(id)
+                    .is_some_and(|name| self.failed_language_servers.remove(&name));
+            }
+            LspStoreEvent::LanguageServerUpdate {
+                name: Some(name),
+                message: proto::update_language_server::Variant::StatusUpdate(status_update),
+                ..
+            } => (name, status_update),
+            _ => return false,
+        };
+        let failed = match &status_update.status {
+            Some(proto::status_update::Status::Binary(binary_status)) => {
+                match proto::ServerBinaryStatus::from_i32(*binary_status) {
+                    Some(proto::ServerBinaryStatus::Failed) => true,
+                    Some(
+                        proto::ServerBinaryStatus::None
+                        | proto::ServerBinaryStatus::Starting
+                        | proto::ServerBinaryStatus::Stopped,
+                    ) => false,
+                    _ => return false,
+                }
+            }
+            Some(proto::status_update::Status::Health(health)) => {
+                proto::ServerHealth::from_i32(*health) == Some(proto::ServerHealth::Error)
+            }
+            None => return false,
+        };
+        if failed {
+            self.failed_language_servers.insert(name.clone())
+        } else {
+            self.failed_language_servers.remove(name)
+        }
+    }
+}
+
+/// The collaboration connection states that change the toolbar's connection
+/// item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ConnectionStatus {
+    Connected,
+    ConnectionError,
+    ConnectionLost,
+    Reauthenticating,
+    Reconnecting,
+    ReconnectionError,
+    UpgradeRequired,
+}
+
+/// Every input that affects which items the native toolbar shows. Rebuilding
+/// the toolbar replaces its AppKit items, so it only happens when one of
+/// these fields changes.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ToolbarState {
+    pub(crate) active_mode: ModeId,
+    pub(crate) is_browser_surface_active: bool,
+    pub(crate) omnibox_text: String,
+    pub(crate) is_new_tab_page: bool,
+    pub(crate) browser_tabs_sidebar_visible: bool,
+    pub(crate) can_go_back: bool,
+    pub(crate) can_go_forward: bool,
+    pub(crate) is_loading: bool,
+    pub(crate) is_bookmarked: bool,
+    pub(crate) translation_offer_language: Option<String>,
+    pub(crate) security_state: Option<PageSecurityState>,
+    pub(crate) restricted_worktree_count: usize,
+    pub(crate) is_remote: bool,
+    pub(crate) show_project_items: bool,
+    pub(crate) show_branch_name: bool,
+    pub(crate) user_login: String,
+    pub(crate) connection_status: ConnectionStatus,
+    pub(crate) is_reconnecting: bool,
+    pub(crate) show_update: bool,
+    pub(crate) status_encoding: Option<String>,
+    pub(crate) status_line_ending: Option<String>,
+    pub(crate) status_toolchain: Option<ToolchainStatus>,
+    pub(crate) status_image_info: Option<String>,
+    pub(crate) status_image_loading: bool,
+    pub(crate) has_status_cursor: bool,
+    pub(crate) status_language: Option<String>,
+    pub(crate) has_status_file_path: bool,
+    pub(crate) has_status_system_info: bool,
+    pub(crate) has_terminal_title: bool,
+    pub(crate) is_busy: bool,
+    pub(crate) language_server_count: usize,
+    pub(crate) failed_language_server_count: usize,
+    pub(crate) edit_predictions_enabled: bool,
+    pub(crate) download_percent: Option<u8>,
+    pub(crate) toolbar_items: Vec<TitleBarStatusItem>,
+}
+
+/// Toolbar text that changes while typing or moving the cursor, or as the
+/// system info refreshes. It is kept out of [`ToolbarState`] so that editing
+/// doesn't rebuild the toolbar on every keystroke; the items pick up the new
+/// text once it settles.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ToolbarStatusText {
+    pub(crate) cursor: Option<String>,
+    pub(crate) file_path: Option<String>,
+    pub(crate) is_dirty: bool,
+    pub(crate) terminal_title: Option<String>,
+    pub(crate) system_info: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor_toolbar_state() -> ToolbarState {
+        ToolbarState {
+            active_mode: ModeId::EDITOR,
+            is_browser_surface_active: false,
+            omnibox_text: String::new(),
+            is_new_tab_page: false,
+            browser_tabs_sidebar_visible: false,
+            can_go_back: false,
+            can_go_forward: false,
+            is_loading: false,
+            is_bookmarked: false,
+            translation_offer_language: None,
+            security_state: None,
+            restricted_worktree_count: 0,
+            is_remote: false,
+            show_project_items: true,
+            show_branch_name: true,
+            user_login: "glass-user".to_string(),
+            connection_status: ConnectionStatus::Connected,
+            is_reconnecting: false,
+            show_update: false,
+            status_encoding: None,
+            status_line_ending: None,
+            status_toolchain: None,
+            status_image_info: None,
+            status_image_loading: false,
+            has_status_cursor: true,
+            status_language: Some("Rust".to_string()),
+            has_status_file_path: true,
+            has_status_system_info: false,
+            has_terminal_title: false,
+            is_busy: false,
+            language_server_count: 0,
+            failed_language_server_count: 0,
+            edit_predictions_enabled: true,
+            download_percent: None,
+            toolbar_items: vec![TitleBarStatusItem::Cursor, TitleBarStatusItem::Language],
+        }
+    }
+
+    fn status_text(cursor: &str) -> ToolbarStatusText {
+        ToolbarStatusText {
+            cursor: Some(cursor.to_string()),
+            file_path: Some("src/main.rs".to_string()),
+            is_dirty: false,
+            terminal_title: None,
+            system_info: None,
+        }
+    }
+
+    #[test]
+    fn test_toolbar_rebuilds_only_when_state_changes() {
+        let mut native_toolbar_state = NativeToolbarState::default();
+
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(editor_toolbar_state(), status_text("1:1")),
+            ToolbarUpdate::Rebuild
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(editor_toolbar_state(), status_text("1:1")),
+            ToolbarUpdate::Unchanged
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(
+                ToolbarState {
+                    is_busy: true,
+                    ..editor_toolbar_state()
+                },
+                status_text("1:1")
+            ),
+            ToolbarUpdate::Rebuild
+        );
+
+        native_toolbar_state.last_toolbar_state = None;
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(editor_toolbar_state(), status_text("1:1")),
+            ToolbarUpdate::Rebuild
+        );
+    }
+
+    #[test]
+    fn test_status_text_changes_defer_the_rebuild() {
+        let mut native_toolbar_state = NativeToolbarState::default();
+        native_toolbar_state.update_toolbar_state(editor_toolbar_state(), status_text("1:1"));
+
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(editor_toolbar_state(), status_text("1:2")),
+            ToolbarUpdate::DeferRebuild
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(editor_toolbar_state(), status_text("1:2")),
+            ToolbarUpdate::Unchanged
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(editor_toolbar_state(), status_text("1:3")),
+            ToolbarUpdate::DeferRebuild
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(editor_toolbar_state(), status_text("1:1")),
+            ToolbarUpdate::Unchanged
+        );
+        assert!(native_toolbar_state.pending_status_text.is_none());
+
+        let dirty_text = ToolbarStatusText {
+            is_dirty: true,
+            ..status_text("1:1")
+        };
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(editor_toolbar_state(), dirty_text.clone()),
+            ToolbarUpdate::DeferRebuild
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(
+                ToolbarState {
+                    is_busy: true,
+                    ..editor_toolbar_state()
+                },
+                dirty_text
+            ),
+            ToolbarUpdate::Rebuild
+        );
+        assert!(native_toolbar_state.pending_status_text.is_none());
+    }
+
+    #[test]
+    fn test_toolbar_rebuilds_when_navigation_state_changes() {
+        let mut native_toolbar_state = NativeToolbarState::default();
+        let browser_state = ToolbarState {
+            active_mode: ModeId::BROWSER,
+            is_browser_surface_active: true,
+            ..editor_toolbar_state()
+        };
+
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(browser_state.clone(), status_text("1:1")),
+            ToolbarUpdate::Rebuild
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(
+                ToolbarState {
+                    can_go_back: true,
+                    ..browser_state.clone()
+                },
+                status_text("1:1")
+            ),
+            ToolbarUpdate::Rebuild
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(
+                ToolbarState {
+                    can_go_back: true,
+                    can_go_forward: true,
+                    ..browser_state.clone()
+                },
+                status_text("1:1")
+            ),
+            ToolbarUpdate::Rebuild
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(
+                ToolbarState {
+                    can_go_back: true,
+                    can_go_forward: true,
+                    is_bookmarked: true,
+                    ..browser_state
+                },
+                status_text("1:1")
+            ),
+            ToolbarUpdate::Rebuild
+        );
+    }
+
+    #[test]
+    fn test_toolbar_rebuilds_when_security_state_changes() {
+        let mut native_toolbar_state = NativeToolbarState::default();
+        let insecure_state = ToolbarState {
+            active_mode: ModeId::BROWSER,
+            is_browser_surface_active: true,
+            security_state: Some(PageSecurityState::Insecure),
+            ..editor_toolbar_state()
+        };
+
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(insecure_state.clone(), status_text("1:1")),
+            ToolbarUpdate::Rebuild
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(
+                ToolbarState {
+                    security_state: Some(PageSecurityState::Secure),
+                    ..insecure_state
+                },
+                status_text("1:1")
+            ),
+            ToolbarUpdate::Rebuild
+        );
+    }
+
+    #[test]
+    fn test_system_info_refreshes_defer_the_rebuild() {
+        let mut native_toolbar_state = NativeToolbarState::default();
+        let system_info_state = ToolbarState {
+            has_status_system_info: true,
+            ..editor_toolbar_state()
+        };
+        let system_info_text = |system_info: &str| ToolbarStatusText {
+            system_info: Some(system_info.to_string()),
+            ..status_text("1:1")
+        };
+
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(
+                system_info_state.clone(),
+                system_info_text("83% · 1.2 GB")
+            ),
+            ToolbarUpdate::Rebuild
+        );
+        assert_eq!(
+            native_toolbar_state
+                .update_toolbar_state(system_info_state, system_info_text("83% · 1.3 GB")),
+            ToolbarUpdate::DeferRebuild
+        );
+    }
+
+    #[test]
+    fn test_toolbar_rebuilds_when_active_editor_is_saved() {
+        let mut native_toolbar_state = NativeToolbarState::default();
+        let dirty_text = ToolbarStatusText {
+            is_dirty: true,
+            ..status_text("1:1")
+        };
+
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(editor_toolbar_state(), dirty_text),
+            ToolbarUpdate::Rebuild
+        );
+        assert_eq!(
+            native_toolbar_state.update_toolbar_state(editor_toolbar_state(), status_text("1:1")),
+            ToolbarUpdate::DeferRebuild
+        );
+    }
+
+    #[test]
+    fn test_toolbar_rebuilds_when_terminal_title_changes() {
+        let mut native_toolbar_state = NativeToolbarState::default();
+        let terminal_state = ToolbarState {
+            active_mode: ModeId::TERMINAL,
+            has_terminal_title: true,
+            ..editor_toolbar_state()
+        };
+        let terminal_text = |title: &str| ToolbarStatusText {
+            terminal_title: Some(title.to_string()),
+            ..ToolbarStatusText::default()
+        };
+
+        assert_eq!(
+            native_toolbar_state
+                .update_toolbar_state(terminal_state.clone(), terminal_text("~/glass — zsh")),
+            ToolbarUpdate::Rebuild
+        );
+        assert_eq!(
+            native_toolbar_state
+                .update_toolbar_state(terminal_state.clone(), terminal_text("~/glass — cargo")),
+            ToolbarUpdate::DeferRebuild
+        );
+        assert_eq!(
+            native_toolbar_state
+                .update_toolbar_state(terminal_state, terminal_text("~/glass — zsh")),
+            ToolbarUpdate::Unchanged
+        );
+    }
+
+    fn status_update_event(
+        name: &'static str,
+        status: proto::status_update::Status,
+    ) -> LspStoreEvent {
+        LspStoreEvent::LanguageServerUpdate {
+            language_server_id: language::LanguageServerId(0),
+            name: Some(LanguageServerName::new_static(name)),
+            message: proto::update_language_server::Variant::StatusUpdate(proto::StatusUpdate {
+                message: None,
+                status: Some(status),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_failed_language_servers_follow_status_updates() {
+        let mut native_toolbar_state = NativeToolbarState::default();
+        let failed = status_update_event(
+            "rust-analyzer",
+            proto::status_update::Status::Binary(proto::ServerBinaryStatus::Failed as i32),
+        );
+
+        assert!(native_toolbar_state.record_language_server_update(&failed));
+        assert!(!native_toolbar_state.record_language_server_update(&failed));
+        assert_eq!(native_toolbar_state.failed_language_servers.len(), 1);
+
+        assert!(
+            !native_toolbar_state.record_language_server_update(&status_update_event(
+                "rust-analyzer",
+                proto::status_update::Status::Binary(proto::ServerBinaryStatus::Downloading as i32),
+            ))
+        );
+        assert!(
+            native_toolbar_state.record_language_server_update(&status_update_event(
+                "rust-analyzer",
+                proto::status_update::Status::Binary(proto::ServerBinaryStatus::Starting as i32),
+            ))
+        );
+        assert!(native_toolbar_state.failed_language_servers.is_empty());
+
+        assert!(
+            native_toolbar_state.record_language_server_update(&status_update_event(
+                "typescript-language-server",
+                proto::status_update::Status::Health(proto::ServerHealth::Error as i32),
+            ))
+        );
+        assert!(
+            native_toolbar_state.record_language_server_update(&status_update_event(
+                "typescript-language-server",
+                proto::status_update::Status::Health(proto::ServerHealth::Ok as i32),
+            ))
+        );
+        assert!(native_toolbar_state.failed_language_servers.is_empty());
+    }
+
+    #[test]
+    fn test_removed_language_servers_are_no_longer_failed() {
+        let mut native_toolbar_state = NativeToolbarState::default();
+        let server_id = language::LanguageServerId(1);
+        let added = LspStoreEvent::LanguageServerAdded(
+            server_id,
+            LanguageServerName::new_static("rust-analyzer"),
+            None,
+        );
+        let removed = LspStoreEvent::LanguageServerRemoved(server_id);
+
+        assert!(!native_toolbar_state.record_language_server_update(&added));
+        assert!(
+            native_toolbar_state.record_language_server_update(&status_update_event(
+                "rust-analyzer",
+                proto::status_update::Status::Health(proto::ServerHealth::Error as i32),
+            ))
+        );
+        assert_eq!(native_toolbar_state.failed_language_servers.len(), 1);
+
+        assert!(native_toolbar_state.record_language_server_update(&removed));
+        assert!(native_toolbar_state.failed_language_servers.is_empty());
+        assert!(!native_toolbar_state.record_language_server_update(&removed));
+    }
+}
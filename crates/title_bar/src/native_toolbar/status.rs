@@ -1,10 +1,18 @@
+use super::state::ToolchainStatus;
 use crate::TitleBar;
+use crate::title_bar_settings::TitleBarSettings;
 use editor::{Editor, EditorEvent};
-use gpui::{App, Context, Window};
+use gpui::{
+    App, Context, NativePopover, NativePopoverAnchor, NativePopoverBehavior,
+    NativePopoverContentItem, Window,
+};
 use image_viewer::ImageView;
 use language::LineEnding;
-use project::image_store::{ImageFormat, ImageMetadata};
+use project::image_store::ImageMetadata;
 use settings::Settings;
+use text::Point;
+
+const MAX_FILE_PATH_LENGTH: usize = 48;
 
 impl TitleBar {
     pub(crate) fn refresh_status_data(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -17,6 +25,11 @@ impl TitleBar {
         self.native_toolbar_state.status_line_ending = None;
         self.native_toolbar_state.status_toolchain = None;
         self.native_toolbar_state.status_image_info = None;
+        self.native_toolbar_state.status_image_loading = false;
+        self.native_toolbar_state.status_cursor = None;
+        self.native_toolbar_state.status_language = None;
+        self.native_toolbar_state.status_file_path = None;
+        self.native_toolbar_state.status_is_dirty = false;
         self.native_toolbar_state.active_editor_subscription = None;
         self.native_toolbar_state.active_image_subscription = None;
 
@@ -27,19 +40,30 @@ impl TitleBar {
                         cx.subscribe_in(&editor, window, |_this, _editor, event, _window, cx| {
                             if matches!(
                                 event,
-                                EditorEvent::SelectionsChanged { .. } | EditorEvent::BufferEdited
+                                EditorEvent::SelectionsChanged { .. }
+                                    | EditorEvent::BufferEdited
+                                    | EditorEvent::DirtyChanged
+                                    | EditorEvent::Saved
                             ) {
                                 cx.notify();
                             }
                         }),
                     );
 
-                let (encoding, line_ending) = editor.update(cx, |editor, cx| {
+                let (encoding, line_ending, language, cursor) = editor.update(cx, |editor, cx| {
                     let mut encoding = None;
                     let mut line_ending = None;
+                    let mut language = None;
+                    let cursor = Self::cursor_position(editor, cx);
 
                     if let Some((_, buffer, _)) = editor.active_excerpt(cx) {
                         let buffer = buffer.read(cx);
+                        language = Some(
+                            buffer
+                                .language()
+                                .map(|language| language.name().to_string())
+                                .unwrap_or_else(|| "Plain Text".to_string()),
+                        );
                         let active_encoding = buffer.encoding();
                         if active_encoding != encoding_rs::UTF_8 || buffer.has_bom() {
                             let mut text = active_encoding.name().to_string();
@@ -55,18 +79,39 @@ impl TitleBar {
                         }
                     }
 
-                    (encoding, line_ending)
+                    (encoding, line_ending, language, cursor)
                 });
 
                 self.native_toolbar_state.status_encoding = encoding;
                 self.native_toolbar_state.status_line_ending = line_ending;
+                self.native_toolbar_state.status_cursor = cursor;
+                self.native_toolbar_state.status_language = language;
+
+                let path_style = self.project.read(cx).path_style(cx);
+                self.native_toolbar_state.status_file_path =
+                    item.project_path(cx).map(|project_path| {
+                        truncate_path_middle(
+                            &project_path.path.display(path_style),
+                            MAX_FILE_PATH_LENGTH,
+                        )
+                    });
+                self.native_toolbar_state.status_is_dirty = item.is_dirty(cx);
             }
 
             if let Some(toolchain) = self.right_item_view::<toolchain_selector::ActiveToolchain>() {
-                self.native_toolbar_state.status_toolchain = toolchain
-                    .read(cx)
-                    .active_toolchain_name()
-                    .map(ToOwned::to_owned);
+                let toolchain = toolchain.read(cx);
+                let show_placeholder = TitleBarSettings::get_global(cx).show_toolchain_placeholder
+                    && item.act_as::<Editor>(cx).is_some();
+                self.native_toolbar_state.status_toolchain = match toolchain.active_toolchain_name()
+                {
+                    Some(name) => Some(ToolchainStatus::Active(name.to_string())),
+                    None => toolchain
+                        .toolchain_term()
+                        .filter(|_| show_placeholder)
+                        .map(|term| ToolchainStatus::Unselected {
+                            term: term.to_string(),
+                        }),
+                };
             }
 
             if let Some(image_view) = item.act_as::<ImageView>(cx) {
@@ -74,11 +119,13 @@ impl TitleBar {
                     self.native_toolbar_state.status_image_info =
                         Some(Self::format_image_metadata(&metadata, cx));
                 } else {
+                    self.native_toolbar_state.status_image_loading = true;
                     self.native_toolbar_state.active_image_subscription =
                         Some(cx.observe(&image_view, |title_bar, image_view, cx| {
                             if let Some(metadata) = image_view.read(cx).image_metadata(cx) {
                                 title_bar.native_toolbar_state.status_image_info =
                                     Some(Self::format_image_metadata(&metadata, cx));
+                                title_bar.native_toolbar_state.status_image_loading = false;
                                 cx.notify();
                             }
                         }));
@@ -87,28 +134,260 @@ impl TitleBar {
         }
     }
 
+    fn cursor_position(editor: &mut Editor, cx: &mut Context<Editor>) -> Option<String> {
+        if !matches!(editor.mode(), editor::EditorMode::Full { .. }) {
+            return None;
+        }
+
+        let snapshot = editor.display_snapshot(cx);
+        if snapshot.buffer_snapshot().excerpts().count() == 0 {
+            return None;
+        }
+
+        let head = editor.selections.newest::<Point>(&snapshot).head();
+        snapshot
+            .buffer_snapshot()
+            .point_to_buffer_point(head)
+            .map(|(_, point, _)| format!("{}:{}", point.row + 1, point.column + 1))
+    }
+
+    pub(crate) fn show_image_details_popover(
+        &self,
+        anchor_item_id: &'static str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(item) = self
+            .active_pane
+            .as_ref()
+            .and_then(|pane| pane.read(cx).active_item())
+        else {
+            return;
+        };
+        let Some(metadata) = item
+            .act_as::<ImageView>(cx)
+            .and_then(|image_view| image_view.read(cx).image_metadata(cx))
+        else {
+            return;
+        };
+        let path_style = self.project.read(cx).path_style(cx);
+        let path = item
+            .project_path(cx)
+            .map(|project_path| project_path.path.display(path_style).to_string());
+        let settings = image_viewer::ImageViewerSettings::get_global(cx);
+        let rows = image_detail_rows(
+            &metadata,
+            matches!(settings.unit, image_viewer::ImageFileSizeUnit::Decimal),
+            path,
+        );
+
+        let row_count = rows.len() + 1;
+        let mut items = vec![NativePopoverContentItem::heading("Image Details")];
+        items.extend(rows.into_iter().map(NativePopoverContentItem::small_label));
+        window.dismiss_native_popover();
+        window.show_native_popover(
+            NativePopover::new(320.0, row_count as f64 * 28.0 + 32.0)
+                .behavior(NativePopoverBehavior::Transient)
+                .items(items),
+            NativePopoverAnchor::ToolbarItem(anchor_item_id.into()),
+        );
+    }
+
     fn format_image_metadata(metadata: &ImageMetadata, cx: &App) -> String {
         let settings = image_viewer::ImageViewerSettings::get_global(cx);
-        let mut components = Vec::new();
-        components.push(format!("{}x{}", metadata.width, metadata.height));
-        components.push(util::size::format_file_size(
-            metadata.file_size,
+        image_metadata_summary(
+            metadata,
             matches!(settings.unit, image_viewer::ImageFileSizeUnit::Decimal),
+            settings.show_color_details,
+        )
+    }
+}
+
+fn image_metadata_summary(
+    metadata: &ImageMetadata,
+    use_decimal: bool,
+    show_color_details: bool,
+) -> String {
+    let mut components = vec![
+        format!("{}x{}", metadata.width, metadata.height),
+        util::size::format_file_size(metadata.file_size, use_decimal),
+        image_viewer::image_format_label(metadata.format).to_string(),
+    ];
+    if show_color_details {
+        if let Some(color_space) = metadata.color_space {
+            components.push(color_space.label().to_string());
+        }
+        if let Some(colors) = metadata.colors {
+            components.push(format!("{}-bit", colors.bits_per_channel));
+        }
+    }
+    components.join(" • ")
+}
+
+/// The rows of the image details popover. Color details are always listed
+/// here, when the image provides them, regardless of `show_color_details`.
+fn image_detail_rows(
+    metadata: &ImageMetadata,
+    use_decimal: bool,
+    path: Option<String>,
+) -> Vec<String> {
+    let mut rows = vec![
+        format!("Dimensions: {}×{}", metadata.width, metadata.height),
+        format!(
+            "File Size: {}",
+            util::size::format_file_size(metadata.file_size, use_decimal)
+        ),
+        format!(
+            "Format: {}",
+            image_viewer::image_format_label(metadata.format)
+        ),
+    ];
+    if let Some(color_space) = metadata.color_space {
+        rows.push(format!("Color Space: {}", color_space.label()));
+    }
+    if let Some(colors) = metadata.colors {
+        rows.push(format!(
+            "Bit Depth: {}-bit, {} channels",
+            colors.bits_per_channel, colors.channels
         ));
-        components.push(
-            match metadata.format {
-                ImageFormat::Png => "PNG",
-                ImageFormat::Jpeg => "JPEG",
-                ImageFormat::Gif => "GIF",
-                ImageFormat::WebP => "WebP",
-                ImageFormat::Tiff => "TIFF",
-                ImageFormat::Bmp => "BMP",
-                ImageFormat::Ico => "ICO",
-                ImageFormat::Avif => "Avif",
-                _ => "Unknown",
-            }
-            .to_string(),
+    }
+    if let Some(path) = path {
+        rows.push(format!("Path: {path}"));
+    }
+    rows
+}
+
+/// Shortens `path` to at most `max_chars` characters by replacing its middle
+/// with an ellipsis, so both the root directory and the file name stay readable.
+fn truncate_path_middle(path: &str, max_chars: usize) -> String {
+    let char_count = path.chars().count();
+    if char_count <= max_chars || max_chars < 2 {
+        return path.to_string();
+    }
+
+    let kept = max_chars - 1;
+    let tail_len = kept.div_ceil(2);
+    let head_len = kept - tail_len;
+    let head = path.chars().take(head_len);
+    let tail = path.chars().skip(char_count - tail_len);
+    head.chain(std::iter::once('…')).chain(tail).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use project::image_store::{ImageColorInfo, ImageColorSpace, ImageFormat};
+
+    #[test]
+    fn test_truncate_path_middle() {
+        assert_eq!(truncate_path_middle("src/main.rs", 20), "src/main.rs");
+        assert_eq!(
+            truncate_path_middle("crates/title_bar/src/native_toolbar.rs", 16),
+            "crates/…olbar.rs"
+        );
+        assert_eq!(
+            truncate_path_middle("crates/title_bar/src/native_toolbar.rs", 16)
+                .chars()
+                .count(),
+            16
         );
-        components.join(" • ")
+    }
+
+    #[test]
+    fn test_image_metadata_summary() {
+        let formats = [
+            (ImageFormat::Png, "PNG"),
+            (ImageFormat::Jpeg, "JPEG"),
+            (ImageFormat::Gif, "GIF"),
+            (ImageFormat::WebP, "WebP"),
+            (ImageFormat::Tiff, "TIFF"),
+            (ImageFormat::Bmp, "BMP"),
+            (ImageFormat::Ico, "ICO"),
+            (ImageFormat::Avif, "AVIF"),
+            (ImageFormat::Pnm, "PNM"),
+            (ImageFormat::Tga, "TGA"),
+            (ImageFormat::Dds, "DDS"),
+            (ImageFormat::Hdr, "HDR"),
+            (ImageFormat::OpenExr, "OpenEXR"),
+            (ImageFormat::Farbfeld, "Farbfeld"),
+            (ImageFormat::Qoi, "QOI"),
+        ];
+        for (format, label) in formats {
+            let metadata = ImageMetadata {
+                width: 640,
+                height: 480,
+                file_size: 2048,
+                colors: None,
+                color_space: None,
+                format,
+            };
+            assert_eq!(
+                image_metadata_summary(&metadata, false, true),
+                format!(
+                    "640x480 • {} • {label}",
+                    util::size::format_file_size(2048, false)
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_image_metadata_summary_color_details() {
+        let metadata = ImageMetadata {
+            width: 4032,
+            height: 3024,
+            file_size: 2048,
+            colors: Some(ImageColorInfo {
+                channels: 3,
+                bits_per_channel: 16,
+            }),
+            color_space: Some(ImageColorSpace::DisplayP3),
+            format: ImageFormat::Png,
+        };
+        let file_size = util::size::format_file_size(2048, false);
+
+        assert_eq!(
+            image_metadata_summary(&metadata, false, false),
+            format!("4032x3024 • {file_size} • PNG")
+        );
+        assert_eq!(
+            image_metadata_summary(&metadata, false, true),
+            format!("4032x3024 • {file_size} • PNG • Display P3 • 16-bit")
+        );
+    }
+
+    #[test]
+    fn test_image_detail_rows() {
+        let metadata = ImageMetadata {
+            width: 640,
+            height: 480,
+            file_size: 2048,
+            colors: Some(ImageColorInfo {
+                channels: 4,
+                bits_per_channel: 8,
+            }),
+            color_space: Some(ImageColorSpace::Srgb),
+            format: ImageFormat::Png,
+        };
+        let file_size = util::size::format_file_size(2048, false);
+
+        assert_eq!(
+            image_detail_rows(&metadata, false, Some("assets/logo.png".to_string())),
+            [
+                "Dimensions: 640×480".to_string(),
+                format!("File Size: {file_size}"),
+                "Format: PNG".to_string(),
+                "Color Space: sRGB".to_string(),
+                "Bit Depth: 8-bit, 4 channels".to_string(),
+                "Path: assets/logo.png".to_string(),
+            ]
+        );
+
+        let metadata = ImageMetadata {
+            colors: None,
+            color_space: None,
+            ..metadata
+        };
+        assert_eq!(image_detail_rows(&metadata, false, None).len(), 3);
     }
 }
@@ -0,0 +1,156 @@
+use std::{
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use gpui::{Context, NativeToolbarItem, NativeToolbarLabel};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::TitleBar;
+
+const SYSTEM_INFO_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+/// Reading the battery spawns `pmset`, so it is polled far less often than
+/// memory, which is read in-process.
+const BATTERY_REFRESH_INTERVAL: Duration = Duration::from_secs(120);
+
+impl TitleBar {
+    /// Starts or stops the periodic system info refresh so that it only runs
+    /// while `title_bar.show_system_info` is enabled.
+    pub(crate) fn sync_system_info_refresh(&mut self, enabled: bool, cx: &mut Context<Self>) {
+        if !enabled {
+            self.native_toolbar_state.system_info_task = None;
+            self.native_toolbar_state.status_system_info = None;
+            return;
+        }
+        if self.native_toolbar_state.system_info_task.is_some() {
+            return;
+        }
+
+        self.native_toolbar_state.system_info_task = Some(cx.spawn(async move |this, cx| {
+            let mut system = System::new();
+            let mut battery = BatteryReader::default();
+            loop {
+                let (system_info, returned_system, returned_battery) = cx
+                    .background_spawn(async move {
+                        let system_info = read_system_info(&mut system, &mut battery);
+                        (system_info, system, battery)
+                    })
+                    .await;
+                system = returned_system;
+                battery = returned_battery;
+
+                let updated = this.update(cx, |title_bar, cx| {
+                    if title_bar.native_toolbar_state.status_system_info != system_info {
+                        title_bar.native_toolbar_state.status_system_info = system_info;
+                        cx.notify();
+                    }
+                });
+                if updated.is_err() {
+                    break;
+                }
+
+                cx.background_executor()
+                    .timer(SYSTEM_INFO_REFRESH_INTERVAL)
+                    .await;
+            }
+        }));
+    }
+
+    pub(crate) fn build_system_info_item(&self, system_info: String) -> NativeToolbarItem {
+        NativeToolbarItem::Label(NativeToolbarLabel::new(
+            "glass.status.system_info",
+            system_info,
+        ))
+    }
+}
+
+#[derive(Default)]
+struct BatteryReader {
+    percentage: Option<u8>,
+    last_read_at: Option<Instant>,
+    has_no_battery: bool,
+}
+
+impl BatteryReader {
+    fn percentage(&mut self) -> Option<u8> {
+        if self.has_no_battery {
+            return None;
+        }
+        if self
+            .last_read_at
+            .is_some_and(|last_read_at| last_read_at.elapsed() < BATTERY_REFRESH_INTERVAL)
+        {
+            return self.percentage;
+        }
+
+        self.last_read_at = Some(Instant::now());
+        let output = Command::new("pmset").args(["-g", "batt"]).output().ok()?;
+        self.percentage = parse_battery_percentage(&String::from_utf8_lossy(&output.stdout));
+        // A machine without a battery won't grow one, so stop spawning `pmset`.
+        self.has_no_battery = output.status.success() && self.percentage.is_none();
+        self.percentage
+    }
+}
+
+fn read_system_info(system: &mut System, battery: &mut BatteryReader) -> Option<String> {
+    let memory = sysinfo::get_current_pid().ok().and_then(|pid| {
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing().with_memory(),
+        );
+        system.process(pid).map(|process| process.memory())
+    });
+    format_system_info(
+        battery.percentage(),
+        memory.map(|bytes| util::size::format_file_size(bytes, true)),
+    )
+}
+
+/// Extracts the internal battery charge from `pmset -g batt` output. Machines
+/// without a battery only report their power source, so this returns `None`.
+fn parse_battery_percentage(pmset_output: &str) -> Option<u8> {
+    let battery_line = pmset_output
+        .lines()
+        .find(|line| line.contains("InternalBattery"))?;
+    let (before_percent, _) = battery_line.split_once('%')?;
+    let digits_start = before_percent
+        .rfind(|character: char| !character.is_ascii_digit())
+        .map_or(0, |index| index + 1);
+    before_percent[digits_start..].parse().ok()
+}
+
+fn format_system_info(battery_percentage: Option<u8>, memory: Option<String>) -> Option<String> {
+    let battery = battery_percentage.map(|percentage| format!("{percentage}%"));
+    match (battery, memory) {
+        (Some(battery), Some(memory)) => Some(format!("{battery} · {memory}")),
+        (battery, memory) => battery.or(memory),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_battery_percentage() {
+        let laptop_output = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=4653155)\t83%; discharging; 5:12 remaining present: true\n";
+        assert_eq!(parse_battery_percentage(laptop_output), Some(83));
+
+        let desktop_output = "Now drawing from 'AC Power'\n";
+        assert_eq!(parse_battery_percentage(desktop_output), None);
+    }
+
+    #[test]
+    fn test_format_system_info_omits_missing_battery() {
+        assert_eq!(
+            format_system_info(Some(83), Some("1.2 GB".to_string())).as_deref(),
+            Some("83% · 1.2 GB")
+        );
+        assert_eq!(
+            format_system_info(None, Some("1.2 GB".to_string())).as_deref(),
+            Some("1.2 GB")
+        );
+        assert_eq!(format_system_info(None, None), None);
+    }
+}
@@ -20,7 +20,7 @@ use crate::application_menu::{
     ActivateDirection, ActivateMenuLeft, ActivateMenuRight, OpenApplicationMenu,
 };
 #[cfg(target_os = "macos")]
-use browser::BrowserView;
+use browser::{BrowserView, OmniboxFocusRequested};
 
 use auto_update::AutoUpdateStatus;
 use client::{Client, UserStore, zed_urls};
@@ -33,28 +33,34 @@ use gpui::{
     WeakEntity, Window, actions, div, native_button, native_icon_button,
 };
 use onboarding_banner::OnboardingBanner;
-use project::{Project, git_store::GitStoreEvent, trusted_worktrees::TrustedWorktrees};
+use project::{
+    Project,
+    git_store::GitStoreEvent,
+    trusted_worktrees::{PathTrust, TrustedWorktrees},
+};
 use remote::RemoteConnectionOptions;
 use settings::Settings;
 use settings::WorktreeId;
 use std::collections::HashSet;
+use std::path::Path;
 use std::sync::Arc;
 use theme::ActiveTheme;
 use title_bar_settings::TitleBarSettings;
 #[allow(unused_imports)]
 use ui::{
-    Avatar, ButtonLike, ContextMenu, IconWithIndicator, Indicator, PopoverMenu, PopoverMenuHandle,
-    TintColor, Tooltip, prelude::*, utils::platform_title_bar_height,
+    Avatar, ButtonLike, ContextMenu, ContextMenuEntry, IconWithIndicator, Indicator, PopoverMenu,
+    PopoverMenuHandle, TintColor, Tooltip, prelude::*, right_click_menu,
+    utils::platform_title_bar_height,
 };
 use update_version::UpdateVersion;
 use util::ResultExt;
 #[allow(unused_imports)]
 use workspace::{
     CloseProjectNavigation, FocusProjectNavigation, MultiWorkspace, Pane, TitleBarItemViewHandle,
-    ToggleProjectNavigation, ToggleWorktreeSecurity, Workspace, WorkspaceId,
+    ToggleProjectNavigation, ToggleWorktreeSecurity, Workspace, WorkspaceId, item::ItemEvent,
     notifications::NotifyResultExt,
 };
-use workspace_chrome::ModeControl;
+use workspace_chrome::{ModeControl, mode_icon, mode_label};
 #[allow(unused_imports)]
 use workspace_modes::{
     ModeId, ModeViewRegistry, SwitchToBrowserMode, SwitchToEditorMode, SwitchToTerminalMode,
@@ -173,10 +179,13 @@ pub struct TitleBar {
     update_version: Entity<UpdateVersion>,
     right_items: Vec<Box<dyn TitleBarItemViewHandle>>,
     active_pane: Option<Entity<Pane>>,
+    active_mode: ModeId,
+    observed_terminal: (Option<EntityId>, Option<EntityId>),
+    terminal_title_subscriptions: Vec<Subscription>,
     #[cfg(target_os = "macos")]
     observed_browser_view: Option<WeakEntity<BrowserView>>,
     #[cfg(target_os = "macos")]
-    browser_view_subscription: Option<Subscription>,
+    browser_view_subscriptions: Vec<Subscription>,
     #[cfg(target_os = "macos")]
     active_browser_tab_id: Option<EntityId>,
     #[cfg(target_os = "macos")]
@@ -186,6 +195,7 @@ pub struct TitleBar {
 impl Render for TitleBar {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.sync_multi_workspace(window, cx);
+        self.sync_terminal_title_subscription(window, cx);
         #[cfg(target_os = "macos")]
         {
             self.sync_browser_view_subscription(window, cx);
@@ -204,18 +214,13 @@ impl TitleBar {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
-        let title_bar_settings = *TitleBarSettings::get_global(cx);
+        let title_bar_settings = TitleBarSettings::get_global(cx).clone();
         let button_layout = title_bar_settings.button_layout;
 
         let show_menus = show_menus(cx);
 
-        let active_mode = self
-            .workspace
-            .upgrade()
-            .map(|ws| ws.read(cx).active_mode_id());
-
-        let is_browser_mode = active_mode == Some(ModeId::BROWSER);
-        let is_terminal_mode = active_mode == Some(ModeId::TERMINAL);
+        let is_browser_mode = self.active_mode == ModeId::BROWSER;
+        let is_terminal_mode = self.active_mode == ModeId::TERMINAL;
 
         let mut children = Vec::new();
 
@@ -243,15 +248,19 @@ impl TitleBar {
                                 title_bar.children(self.render_project_host(cx))
                             })
                         })
+                        .when(!is_browser_mode && !is_terminal_mode, |title_bar| {
+                            title_bar.children(self.render_active_file_name(cx))
+                        })
+                        .when(is_terminal_mode, |title_bar| {
+                            title_bar.children(self.render_active_terminal_title(cx))
+                        })
                 })
                 .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
                 .into_any_element(),
         );
 
-        let titlebar_center = active_mode.and_then(|mode_id| {
-            ModeViewRegistry::try_global(cx)
-                .and_then(|reg| reg.titlebar_center_view(mode_id).cloned())
-        });
+        let titlebar_center = ModeViewRegistry::try_global(cx)
+            .and_then(|reg| reg.titlebar_center_view(self.active_mode).cloned());
 
         if let Some(center_view) = titlebar_center {
             children.push(
@@ -376,10 +385,18 @@ impl TitleBar {
         subscriptions.push(cx.subscribe_in(
             &workspace_handle,
             window,
-            |this, workspace, event: &workspace::Event, window, cx| {
-                if matches!(event, workspace::Event::ActiveItemChanged) {
+            |this, workspace, event: &workspace::Event, window, cx| match event {
+                workspace::Event::ActiveItemChanged => {
                     this.set_active_pane(&workspace.read(cx).active_pane().clone(), window, cx);
                 }
+                workspace::Event::ActiveModeChanged(mode_id) => {
+                    this.active_mode = *mode_id;
+                    #[cfg(target_os = "macos")]
+                    this.invalidate_native_toolbar(cx);
+                    #[cfg(not(target_os = "macos"))]
+                    cx.notify();
+                }
+                _ => {}
             },
         ));
         subscriptions.push(
@@ -408,6 +425,48 @@ impl TitleBar {
             }),
         );
         subscriptions.push(cx.observe(&user_store, |_a, _, cx| cx.notify()));
+        // Language server progress notifies the LSP store often; only re-render
+        // when the native activity item needs to switch between idle and busy.
+        #[cfg(target_os = "macos")]
+        subscriptions.push(
+            cx.observe(&project.read(cx).lsp_store(), |this: &mut Self, _, cx| {
+                let is_busy = this.project_is_busy(cx);
+                if this
+                    .native_toolbar_state
+                    .last_toolbar_state
+                    .as_ref()
+                    .is_some_and(|toolbar_state| toolbar_state.is_busy != is_busy)
+                {
+                    cx.notify();
+                }
+            }),
+        );
+        #[cfg(target_os = "macos")]
+        subscriptions.push(cx.observe_global::<settings::SettingsStore>(|this, cx| {
+            if this.native_toolbar_state.edit_predictions_enabled
+                != this.edit_predictions_enabled(cx)
+            {
+                cx.notify();
+            }
+        }));
+        #[cfg(target_os = "macos")]
+        subscriptions.push(cx.subscribe(
+            &project.read(cx).lsp_store(),
+            |this: &mut Self, _, event: &project::LspStoreEvent, cx| {
+                let servers_changed = matches!(
+                    event,
+                    project::LspStoreEvent::LanguageServerAdded(..)
+                        | project::LspStoreEvent::LanguageServerRemoved(_)
+                );
+                if this
+                    .native_toolbar_state
+                    .record_language_server_update(event)
+                    || servers_changed
+                {
+                    cx.notify();
+                }
+            },
+        ));
         subscriptions.push(cx.observe_button_layout_changed(window, move |_, _, cx| {
             let title_bar = title_bar.clone();
             cx.defer(move |cx| {
@@ -483,6 +542,7 @@ impl TitleBar {
             platform_titlebar,
             application_menu,
             workspace: workspace.weak_handle(),
+            active_mode: workspace.active_mode_id(),
             multi_workspace: None,
             project,
             user_store,
@@ -492,10 +552,12 @@ impl TitleBar {
             update_version,
             right_items: Vec::new(),
             active_pane: None,
+            observed_terminal: (None, None),
+            terminal_title_subscriptions: Vec::new(),
             #[cfg(target_os = "macos")]
             observed_browser_view: None,
             #[cfg(target_os = "macos")]
-            browser_view_subscription: None,
+            browser_view_subscriptions: Vec::new(),
             #[cfg(target_os = "macos")]
             active_browser_tab_id: None,
             #[cfg(target_os = "macos")]
@@ -550,6 +612,65 @@ impl TitleBar {
         cx.notify();
     }
 
+    /// Re-renders when the focused terminal changes or retitles itself, as
+    /// its foreground process or working directory changes.
+    fn sync_terminal_title_subscription(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let terminal_pane = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| workspace.read(cx).active_terminal_pane(cx));
+        let terminal_item = terminal_pane
+            .as_ref()
+            .and_then(|pane| pane.read(cx).active_item());
+        let observed_terminal = (
+            terminal_pane.as_ref().map(Entity::entity_id),
+            terminal_item.as_ref().map(|item| item.item_id()),
+        );
+        if self.observed_terminal == observed_terminal {
+            return;
+        }
+
+        self.observed_terminal = observed_terminal;
+        self.terminal_title_subscriptions.clear();
+        if let Some(terminal_pane) = terminal_pane {
+            self.terminal_title_subscriptions
+                .push(cx.observe(&terminal_pane, |_, _, cx| cx.notify()));
+        }
+        if let Some(terminal_item) = terminal_item {
+            let title_bar = cx.weak_entity();
+            self.terminal_title_subscriptions
+                .push(terminal_item.subscribe_to_item_events(
+                    window,
+                    cx,
+                    Box::new(move |event, _, cx| {
+                        if event == ItemEvent::UpdateTab {
+                            title_bar.update(cx, |_, cx| cx.notify()).ok();
+                        }
+                    }),
+                ));
+        }
+    }
+
+    fn active_terminal_title(&self, cx: &App) -> Option<SharedString> {
+        self.workspace
+            .upgrade()?
+            .read(cx)
+            .active_terminal_title(cx)
+            .filter(|title| !title.trim().is_empty())
+    }
+
+    fn render_active_terminal_title(&self, cx: &App) -> Option<impl IntoElement> {
+        let title = self.active_terminal_title(cx)?;
+        Some(
+            h_flex().px_1().child(
+                Label::new(title)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .truncate(),
+            ),
+        )
+    }
+
     fn render_right_items(&self) -> impl IntoElement {
         h_flex()
             .gap_1()
@@ -575,7 +696,7 @@ impl TitleBar {
             return;
         }
 
-        self.browser_view_subscription = None;
+        self.browser_view_subscriptions.clear();
         self.observed_browser_view = browser_view.as_ref().map(Entity::downgrade);
         self.active_browser_tab_id = browser_view.as_ref().and_then(|browser_view| {
             browser_view
@@ -585,20 +706,27 @@ impl TitleBar {
         });
 
         if let Some(browser_view) = browser_view {
-            self.browser_view_subscription =
-                Some(
-                    cx.observe_in(&browser_view, window, |this, browser_view, window, cx| {
-                        let active_browser_tab_id = browser_view
-                            .read(cx)
-                            .active_tab()
-                            .map(|tab| tab.entity_id());
-                        if this.active_browser_tab_id != active_browser_tab_id {
-                            this.active_browser_tab_id = active_browser_tab_id;
-                            window.dismiss_native_search_suggestion_menu();
-                        }
-                        cx.notify();
-                    }),
-                );
+            self.browser_view_subscriptions.push(cx.observe_in(
+                &browser_view,
+                window,
+                |this, browser_view, window, cx| {
+                    let active_browser_tab_id = browser_view
+                        .read(cx)
+                        .active_tab()
+                        .map(|tab| tab.entity_id());
+                    if this.active_browser_tab_id != active_browser_tab_id {
+                        this.active_browser_tab_id = active_browser_tab_id;
+                        window.dismiss_native_search_suggestion_menu();
+                    }
+                    cx.notify();
+                },
+            ));
+            self.browser_view_subscriptions.push(cx.subscribe(
+                &browser_view,
+                |this, _, _: &OmniboxFocusRequested, cx| {
+                    this.begin_omnibox_editing(cx);
+                },
+            ));
         }
     }
 
@@ -834,29 +962,76 @@ impl TitleBar {
     }
 
     pub fn render_restricted_mode(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
-        let has_restricted_worktrees = TrustedWorktrees::try_get_global(cx)
+        let restricted_worktree_count = TrustedWorktrees::try_get_global(cx)
             .map(|trusted_worktrees| {
                 trusted_worktrees
                     .read(cx)
-                    .has_restricted_worktrees(&self.project.read(cx).worktree_store(), cx)
+                    .restricted_worktree_count(&self.project.read(cx).worktree_store(), cx)
             })
-            .unwrap_or(false);
-        if !has_restricted_worktrees {
+            .unwrap_or(0);
+        if restricted_worktree_count == 0 {
             return None;
         }
 
-        let button = native_button("restricted_mode_trigger", "Restricted Mode")
-            .button_style(NativeButtonStyle::Filled)
-            .tint(NativeButtonTint::Warning)
-            .on_click({
-                cx.listener(move |this, _, window, cx| {
-                    this.workspace
-                        .update(cx, |workspace, cx| {
-                            workspace.show_worktree_trust_security_modal(true, window, cx)
-                        })
-                        .log_err();
-                })
-            });
+        let title_bar = cx.entity().downgrade();
+        let workspace = self.workspace.clone();
+        let button = PopoverMenu::new("restricted-mode-menu")
+            .menu(move |window, cx| {
+                let title_bar = title_bar.clone();
+                let workspace = workspace.clone();
+                Some(ContextMenu::build_persistent(
+                    window,
+                    cx,
+                    move |mut menu, _, cx| {
+                        let Some(title_bar) = title_bar.upgrade() else {
+                            return menu;
+                        };
+                        menu = menu.header("Restricted Worktrees");
+                        for (worktree_id, abs_path) in title_bar.read(cx).restricted_worktrees(cx) {
+                            let trust = {
+                                let title_bar = title_bar.downgrade();
+                                move |_: &mut Window, cx: &mut App| {
+                                    title_bar
+                                        .update(cx, |title_bar, cx| {
+                                            title_bar.trust_worktree(worktree_id, cx)
+                                        })
+                                        .log_err();
+                                }
+                            };
+                            menu = menu.entry_with_end_slot(
+                                abs_path.to_string_lossy().into_owned(),
+                                None,
+                                trust.clone(),
+                                IconName::Check,
+                                "Trust".into(),
+                                trust,
+                            );
+                        }
+                        let workspace = workspace.clone();
+                        menu.separator().entry(
+                            "Manage Worktree Trust…",
+                            None,
+                            move |window, cx| {
+                                workspace
+                                    .update(cx, |workspace, cx| {
+                                        workspace
+                                            .show_worktree_trust_security_modal(true, window, cx)
+                                    })
+                                    .log_err();
+                            },
+                        )
+                    },
+                ))
+            })
+            .trigger(
+                native_button(
+                    "restricted_mode_trigger",
+                    restricted_mode_label(restricted_worktree_count),
+                )
+                .button_style(NativeButtonStyle::Filled)
+                .tint(NativeButtonTint::Warning),
+            )
+            .anchor(gpui::Corner::TopLeft);
 
         if cfg!(macos_sdk_26) {
             // Make up for Tahoe's traffic light buttons having less spacing around them
@@ -866,6 +1041,58 @@ impl TitleBar {
         }
     }
 
+    /// The project's restricted worktrees, ordered by path.
+    pub(crate) fn restricted_worktrees(&self, cx: &App) -> Vec<(WorktreeId, Arc<Path>)> {
+        let Some(trusted_worktrees) = TrustedWorktrees::try_get_global(cx) else {
+            return Vec::new();
+        };
+        let mut restricted_worktrees = trusted_worktrees
+            .read(cx)
+            .restricted_worktrees(&self.project.read(cx).worktree_store(), cx)
+            .into_iter()
+            .collect::<Vec<_>>();
+        restricted_worktrees.sort_by(|(_, left_path), (_, right_path)| left_path.cmp(right_path));
+        restricted_worktrees
+    }
+
+    /// Trusts a single worktree. Once the last restricted worktree is trusted,
+    /// the Restricted Mode button and its menu disappear on the next render.
+    pub(crate) fn trust_worktree(&mut self, worktree_id: WorktreeId, cx: &mut Context<Self>) {
+        let Some(trusted_worktrees) = TrustedWorktrees::try_get_global(cx) else {
+            return;
+        };
+        let worktree_store = self.project.read(cx).worktree_store();
+        trusted_worktrees.update(cx, |trusted_worktrees, cx| {
+            trusted_worktrees.trust(
+                &worktree_store,
+                [PathTrust::Worktree(worktree_id)].into_iter().collect(),
+                cx,
+            );
+        });
+        cx.notify();
+    }
+
+    /// The active item's file name, followed by a dot while it has unsaved
+    /// changes.
+    fn render_active_file_name(&self, cx: &App) -> Option<impl IntoElement> {
+        let item = self.active_pane.as_ref()?.read(cx).active_item()?;
+        let file_name = item.project_path(cx)?.path.file_name()?.to_string();
+        let is_dirty = item.is_dirty(cx);
+        Some(
+            h_flex()
+                .px_1()
+                .gap_1()
+                .child(
+                    Label::new(file_name)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .when(is_dirty, |this| {
+                    this.child(Indicator::dot().color(Color::Modified))
+                }),
+        )
+    }
+
     pub fn render_project_host(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
         if self.project.read(cx).is_via_remote_server() {
             return self.render_remote_project_connection(cx);
@@ -881,15 +1108,27 @@ impl TitleBar {
 
         let host = self.project.read(cx).host()?;
         let host_user = self.user_store.read(cx).get_cached_user(host.user_id)?;
+        let is_following_host = self
+            .workspace
+            .upgrade()
+            .is_some_and(|workspace| workspace.read(cx).is_being_followed(host.peer_id));
         Some(
             native_button("project_owner_trigger", host_user.github_login.clone())
-                .button_style(NativeButtonStyle::Inline)
+                .button_style(if is_following_host {
+                    NativeButtonStyle::Filled
+                } else {
+                    NativeButtonStyle::Inline
+                })
                 .on_click({
                     let host_peer_id = host.peer_id;
                     cx.listener(move |this, _, window, cx| {
                         this.workspace
                             .update(cx, |workspace, cx| {
-                                workspace.follow(host_peer_id, window, cx);
+                                if workspace.is_being_followed(host_peer_id) {
+                                    workspace.unfollow(host_peer_id, window, cx);
+                                } else {
+                                    workspace.follow(host_peer_id, window, cx);
+                                }
                             })
                             .log_err();
                     })
@@ -900,26 +1139,37 @@ impl TitleBar {
     fn render_mode_switcher(
         &self,
         _window: &mut Window,
-        cx: &mut Context<Self>,
+        _cx: &mut Context<Self>,
     ) -> impl IntoElement {
-        let active_mode = self
-            .workspace
-            .upgrade()
-            .map(|ws| ws.read(cx).active_mode_id())
-            .unwrap_or(ModeId::BROWSER);
+        let active_mode = self.active_mode;
 
-        ModeControl::new(active_mode).on_mode_select(move |mode_id, window, cx| match mode_id {
-            ModeId::BROWSER => {
-                window.dispatch_action(SwitchToBrowserMode.boxed_clone(), cx);
-            }
-            ModeId::EDITOR => {
-                window.dispatch_action(SwitchToEditorMode.boxed_clone(), cx);
-            }
-            ModeId::TERMINAL => {
-                window.dispatch_action(SwitchToTerminalMode.boxed_clone(), cx);
-            }
-            _ => {}
-        })
+        right_click_menu("mode-switcher-menu")
+            .trigger(move |_, _, _| {
+                ModeControl::new(active_mode).on_mode_select(|mode_id, window, cx| {
+                    if let Some(action) = mode_switch_action(mode_id) {
+                        window.dispatch_action(action, cx);
+                    }
+                })
+            })
+            .menu(move |window, cx| {
+                ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    for mode_id in [ModeId::BROWSER, ModeId::EDITOR, ModeId::TERMINAL] {
+                        let Some(action) = mode_switch_action(mode_id) else {
+                            continue;
+                        };
+                        menu = menu.item(
+                            ContextMenuEntry::new(mode_label(mode_id))
+                                .icon(mode_icon(mode_id))
+                                .toggleable(IconPosition::End, mode_id == active_mode)
+                                .action(action.boxed_clone())
+                                .handler(move |window, cx| {
+                                    window.dispatch_action(action.boxed_clone(), cx);
+                                }),
+                        );
+                    }
+                    menu
+                })
+            })
     }
 
     #[cfg(not(target_os = "macos"))]
@@ -1096,14 +1346,10 @@ impl TitleBar {
             .anchor(gpui::Corner::TopLeft)
     }
 
-    fn browser_surface_active(&self, cx: &App) -> bool {
-        let Some(workspace) = self.workspace.upgrade() else {
-            return false;
-        };
-
+    fn browser_surface_active(&self) -> bool {
         // Embedded browser panes render their controls in the pane toolbar.
         // The window title bar only adopts browser chrome in Browser mode itself.
-        workspace.read(cx).active_mode_id() == ModeId::BROWSER
+        self.active_mode == ModeId::BROWSER
     }
 
     fn render_editor_nav_buttons(&self, cx: &mut Context<Self>) -> impl IntoElement {
@@ -1476,3 +1722,16 @@ impl TitleBar {
             .anchor(gpui::Corner::TopRight)
     }
 }
+
+pub(crate) fn restricted_mode_label(restricted_worktree_count: usize) -> String {
+    format!("Restricted Mode ({restricted_worktree_count})")
+}
+
+fn mode_switch_action(mode_id: ModeId) -> Option<Box<dyn Action>> {
+    match mode_id {
+        ModeId::BROWSER => Some(SwitchToBrowserMode.boxed_clone()),
+        ModeId::EDITOR => Some(SwitchToEditorMode.boxed_clone()),
+        ModeId::TERMINAL => Some(SwitchToTerminalMode.boxed_clone()),
+        _ => None,
+    }
+}
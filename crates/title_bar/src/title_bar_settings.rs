@@ -1,7 +1,9 @@
 use gpui::WindowButtonLayout;
 use settings::{RegisterSetting, Settings, SettingsContent};
 
-#[derive(Copy, Clone, Debug, RegisterSetting)]
+pub use settings::TitleBarStatusItem;
+
+#[derive(Clone, Debug, RegisterSetting)]
 pub struct TitleBarSettings {
     pub show_branch_icon: bool,
     #[cfg(not(target_os = "macos"))]
@@ -12,6 +14,9 @@ pub struct TitleBarSettings {
     pub show_sign_in: bool,
     pub show_user_menu: bool,
     pub show_menus: bool,
+    pub show_system_info: bool,
+    pub show_toolchain_placeholder: bool,
+    pub toolbar_items: Vec<TitleBarStatusItem>,
     pub button_layout: Option<WindowButtonLayout>,
 }
 
@@ -28,6 +33,9 @@ impl Settings for TitleBarSettings {
             show_sign_in: content.show_sign_in.unwrap(),
             show_user_menu: content.show_user_menu.unwrap(),
             show_menus: content.show_menus.unwrap(),
+            show_system_info: content.show_system_info.unwrap(),
+            show_toolchain_placeholder: content.show_toolchain_placeholder.unwrap(),
+            toolbar_items: content.toolbar_items.unwrap().0,
             button_layout: content.button_layout.unwrap_or_default().into_layout(),
         }
     }
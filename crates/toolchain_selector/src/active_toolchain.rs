@@ -16,6 +16,8 @@ use crate::ToolchainSelector;
 
 pub struct ActiveToolchain {
     active_toolchain: Option<Toolchain>,
+    /// Whether the active buffer's language has toolchains to pick from.
+    supports_toolchains: bool,
     term: SharedString,
     workspace: WeakEntity<Workspace>,
     active_buffer: Option<(WorktreeId, WeakEntity<Buffer>, Subscription)>,
@@ -47,6 +49,7 @@ impl ActiveToolchain {
         }
         Self {
             active_toolchain: None,
+            supports_toolchains: false,
             active_buffer: None,
             term: SharedString::new_static("Toolchain"),
             workspace: workspace.weak_handle(),
@@ -56,6 +59,8 @@ impl ActiveToolchain {
     }
     fn spawn_tracker_task(window: &mut Window, cx: &mut Context<Self>) -> Task<Option<()>> {
         cx.spawn_in(window, async move |this, cx| {
+            this.update(cx, |this, _| this.supports_toolchains = false)
+                .ok();
             let did_set_toolchain = maybe!(async {
                 let active_file = this
                     .read_with(cx, |this, _| {
@@ -79,6 +84,7 @@ impl ActiveToolchain {
                     .await?;
                 let _ = this.update(cx, |this, cx| {
                     this.term = meta.term;
+                    this.supports_toolchains = true;
                     cx.notify();
                 });
                 let (worktree_id, path) = active_file
@@ -236,6 +242,12 @@ impl ActiveToolchain {
             .as_ref()
             .map(|toolchain| toolchain.name.as_ref())
     }
+
+    /// What the active buffer's language calls its toolchains, such as
+    /// "Virtual Environment", or `None` when toolchains don't apply to it.
+    pub fn toolchain_term(&self) -> Option<&SharedString> {
+        self.supports_toolchains.then_some(&self.term)
+    }
 }
 
 impl Render for ActiveToolchain {
@@ -8,11 +8,21 @@ use crate::prelude::*;
 #[derive(IntoElement, RegisterComponent)]
 pub struct CountBadge {
     count: usize,
+    color: Color,
 }
 
 impl CountBadge {
     pub fn new(count: usize) -> Self {
-        Self { count }
+        Self {
+            count,
+            color: Color::Error,
+        }
+    }
+
+    /// Sets the status color the badge background is tinted with.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
     }
 }
 
@@ -28,7 +38,7 @@ impl RenderOnce for CountBadge {
             .theme()
             .colors()
             .editor_background
-            .blend(cx.theme().status().error.opacity(0.4));
+            .blend(self.color.color(cx).opacity(0.4));
 
         h_flex()
             .absolute()
@@ -85,6 +95,12 @@ impl Component for CountBadge {
                             "Capped Count",
                             container().child(CountBadge::new(150)).into_any_element(),
                         ),
+                        single_example(
+                            "Warning Count",
+                            container()
+                                .child(CountBadge::new(7).color(Color::Warning))
+                                .into_any_element(),
+                        ),
                     ],
                 ))
                 .into_any_element(),
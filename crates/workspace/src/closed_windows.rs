@@ -0,0 +1,96 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use gpui::{App, Global};
+use util::ResultExt as _;
+use workspace_modes::{ModeId, ModeViewRegistry};
+
+use crate::{AppState, SerializedMultiWorkspace, restore_multiworkspace};
+
+/// How many closed windows "Reopen Closed Window" can bring back.
+const MAX_CLOSED_WINDOWS: usize = 5;
+
+/// What a window held when it was closed: its workspaces, its per-window
+/// state and the saved state of its mode views, such as browser tabs.
+pub(crate) struct ClosedWindow {
+    pub(crate) multi_workspace: SerializedMultiWorkspace,
+    pub(crate) mode_snapshots: Vec<(ModeId, String)>,
+}
+
+#[derive(Default)]
+struct ClosedWindows(VecDeque<ClosedWindow>);
+
+impl Global for ClosedWindows {}
+
+pub(crate) fn push_closed_window(closed_window: ClosedWindow, cx: &mut App) {
+    let closed_windows = &mut cx.default_global::<ClosedWindows>().0;
+    closed_windows.push_front(closed_window);
+    closed_windows.truncate(MAX_CLOSED_WINDOWS);
+}
+
+/// Reopens the most recently closed window, doing nothing if none was closed
+/// since launch.
+pub(crate) fn reopen_closed_window(app_state: Arc<AppState>, cx: &mut App) {
+    if !cx.has_global::<ClosedWindows>() {
+        return;
+    }
+    let Some(closed_window) = cx.global_mut::<ClosedWindows>().0.pop_front() else {
+        return;
+    };
+
+    cx.spawn(async move |cx| {
+        let restored = restore_multiworkspace(closed_window.multi_workspace, app_state, cx).await?;
+        for error in restored.errors {
+            log::error!("failed to reopen a workspace of a closed window: {error:#}");
+        }
+        restored
+            .window_handle
+            .update(cx, |multi_workspace, _, cx| {
+                for (mode_id, snapshot) in closed_window.mode_snapshots {
+                    let Some(handler) = ModeViewRegistry::try_global(cx)
+                        .and_then(|registry| registry.window_snapshot_handler(mode_id))
+                    else {
+                        continue;
+                    };
+                    if let Some(mode_view) = multi_workspace.shared_mode_view(mode_id) {
+                        (handler.restore)(&mode_view, snapshot, cx);
+                    }
+                }
+            })?;
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::model::MultiWorkspaceState;
+    use gpui::TestAppContext;
+
+    fn closed_window(index: usize) -> ClosedWindow {
+        ClosedWindow {
+            multi_workspace: SerializedMultiWorkspace {
+                workspaces: Vec::new(),
+                state: MultiWorkspaceState::default(),
+            },
+            mode_snapshots: vec![(ModeId::BROWSER, index.to_string())],
+        }
+    }
+
+    #[gpui::test]
+    fn test_closed_windows_keep_the_most_recent(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            for index in 0..MAX_CLOSED_WINDOWS + 2 {
+                push_closed_window(closed_window(index), cx);
+            }
+
+            let closed_windows = &cx.global::<ClosedWindows>().0;
+            assert_eq!(closed_windows.len(), MAX_CLOSED_WINDOWS);
+            assert_eq!(
+                closed_windows[0].mode_snapshots,
+                [(ModeId::BROWSER, (MAX_CLOSED_WINDOWS + 1).to_string())]
+            );
+        });
+    }
+}
@@ -3,6 +3,7 @@ use crate::{DraggedDock, Event, ModalLayer, Pane};
 use crate::{MultiWorkspace, Workspace};
 use anyhow::Context as _;
 use client::proto;
+use project::Project;
 
 use gpui::{
     Action, AnyView, App, Axis, Context, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
@@ -30,17 +31,35 @@ actions!(
         ToggleProjectSearch,
         /// Toggles the project diagnostics view open or closed.
         ToggleProjectDiagnostics,
+        /// Moves keyboard focus to the sections of the dock button bar.
+        FocusSidebarSections,
+        /// Selects the previous section in the dock button bar.
+        SelectPreviousSidebarSection,
+        /// Selects the next section in the dock button bar.
+        SelectNextSidebarSection,
     ]
 );
 
 pub(crate) const RESIZE_HANDLE_SIZE: Pixels = px(6.);
 
+/// The sidebar sections in the order their rows appear in the dock button bar.
+const SIDEBAR_SECTION_ORDER: [crate::WorkspaceSidebarSection; 5] = [
+    crate::WorkspaceSidebarSection::Project,
+    crate::WorkspaceSidebarSection::Git,
+    crate::WorkspaceSidebarSection::BrowserTabs,
+    crate::WorkspaceSidebarSection::Terminal,
+    crate::WorkspaceSidebarSection::Services,
+];
+
 /// Shared sidebar chrome rendered above dock or hosted sidebar content.
 /// This is a separate entity to avoid borrow conflicts when reading workspace
 /// state during render - when this entity renders, the workspace update is complete.
 pub struct DockButtonBar {
     workspace: WeakEntity<Workspace>,
     language_server_button: Option<AnyView>,
+    focus_handle: FocusHandle,
+    /// The section highlighted by the arrow keys, activated on confirm.
+    keyboard_section: Option<crate::WorkspaceSidebarSection>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -76,15 +95,97 @@ fn show_project_sidebar_tab(
     }
 }
 
+fn activate_sidebar_section(
+    workspace: &WeakEntity<Workspace>,
+    multi_workspace: Option<&Entity<MultiWorkspace>>,
+    section: crate::WorkspaceSidebarSection,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    match section {
+        crate::WorkspaceSidebarSection::Project => {
+            show_project_sidebar_tab(workspace, multi_workspace, false, window, cx);
+            return;
+        }
+        crate::WorkspaceSidebarSection::Services => {
+            if let Some(multi_workspace) = multi_workspace
+                && multi_workspace.read(cx).sidebar_open()
+            {
+                multi_workspace.update(cx, |multi_workspace, cx| {
+                    multi_workspace.close_sidebar(window, cx);
+                });
+            }
+
+            window.dispatch_action(OpenServices.boxed_clone(), cx);
+            return;
+        }
+        crate::WorkspaceSidebarSection::Git
+        | crate::WorkspaceSidebarSection::BrowserTabs
+        | crate::WorkspaceSidebarSection::Terminal => {}
+    }
+
+    if let Some(multi_workspace) = multi_workspace
+        && multi_workspace.read(cx).sidebar_open()
+    {
+        multi_workspace.update(cx, |multi_workspace, cx| {
+            multi_workspace.close_sidebar(window, cx);
+        });
+    }
+
+    if let Some(workspace) = workspace.upgrade() {
+        workspace.update(cx, |workspace, cx| {
+            workspace.select_sidebar_section(section, window, cx);
+        });
+    }
+}
+
+fn adjacent_sidebar_section(
+    section: crate::WorkspaceSidebarSection,
+    forward: bool,
+) -> crate::WorkspaceSidebarSection {
+    let section_count = SIDEBAR_SECTION_ORDER.len();
+    let index = SIDEBAR_SECTION_ORDER
+        .iter()
+        .position(|candidate| *candidate == section)
+        .unwrap_or(0);
+    let adjacent_index = if forward {
+        (index + 1) % section_count
+    } else {
+        (index + section_count - 1) % section_count
+    };
+    SIDEBAR_SECTION_ORDER[adjacent_index]
+}
+
 impl DockButtonBar {
-    pub fn new(workspace: WeakEntity<Workspace>, cx: &mut App) -> Entity<Self> {
-        cx.new(|_cx| Self {
+    pub fn new(
+        workspace: WeakEntity<Workspace>,
+        project: &Entity<Project>,
+        cx: &mut App,
+    ) -> Entity<Self> {
+        cx.new(|cx| Self {
             workspace,
             language_server_button: None,
-            _subscriptions: vec![],
+            focus_handle: cx.focus_handle(),
+            keyboard_section: None,
+            _subscriptions: vec![cx.subscribe(project, |_, _, event, cx| {
+                if let project::Event::DiagnosticsUpdated { .. } = event {
+                    cx.notify();
+                }
+            })],
         })
     }
 
+    /// Re-renders the bar when the bottom dock opens or closes, so its toggle
+    /// stays highlighted while the dock is visible.
+    pub(crate) fn observe_bottom_dock(
+        &mut self,
+        bottom_dock: &Entity<Dock>,
+        cx: &mut Context<Self>,
+    ) {
+        self._subscriptions
+            .push(cx.observe(bottom_dock, |_, _, cx| cx.notify()));
+    }
+
     pub fn set_language_server_button(
         &mut self,
         language_server_button: Option<AnyView>,
@@ -93,6 +194,67 @@ impl DockButtonBar {
         self.language_server_button = language_server_button;
         cx.notify();
     }
+
+    fn select_previous_sidebar_section(
+        &mut self,
+        _: &SelectPreviousSidebarSection,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_adjacent_sidebar_section(false, cx);
+    }
+
+    fn select_next_sidebar_section(
+        &mut self,
+        _: &SelectNextSidebarSection,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_adjacent_sidebar_section(true, cx);
+    }
+
+    fn select_adjacent_sidebar_section(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let Some(section) = self.highlighted_section(cx) else {
+            return;
+        };
+        self.keyboard_section = Some(adjacent_sidebar_section(section, forward));
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(section) = self.keyboard_section.take() else {
+            return;
+        };
+        let multi_workspace = window.root::<MultiWorkspace>().flatten();
+        activate_sidebar_section(
+            &self.workspace,
+            multi_workspace.as_ref(),
+            section,
+            window,
+            cx,
+        );
+        cx.notify();
+    }
+
+    pub(crate) fn focus_sections(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.keyboard_section = None;
+        window.focus(&self.focus_handle, cx);
+        cx.notify();
+    }
+
+    fn highlighted_section(&self, cx: &App) -> Option<crate::WorkspaceSidebarSection> {
+        let workspace = self.workspace.upgrade()?;
+        Some(
+            self.keyboard_section
+                .unwrap_or_else(|| workspace.read(cx).active_sidebar_section()),
+        )
+    }
+}
+
+impl Focusable for DockButtonBar {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
 }
 
 impl Render for DockButtonBar {
@@ -104,7 +266,10 @@ impl Render for DockButtonBar {
         let workspace_read = workspace.read(cx);
 
         let multi_workspace = window.root::<MultiWorkspace>().flatten();
-        let active_sidebar_section = workspace_read.active_sidebar_section();
+        let active_sidebar_section = self
+            .keyboard_section
+            .filter(|_| self.focus_handle.is_focused(window))
+            .unwrap_or_else(|| workspace_read.active_sidebar_section());
         let project = workspace_read.project();
         let selected_worktree = workspace_read
             .active_worktree_override()
@@ -281,23 +446,13 @@ impl Render for DockButtonBar {
                     let workspace = self.workspace.clone();
                     let multi_workspace = multi_workspace.clone();
                     move |_, window, cx| {
-                        if let Some(multi_workspace) = multi_workspace.as_ref()
-                            && multi_workspace.read(cx).sidebar_open()
-                        {
-                            multi_workspace.update(cx, |multi_workspace, cx| {
-                                multi_workspace.close_sidebar(window, cx);
-                            });
-                        }
-
-                        if let Some(workspace) = workspace.upgrade() {
-                            workspace.update(cx, |workspace, cx| {
-                                workspace.select_sidebar_section(
-                                    crate::WorkspaceSidebarSection::Git,
-                                    window,
-                                    cx,
-                                );
-                            });
-                        }
+                        activate_sidebar_section(
+                            &workspace,
+                            multi_workspace.as_ref(),
+                            crate::WorkspaceSidebarSection::Git,
+                            window,
+                            cx,
+                        );
                     }
                 })
                 .into_any_element(),
@@ -310,23 +465,13 @@ impl Render for DockButtonBar {
                     let workspace = self.workspace.clone();
                     let multi_workspace = multi_workspace.clone();
                     move |_, window, cx| {
-                        if let Some(multi_workspace) = multi_workspace.as_ref()
-                            && multi_workspace.read(cx).sidebar_open()
-                        {
-                            multi_workspace.update(cx, |multi_workspace, cx| {
-                                multi_workspace.close_sidebar(window, cx);
-                            });
-                        }
-
-                        if let Some(workspace) = workspace.upgrade() {
-                            workspace.update(cx, |workspace, cx| {
-                                workspace.select_sidebar_section(
-                                    crate::WorkspaceSidebarSection::BrowserTabs,
-                                    window,
-                                    cx,
-                                );
-                            });
-                        }
+                        activate_sidebar_section(
+                            &workspace,
+                            multi_workspace.as_ref(),
+                            crate::WorkspaceSidebarSection::BrowserTabs,
+                            window,
+                            cx,
+                        );
                     }
                 })
                 .into_any_element(),
@@ -339,23 +484,13 @@ impl Render for DockButtonBar {
                     let workspace = self.workspace.clone();
                     let multi_workspace = multi_workspace.clone();
                     move |_, window, cx| {
-                        if let Some(multi_workspace) = multi_workspace.as_ref()
-                            && multi_workspace.read(cx).sidebar_open()
-                        {
-                            multi_workspace.update(cx, |multi_workspace, cx| {
-                                multi_workspace.close_sidebar(window, cx);
-                            });
-                        }
-
-                        if let Some(workspace) = workspace.upgrade() {
-                            workspace.update(cx, |workspace, cx| {
-                                workspace.select_sidebar_section(
-                                    crate::WorkspaceSidebarSection::Terminal,
-                                    window,
-                                    cx,
-                                );
-                            });
-                        }
+                        activate_sidebar_section(
+                            &workspace,
+                            multi_workspace.as_ref(),
+                            crate::WorkspaceSidebarSection::Terminal,
+                            window,
+                            cx,
+                        );
                     }
                 })
                 .into_any_element(),
@@ -365,22 +500,22 @@ impl Render for DockButtonBar {
             SidebarRow::new("sidebar-services", "Services", IconName::Server)
                 .selected(active_sidebar_section == crate::WorkspaceSidebarSection::Services)
                 .on_click({
+                    let workspace = self.workspace.clone();
                     let multi_workspace = multi_workspace.clone();
                     move |_, window, cx| {
-                        if let Some(multi_workspace) = multi_workspace.as_ref()
-                            && multi_workspace.read(cx).sidebar_open()
-                        {
-                            multi_workspace.update(cx, |multi_workspace, cx| {
-                                multi_workspace.close_sidebar(window, cx);
-                            });
-                        }
-
-                        window.dispatch_action(OpenServices.boxed_clone(), cx);
+                        activate_sidebar_section(
+                            &workspace,
+                            multi_workspace.as_ref(),
+                            crate::WorkspaceSidebarSection::Services,
+                            window,
+                            cx,
+                        );
                     }
                 })
                 .into_any_element(),
         );
 
+        let bottom_dock_open = workspace_read.bottom_dock.read(cx).is_open();
         let radius = cx.theme().component_radius().panel.unwrap_or(px(10.0));
         let diagnostics = project.read(cx).diagnostic_summary(false, cx);
         let (diagnostics_icon, diagnostics_icon_color) = if diagnostics.error_count > 0 {
@@ -390,6 +525,13 @@ impl Render for DockButtonBar {
         } else {
             (IconName::Check, Color::Success)
         };
+        let diagnostics_badge = if diagnostics.error_count > 0 {
+            Some(CountBadge::new(diagnostics.error_count).color(Color::Error))
+        } else if diagnostics.warning_count > 0 {
+            Some(CountBadge::new(diagnostics.warning_count).color(Color::Warning))
+        } else {
+            None
+        };
 
         let supplementary_actions = h_flex()
             .w_full()
@@ -438,33 +580,63 @@ impl Render for DockButtonBar {
                         window.dispatch_action(OpenRuntimeActions.boxed_clone(), cx);
                     })
                     .into_any_element(),
-                IconButton::new("sidebar-action-diagnostics", diagnostics_icon)
+                div()
+                    .relative()
+                    .child(
+                        IconButton::new("sidebar-action-diagnostics", diagnostics_icon)
+                            .shape(IconButtonShape::Square)
+                            .style(ButtonStyle::Transparent)
+                            .size(ButtonSize::Compact)
+                            .icon_size(IconSize::Small)
+                            .icon_color(diagnostics_icon_color)
+                            .tooltip(|_window, cx| {
+                                Tooltip::for_action(
+                                    "Project Diagnostics",
+                                    &ToggleProjectDiagnostics,
+                                    cx,
+                                )
+                            })
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(ToggleProjectDiagnostics.boxed_clone(), cx);
+                            }),
+                    )
+                    .children(diagnostics_badge)
+                    .into_any_element(),
+                IconButton::new("sidebar-action-debugger", IconName::Debug)
                     .shape(IconButtonShape::Square)
                     .style(ButtonStyle::Transparent)
                     .size(ButtonSize::Compact)
                     .icon_size(IconSize::Small)
-                    .icon_color(diagnostics_icon_color)
                     .tooltip(|_window, cx| {
-                        Tooltip::for_action("Project Diagnostics", &ToggleProjectDiagnostics, cx)
+                        Tooltip::for_action(
+                            "Toggle Debug Panel",
+                            &zed_actions::debug_panel::Toggle,
+                            cx,
+                        )
                     })
                     .on_click(|_, window, cx| {
-                        window.dispatch_action(ToggleProjectDiagnostics.boxed_clone(), cx);
+                        window.dispatch_action(zed_actions::debug_panel::Toggle.boxed_clone(), cx);
                     })
                     .into_any_element(),
-                IconButton::new("sidebar-action-debugger", IconName::Debug)
+                IconButton::new("sidebar-action-bottom-dock", IconName::TerminalAlt)
                     .shape(IconButtonShape::Square)
                     .style(ButtonStyle::Transparent)
                     .size(ButtonSize::Compact)
                     .icon_size(IconSize::Small)
-                    .tooltip(|_window, cx| {
+                    .toggle_state(bottom_dock_open)
+                    .tooltip(move |_window, cx| {
                         Tooltip::for_action(
-                            "Toggle Debug Panel",
-                            &zed_actions::debug_panel::Toggle,
+                            if bottom_dock_open {
+                                "Close Bottom Dock"
+                            } else {
+                                "Open Bottom Dock"
+                            },
+                            &crate::ToggleBottomDock,
                             cx,
                         )
                     })
                     .on_click(|_, window, cx| {
-                        window.dispatch_action(zed_actions::debug_panel::Toggle.boxed_clone(), cx);
+                        window.dispatch_action(crate::ToggleBottomDock.boxed_clone(), cx);
                     })
                     .into_any_element(),
             ])
@@ -479,6 +651,11 @@ impl Render for DockButtonBar {
         );
 
         div()
+            .key_context("DockButtonBar")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_previous_sidebar_section))
+            .on_action(cx.listener(Self::select_next_sidebar_section))
+            .on_action(cx.listener(Self::confirm))
             .w_full()
             .flex()
             .flex_col()
@@ -999,6 +1176,23 @@ impl Dock {
         zoomed: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        self.zoom_panel(panel, zoomed, window, cx);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.serialize_workspace(window, cx);
+            })
+            .ok();
+    }
+
+    /// Like [`Self::set_panel_zoomed`], but leaves serializing the workspace
+    /// to the caller, for use while the workspace is being updated.
+    pub(crate) fn zoom_panel(
+        &mut self,
+        panel: &AnyView,
+        zoomed: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         for entry in &mut self.panel_entries {
             if entry.panel.panel_id() == panel.entity_id() {
@@ -1009,12 +1203,6 @@ impl Dock {
                 entry.panel.set_zoomed(false, window, cx);
             }
         }
-
-        self.workspace
-            .update(cx, |workspace, cx| {
-                workspace.serialize_workspace(window, cx);
-            })
-            .ok();
         cx.notify();
     }
 
@@ -1197,8 +1385,24 @@ impl Dock {
     pub fn restore_state(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
         if let Some(serialized) = self.serialized_dock.clone() {
             let mut activated_panel = false;
-            if let Some(active_panel) = serialized.active_panel.filter(|_| serialized.visible)
-                && let Some(idx) = self.panel_index_for_persistent_name(active_panel.as_str(), cx)
+            // Panels register one at a time, so the serialized panel may not exist yet.
+            // Falling back to the first enabled panel keeps a visible dock from showing
+            // nothing; once the serialized panel registers, this runs again and selects it.
+            // The fallback only applies while nothing is active, so later registrations
+            // don't undo a panel the user picked or a dock they closed.
+            let active_panel_index = serialized
+                .active_panel
+                .as_deref()
+                .and_then(|name| self.panel_index_for_persistent_name(name, cx))
+                .or_else(|| {
+                    if self.active_panel_index.is_none() {
+                        self.first_enabled_panel_idx(cx).ok()
+                    } else {
+                        None
+                    }
+                });
+            if serialized.visible
+                && let Some(idx) = active_panel_index
             {
                 // Activate the panel directly without querying visible_content_size,
                 // which would try to read the window root entity (MultiWorkspace) and
@@ -1395,6 +1599,59 @@ impl Dock {
         }
     }
 
+    fn render_bottom_dock_tabs(&self, window: &Window, cx: &Context<Self>) -> Option<AnyElement> {
+        if self.position != DockPosition::Bottom {
+            return None;
+        }
+
+        let tabs = self
+            .panel_entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.panel.enabled(cx))
+            .filter_map(|(panel_index, entry)| {
+                let icon = entry.panel.icon(window, cx)?;
+                let label = entry.panel.icon_tooltip(window, cx)?;
+                let is_active = self.active_panel_index == Some(panel_index);
+                Some(
+                    Button::new(("bottom-dock-tab", panel_index), label)
+                        .style(ButtonStyle::Subtle)
+                        .size(ButtonSize::Compact)
+                        .label_size(LabelSize::Small)
+                        .start_icon(Icon::new(icon).size(IconSize::Small).color(if is_active {
+                            Color::Default
+                        } else {
+                            Color::Muted
+                        }))
+                        .toggle_state(is_active)
+                        .on_click(cx.listener(move |dock, _, window, cx| {
+                            dock.activate_panel(panel_index, window, cx);
+                            if let Some(panel) = dock.active_panel() {
+                                window.focus(&panel.panel_focus_handle(cx), cx);
+                            }
+                        }))
+                        .into_any_element(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if tabs.len() < 2 {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .w_full()
+                .gap_1()
+                .px_1()
+                .py_0p5()
+                .border_b_1()
+                .border_color(cx.theme().colors().border_variant)
+                .children(tabs)
+                .into_any_element(),
+        )
+    }
+
     fn render_native_sidebar_content(
         &mut self,
         _window: &mut Window,
@@ -1588,6 +1845,7 @@ impl Render for Dock {
                         .when_some(self.dock_button_bar.clone(), |this, dock_button_bar| {
                             this.child(dock_button_bar)
                         })
+                        .children(self.render_bottom_dock_tabs(window, cx))
                         .child(div().flex().flex_1().overflow_hidden().child(
                             content.cached(StyleRefinement::default().v_flex().size_full()),
                         )),
@@ -4,10 +4,10 @@ use gpui::native_sidebar;
 use gpui::{
     AnyView, App, Context, DragMoveEvent, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
     ManagedView, Pixels, Render, Subscription, Task, Tiling, Window, WindowBackgroundAppearance,
-    WindowId, actions,
+    WindowId, actions, px,
 };
 #[cfg(not(target_os = "macos"))]
-use gpui::{MouseButton, deferred, px};
+use gpui::{MouseButton, deferred};
 use project::Project;
 use std::future::Future;
 use std::path::PathBuf;
@@ -21,9 +21,15 @@ use zed_actions::agents_sidebar::MoveWorkspaceToNewWindow;
 #[cfg(not(target_os = "macos"))]
 pub const SIDEBAR_RESIZE_HANDLE_SIZE: Pixels = px(6.0);
 
+const MIN_RESTORED_SIDEBAR_WIDTH: Pixels = px(160.0);
+const MAX_RESTORED_SIDEBAR_WIDTH: Pixels = px(480.0);
+
 use crate::{
     CloseIntent, CloseWindow, DockPosition, Event as WorkspaceEvent, Item, ModalView, Panel,
-    Workspace, WorkspaceId, WorkspaceSidebarHost, client_side_decorations,
+    SerializedMultiWorkspace, SerializedWorkspaceLocation, SessionWorkspace, Workspace,
+    WorkspaceId, WorkspaceLocation, WorkspaceSidebarHost, client_side_decorations,
+    closed_windows::{ClosedWindow, push_closed_window},
+    persistence::model::MultiWorkspaceState,
 };
 
 actions!(
@@ -45,6 +51,12 @@ actions!(
         NextWorkspace,
         /// Switches to the previous workspace.
         PreviousWorkspace,
+        /// Scales up the whole interface of the current window, chrome included.
+        IncreaseUiScale,
+        /// Scales down the whole interface of the current window, chrome included.
+        DecreaseUiScale,
+        /// Resets the interface scale of the current window.
+        ResetUiScale,
     ]
 );
 
@@ -148,8 +160,11 @@ pub struct MultiWorkspace {
     workspace_sidebar_host: Entity<WorkspaceSidebarHost>,
     sidebar_open: bool,
     sidebar_has_notifications: bool,
+    sidebar_width_restored: bool,
+    ui_scale: f32,
     pending_removal_tasks: Vec<Task<()>>,
     _serialize_task: Option<Task<()>>,
+    _serialize_sidebar_width_task: Option<Task<()>>,
     _create_task: Option<Task<()>>,
     shared_mode_views: collections::HashMap<ModeId, RegisteredModeView>,
     _subscriptions: Vec<Subscription>,
@@ -170,10 +185,14 @@ impl MultiWorkspace {
             });
         }
 
-        let release_subscription = cx.on_release(|this: &mut MultiWorkspace, _cx| {
+        let release_subscription = cx.on_release(|this: &mut MultiWorkspace, cx| {
+            theme::remove_ui_scale(this.window_id, cx);
             if let Some(task) = this._serialize_task.take() {
                 task.detach();
             }
+            if let Some(task) = this._serialize_sidebar_width_task.take() {
+                task.detach();
+            }
             for task in std::mem::take(&mut this.pending_removal_tasks) {
                 task.detach();
             }
@@ -196,8 +215,11 @@ impl MultiWorkspace {
             workspace_sidebar_host,
             sidebar_open: false,
             sidebar_has_notifications: false,
+            sidebar_width_restored: false,
+            ui_scale: 1.0,
             pending_removal_tasks: Vec::new(),
             _serialize_task: None,
+            _serialize_sidebar_width_task: None,
             _create_task: None,
             shared_mode_views,
             _subscriptions: vec![release_subscription, quit_subscription],
@@ -258,6 +280,9 @@ impl MultiWorkspace {
         }
 
         self.sidebar_open = open;
+        if open {
+            self.restore_sidebar_width(cx);
+        }
         cx.notify();
     }
 
@@ -335,10 +360,84 @@ impl MultiWorkspace {
         }
 
         self.sidebar_open = true;
+        self.restore_sidebar_width(cx);
         self.serialize(cx);
         cx.notify();
     }
 
+    pub fn ui_scale(&self) -> f32 {
+        self.ui_scale
+    }
+
+    /// Scales the whole interface of this window, including the title bar,
+    /// docks and mode views, which all size themselves in rems.
+    pub fn set_ui_scale(&mut self, ui_scale: f32, cx: &mut Context<Self>) {
+        let ui_scale = theme::set_ui_scale(self.window_id, ui_scale, cx);
+        if self.ui_scale == ui_scale {
+            return;
+        }
+        self.ui_scale = ui_scale;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn increase_ui_scale(&mut self, _: &IncreaseUiScale, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_ui_scale(self.ui_scale + theme::UI_SCALE_STEP, cx);
+    }
+
+    fn decrease_ui_scale(&mut self, _: &DecreaseUiScale, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_ui_scale(self.ui_scale - theme::UI_SCALE_STEP, cx);
+    }
+
+    fn reset_ui_scale(&mut self, _: &ResetUiScale, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_ui_scale(1.0, cx);
+    }
+
+    /// Applies the persisted sidebar width the first time the sidebar opens.
+    fn restore_sidebar_width(&mut self, cx: &mut Context<Self>) {
+        if self.sidebar_width_restored || self.sidebar.is_none() {
+            return;
+        }
+        self.sidebar_width_restored = true;
+
+        let kvp = db::kvp::KeyValueStore::global(cx);
+        if let Some(width) = crate::persistence::read_sidebar_width(&kvp)
+            && let Some(sidebar) = &self.sidebar
+        {
+            let width = px(width).clamp(MIN_RESTORED_SIDEBAR_WIDTH, MAX_RESTORED_SIDEBAR_WIDTH);
+            sidebar.set_width(Some(width), cx);
+        }
+    }
+
+    /// Resizes the sidebar and persists the new width. Passing `None` resets
+    /// the sidebar to its default width and clears the persisted value.
+    pub fn resize_sidebar(&mut self, width: Option<Pixels>, cx: &mut Context<Self>) {
+        self.sidebar_width_restored = true;
+        let Some(sidebar) = &self.sidebar else {
+            return;
+        };
+        sidebar.set_width(width, cx);
+
+        let kvp = db::kvp::KeyValueStore::global(cx);
+        let persisted_width = width.map(|_| f32::from(sidebar.width(cx)));
+        let executor = cx.background_executor().clone();
+        self._serialize_sidebar_width_task = Some(cx.background_spawn(async move {
+            match persisted_width {
+                Some(width) => {
+                    executor.timer(crate::SERIALIZATION_THROTTLE_TIME).await;
+                    crate::persistence::write_sidebar_width(&kvp, width)
+                        .await
+                        .log_err();
+                }
+                None => {
+                    crate::persistence::delete_sidebar_width(&kvp)
+                        .await
+                        .log_err();
+                }
+            }
+        }));
+    }
+
     pub fn close_sidebar(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if !self.sidebar_open {
             return;
@@ -369,6 +468,11 @@ impl MultiWorkspace {
                 }
             }
 
+            this.update(cx, |multi_workspace, cx| {
+                if let Some(closed_window) = multi_workspace.closed_window(cx) {
+                    push_closed_window(closed_window, cx);
+                }
+            })?;
             cx.update(|window, _cx| {
                 window.remove_window();
             })?;
@@ -378,6 +482,58 @@ impl MultiWorkspace {
         .detach_and_log_err(cx);
     }
 
+    pub(crate) fn shared_mode_view(&self, mode_id: ModeId) -> Option<AnyView> {
+        self.shared_mode_views
+            .get(&mode_id)
+            .map(|mode_view| mode_view.view.clone())
+    }
+
+    /// Snapshots this window for "Reopen Closed Window". Only local
+    /// workspaces saved to the database can be reopened.
+    fn closed_window(&self, cx: &App) -> Option<ClosedWindow> {
+        let workspaces = self
+            .workspaces
+            .iter()
+            .filter_map(|workspace| {
+                let workspace = workspace.read(cx);
+                let workspace_id = workspace.database_id()?;
+                match workspace.workspace_location(cx) {
+                    WorkspaceLocation::Location(
+                        location @ SerializedWorkspaceLocation::Local,
+                        paths,
+                    ) => Some(SessionWorkspace {
+                        workspace_id,
+                        location,
+                        paths,
+                        window_id: None,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        if workspaces.is_empty() {
+            return None;
+        }
+
+        let registry = ModeViewRegistry::try_global(cx);
+        let mode_snapshots = self
+            .shared_mode_views
+            .iter()
+            .filter_map(|(mode_id, mode_view)| {
+                let handler = registry?.window_snapshot_handler(*mode_id)?;
+                Some((*mode_id, (handler.snapshot)(&mode_view.view, cx)?))
+            })
+            .collect();
+
+        Some(ClosedWindow {
+            multi_workspace: SerializedMultiWorkspace {
+                workspaces,
+                state: self.window_state(cx),
+            },
+            mode_snapshots,
+        })
+    }
+
     fn subscribe_to_workspace(workspace: &Entity<Workspace>, cx: &mut Context<Self>) {
         cx.subscribe(workspace, |this, workspace, event, cx| {
             if let WorkspaceEvent::Activate = event {
@@ -522,12 +678,17 @@ impl MultiWorkspace {
         self.activate_previous_workspace(window, cx);
     }
 
-    fn serialize(&mut self, cx: &mut App) {
-        let window_id = self.window_id;
-        let state = crate::persistence::model::MultiWorkspaceState {
+    fn window_state(&self, cx: &App) -> MultiWorkspaceState {
+        MultiWorkspaceState {
             active_workspace_id: self.workspace().read(cx).database_id(),
             sidebar_open: self.sidebar_open,
-        };
+            ui_scale: (self.ui_scale != 1.0).then_some(self.ui_scale),
+        }
+    }
+
+    fn serialize(&mut self, cx: &mut App) {
+        let window_id = self.window_id;
+        let state = self.window_state(cx);
         let kvp = db::kvp::KeyValueStore::global(cx);
         self._serialize_task = Some(cx.background_spawn(async move {
             crate::persistence::write_multi_workspace_state(&kvp, window_id, state).await;
@@ -546,6 +707,9 @@ impl MultiWorkspace {
         if let Some(task) = self._serialize_task.take() {
             tasks.push(task);
         }
+        if let Some(task) = self._serialize_sidebar_width_task.take() {
+            tasks.push(task);
+        }
         tasks.extend(std::mem::take(&mut self.pending_removal_tasks));
 
         async move {
@@ -874,9 +1038,7 @@ impl Render for MultiWorkspace {
                         .on_mouse_up(MouseButton::Left, move |event, _, cx| {
                             if event.click_count == 2 {
                                 weak.update(cx, |this, cx| {
-                                    if let Some(sidebar) = this.sidebar.as_mut() {
-                                        sidebar.set_width(None, cx);
-                                    }
+                                    this.resize_sidebar(None, cx);
                                 })
                                 .ok();
                                 cx.stop_propagation();
@@ -915,6 +1077,9 @@ impl Render for MultiWorkspace {
                 .font(ui_font)
                 .text_color(text_color)
                 .on_action(cx.listener(Self::close_window))
+                .on_action(cx.listener(Self::increase_ui_scale))
+                .on_action(cx.listener(Self::decrease_ui_scale))
+                .on_action(cx.listener(Self::reset_ui_scale))
                 .on_action(
                     cx.listener(|this: &mut Self, _: &NewWorkspaceInWindow, window, cx| {
                         this.create_workspace(window, cx);
@@ -945,10 +1110,7 @@ impl Render for MultiWorkspace {
                     |this| {
                         this.on_drag_move(cx.listener(
                             |this: &mut Self, e: &DragMoveEvent<DraggedSidebar>, _window, cx| {
-                                if let Some(sidebar) = &this.sidebar {
-                                    let new_width = e.event.position.x;
-                                    sidebar.set_width(Some(new_width), cx);
-                                }
+                                this.resize_sidebar(Some(e.event.position.x), cx);
                             },
                         ))
                         .children(sidebar)
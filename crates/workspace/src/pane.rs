@@ -386,6 +386,7 @@ pub struct Pane {
     can_toggle_zoom: bool,
     should_display_tab_bar: Rc<dyn Fn(&Window, &mut Context<Pane>) -> bool>,
     should_display_welcome_page: bool,
+    empty_state_label: Option<SharedString>,
     render_tab_bar_buttons: Rc<
         dyn Fn(
             &mut Pane,
@@ -567,6 +568,7 @@ impl Pane {
             can_toggle_zoom: true,
             should_display_tab_bar: Rc::new(|_, cx| TabBarSettings::get_global(cx).show),
             should_display_welcome_page: false,
+            empty_state_label: None,
             render_tab_bar_buttons: Rc::new(default_render_tab_bar_buttons),
             render_tab_bar: Rc::new(Self::render_tab_bar),
             show_tab_bar_buttons: TabBarSettings::get_global(cx).show_tab_bar_buttons,
@@ -794,6 +796,12 @@ impl Pane {
         self.should_display_welcome_page = should_display_welcome_page;
     }
 
+    /// Shows a button with this label in the empty pane that dispatches the
+    /// same action as double-clicking it.
+    pub fn set_empty_state_label(&mut self, label: impl Into<SharedString>) {
+        self.empty_state_label = Some(label.into());
+    }
+
     pub fn set_can_split(
         &mut self,
         can_split_predicate: Option<
@@ -3960,11 +3968,22 @@ impl Pane {
                     {
                         let opened_items: Vec<_> = open_task.await;
                         _ = workspace.update_in(cx, |workspace, window, cx| {
+                            let mut last_opened_item = None;
                             for item in opened_items.into_iter().flatten() {
-                                if let Err(e) = item {
-                                    workspace.show_error(&e, cx);
+                                match item {
+                                    Ok(item) => last_opened_item = Some(item),
+                                    Err(e) => workspace.show_error(&e, cx),
                                 }
                             }
+                            // Dropped files open concurrently, so whichever finished last would
+                            // otherwise end up focused instead of the last one dropped.
+                            if let Some(item) = last_opened_item {
+                                to_pane.update(cx, |pane, cx| {
+                                    if let Some(index) = pane.index_for_item(item.as_ref()) {
+                                        pane.activate_item(index, true, true, window, cx);
+                                    }
+                                });
+                            }
                             if to_pane.read(cx).items_len() == 0 {
                                 workspace.remove_pane(to_pane, None, window, cx);
                             }
@@ -4344,6 +4363,22 @@ impl Render for Pane {
                                             .opacity(0.15),
                                     )
                                 })
+                                .when_some(
+                                    self.empty_state_label.clone().filter(|_| !show_welcome),
+                                    |div, label| {
+                                        div.gap_4().child(
+                                            Button::new("empty-pane-action", label)
+                                                .style(ButtonStyle::Filled)
+                                                .on_click(cx.listener(|this, _, window, cx| {
+                                                    window.dispatch_action(
+                                                        this.double_click_dispatch_action
+                                                            .boxed_clone(),
+                                                        cx,
+                                                    );
+                                                })),
+                                        )
+                                    },
+                                )
                                 .on_click(cx.listener(
                                     move |this, event: &ClickEvent, window, cx| {
                                         if event.click_count() == 2 {
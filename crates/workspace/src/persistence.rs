@@ -350,6 +350,46 @@ pub fn read_serialized_multi_workspaces(
         .collect()
 }
 
+const SIDEBAR_WIDTH_KEY: &str = "workspace_sidebar_width";
+
+pub fn read_sidebar_width(kvp: &KeyValueStore) -> Option<f32> {
+    kvp.read_kvp(SIDEBAR_WIDTH_KEY)
+        .log_err()
+        .flatten()?
+        .parse()
+        .ok()
+}
+
+pub async fn write_sidebar_width(kvp: &KeyValueStore, width: f32) -> anyhow::Result<()> {
+    kvp.write_kvp(SIDEBAR_WIDTH_KEY.to_string(), width.to_string())
+        .await
+}
+
+pub async fn delete_sidebar_width(kvp: &KeyValueStore) -> anyhow::Result<()> {
+    kvp.delete_kvp(SIDEBAR_WIDTH_KEY.to_string()).await
+}
+
+const BROWSER_TABS_SIDEBAR_COLLAPSED_KEY: &str = "browser_tabs_sidebar_collapsed";
+
+pub fn read_browser_tabs_sidebar_collapsed(kvp: &KeyValueStore) -> bool {
+    kvp.read_kvp(BROWSER_TABS_SIDEBAR_COLLAPSED_KEY)
+        .log_err()
+        .flatten()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(false)
+}
+
+pub async fn write_browser_tabs_sidebar_collapsed(
+    kvp: &KeyValueStore,
+    collapsed: bool,
+) -> anyhow::Result<()> {
+    kvp.write_kvp(
+        BROWSER_TABS_SIDEBAR_COLLAPSED_KEY.to_string(),
+        collapsed.to_string(),
+    )
+    .await
+}
+
 const DEFAULT_DOCK_STATE_KEY: &str = "default_dock_state";
 
 pub fn read_default_dock_state(kvp: &KeyValueStore) -> Option<DockStructure> {
@@ -4086,6 +4126,7 @@ mod tests {
             MultiWorkspaceState {
                 active_workspace_id: Some(WorkspaceId(2)),
                 sidebar_open: true,
+                ui_scale: Some(1.5),
             },
         )
         .await;
@@ -4096,6 +4137,7 @@ mod tests {
             MultiWorkspaceState {
                 active_workspace_id: Some(WorkspaceId(3)),
                 sidebar_open: false,
+                ui_scale: None,
             },
         )
         .await;
@@ -4138,12 +4180,14 @@ mod tests {
         assert_eq!(group_10.workspaces.len(), 2);
         assert_eq!(group_10.state.active_workspace_id, Some(WorkspaceId(2)));
         assert_eq!(group_10.state.sidebar_open, true);
+        assert_eq!(group_10.state.ui_scale, Some(1.5));
 
         // Window 20 group: 1 workspace, active_workspace_id = 3, sidebar closed.
         let group_20 = &results[1];
         assert_eq!(group_20.workspaces.len(), 1);
         assert_eq!(group_20.state.active_workspace_id, Some(WorkspaceId(3)));
         assert_eq!(group_20.state.sidebar_open, false);
+        assert_eq!(group_20.state.ui_scale, None);
 
         // Orphan group: no window_id, so state is default.
         let group_none = &results[2];
@@ -4152,6 +4196,18 @@ mod tests {
         assert_eq!(group_none.state.sidebar_open, false);
     }
 
+    #[gpui::test]
+    async fn test_sidebar_width_round_trip(cx: &mut gpui::TestAppContext) {
+        let kvp = cx.update(|cx| KeyValueStore::global(cx));
+        assert_eq!(read_sidebar_width(&kvp), None);
+
+        write_sidebar_width(&kvp, 320.0).await.unwrap();
+        assert_eq!(read_sidebar_width(&kvp), Some(320.0));
+
+        delete_sidebar_width(&kvp).await.unwrap();
+        assert_eq!(read_sidebar_width(&kvp), None);
+    }
+
     #[gpui::test]
     async fn test_flush_serialization_completes_before_quit(cx: &mut gpui::TestAppContext) {
         use crate::multi_workspace::MultiWorkspace;
@@ -64,6 +64,9 @@ pub struct SessionWorkspace {
 pub struct MultiWorkspaceState {
     pub active_workspace_id: Option<WorkspaceId>,
     pub sidebar_open: bool,
+    /// The window's interface scale, when it isn't 1.0.
+    #[serde(default)]
+    pub ui_scale: Option<f32>,
 }
 
 /// The serialized state of a single MultiWorkspace window from a previous session:
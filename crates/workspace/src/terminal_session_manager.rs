@@ -80,6 +80,26 @@ impl TerminalSessionManager {
         self.active_session_id
     }
 
+    pub fn sessions(&self) -> &[WorkspaceTerminalSession] {
+        &self.sessions
+    }
+
+    /// The session after (or before) the active one, wrapping around at the ends.
+    pub fn adjacent_session_id(&self, forward: bool) -> Option<u64> {
+        let session_count = self.sessions.len();
+        if session_count < 2 {
+            return None;
+        }
+
+        let index = self.current_session_index();
+        let adjacent_index = if forward {
+            (index + 1) % session_count
+        } else {
+            (index + session_count - 1) % session_count
+        };
+        Some(self.sessions[adjacent_index].id)
+    }
+
     fn current_session_index(&self) -> usize {
         self.sessions
             .iter()
@@ -138,6 +158,26 @@ impl TerminalSessionManager {
         session
     }
 
+    /// Appends a session restored from a previous run without activating it.
+    pub fn push_restored_session(
+        &mut self,
+        title: SharedString,
+        pinned: bool,
+        center: PaneGroup,
+        active_pane: Entity<Pane>,
+    ) -> u64 {
+        let session_id = self.next_session_id;
+        self.next_session_id += 1;
+        self.sessions.push(WorkspaceTerminalSession::new(
+            session_id,
+            title,
+            pinned,
+            center,
+            active_pane,
+        ));
+        session_id
+    }
+
     pub fn activate_session_by_id(&mut self, session_id: u64) -> Option<WorkspaceTerminalSession> {
         if self.active_session_id == session_id {
             return None;
@@ -1,3 +1,4 @@
+mod closed_windows;
 pub mod dock;
 pub mod history_manager;
 pub mod invalid_item_view;
@@ -25,9 +26,9 @@ mod workspace_settings;
 pub use crate::notifications::NotificationFrame;
 pub use dock::{DeployProjectDiagnostics, Panel, ToggleProjectDiagnostics, ToggleProjectSearch};
 pub use multi_workspace::{
-    CloseProjectNavigation, DraggedSidebar, FocusProjectNavigation, MultiWorkspace,
-    MultiWorkspaceEvent, NextWorkspace, PreviousWorkspace, Sidebar, SidebarHandle,
-    ToggleProjectNavigation,
+    CloseProjectNavigation, DecreaseUiScale, DraggedSidebar, FocusProjectNavigation,
+    IncreaseUiScale, MultiWorkspace, MultiWorkspaceEvent, NextWorkspace, PreviousWorkspace,
+    ResetUiScale, Sidebar, SidebarHandle, ToggleProjectNavigation,
 };
 pub use path_list::{PathList, SerializedPathList};
 pub use terminal_session_manager::{
@@ -44,7 +45,8 @@ use client::{
 use collections::{HashMap, HashSet, hash_map};
 use db::smol::future::yield_now;
 use dock::{
-    Dock, DockButtonBar, DockPosition, PanelHandle, PanelNavigationEntry, RESIZE_HANDLE_SIZE,
+    Dock, DockButtonBar, DockPosition, FocusSidebarSections, PanelHandle, PanelNavigationEntry,
+    RESIZE_HANDLE_SIZE,
 };
 use futures::{
     Future, FutureExt, StreamExt,
@@ -60,10 +62,10 @@ use gpui::{
     Action, AnyEntity, AnyView, AnyWeakView, App, AsyncApp, AsyncWindowContext, Bounds, Context,
     CursorStyle, Decorations, DragMoveEvent, Entity, EntityId, EventEmitter, FocusHandle,
     Focusable, Global, HitboxBehavior, Hsla, KeyContext, Keystroke, ManagedView, MouseButton,
-    PathPromptOptions, Point, PromptLevel, Render, ResizeEdge, Size, Stateful, Subscription,
-    SystemWindowTabController, Task, Tiling, WeakEntity, WindowBackgroundAppearance, WindowBounds,
-    WindowHandle, WindowId, WindowOptions, actions, canvas, point, px, relative, size,
-    transparent_black,
+    MouseUpEvent, PathPromptOptions, Point, PromptLevel, Render, ResizeEdge, Size, Stateful,
+    Subscription, SystemWindowTabController, Task, Tiling, WeakEntity, WindowBackgroundAppearance,
+    WindowBounds, WindowHandle, WindowId, WindowOptions, actions, canvas, point, px, relative,
+    size, transparent_black,
 };
 pub use history_manager::*;
 pub use item::{
@@ -150,7 +152,7 @@ use uuid::Uuid;
 use workspace_chrome::SidebarRow;
 use workspace_modes::{
     ModeActivateCallback, ModeDeactivateCallback, ModeId, ModeNavigationHost, ModeViewRegistry,
-    SwitchToBrowserMode, SwitchToEditorMode, SwitchToTerminalMode,
+    SwitchToBrowserMode, SwitchToEditorMode, SwitchToTerminalMode, ToggleSplitWithBrowser,
 };
 pub use workspace_settings::{
     AutosaveSetting, BottomDockLayout, RestoreOnStartupBehavior, TabBarSettings, WorkspaceSettings,
@@ -727,6 +729,8 @@ actions!(
         NewSearch,
         /// Opens a new window.
         NewWindow,
+        /// Reopens the most recently closed window.
+        ReopenClosedWindow,
         /// Opens multiple files.
         OpenFiles,
         /// Opens the current location in terminal.
@@ -763,6 +767,8 @@ actions!(
         ToggleRightDock,
         /// Toggles zoom on the active pane.
         ToggleZoom,
+        /// Toggles zoom on the focused dock panel, or on the active pane if no panel is focused.
+        TogglePanelZoom,
         /// Toggles read-only mode for the active item (if supported by that item).
         ToggleReadOnlyFile,
         /// Zooms in on the active pane.
@@ -1168,6 +1174,14 @@ pub fn init(app_state: Arc<AppState>, cx: &mut App) {
 
     cx.on_action(|_: &CloseWindow, cx| Workspace::close_global(cx))
         .on_action(|_: &Reload, cx| reload(cx))
+        .on_action({
+            let app_state = Arc::downgrade(&app_state);
+            move |_: &ReopenClosedWindow, cx: &mut App| {
+                if let Some(app_state) = app_state.upgrade() {
+                    closed_windows::reopen_closed_window(app_state, cx);
+                }
+            }
+        })
         .on_action({
             let app_state = Arc::downgrade(&app_state);
             move |_: &Open, cx: &mut App| {
@@ -1640,6 +1654,9 @@ pub enum Event {
     ModalOpened,
     Activate,
     PanelAdded(AnyView),
+    /// The workspace switched to a different mode. Not emitted when switching
+    /// to the mode that is already active.
+    ActiveModeChanged(ModeId),
 }
 
 #[derive(Debug, Clone)]
@@ -1670,12 +1687,17 @@ pub type EmbeddedBrowserItemFactory =
 pub struct BrowserSurfaceRegistry {
     item_factory: Option<EmbeddedBrowserItemFactory>,
     mode_opener: Option<BrowserViewOpenUrlCallback>,
+    mode_navigator: Option<BrowserViewNavigateCallback>,
 }
 
 impl Global for BrowserSurfaceRegistry {}
 
 type BrowserViewOpenUrlCallback = Arc<dyn Fn(&AnyView, &str, &mut Window, &mut App) + Send + Sync>;
 
+/// Receives the raw URL text and whether it should open in a background tab.
+type BrowserViewNavigateCallback =
+    Arc<dyn Fn(&AnyView, &str, bool, &mut Window, &mut App) + Send + Sync>;
+
 impl BrowserSurfaceRegistry {
     fn item_factory(cx: &App) -> Option<EmbeddedBrowserItemFactory> {
         cx.try_global::<Self>()
@@ -1686,6 +1708,11 @@ impl BrowserSurfaceRegistry {
         cx.try_global::<Self>()
             .and_then(|registry| registry.mode_opener.clone())
     }
+
+    fn mode_navigator(cx: &App) -> Option<BrowserViewNavigateCallback> {
+        cx.try_global::<Self>()
+            .and_then(|registry| registry.mode_navigator.clone())
+    }
 }
 
 pub fn register_embedded_browser_item_factory(factory: EmbeddedBrowserItemFactory, cx: &mut App) {
@@ -1696,6 +1723,10 @@ pub fn register_browser_mode_url_opener(callback: BrowserViewOpenUrlCallback, cx
     cx.default_global::<BrowserSurfaceRegistry>().mode_opener = Some(callback);
 }
 
+pub fn register_browser_mode_url_navigator(callback: BrowserViewNavigateCallback, cx: &mut App) {
+    cx.default_global::<BrowserSurfaceRegistry>().mode_navigator = Some(callback);
+}
+
 enum WorkspaceLocation {
     // Valid local paths or SSH project to serialize
     Location(SerializedWorkspaceLocation, PathList),
@@ -1731,6 +1762,19 @@ struct DispatchingKeystrokes {
     task: Option<Shared<Task<()>>>,
 }
 
+/// Browser mode view shown beside the editor while Editor Mode stays active.
+struct BrowserSplit {
+    /// The mode that was active before the split opened, restored when it closes.
+    previous_mode: ModeId,
+    /// Fraction of the center area's width given to the editor.
+    editor_fraction: f32,
+    bounds: Bounds<Pixels>,
+}
+
+const DEFAULT_BROWSER_SPLIT_FRACTION: f32 = 0.5;
+const MIN_BROWSER_SPLIT_FRACTION: f32 = 0.2;
+const MAX_BROWSER_SPLIT_FRACTION: f32 = 0.8;
+
 struct PerWorkspaceModeView {
     view: AnyView,
     focus_handle: FocusHandle,
@@ -1804,6 +1848,7 @@ pub struct Workspace {
     /// Tracks whether the bottom dock was visible before entering Terminal Mode,
     /// so we can restore it when returning to Editor Mode
     bottom_dock_visible_before_terminal_mode: Option<bool>,
+    browser_split: Option<BrowserSplit>,
     scheduled_tasks: Vec<Task<()>>,
     last_open_dock_positions: Vec<DockPosition>,
     removing: bool,
@@ -2064,11 +2109,11 @@ impl Workspace {
         )
         .detach();
 
-        let dock_button_bar = DockButtonBar::new(weak_handle.clone(), cx);
+        let dock_button_bar = DockButtonBar::new(weak_handle.clone(), &project, cx);
         let left_dock = Dock::new(
             DockPosition::Left,
             modal_layer.clone(),
-            Some(dock_button_bar),
+            Some(dock_button_bar.clone()),
             window,
             cx,
         );
@@ -2077,14 +2122,25 @@ impl Workspace {
             dock.in_native_sidebar = true;
         });
         let bottom_dock = Dock::new(DockPosition::Bottom, modal_layer.clone(), None, window, cx);
+        dock_button_bar.update(cx, |dock_button_bar, cx| {
+            dock_button_bar.observe_bottom_dock(&bottom_dock, cx);
+        });
         let right_dock = Dock::new(DockPosition::Right, modal_layer.clone(), None, window, cx);
         #[cfg(target_os = "macos")]
         let workspace_sidebar_host = {
             let left_dock_clone = left_dock.clone();
             let bottom_dock_clone = bottom_dock.clone();
             let right_dock_clone = right_dock.clone();
-            cx.new(|_cx| {
-                WorkspaceSidebarHost::new(left_dock_clone, bottom_dock_clone, right_dock_clone)
+            // The sidebar opens on the browser's tab list, so restore that
+            // section's collapsed state before the first render.
+            let collapsed = persistence::read_browser_tabs_sidebar_collapsed(
+                &db::kvp::KeyValueStore::global(cx),
+            );
+            cx.new(|cx| {
+                let mut sidebar =
+                    WorkspaceSidebarHost::new(left_dock_clone, bottom_dock_clone, right_dock_clone);
+                sidebar.set_collapsed(collapsed, cx);
+                sidebar
             })
         };
         #[cfg(target_os = "macos")]
@@ -2231,6 +2287,7 @@ impl Workspace {
             per_workspace_mode_views: HashMap::default(),
             shared_mode_views: HashMap::default(),
             bottom_dock_visible_before_terminal_mode: None,
+            browser_split: None,
 
             scheduled_tasks: Vec::new(),
             last_open_dock_positions: Vec::new(),
@@ -2409,8 +2466,11 @@ impl Workspace {
                         .as_ref()
                         .map(|w| w.centered_layout)
                         .unwrap_or(false);
+                    let restore_last_mode =
+                        cx.update(|cx| WorkspaceSettings::get_global(cx).restore_last_mode);
                     let active_mode = serialized_workspace
                         .as_ref()
+                        .filter(|_| restore_last_mode)
                         .and_then(|w| w.active_mode.as_ref())
                         .map(|s| ModeId::from_str(s))
                         .unwrap_or(ModeId::BROWSER);
@@ -2427,7 +2487,6 @@ impl Workspace {
                                     cx,
                                 );
                                 workspace.centered_layout = centered_layout;
-                                workspace.active_mode = active_mode;
 
                                 // Call init callback to add items before window renders
                                 if let Some(init) = init {
@@ -2436,6 +2495,11 @@ impl Workspace {
 
                                 workspace
                             });
+                            // Panels start loading once the workspace exists, so the
+                            // mode is restored from here rather than from the constructor.
+                            workspace.update(cx, |workspace, cx| {
+                                workspace.restore_active_mode(active_mode, window, cx);
+                            });
                             cx.new(|cx| MultiWorkspace::new(workspace, window, cx))
                         }
                     })?;
@@ -3287,22 +3351,57 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let docks = self.all_docks();
-        let active_dock = docks
-            .into_iter()
-            .find(|dock| dock.focus_handle(cx).contains_focused(window, cx));
+        // A zoomed panel is rendered outside of its dock, so look for focus in
+        // the active panel itself rather than in the dock's focus handle.
+        let focused_panel = self.all_docks().into_iter().find_map(|dock| {
+            dock.read(cx)
+                .active_panel()
+                .filter(|panel| panel.panel_focus_handle(cx).contains_focused(window, cx))
+                .cloned()
+        });
 
-        if let Some(dock) = active_dock {
-            dock.update(cx, |dock, cx| {
-                let active_panel = dock
-                    .active_panel()
-                    .filter(|panel| panel.panel_focus_handle(cx).contains_focused(window, cx));
+        if let Some(panel) = focused_panel {
+            panel.move_to_next_position(window, cx);
+        }
+    }
 
-                if let Some(panel) = active_panel {
-                    panel.move_to_next_position(window, cx);
-                }
-            })
+    pub fn toggle_panel_zoom(
+        &mut self,
+        _: &TogglePanelZoom,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let focused_panel = self.all_docks().into_iter().find_map(|dock| {
+            let panel = dock
+                .read(cx)
+                .active_panel()
+                .filter(|panel| panel.panel_focus_handle(cx).contains_focused(window, cx))
+                .cloned()?;
+            Some((dock.clone(), panel))
+        });
+
+        let Some((dock, panel)) = focused_panel else {
+            self.active_pane.update(cx, |pane, cx| {
+                pane.toggle_zoom(&ToggleZoom, window, cx);
+            });
+            return;
+        };
+
+        let zoomed = !panel.is_zoomed(window, cx);
+        let position = dock.read(cx).position();
+        dock.update(cx, |dock, cx| {
+            dock.zoom_panel(&panel.to_any(), zoomed, window, cx);
+        });
+        if zoomed {
+            self.zoomed = Some(panel.to_any().downgrade());
+            self.zoomed_position = Some(position);
+        } else if self.zoomed_position == Some(position) {
+            self.zoomed = None;
+            self.zoomed_position = None;
         }
+        self.serialize_workspace(window, cx);
+        cx.emit(Event::ZoomChanged);
+        cx.notify();
     }
 
     pub fn prepare_to_close(
@@ -4615,6 +4714,27 @@ impl Workspace {
         Ok(())
     }
 
+    /// Opens `url` in Browser Mode. Foreground opens switch to Browser Mode and
+    /// navigate the active tab, while background opens add an inactive tab and
+    /// leave the current mode alone.
+    pub fn open_url_in_browser(
+        &mut self,
+        url: impl Into<String>,
+        background: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let browser_view = self.ensure_browser_view(cx)?;
+        let Some(mode_navigator) = BrowserSurfaceRegistry::mode_navigator(cx) else {
+            anyhow::bail!("browser mode navigator is not registered");
+        };
+        if !background {
+            self.switch_to_mode(ModeId::BROWSER, window, cx);
+        }
+        mode_navigator(&browser_view, &url.into(), background, window, cx);
+        Ok(())
+    }
+
     pub fn show_browser_surface(
         &mut self,
         focus_item: bool,
@@ -5701,11 +5821,49 @@ impl Workspace {
         }
     }
 
+    /// The terminal panel's active pane, whose active item is the focused
+    /// terminal in Terminal mode.
+    pub fn active_terminal_pane(&self, cx: &App) -> Option<Entity<Pane>> {
+        self.bottom_dock
+            .read(cx)
+            .panel_for_key("TerminalPanel")?
+            .pane(cx)
+    }
+
+    /// The focused terminal's title, which follows its foreground process or
+    /// working directory.
+    pub fn active_terminal_title(&self, cx: &App) -> Option<SharedString> {
+        let item = self.active_terminal_pane(cx)?.read(cx).active_item()?;
+        (item.workspace_item_kind(cx)? == WorkspaceItemKind::Terminal)
+            .then(|| item.tab_content_text(0, cx))
+    }
+
     #[cfg(target_os = "macos")]
     pub(crate) fn workspace_sidebar_host_collapsed(&self, _window: &Window, cx: &App) -> bool {
         self.workspace_sidebar_host.read(cx).collapsed()
     }
 
+    /// Whether the sidebar is expanded and showing the browser's tab list.
+    #[cfg(target_os = "macos")]
+    pub fn browser_tabs_sidebar_visible(&self, cx: &App) -> bool {
+        self.active_sidebar_section == WorkspaceSidebarSection::BrowserTabs
+            && !self.workspace_sidebar_host.read(cx).collapsed()
+    }
+
+    /// Collapses the sidebar if it's showing the browser's tab list, and
+    /// otherwise expands it to that list, leaving other sections untouched.
+    #[cfg(target_os = "macos")]
+    pub fn toggle_browser_tabs_sidebar(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.browser_tabs_sidebar_visible(cx) {
+            self.workspace_sidebar_host
+                .update(cx, |sidebar, cx| sidebar.set_collapsed(true, cx));
+            self.persist_browser_tabs_sidebar_collapsed(cx);
+            cx.notify();
+        } else {
+            self.select_sidebar_section(WorkspaceSidebarSection::BrowserTabs, window, cx);
+        }
+    }
+
     #[cfg(target_os = "macos")]
     pub fn select_sidebar_section(
         &mut self,
@@ -5732,9 +5890,25 @@ impl Workspace {
             sidebar.set_active_section(section, cx);
             sidebar.set_collapsed(false, cx);
         });
+        self.persist_browser_tabs_sidebar_collapsed(cx);
         cx.notify();
     }
 
+    #[cfg(target_os = "macos")]
+    fn persist_browser_tabs_sidebar_collapsed(&self, cx: &mut Context<Self>) {
+        if self.active_sidebar_section != WorkspaceSidebarSection::BrowserTabs {
+            return;
+        }
+        let collapsed = self.workspace_sidebar_host.read(cx).collapsed();
+        let kvp = db::kvp::KeyValueStore::global(cx);
+        cx.background_spawn(async move {
+            persistence::write_browser_tabs_sidebar_collapsed(&kvp, collapsed)
+                .await
+                .log_err();
+        })
+        .detach();
+    }
+
     fn activate_sidebar_panel(
         &mut self,
         panel_key: &str,
@@ -5787,6 +5961,7 @@ impl Workspace {
             let _ = window;
             self.workspace_sidebar_host
                 .update(cx, |sidebar, cx| sidebar.toggle_collapsed(cx));
+            self.persist_browser_tabs_sidebar_collapsed(cx);
             cx.notify();
             return;
         }
@@ -5869,6 +6044,47 @@ impl Workspace {
             })
     }
 
+    /// Restores the mode a window was last left in, once its panels have loaded. Modes
+    /// backed by a registered view are only restored if that view can be constructed;
+    /// otherwise the window falls back to browser mode.
+    fn restore_active_mode(
+        &mut self,
+        mode_id: ModeId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(panels_task) = self._panels_task.take() else {
+            self.switch_to_restored_mode(mode_id, window, cx);
+            return;
+        };
+        self._panels_task = Some(cx.spawn_in(window, async move |this, cx| {
+            let result = panels_task.await;
+            this.update_in(cx, |this, window, cx| {
+                this.switch_to_restored_mode(mode_id, window, cx);
+            })?;
+            result
+        }));
+    }
+
+    fn switch_to_restored_mode(
+        &mut self,
+        mode_id: ModeId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let can_restore = match mode_id {
+            ModeId::EDITOR | ModeId::TERMINAL => true,
+            _ => self.mode_view(mode_id, cx).is_some(),
+        };
+        let mode_id = if can_restore {
+            mode_id
+        } else {
+            log::warn!("failed to restore {mode_id} mode, falling back to browser mode");
+            ModeId::BROWSER
+        };
+        self.switch_to_mode(mode_id, window, cx);
+    }
+
     /// Get an already-created mode view without lazily creating it.
     /// Use this when you only need to read the view and can't call `mode_view`
     /// (e.g. when the workspace entity is already leased).
@@ -5881,32 +6097,18 @@ impl Workspace {
         if self.active_mode != mode_id {
             let previous_mode = self.active_mode;
 
-            if let Some(deactivate) = self
-                .mode_view_entry(previous_mode)
-                .and_then(|v| v.on_deactivate.clone())
-                .or_else(|| {
-                    ModeViewRegistry::try_global(cx)
-                        .and_then(|registry| registry.get(previous_mode))
-                        .and_then(|mode_view| mode_view.on_deactivate.clone())
-                })
-            {
-                deactivate(cx);
+            // The split keeps the browser surface visible from Editor Mode, so
+            // leaving Editor Mode closes it and parks the browser unless the
+            // browser is about to become the active mode anyway.
+            if self.browser_split.take().is_some() && mode_id != ModeId::BROWSER {
+                self.deactivate_mode_view(ModeId::BROWSER, cx);
             }
 
+            self.deactivate_mode_view(previous_mode, cx);
+
             self.active_mode = mode_id;
             self.ensure_mode_view(mode_id, cx);
-
-            if let Some(activate) = self
-                .mode_view_entry(mode_id)
-                .and_then(|v| v.on_activate.clone())
-                .or_else(|| {
-                    ModeViewRegistry::try_global(cx)
-                        .and_then(|registry| registry.get(mode_id))
-                        .and_then(|mode_view| mode_view.on_activate.clone())
-                })
-            {
-                activate(window, cx);
-            }
+            self.activate_mode_view(mode_id, window, cx);
 
             match mode_id {
                 ModeId::BROWSER => {
@@ -5967,8 +6169,86 @@ impl Workspace {
                 _ => {}
             }
             self.serialize_workspace(window, cx);
+            cx.emit(Event::ActiveModeChanged(mode_id));
+            cx.notify();
+        }
+    }
+
+    fn activate_mode_view(&mut self, mode_id: ModeId, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(activate) = self
+            .mode_view_entry(mode_id)
+            .and_then(|v| v.on_activate.clone())
+            .or_else(|| {
+                ModeViewRegistry::try_global(cx)
+                    .and_then(|registry| registry.get(mode_id))
+                    .and_then(|mode_view| mode_view.on_activate.clone())
+            })
+        {
+            activate(window, cx);
+        }
+    }
+
+    fn deactivate_mode_view(&mut self, mode_id: ModeId, cx: &mut Context<Self>) {
+        if let Some(deactivate) = self
+            .mode_view_entry(mode_id)
+            .and_then(|v| v.on_deactivate.clone())
+            .or_else(|| {
+                ModeViewRegistry::try_global(cx)
+                    .and_then(|registry| registry.get(mode_id))
+                    .and_then(|mode_view| mode_view.on_deactivate.clone())
+            })
+        {
+            deactivate(cx);
+        }
+    }
+
+    pub fn is_browser_split_open(&self) -> bool {
+        self.browser_split.is_some()
+    }
+
+    /// Shows the browser mode view beside the editor, or closes the split and
+    /// returns to the mode that was active before it opened.
+    pub fn toggle_split_with_browser(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(browser_split) = self.browser_split.take() {
+            self.deactivate_mode_view(ModeId::BROWSER, cx);
+            self.switch_to_mode(browser_split.previous_mode, window, cx);
             cx.notify();
+            return;
+        }
+
+        if self.ensure_browser_view(cx).log_err().is_none() {
+            return;
         }
+
+        let previous_mode = self.active_mode;
+        self.switch_to_mode(ModeId::EDITOR, window, cx);
+        self.browser_split = Some(BrowserSplit {
+            previous_mode,
+            editor_fraction: DEFAULT_BROWSER_SPLIT_FRACTION,
+            bounds: Bounds::default(),
+        });
+        self.activate_mode_view(ModeId::BROWSER, window, cx);
+        window.focus(&self.active_pane.focus_handle(cx), cx);
+        cx.notify();
+    }
+
+    /// Moves the split divider to `position`, or back to the middle when `None`.
+    fn resize_browser_split(&mut self, position: Option<Pixels>, cx: &mut Context<Self>) {
+        let Some(browser_split) = self.browser_split.as_mut() else {
+            return;
+        };
+
+        let editor_fraction = match position {
+            Some(position) if browser_split.bounds.size.width > Pixels::ZERO => {
+                f32::from(position - browser_split.bounds.left())
+                    / f32::from(browser_split.bounds.size.width)
+            }
+            Some(_) => return,
+            None => DEFAULT_BROWSER_SPLIT_FRACTION,
+        };
+        browser_split.editor_fraction =
+            editor_fraction.clamp(MIN_BROWSER_SPLIT_FRACTION, MAX_BROWSER_SPLIT_FRACTION);
+        cx.notify();
     }
 
     pub fn focused_pane(&self, window: &Window, cx: &App) -> Entity<Pane> {
@@ -6944,11 +7224,15 @@ impl Workspace {
             let mut center_group = None;
             let mut center_items = None;
 
+            let restore_editor_session =
+                cx.update(|_, cx| WorkspaceSettings::get_global(cx).restore_editor_session)?;
+
             // Traverse the splits tree and add to things
-            if let Some((group, active_pane, items)) = serialized_workspace
-                .center_group
-                .deserialize(&project, serialized_workspace.id, workspace.clone(), cx)
-                .await
+            if restore_editor_session
+                && let Some((group, active_pane, items)) = serialized_workspace
+                    .center_group
+                    .deserialize(&project, serialized_workspace.id, workspace.clone(), cx)
+                    .await
             {
                 center_items = Some(items);
                 center_group = Some((group, active_pane))
@@ -7123,6 +7407,7 @@ impl Workspace {
             .on_action(cx.listener(Self::activate_pane_at_index))
             .on_action(cx.listener(Self::move_item_to_pane_at_index))
             .on_action(cx.listener(Self::move_focused_panel_to_next_position))
+            .on_action(cx.listener(Self::toggle_panel_zoom))
             .on_action(cx.listener(Self::toggle_edit_predictions_all_files))
             .on_action(cx.listener(Self::toggle_theme_mode))
             .on_action(cx.listener(|workspace, _: &Unfollow, window, cx| {
@@ -7224,6 +7509,11 @@ impl Workspace {
             .on_action(cx.listener(|this, _: &ToggleLeftDock, window, cx| {
                 this.toggle_dock(DockPosition::Left, window, cx);
             }))
+            .on_action(cx.listener(|this, _: &FocusSidebarSections, window, cx| {
+                if let Some(button_bar) = this.button_bar(cx) {
+                    button_bar.update(cx, |button_bar, cx| button_bar.focus_sections(window, cx));
+                }
+            }))
             .on_action(cx.listener(|this, _: &ToggleSidebar, window, cx| {
                 this.toggle_sidebar(window, cx);
             }))
@@ -7483,6 +7773,11 @@ impl Workspace {
                     workspace.switch_to_mode(ModeId::TERMINAL, window, cx);
                 }),
             )
+            .on_action(
+                cx.listener(|workspace, _: &ToggleSplitWithBrowser, window, cx| {
+                    workspace.toggle_split_with_browser(window, cx);
+                }),
+            )
             .on_action(cx.listener(|workspace, _: &FocusCenterPane, window, cx| {
                 workspace.focus_center_pane(window, cx);
             }))
@@ -7665,6 +7960,87 @@ impl Workspace {
             .border_color(cx.theme().colors().pane_group_border)
     }
 
+    fn render_center_with_browser_split(
+        &self,
+        center: AnyElement,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let Some((browser_split, browser_view)) = self
+            .browser_split
+            .as_ref()
+            .zip(self.get_mode_view(ModeId::BROWSER))
+        else {
+            return center;
+        };
+
+        let this = cx.entity();
+        div()
+            .relative()
+            .size_full()
+            .flex()
+            .flex_row()
+            .child(
+                canvas(
+                    move |bounds, _window, cx| {
+                        this.update(cx, |workspace, _cx| {
+                            if let Some(browser_split) = workspace.browser_split.as_mut() {
+                                browser_split.bounds = bounds;
+                            }
+                        })
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .flex_none()
+                    .h_full()
+                    .w(relative(browser_split.editor_fraction))
+                    .overflow_hidden()
+                    .child(center),
+            )
+            .child(
+                div()
+                    .id("browser-split-divider")
+                    .flex_none()
+                    .h_full()
+                    .w(RESIZE_HANDLE_SIZE)
+                    .cursor_col_resize()
+                    .on_drag(DraggedBrowserSplit, |dragged, _, _, cx| {
+                        cx.stop_propagation();
+                        cx.new(|_| dragged.clone())
+                    })
+                    .on_mouse_up(
+                        MouseButton::Left,
+                        cx.listener(|workspace, event: &MouseUpEvent, _window, cx| {
+                            if event.click_count == 2 {
+                                workspace.resize_browser_split(None, cx);
+                                cx.stop_propagation();
+                            }
+                        }),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .h_full()
+                    .overflow_hidden()
+                    .child(browser_view),
+            )
+            .on_drag_move(cx.listener(
+                |workspace, event: &DragMoveEvent<DraggedBrowserSplit>, _window, cx| {
+                    workspace.resize_browser_split(Some(event.event.position.x), cx);
+                },
+            ))
+            .into_any_element()
+    }
+
     fn render_primary_content_shell(
         &self,
         content: impl IntoElement,
@@ -8237,6 +8613,15 @@ impl Render for DraggedDock {
     }
 }
 
+#[derive(Clone)]
+struct DraggedBrowserSplit;
+
+impl Render for DraggedBrowserSplit {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        gpui::Empty
+    }
+}
+
 impl Render for Workspace {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         static FIRST_PAINT: AtomicBool = AtomicBool::new(true);
@@ -8279,202 +8664,225 @@ impl Render for Workspace {
             .flex_col()
             .font(ui_font)
             .gap_0()
-                .justify_start()
-                .items_start()
-                .text_color(colors.text)
-                .overflow_hidden()
-                .children(self.titlebar_item.clone())
-                .on_modifiers_changed(move |_, _, cx| {
-                    for &id in &notification_entities {
-                        cx.notify(id);
-                    }
-                })
-                .child(
-                    div()
-                        .size_full()
-                        .relative()
-                        .flex_1()
-                        .flex()
-                        .flex_col()
-                        .child(
-                            div()
-                                .id("workspace")
-                                .bg(colors.background)
-                                .relative()
-                                .flex_1()
-                                .w_full()
-                                .flex()
-                                .flex_col()
-                                .overflow_hidden()
-                                .when(flat_center_chrome, |this| this.border_t_1())
-                                .border_b_1()
-                                .border_color(colors.border)
-                                .child({
-                                    let this = cx.entity();
-                                    canvas(
-                                        move |bounds, window, cx| {
-                                            this.update(cx, |this, cx| {
-                                                let bounds_changed = this.bounds != bounds;
-                                                this.bounds = bounds;
-
-                                                if bounds_changed {
-                                                    this.left_dock.update(cx, |dock, cx| {
-                                                        dock.clamp_panel_size(
-                                                            bounds.size.width,
-                                                            window,
-                                                            cx,
-                                                        )
-                                                    });
+            .justify_start()
+            .items_start()
+            .text_color(colors.text)
+            .overflow_hidden()
+            .children(self.titlebar_item.clone())
+            .on_modifiers_changed(move |_, _, cx| {
+                for &id in &notification_entities {
+                    cx.notify(id);
+                }
+            })
+            .child(
+                div()
+                    .size_full()
+                    .relative()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .child(
+                        div()
+                            .id("workspace")
+                            .bg(colors.background)
+                            .relative()
+                            .flex_1()
+                            .w_full()
+                            .flex()
+                            .flex_col()
+                            .overflow_hidden()
+                            .when(flat_center_chrome, |this| this.border_t_1())
+                            .border_b_1()
+                            .border_color(colors.border)
+                            .child({
+                                let this = cx.entity();
+                                canvas(
+                                    move |bounds, window, cx| {
+                                        this.update(cx, |this, cx| {
+                                            let bounds_changed = this.bounds != bounds;
+                                            this.bounds = bounds;
+
+                                            if bounds_changed {
+                                                this.left_dock.update(cx, |dock, cx| {
+                                                    dock.clamp_panel_size(
+                                                        bounds.size.width,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                });
 
-                                                    this.right_dock.update(cx, |dock, cx| {
-                                                        dock.clamp_panel_size(
-                                                            bounds.size.width,
-                                                            window,
-                                                            cx,
-                                                        )
-                                                    });
+                                                this.right_dock.update(cx, |dock, cx| {
+                                                    dock.clamp_panel_size(
+                                                        bounds.size.width,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                });
 
-                                                    this.bottom_dock.update(cx, |dock, cx| {
-                                                        dock.clamp_panel_size(
-                                                            bounds.size.height,
-                                                            window,
-                                                            cx,
-                                                        )
-                                                    });
-                                                }
-                                            })
-                                        },
-                                        |_, _, _, _| {},
-                                    )
-                                    .absolute()
-                                    .size_full()
-                                })
-                                .when(self.zoomed.is_none(), |this| {
-                                    this.on_drag_move(cx.listener(
-                                        move |workspace,
-                                              e: &DragMoveEvent<DraggedDock>,
-                                              window,
-                                              cx| {
-                                            if workspace.previous_dock_drag_coordinates
-                                                != Some(e.event.position)
-                                            {
-                                                workspace.previous_dock_drag_coordinates =
-                                                    Some(e.event.position);
-
-                                                match e.drag(cx).0 {
-                                                    DockPosition::Left => {
-                                                        workspace.resize_left_dock(
-                                                            e.event.position.x
-                                                                - workspace.bounds.left(),
-                                                            window,
-                                                            cx,
-                                                        );
-                                                    }
-                                                    DockPosition::Right => {
-                                                        workspace.resize_right_dock(
-                                                            workspace.bounds.right()
-                                                                - e.event.position.x,
-                                                            window,
-                                                            cx,
-                                                        );
-                                                    }
-                                                    DockPosition::Bottom => {
-                                                        workspace.resize_bottom_dock(
-                                                            workspace.bounds.bottom()
-                                                                - e.event.position.y,
-                                                            window,
-                                                            cx,
-                                                        );
-                                                    }
-                                                };
-                                                workspace.serialize_workspace(window, cx);
+                                                this.bottom_dock.update(cx, |dock, cx| {
+                                                    dock.clamp_panel_size(
+                                                        bounds.size.height,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                });
                                             }
-                                        },
-                                    ))
-
-                                })
-                                .child({
-                                    let mode_content = if self.active_mode == ModeId::BROWSER {
-                                        let browser_view = self.mode_view(ModeId::BROWSER, cx);
-
-                                        div()
-                                            .size_full()
-                                            .flex()
-                                            .flex_row()
-                                            .when(!cfg!(target_os = "macos"), |this| {
-                                                this.children(self.render_dock(
-                                                    DockPosition::Left,
-                                                    &self.left_dock,
-                                                    window,
-                                                    cx,
-                                                ))
-                                            })
-                                            .child(
-                                                div()
-                                                    .flex()
-                                                    .flex_col()
-                                                    .flex_1()
-                                                    .overflow_hidden()
-                                                    .when_some(browser_view, |this, view| {
-                                                        this.child(view)
-                                                    })
-                                                    .children(self.render_dock(
-                                                        DockPosition::Bottom,
-                                                        &self.bottom_dock,
+                                        })
+                                    },
+                                    |_, _, _, _| {},
+                                )
+                                .absolute()
+                                .size_full()
+                            })
+                            .when(self.zoomed.is_none(), |this| {
+                                this.on_drag_move(cx.listener(
+                                    move |workspace, e: &DragMoveEvent<DraggedDock>, window, cx| {
+                                        if workspace.previous_dock_drag_coordinates
+                                            != Some(e.event.position)
+                                        {
+                                            workspace.previous_dock_drag_coordinates =
+                                                Some(e.event.position);
+
+                                            match e.drag(cx).0 {
+                                                DockPosition::Left => {
+                                                    workspace.resize_left_dock(
+                                                        e.event.position.x
+                                                            - workspace.bounds.left(),
                                                         window,
                                                         cx,
-                                                    )),
-                                            )
-                                            .children(self.render_dock(
-                                                DockPosition::Right,
-                                                &self.right_dock,
+                                                    );
+                                                }
+                                                DockPosition::Right => {
+                                                    workspace.resize_right_dock(
+                                                        workspace.bounds.right()
+                                                            - e.event.position.x,
+                                                        window,
+                                                        cx,
+                                                    );
+                                                }
+                                                DockPosition::Bottom => {
+                                                    workspace.resize_bottom_dock(
+                                                        workspace.bounds.bottom()
+                                                            - e.event.position.y,
+                                                        window,
+                                                        cx,
+                                                    );
+                                                }
+                                            };
+                                            workspace.serialize_workspace(window, cx);
+                                        }
+                                    },
+                                ))
+                            })
+                            .child({
+                                let mode_content = if self.active_mode == ModeId::BROWSER {
+                                    let browser_view = self.mode_view(ModeId::BROWSER, cx);
+
+                                    div()
+                                        .size_full()
+                                        .flex()
+                                        .flex_row()
+                                        .when(!cfg!(target_os = "macos"), |this| {
+                                            this.children(self.render_dock(
+                                                DockPosition::Left,
+                                                &self.left_dock,
                                                 window,
                                                 cx,
                                             ))
-                                            .into_any_element()
-                                    } else if self.active_mode == ModeId::TERMINAL {
-                                        // Terminal preset keeps the terminal surface primary while
-                                        // still participating in the shared dock shell.
-                                        let terminal_panel = self
-                                            .bottom_dock
-                                            .read(cx)
-                                            .panel_for_key("TerminalPanel")
-                                            .map(|panel| panel.to_any());
-
-                                        let terminal_content = div()
-                                            .size_full()
-                                            .flex()
-                                            .flex_row()
-                                            .when(!cfg!(target_os = "macos"), |this| {
-                                                this.children(self.render_dock(DockPosition::Left, &self.left_dock, window, cx))
-                                            })
-                                            .child(
-                                                div()
-                                                    .flex()
-                                                    .flex_col()
-                                                    .flex_1()
-                                                    .overflow_hidden()
-                                                    .when_some(terminal_panel, |this, panel| {
-                                                        this.child(self.render_primary_content_shell(
-                                                            panel,
-                                                            None,
-                                                            None,
-                                                            cx,
-                                                        ))
-                                                    }),
-                                            );
-
-                                        terminal_content
-                                            .children(self.render_dock(
-                                                DockPosition::Right,
-                                                &self.right_dock,
+                                        })
+                                        .child(
+                                            div()
+                                                .flex()
+                                                .flex_col()
+                                                .flex_1()
+                                                .overflow_hidden()
+                                                .when_some(browser_view, |this, view| {
+                                                    this.child(view)
+                                                })
+                                                .children(self.render_dock(
+                                                    DockPosition::Bottom,
+                                                    &self.bottom_dock,
+                                                    window,
+                                                    cx,
+                                                )),
+                                        )
+                                        .children(self.render_dock(
+                                            DockPosition::Right,
+                                            &self.right_dock,
+                                            window,
+                                            cx,
+                                        ))
+                                        .into_any_element()
+                                } else if self.active_mode == ModeId::TERMINAL {
+                                    // Terminal preset keeps the terminal surface primary while
+                                    // still participating in the shared dock shell.
+                                    let terminal_panel = self
+                                        .bottom_dock
+                                        .read(cx)
+                                        .panel_for_key("TerminalPanel")
+                                        .map(|panel| panel.to_any());
+
+                                    let terminal_content = div()
+                                        .size_full()
+                                        .flex()
+                                        .flex_row()
+                                        .when(!cfg!(target_os = "macos"), |this| {
+                                            this.children(self.render_dock(
+                                                DockPosition::Left,
+                                                &self.left_dock,
                                                 window,
                                                 cx,
                                             ))
-                                            .into_any_element()
-                                    } else {
-                                        // Editor Mode: render normal dock layout
-                                        let editor_layout = match bottom_dock_layout {
+                                        })
+                                        .child(
+                                            div()
+                                                .flex()
+                                                .flex_col()
+                                                .flex_1()
+                                                .overflow_hidden()
+                                                .when_some(terminal_panel, |this, panel| {
+                                                    this.child(self.render_primary_content_shell(
+                                                        panel, None, None, cx,
+                                                    ))
+                                                }),
+                                        );
+
+                                    terminal_content
+                                        .children(self.render_dock(
+                                            DockPosition::Right,
+                                            &self.right_dock,
+                                            window,
+                                            cx,
+                                        ))
+                                        .into_any_element()
+                                } else {
+                                    // Editor Mode: render normal dock layout
+                                    let center = self
+                                        .center
+                                        .render(
+                                            self.zoomed.as_ref(),
+                                            &PaneRenderContext {
+                                                follower_states: &self.follower_states,
+                                                active_pane: &self.active_pane,
+                                                app_state: &self.app_state,
+                                                project: &self.project,
+                                                workspace: &self.weak_self,
+                                            },
+                                            window,
+                                            cx,
+                                        )
+                                        .into_any_element();
+                                    let center_content = self.render_center_with_browser_split(
+                                        self.render_primary_content_shell(
+                                            center,
+                                            padding_sizes.0,
+                                            padding_sizes.1,
+                                            cx,
+                                        )
+                                        .into_any_element(),
+                                        cx,
+                                    );
+                                    let editor_layout = match bottom_dock_layout {
                                         BottomDockLayout::Full => div()
                                             .flex()
                                             .flex_col()
@@ -8493,35 +8901,14 @@ impl Render for Workspace {
                                                             cx,
                                                         ))
                                                     })
-
                                                     .child(
                                                         div()
                                                             .flex()
                                                             .flex_col()
                                                             .flex_1()
                                                             .overflow_hidden()
-                                                            .child(self.render_primary_content_shell(
-                                                                self.center
-                                                                    .render(
-                                                                        self.zoomed.as_ref(),
-                                                                        &PaneRenderContext {
-                                                                            follower_states:
-                                                                                &self.follower_states,
-                                                                            active_pane: &self.active_pane,
-                                                                            app_state: &self.app_state,
-                                                                            project: &self.project,
-                                                                            workspace: &self.weak_self,
-                                                                        },
-                                                                        window,
-                                                                        cx,
-                                                                    )
-                                                                    .into_any_element(),
-                                                                padding_sizes.0,
-                                                                padding_sizes.1,
-                                                                cx,
-                                                            )),
+                                                            .child(center_content),
                                                     )
-
                                                     .children(self.render_dock(
                                                         DockPosition::Right,
                                                         &self.right_dock,
@@ -8533,7 +8920,7 @@ impl Render for Workspace {
                                                 DockPosition::Bottom,
                                                 &self.bottom_dock,
                                                 window,
-                                                cx
+                                                cx,
                                             ))),
 
                                         BottomDockLayout::LeftAligned => div()
@@ -8551,44 +8938,34 @@ impl Render for Workspace {
                                                             .flex()
                                                             .flex_row()
                                                             .flex_1()
-                                                            .when(!cfg!(target_os = "macos"), |this| {
-                                                                this.children(self.render_dock(DockPosition::Left, &self.left_dock, window, cx))
-                                                            })
-
+                                                            .when(
+                                                                !cfg!(target_os = "macos"),
+                                                                |this| {
+                                                                    this.children(self.render_dock(
+                                                                        DockPosition::Left,
+                                                                        &self.left_dock,
+                                                                        window,
+                                                                        cx,
+                                                                    ))
+                                                                },
+                                                            )
                                                             .child(
                                                                 div()
                                                                     .flex()
                                                                     .flex_col()
                                                                     .flex_1()
                                                                     .overflow_hidden()
-                                                                    .child(self.render_primary_content_shell(
-                                                                        self.center
-                                                                            .render(
-                                                                                self.zoomed.as_ref(),
-                                                                                &PaneRenderContext {
-                                                                                    follower_states:
-                                                                                        &self.follower_states,
-                                                                                    active_pane: &self.active_pane,
-                                                                                    app_state: &self.app_state,
-                                                                                    project: &self.project,
-                                                                                    workspace: &self.weak_self,
-                                                                                },
-                                                                                window,
-                                                                                cx,
-                                                                            )
-                                                                            .into_any_element(),
-                                                                        padding_sizes.0,
-                                                                        padding_sizes.1,
-                                                                        cx,
-                                                                    ))
-                                                            )
-
+                                                                    .child(center_content),
+                                                            ),
                                                     )
-                                                    .child(
-                                                        div()
-                                                            .w_full()
-                                                            .children(self.render_dock(DockPosition::Bottom, &self.bottom_dock, window, cx))
-                                                    ),
+                                                    .child(div().w_full().children(
+                                                        self.render_dock(
+                                                            DockPosition::Bottom,
+                                                            &self.bottom_dock,
+                                                            window,
+                                                            cx,
+                                                        ),
+                                                    )),
                                             )
                                             .children(self.render_dock(
                                                 DockPosition::Right,
@@ -8608,7 +8985,6 @@ impl Render for Workspace {
                                                     cx,
                                                 ))
                                             })
-
                                             .child(
                                                 div()
                                                     .flex()
@@ -8626,35 +9002,23 @@ impl Render for Workspace {
                                                                     .flex_col()
                                                                     .flex_1()
                                                                     .overflow_hidden()
-                                                                    .child(self.render_primary_content_shell(
-                                                                        self.center
-                                                                            .render(
-                                                                                self.zoomed.as_ref(),
-                                                                                &PaneRenderContext {
-                                                                                    follower_states:
-                                                                                        &self.follower_states,
-                                                                                    active_pane: &self.active_pane,
-                                                                                    app_state: &self.app_state,
-                                                                                    project: &self.project,
-                                                                                    workspace: &self.weak_self,
-                                                                                },
-                                                                                window,
-                                                                                cx,
-                                                                            )
-                                                                            .into_any_element(),
-                                                                        padding_sizes.0,
-                                                                        padding_sizes.1,
-                                                                        cx,
-                                                                    ))
+                                                                    .child(center_content),
                                                             )
-
-                                                            .children(self.render_dock(DockPosition::Right, &self.right_dock, window, cx))
+                                                            .children(self.render_dock(
+                                                                DockPosition::Right,
+                                                                &self.right_dock,
+                                                                window,
+                                                                cx,
+                                                            )),
                                                     )
-                                                    .child(
-                                                        div()
-                                                            .w_full()
-                                                            .children(self.render_dock(DockPosition::Bottom, &self.bottom_dock, window, cx))
-                                                    ),
+                                                    .child(div().w_full().children(
+                                                        self.render_dock(
+                                                            DockPosition::Bottom,
+                                                            &self.bottom_dock,
+                                                            window,
+                                                            cx,
+                                                        ),
+                                                    )),
                                             ),
                                         BottomDockLayout::Contained => div()
                                             .flex()
@@ -8668,33 +9032,13 @@ impl Render for Workspace {
                                                     cx,
                                                 ))
                                             })
-
                                             .child(
                                                 div()
                                                     .flex()
                                                     .flex_col()
                                                     .flex_1()
                                                     .overflow_hidden()
-                                                    .child(self.render_primary_content_shell(
-                                                        self.center
-                                                            .render(
-                                                                self.zoomed.as_ref(),
-                                                                &PaneRenderContext {
-                                                                    follower_states:
-                                                                        &self.follower_states,
-                                                                    active_pane: &self.active_pane,
-                                                                    app_state: &self.app_state,
-                                                                    project: &self.project,
-                                                                    workspace: &self.weak_self,
-                                                                },
-                                                                window,
-                                                                cx,
-                                                            )
-                                                            .into_any_element(),
-                                                        padding_sizes.0,
-                                                        padding_sizes.1,
-                                                        cx,
-                                                    ))
+                                                    .child(center_content)
                                                     .children(self.render_dock(
                                                         DockPosition::Bottom,
                                                         &self.bottom_dock,
@@ -8702,28 +9046,24 @@ impl Render for Workspace {
                                                         cx,
                                                     )),
                                             )
-
                                             .children(self.render_dock(
                                                 DockPosition::Right,
                                                 &self.right_dock,
                                                 window,
                                                 cx,
                                             )),
-                                        };
-
-                                        editor_layout.into_any_element()
                                     };
 
-                                    // In multi-workspace windows, the native sidebar shell is
-                                    // installed at the window boundary. Standalone workspaces
-                                    // still wrap themselves with the same GPUI native sidebar
-                                    // primitive so both paths share the same hosted-content model.
-                                    #[cfg(target_os = "macos")]
-                                    let mode_content = if window
-                                        .root::<MultiWorkspace>()
-                                        .flatten()
-                                        .is_some()
-                                    {
+                                    editor_layout.into_any_element()
+                                };
+
+                                // In multi-workspace windows, the native sidebar shell is
+                                // installed at the window boundary. Standalone workspaces
+                                // still wrap themselves with the same GPUI native sidebar
+                                // primitive so both paths share the same hosted-content model.
+                                #[cfg(target_os = "macos")]
+                                let mode_content =
+                                    if window.root::<MultiWorkspace>().flatten().is_some() {
                                         mode_content
                                     } else {
                                         self.render_with_workspace_sidebar_host(
@@ -8734,37 +9074,35 @@ impl Render for Workspace {
                                         )
                                     };
 
-                                    mode_content
-                                })
-                                .children(self.zoomed.as_ref().and_then(|view| {
-                                    let zoomed_view = view.upgrade()?;
-                                    let div = div()
-                                        .occlude()
-                                        .absolute()
-                                        .overflow_hidden()
-                                        .border_color(colors.border)
-                                        .bg(colors.background)
-                                        .child(zoomed_view)
-                                        .inset_0()
-                                        .shadow_lg();
-
-                                    if !WorkspaceSettings::get_global(cx).zoomed_padding {
-                                       return Some(div);
-                                    }
+                                mode_content
+                            })
+                            .children(self.zoomed.as_ref().and_then(|view| {
+                                let zoomed_view = view.upgrade()?;
+                                let div = div()
+                                    .occlude()
+                                    .absolute()
+                                    .overflow_hidden()
+                                    .border_color(colors.border)
+                                    .bg(colors.background)
+                                    .child(zoomed_view)
+                                    .inset_0()
+                                    .shadow_lg();
+
+                                if !WorkspaceSettings::get_global(cx).zoomed_padding {
+                                    return Some(div);
+                                }
 
-                                    Some(match self.zoomed_position {
-                                        Some(DockPosition::Left) => div.right_2().border_r_1(),
-                                        Some(DockPosition::Right) => div.left_2().border_l_1(),
-                                        Some(DockPosition::Bottom) => div.top_2().border_t_1(),
-                                        None => {
-                                            div.top_2().bottom_2().left_2().right_2().border_1()
-                                        }
-                                    })
-                                }))
-                                .children(self.render_notifications(window, cx)),
-                        )
-                        .child(self.toast_layer.clone()),
-                )
+                                Some(match self.zoomed_position {
+                                    Some(DockPosition::Left) => div.right_2().border_r_1(),
+                                    Some(DockPosition::Right) => div.left_2().border_l_1(),
+                                    Some(DockPosition::Bottom) => div.top_2().border_t_1(),
+                                    None => div.top_2().bottom_2().left_2().right_2().border_1(),
+                                })
+                            }))
+                            .children(self.render_notifications(window, cx)),
+                    )
+                    .child(self.toast_layer.clone()),
+            )
     }
 }
 
@@ -8937,6 +9275,14 @@ pub async fn restore_multiworkspace(
             .ok();
     }
 
+    if let Some(ui_scale) = state.ui_scale {
+        window_handle
+            .update(cx, |multi_workspace, _, cx| {
+                multi_workspace.set_ui_scale(ui_scale, cx);
+            })
+            .ok();
+    }
+
     window_handle
         .update(cx, |_, window, _cx| {
             window.activate_window();
@@ -11818,6 +12164,89 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_dock_restores_serialized_active_panel(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project, window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, 100, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace.open_panel::<TestPanel>(window, cx);
+            panel
+        });
+
+        let serialized_left_dock = workspace.update_in(cx, |workspace, window, cx| {
+            workspace.capture_dock_state(window, cx).left
+        });
+        assert!(serialized_left_dock.visible);
+        assert_eq!(
+            serialized_left_dock.active_panel.as_deref(),
+            Some("TestPanel")
+        );
+
+        // Re-register the panel after the dock state has been restored, as happens
+        // when panels load asynchronously.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.remove_panel(&panel, window, cx);
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.set_open(false, window, cx);
+                dock.serialized_dock = Some(serialized_left_dock.clone());
+            });
+
+            workspace.add_panel(panel.clone(), window, cx);
+            let left_dock = workspace.left_dock().read(cx);
+            assert!(left_dock.is_open());
+            assert_eq!(
+                left_dock.active_panel().map(|panel| panel.panel_id()),
+                Some(panel.entity_id())
+            );
+            assert!(panel.read(cx).active);
+        });
+
+        // A serialized panel that never registers falls back to the first enabled
+        // panel instead of leaving the dock open with nothing active.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.remove_panel(&panel, window, cx);
+            workspace.left_dock().update(cx, |dock, cx| {
+                dock.set_open(false, window, cx);
+                dock.serialized_dock = Some(DockData {
+                    active_panel: Some("MissingPanel".to_string()),
+                    ..serialized_left_dock
+                });
+            });
+
+            workspace.add_panel(panel.clone(), window, cx);
+            let left_dock = workspace.left_dock().read(cx);
+            assert!(left_dock.is_open());
+            assert_eq!(
+                left_dock.active_panel().map(|panel| panel.panel_id()),
+                Some(panel.entity_id())
+            );
+        });
+
+        // The fallback is applied once, so a panel registering later doesn't reopen
+        // a dock the user closed.
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace
+                .left_dock()
+                .update(cx, |dock, cx| dock.set_open(false, window, cx));
+
+            let later_panel = cx.new(|cx| TestPanel::new(DockPosition::Left, 101, cx));
+            workspace.add_panel(later_panel, window, cx);
+            let left_dock = workspace.left_dock().read(cx);
+            assert!(!left_dock.is_open());
+            assert_eq!(
+                left_dock.active_panel().map(|panel| panel.panel_id()),
+                Some(panel.entity_id())
+            );
+        });
+    }
+
     #[gpui::test]
     async fn test_toggle_all_docks_after_dock_move(cx: &mut gpui::TestAppContext) {
         init_test(cx);
@@ -13224,6 +13653,85 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_toggle_panel_zoom(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project, window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Left, 100, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+            workspace
+                .left_dock()
+                .update(cx, |left_dock, cx| left_dock.set_open(true, window, cx));
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+            panel
+        });
+
+        cx.dispatch_action(TogglePanelZoom);
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(panel.is_zoomed(window, cx));
+            assert!(
+                workspace
+                    .left_dock()
+                    .read(cx)
+                    .zoomed_panel(window, cx)
+                    .is_some()
+            );
+            assert_eq!(workspace.zoomed, Some(panel.to_any().downgrade()));
+            assert_eq!(workspace.zoomed_position, Some(DockPosition::Left));
+        });
+
+        cx.dispatch_action(TogglePanelZoom);
+        workspace.update_in(cx, |workspace, window, cx| {
+            assert!(!panel.is_zoomed(window, cx));
+            assert!(
+                workspace
+                    .left_dock()
+                    .read(cx)
+                    .zoomed_panel(window, cx)
+                    .is_none()
+            );
+            assert_eq!(workspace.zoomed, None);
+            assert_eq!(workspace.zoomed_position, None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_move_zoomed_panel_to_next_position(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project, window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = cx.new(|cx| TestPanel::new(DockPosition::Right, 100, cx));
+            workspace.add_panel(panel.clone(), window, cx);
+
+            workspace
+                .right_dock()
+                .update(cx, |right_dock, cx| right_dock.set_open(true, window, cx));
+
+            workspace.toggle_panel_focus::<TestPanel>(window, cx);
+
+            panel
+        });
+        panel.update(cx, |_, cx| cx.emit(PanelEvent::ZoomIn));
+
+        cx.dispatch_action(MoveFocusedPanelToNextPosition);
+        workspace.read_with(cx, |workspace, cx| {
+            assert_eq!(panel.read(cx).position, DockPosition::Left);
+            assert_eq!(workspace.zoomed, Some(panel.to_any().downgrade()));
+            assert_eq!(workspace.zoomed_position, Some(DockPosition::Left));
+        });
+    }
+
     #[gpui::test]
     async fn test_moving_items_create_panes(cx: &mut TestAppContext) {
         init_test(cx);
@@ -14031,6 +14539,40 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_active_mode_changed_fires_once_per_transition(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let mode_changes = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|_, cx| {
+            let mode_changes = mode_changes.clone();
+            cx.subscribe(&workspace, move |_, event: &Event, _| {
+                if let Event::ActiveModeChanged(mode_id) = event {
+                    mode_changes.borrow_mut().push(*mode_id);
+                }
+            })
+            .detach();
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.switch_to_mode(ModeId::BROWSER, window, cx);
+            workspace.switch_to_mode(ModeId::TERMINAL, window, cx);
+            workspace.switch_to_mode(ModeId::TERMINAL, window, cx);
+            workspace.switch_to_mode(ModeId::EDITOR, window, cx);
+        });
+        cx.run_until_parked();
+
+        assert_eq!(
+            *mode_changes.borrow(),
+            vec![ModeId::TERMINAL, ModeId::EDITOR]
+        );
+    }
+
     #[gpui::test]
     async fn test_mode_switching_idempotent(cx: &mut TestAppContext) {
         init_test(cx);
@@ -14062,6 +14604,57 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_restore_active_mode(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.restore_active_mode(ModeId::TERMINAL, window, cx);
+            assert_eq!(workspace.active_mode_id(), ModeId::TERMINAL);
+
+            workspace.restore_active_mode(ModeId::EDITOR, window, cx);
+            assert_eq!(workspace.active_mode_id(), ModeId::EDITOR);
+
+            // A mode without a registered view can't be restored.
+            workspace.restore_active_mode(ModeId("notes"), window, cx);
+            assert_eq!(workspace.active_mode_id(), ModeId::BROWSER);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_restore_active_mode_waits_for_panels(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let (panels_loaded_tx, panels_loaded_rx) = oneshot::channel::<()>();
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.set_panels_task(cx.background_spawn(async move {
+                panels_loaded_rx.await.ok();
+                anyhow::Ok(())
+            }));
+            workspace.restore_active_mode(ModeId::TERMINAL, window, cx);
+        });
+        cx.run_until_parked();
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.active_mode_id(), ModeId::BROWSER);
+        });
+
+        panels_loaded_tx.send(()).ok();
+        cx.run_until_parked();
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.active_mode_id(), ModeId::TERMINAL);
+        });
+    }
+
     #[cfg(target_os = "macos")]
     #[gpui::test]
     async fn test_sidebar_section_persists_across_mode_switches(cx: &mut TestAppContext) {
@@ -14231,6 +14824,139 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_toggle_split_with_browser_restores_previous_mode(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        cx.update(|cx| {
+            workspace_modes::init(cx);
+            ModeViewRegistry::global_mut(cx).register_factory(
+                ModeId::BROWSER,
+                Arc::new(|cx| {
+                    let browser_view: Entity<TestItem> = cx.new(TestItem::new);
+                    let focus_handle = browser_view.focus_handle(cx);
+
+                    RegisteredModeView {
+                        view: browser_view.into(),
+                        focus_handle,
+                        titlebar_center_view: None,
+                        sidebar_view: None,
+                        navigation_host: None,
+                        on_activate: None,
+                        on_deactivate: None,
+                    }
+                }),
+            );
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        cx.dispatch_action(ToggleSplitWithBrowser);
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.active_mode_id(), ModeId::EDITOR);
+            assert!(workspace.is_browser_split_open());
+        });
+
+        cx.dispatch_action(ToggleSplitWithBrowser);
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.active_mode_id(), ModeId::BROWSER);
+            assert!(!workspace.is_browser_split_open());
+        });
+
+        // Leaving Editor Mode closes the split.
+        cx.dispatch_action(ToggleSplitWithBrowser);
+        cx.dispatch_action(SwitchToTerminalMode);
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.active_mode_id(), ModeId::TERMINAL);
+            assert!(!workspace.is_browser_split_open());
+        });
+    }
+
+    #[gpui::test]
+    async fn test_switch_to_active_mode_is_noop(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        cx.dispatch_action(SwitchToTerminalMode);
+        workspace.update(cx, |workspace, _| {
+            assert_eq!(workspace.active_mode_id(), ModeId::TERMINAL);
+            workspace.bottom_dock_visible_before_terminal_mode = Some(true);
+        });
+
+        // Re-entering the active mode must not re-run its activation side effects.
+        cx.dispatch_action(SwitchToTerminalMode);
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.active_mode_id(), ModeId::TERMINAL);
+            assert_eq!(
+                workspace.bottom_dock_visible_before_terminal_mode,
+                Some(true)
+            );
+        });
+
+        cx.dispatch_action(SwitchToBrowserMode);
+        workspace.read_with(cx, |workspace, _| {
+            assert_eq!(workspace.active_mode_id(), ModeId::BROWSER);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_terminal_session_cycling_and_closing(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let first_pane = new_test_pane(workspace, window, cx);
+            let mut manager = TerminalSessionManager::new(WorkspaceTerminalSession::initial(
+                PaneGroup::new(first_pane.clone()),
+                first_pane,
+            ));
+            assert_eq!(manager.adjacent_session_id(true), None);
+
+            let second_pane = new_test_pane(workspace, window, cx);
+            let second_id = manager.push_restored_session(
+                "Build".into(),
+                false,
+                PaneGroup::new(second_pane.clone()),
+                second_pane,
+            );
+            let third_pane = new_test_pane(workspace, window, cx);
+            let third_id = manager.push_restored_session(
+                "Server".into(),
+                true,
+                PaneGroup::new(third_pane.clone()),
+                third_pane,
+            );
+            let first_id = manager.current_session_id();
+
+            assert_eq!(manager.adjacent_session_id(true), Some(second_id));
+            assert_eq!(manager.adjacent_session_id(false), Some(third_id));
+
+            manager.activate_session_by_id(third_id);
+            assert_eq!(manager.adjacent_session_id(true), Some(first_id));
+
+            manager.activate_session_by_id(second_id);
+            assert!(matches!(
+                manager.close_session_by_id(second_id),
+                TerminalSessionCloseResult::Switched(session) if session.id == third_id
+            ));
+            assert!(matches!(
+                manager.close_session_by_id(third_id),
+                TerminalSessionCloseResult::Switched(session) if session.id == first_id
+            ));
+        });
+    }
+
     #[gpui::test]
     async fn test_resolve_project_open_target_prefers_terminal_session_pane(
         cx: &mut TestAppContext,
@@ -14324,6 +15050,75 @@ mod tests {
         assert_eq!(*item_factory_calls.lock().unwrap(), 0);
     }
 
+    #[gpui::test]
+    async fn test_open_url_in_browser_only_switches_mode_for_foreground_opens(
+        cx: &mut TestAppContext,
+    ) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let navigations = Arc::new(std::sync::Mutex::new(Vec::<(String, bool)>::new()));
+        cx.update({
+            let navigations = navigations.clone();
+            move |_window, cx| {
+                register_browser_mode_url_navigator(
+                    Arc::new(move |_view, url, background, _window, _cx| {
+                        navigations
+                            .lock()
+                            .unwrap()
+                            .push((url.to_string(), background));
+                    }),
+                    cx,
+                );
+                workspace_modes::init(cx);
+                ModeViewRegistry::global_mut(cx).register_factory(
+                    ModeId::BROWSER,
+                    Arc::new(|cx| {
+                        let browser_view: Entity<TestBrowserChromeView> =
+                            cx.new(TestBrowserChromeView::new);
+                        let focus_handle = browser_view.focus_handle(cx);
+                        RegisteredModeView {
+                            view: browser_view.clone().into(),
+                            focus_handle,
+                            titlebar_center_view: None,
+                            sidebar_view: Some(browser_view.into()),
+                            navigation_host: None,
+                            on_activate: None,
+                            on_deactivate: None,
+                        }
+                    }),
+                );
+            }
+        });
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.switch_to_mode(ModeId::EDITOR, window, cx);
+            assert_eq!(workspace.active_mode_id(), ModeId::EDITOR);
+
+            workspace
+                .open_url_in_browser("example.com", true, window, cx)
+                .expect("background open should reach the navigator");
+            assert_eq!(workspace.active_mode_id(), ModeId::EDITOR);
+
+            workspace
+                .open_url_in_browser("example.org", false, window, cx)
+                .expect("foreground open should reach the navigator");
+            assert_eq!(workspace.active_mode_id(), ModeId::BROWSER);
+        });
+
+        assert_eq!(
+            navigations.lock().unwrap().as_slice(),
+            [
+                ("example.com".to_string(), true),
+                ("example.org".to_string(), false)
+            ]
+        );
+    }
+
     #[gpui::test]
     async fn test_open_url_uses_center_pane_in_editor_preset(cx: &mut TestAppContext) {
         init_test(cx);
@@ -36,6 +36,18 @@ pub struct ModeNavigationHost {
     pub create: ModeNavigationCreateFn,
 }
 
+/// Saves a mode view's state as a window closes, for reopening the window.
+pub type ModeWindowSnapshotFn = fn(&AnyView, &App) -> Option<String>;
+/// Restores state saved by a [`ModeWindowSnapshotFn`] into the mode view of a
+/// reopened window.
+pub type ModeWindowRestoreFn = fn(&AnyView, String, &mut App);
+
+#[derive(Clone, Copy)]
+pub struct ModeWindowSnapshotHandler {
+    pub snapshot: ModeWindowSnapshotFn,
+    pub restore: ModeWindowRestoreFn,
+}
+
 /// A view that can be displayed for a workspace mode.
 ///
 /// Mode views are registered with the `ModeViewRegistry` and retrieved by
@@ -73,6 +85,7 @@ pub struct ModeViewRegistry {
     views: HashMap<ModeId, RegisteredModeView>,
     factories: HashMap<ModeId, ModeViewFactory>,
     titlebar_center_views: HashMap<ModeId, AnyView>,
+    window_snapshot_handlers: HashMap<ModeId, ModeWindowSnapshotHandler>,
 }
 
 impl Global for ModeViewRegistry {}
@@ -114,6 +127,21 @@ impl ModeViewRegistry {
         self.factories.get(&mode_id)
     }
 
+    /// Register how a mode's per-window view is saved when its window closes
+    /// and restored when the window is reopened.
+    pub fn register_window_snapshot_handler(
+        &mut self,
+        mode_id: ModeId,
+        handler: ModeWindowSnapshotHandler,
+    ) {
+        self.window_snapshot_handlers.insert(mode_id, handler);
+    }
+
+    /// Get the window snapshot handler for a mode, if one is registered.
+    pub fn window_snapshot_handler(&self, mode_id: ModeId) -> Option<ModeWindowSnapshotHandler> {
+        self.window_snapshot_handlers.get(&mode_id).copied()
+    }
+
     /// Get the registered view for a mode (concrete, shared view)
     pub fn get(&self, mode_id: ModeId) -> Option<&RegisteredModeView> {
         self.views.get(&mode_id)
@@ -124,12 +152,24 @@ impl ModeViewRegistry {
         self.views.contains_key(&mode_id) || self.factories.contains_key(&mode_id)
     }
 
-    /// Set the title bar center view for a mode
+    /// Set the title bar center view for a mode.
+    ///
+    /// Any mode can register a center view, including modes without a
+    /// registered mode view (such as editor mode). The title bar renders the
+    /// active mode's center view, replacing the onboarding banner.
     pub fn set_titlebar_center_view(&mut self, mode_id: ModeId, view: AnyView) {
         self.titlebar_center_views.insert(mode_id, view);
     }
 
-    /// Get the title bar center view for a mode
+    /// Remove the title bar center view set for a mode, returning it if one
+    /// was set. A center view provided by the mode's registered view still
+    /// applies.
+    pub fn clear_titlebar_center_view(&mut self, mode_id: ModeId) -> Option<AnyView> {
+        self.titlebar_center_views.remove(&mode_id)
+    }
+
+    /// Get the title bar center view for a mode, preferring one set via
+    /// `set_titlebar_center_view` over the registered view's own.
     pub fn titlebar_center_view(&self, mode_id: ModeId) -> Option<&AnyView> {
         self.titlebar_center_views.get(&mode_id).or_else(|| {
             self.views
@@ -20,7 +20,8 @@ mod mode_view_registry;
 
 pub use mode_view_registry::{
     ModeActivateCallback, ModeDeactivateCallback, ModeNavigationEntry, ModeNavigationHost,
-    ModeViewFactory, ModeViewRegistry, RegisteredModeView,
+    ModeViewFactory, ModeViewRegistry, ModeWindowRestoreFn, ModeWindowSnapshotFn,
+    ModeWindowSnapshotHandler, RegisteredModeView,
 };
 
 use gpui::{App, actions};
@@ -36,6 +37,8 @@ actions!(
         SwitchToEditorMode,
         /// Switch to Terminal Mode
         SwitchToTerminalMode,
+        /// Show the browser beside the editor, or close that split
+        ToggleSplitWithBrowser,
     ]
 );
 
@@ -572,6 +572,10 @@ pub fn initialize_workspace(
             window,
             cx,
         );
+        let cursor_position =
+            cx.new(|_| go_to_line::cursor_position::CursorPosition::new(workspace));
+        let active_buffer_language =
+            cx.new(|_| language_selector::ActiveBufferLanguage::new(workspace));
         let active_buffer_encoding =
             cx.new(|_| encoding_selector::ActiveBufferEncoding::new(workspace));
         let active_toolchain_language =
@@ -586,6 +590,8 @@ pub fn initialize_workspace(
         {
             title_bar.update(cx, |title_bar, cx| {
                 title_bar.add_right_item(image_info, window, cx);
+                title_bar.add_right_item(cursor_position, window, cx);
+                title_bar.add_right_item(active_buffer_language, window, cx);
                 title_bar.add_right_item(line_ending_indicator, window, cx);
                 title_bar.add_right_item(active_toolchain_language, window, cx);
                 title_bar.add_right_item(active_buffer_encoding, window, cx);